@@ -12,6 +12,8 @@ pub struct BuildProfile {
     pub print_dca_graph: Option<String>,
     pub print_dca_graph_url_format: Option<String>,
     #[serde(default)]
+    pub print_trait_map: Option<String>,
+    #[serde(default)]
     pub print_ir: PrintIr,
     #[serde(default)]
     pub print_asm: PrintAsm,
@@ -48,6 +50,7 @@ impl BuildProfile {
             print_ast: false,
             print_dca_graph: None,
             print_dca_graph_url_format: None,
+            print_trait_map: None,
             print_ir: PrintIr::default(),
             print_asm: PrintAsm::default(),
             print_bytecode: false,
@@ -69,6 +72,7 @@ impl BuildProfile {
             print_ast: false,
             print_dca_graph: None,
             print_dca_graph_url_format: None,
+            print_trait_map: None,
             print_ir: PrintIr::default(),
             print_asm: PrintAsm::default(),
             print_bytecode: false,
@@ -138,6 +142,7 @@ mod tests {
             print_ast: true,
             print_dca_graph: Some("dca_graph".into()),
             print_dca_graph_url_format: Some("print_dca_graph_url_format".into()),
+            print_trait_map: Some("my_lib::my_module".into()),
             print_ir: PrintIr::r#final(),
             print_asm: PrintAsm::all(),
             print_bytecode: true,
@@ -254,6 +254,9 @@ pub struct PrintOpts {
     /// Variables {path}, {line} {col} can be used in the provided format.
     /// An example for vscode would be: "vscode://file/{path}:{line}:{col}"
     pub dca_graph_url_format: Option<String>,
+    /// Print the trait map of the module with the specified absolute path,
+    /// e.g., `my_lib::my_module`.
+    pub trait_map: Option<String>,
     /// Print the generated ASM.
     pub asm: PrintAsm,
     /// Print the bytecode. This is the final output of the compiler.
@@ -1555,6 +1558,7 @@ pub fn sway_build_config(
     )
     .with_print_dca_graph(build_profile.print_dca_graph.clone())
     .with_print_dca_graph_url_format(build_profile.print_dca_graph_url_format.clone())
+    .with_print_trait_map(build_profile.print_trait_map.clone())
     .with_print_asm(build_profile.print_asm)
     .with_print_bytecode(
         build_profile.print_bytecode,
@@ -2112,6 +2116,9 @@ fn build_profile_from_opts(
             .print_dca_graph_url_format
             .clone_from(&print.dca_graph_url_format);
     }
+    if profile.print_trait_map.is_none() {
+        profile.print_trait_map.clone_from(&print.trait_map);
+    }
     profile.print_ir |= print.ir.clone();
     profile.print_asm |= print.asm;
     profile.print_bytecode |= print.bytecode;
@@ -809,6 +809,7 @@ fn build_opts_from_cmd(cmd: &cmd::Deploy, member_filter: pkg::MemberFilter) -> p
             ast: cmd.print.ast,
             dca_graph: cmd.print.dca_graph.clone(),
             dca_graph_url_format: cmd.print.dca_graph_url_format.clone(),
+            trait_map: cmd.print.trait_map.clone(),
             asm: cmd.print.asm(),
             bytecode: cmd.print.bytecode,
             bytecode_spans: false,
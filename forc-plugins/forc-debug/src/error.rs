@@ -65,18 +65,27 @@ pub enum AdapterError {
     #[error("Missing source path argument")]
     MissingSourcePathArgument,
 
-    #[error("Missing breakpoint location")]
-    MissingBreakpointLocation,
+    #[error("Invalid memory reference: {memory_reference}")]
+    InvalidMemoryReference { memory_reference: String },
 
     #[error("Missing source map")]
     MissingSourceMap { pc: Instruction },
 
-    #[error("Unknown breakpoint")]
-    UnknownBreakpoint { pc: Instruction },
-
     #[error("Build failed")]
     BuildFailed { reason: String },
 
+    #[error("No tests match the filter `{filter}`, available tests: {}", .available.join(", "))]
+    NoMatchingTests {
+        filter: String,
+        available: Vec<String>,
+    },
+
+    #[error("Invalid execution context: {reason}. The accepted fields are `blockHeight`, `coinAmount` and `seed`")]
+    InvalidExecutionContext { reason: String },
+
+    #[error("Unknown goto target: {target_id}")]
+    UnknownGotoTarget { target_id: i64 },
+
     #[error("No active test executor")]
     NoActiveTestExecutor,
 
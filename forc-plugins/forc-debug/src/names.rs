@@ -1,3 +1,5 @@
+use fuel_vm::consts::VM_REGISTER_COUNT;
+
 /// A list of predefined register names mapped to their corresponding indices.
 pub const REGISTERS: [&str; 16] = [
     "zero", "one", "of", "pc", "ssp", "sp", "fp", "hp", "err", "ggas", "cgas", "bal", "is", "ret",
@@ -41,3 +43,25 @@ pub fn register_name(index: usize) -> String {
 pub fn register_index(name: &str) -> Option<usize> {
     REGISTERS.iter().position(|&n| n == name)
 }
+
+/// Returns the index of a register given its name as written in an expression.
+///
+/// The name can have a leading `$`, and can be either a predefined register
+/// name or `reg{index}` for any register. Returns `None` for unknown names.
+///
+/// # Examples
+///
+/// ```
+/// use forc_debug::names::parse_register;
+/// assert_eq!(parse_register("$pc"), Some(3));
+/// assert_eq!(parse_register("reg12"), Some(12));
+/// assert_eq!(parse_register("reg64"), None);
+/// ```
+pub fn parse_register(name: &str) -> Option<usize> {
+    let name = name.strip_prefix('$').unwrap_or(name);
+    register_index(name).or_else(|| {
+        name.strip_prefix("reg")
+            .and_then(|index| index.parse().ok())
+            .filter(|index| *index < VM_REGISTER_COUNT)
+    })
+}
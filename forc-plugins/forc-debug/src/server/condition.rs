@@ -0,0 +1,239 @@
+//! Conditional breakpoints and logpoints.
+//!
+//! Conditions are written in the expression language of the debug console, see [super::expression], e.g.
+//! `$pc > 0x100 && (reg16 == 3 || $of != 0)`.
+//!
+//! Hit conditions decide on which hits a breakpoint stops, e.g. `>= 5` or `% 100`. A count on its own stops
+//! only on that hit.
+//!
+//! Logpoint messages interpolate expressions into text, e.g. `pc={$pc} gas={gas}`. Braces are escaped by
+//! doubling them.
+
+use super::expression::{Comparison, EvaluationContext, Expression, Value};
+use crate::error::ArgumentError;
+
+/// A parsed breakpoint condition, ready to be evaluated against a stopped test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakpointCondition(Expression);
+
+impl BreakpointCondition {
+    /// Parses a condition from the source sent by the client.
+    pub fn parse(source: &str) -> Result<Self, ArgumentError> {
+        Expression::parse_condition(source).map(Self)
+    }
+
+    /// Returns true if the condition holds for the given test.
+    ///
+    /// A condition that cannot be evaluated, e.g. because it reads unreadable memory, does not hold.
+    pub fn evaluate(&self, context: &impl EvaluationContext) -> bool {
+        self.0.is_true(context).unwrap_or(false)
+    }
+}
+
+/// A parsed breakpoint hit condition, evaluated against the number of times the breakpoint has been hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HitCondition {
+    comparison: Option<Comparison>,
+    count: u64,
+}
+
+impl HitCondition {
+    /// Parses a hit condition from the source sent by the client.
+    pub fn parse(source: &str) -> Result<Self, ArgumentError> {
+        let invalid = |reason: &str| ArgumentError::Invalid(format!("hit condition: {reason}"));
+        let source = source.trim();
+        let digits_start = source
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(|| invalid("expected a hit count"))?;
+        let (operator, count) = source.split_at(digits_start);
+        let comparison = match operator.trim() {
+            "" | "=" | "==" => Some(Comparison::Eq),
+            "!=" => Some(Comparison::Ne),
+            "<" => Some(Comparison::Lt),
+            "<=" => Some(Comparison::Le),
+            ">" => Some(Comparison::Gt),
+            ">=" => Some(Comparison::Ge),
+            "%" => None,
+            operator => return Err(invalid(&format!("unknown operator `{operator}`"))),
+        };
+        let count = count
+            .parse()
+            .map_err(|_| invalid(&format!("invalid hit count `{count}`")))?;
+        if comparison.is_none() && count == 0 {
+            return Err(invalid("cannot stop on every 0th hit"));
+        }
+        Ok(Self { comparison, count })
+    }
+
+    /// Returns true if the breakpoint should stop on its `hits`th hit.
+    pub fn is_met(&self, hits: u64) -> bool {
+        match self.comparison {
+            Some(comparison) => comparison.compare(hits, self.count),
+            None => hits % self.count == 0,
+        }
+    }
+}
+
+/// A parsed logpoint message, ready to be rendered with the VM state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogMessage(Vec<LogSegment>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LogSegment {
+    Text(String),
+    Expression(Expression),
+}
+
+impl LogMessage {
+    /// Parses a logpoint message from the source sent by the client.
+    pub fn parse(source: &str) -> Result<Self, ArgumentError> {
+        let invalid = |reason: &str| ArgumentError::Invalid(format!("log message: {reason}"));
+        let mut segments = vec![];
+        let mut text = String::new();
+        let mut chars = source.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let (expression, rest) = chars
+                        .as_str()
+                        .split_once('}')
+                        .ok_or_else(|| invalid("expected `}`"))?;
+                    chars = rest.chars();
+                    segments.push(LogSegment::Text(std::mem::take(&mut text)));
+                    segments.push(LogSegment::Expression(Expression::parse(expression)?));
+                }
+                '}' => return Err(invalid("unexpected `}`")),
+                c => text.push(c),
+            }
+        }
+        segments.push(LogSegment::Text(text));
+        Ok(Self(segments))
+    }
+
+    /// Renders the message with the state of the given test.
+    ///
+    /// Words are rendered as decimal numbers, and expressions that cannot be evaluated as their error.
+    pub fn render(&self, context: &impl EvaluationContext) -> String {
+        self.0
+            .iter()
+            .map(|segment| match segment {
+                LogSegment::Text(text) => text.clone(),
+                LogSegment::Expression(expression) => match expression.evaluate(context) {
+                    Ok(Value::Word { value, .. }) => value.to_string(),
+                    Ok(Value::Text(text)) => text,
+                    Ok(value) => value.format(),
+                    Err(err) => format!("<{err}>"),
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{names::register_index, server::expression::tests::TestContext};
+    use fuel_vm::consts::VM_REGISTER_COUNT;
+
+    fn context() -> TestContext {
+        let mut registers = vec![0; VM_REGISTER_COUNT];
+        registers[register_index("one").unwrap()] = 1;
+        registers[register_index("pc").unwrap()] = 0x120;
+        registers[16] = 3;
+        TestContext::with_registers(registers)
+    }
+
+    fn evaluate(source: &str) -> bool {
+        BreakpointCondition::parse(source)
+            .expect("valid condition")
+            .evaluate(&context())
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert!(evaluate("$pc == 0x120"));
+        assert!(evaluate("pc == 288"));
+        assert!(evaluate("reg16 != 2"));
+        assert!(evaluate("$reg16 < 4"));
+        assert!(evaluate("reg16 <= 3"));
+        assert!(evaluate("$one > $zero"));
+        assert!(evaluate("1_000 >= reg16"));
+        assert!(!evaluate("reg16 > 3"));
+    }
+
+    #[test]
+    fn test_logical_operators() {
+        assert!(evaluate("$pc > 0x100 && (reg16 == 1 || $one != 0)"));
+        assert!(evaluate("reg16 == 1 or reg16 == 3"));
+        assert!(!evaluate("reg16 == 3 and $zero"));
+        assert!(evaluate("$one"));
+        assert!(!evaluate("($zero)"));
+    }
+
+    #[test]
+    fn test_invalid_conditions() {
+        for source in [
+            "",
+            "foo == 1",
+            "reg64 == 1",
+            "0xZZ == 1",
+            "$pc ==",
+            "($pc == 1",
+            "$pc == 1)",
+            "$pc = 1",
+            "$pc == 1 &&",
+        ] {
+            let err = BreakpointCondition::parse(source).expect_err(source);
+            assert!(matches!(err, ArgumentError::Invalid(_)), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_hit_conditions() {
+        let stops = |source: &str| {
+            let condition = HitCondition::parse(source).expect("valid hit condition");
+            (1..=10)
+                .filter(|hits| condition.is_met(*hits))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(stops("3"), vec![3]);
+        assert_eq!(stops("== 3"), vec![3]);
+        assert_eq!(stops(">= 8"), vec![8, 9, 10]);
+        assert_eq!(stops(">8"), vec![9, 10]);
+        assert_eq!(stops("< 3"), vec![1, 2]);
+        assert_eq!(stops("<= 2"), vec![1, 2]);
+        assert_eq!(stops("!= 1").len(), 9);
+        assert_eq!(stops("% 3"), vec![3, 6, 9]);
+
+        for source in ["", ">=", "% 0", "=> 3", "3 hits", "-1"] {
+            let err = HitCondition::parse(source).expect_err(source);
+            assert!(matches!(err, ArgumentError::Invalid(_)), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_log_messages() {
+        let render = |source: &str| {
+            LogMessage::parse(source)
+                .expect("valid log message")
+                .render(&context())
+        };
+        assert_eq!(render("no expressions"), "no expressions");
+        assert_eq!(render("pc={$pc} gas={ gas }"), "pc=288 gas=42");
+        assert_eq!(render("{reg16}{one}"), "31");
+        assert_eq!(render("{{$pc}} = {$pc}"), "{$pc} = 288");
+
+        for source in ["{$pc", "{unknown}", "{}", "pc}"] {
+            let err = LogMessage::parse(source).expect_err(source);
+            assert!(matches!(err, ArgumentError::Invalid(_)), "{source}");
+        }
+    }
+}
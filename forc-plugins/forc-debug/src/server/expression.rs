@@ -0,0 +1,646 @@
+//! A small expression language for the debug console, watch expressions, conditional breakpoints and
+//! logpoints.
+//!
+//! Expressions do integer arithmetic (`+`, `-`, `*`, `/`, `%`) on registers and integer literals, e.g.
+//! `$sp - $ssp` or `(reg12 + 0x10) * 2`. Registers are written with or without a leading `$`, using their
+//! names (`pc`, `ggas`) or `reg{index}` for the general purpose ones.
+//!
+//! Memory is read with `[address]`, which reads the word at `address`, or `[address; length]`, which reads
+//! `length` bytes, e.g. `[reg5 + 8; 32]`. The address and length are expressions themselves.
+//!
+//! The pseudo-variables `gas_used` (or `gas`) and `test_name` hold the gas the test has used so far and the
+//! name of the test.
+//!
+//! Conditions additionally compare expressions and combine the comparisons with `&&` and `||` (or `and` and
+//! `or`), e.g. `$pc > 0x100 && (reg16 == 3 || [$sp - 8] != 0)`. An expression on its own holds when it is
+//! not zero.
+
+use crate::{error::ArgumentError, names::parse_register, server::util};
+use forc_test::execute::TestExecutor;
+
+/// The size of a VM word in bytes.
+const WORD_SIZE: u64 = 8;
+
+/// The maximum number of bytes a single memory read can return.
+const MAX_READ_LENGTH: u64 = 4096;
+
+/// The state of a stopped test that expressions are evaluated against.
+pub trait EvaluationContext {
+    /// Returns the VM registers.
+    fn registers(&self) -> &[u64];
+
+    /// Reads up to `count` bytes of memory at `address`, returning fewer bytes if the rest are unreadable.
+    fn read_memory(&self, address: u64, count: u64) -> &[u8];
+
+    /// Returns the gas the test has used so far.
+    fn gas_used(&self) -> u64;
+
+    /// Returns the name of the test.
+    fn test_name(&self) -> &str;
+}
+
+impl EvaluationContext for TestExecutor {
+    fn registers(&self) -> &[u64] {
+        self.interpreter.registers()
+    }
+
+    fn read_memory(&self, address: u64, count: u64) -> &[u8] {
+        util::read_memory(self, address, count)
+    }
+
+    fn gas_used(&self) -> u64 {
+        util::gas_used(self)
+    }
+
+    fn test_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A parsed expression, ready to be evaluated against a stopped test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression(Expr);
+
+impl Expression {
+    /// Parses an expression from the source sent by the client.
+    pub fn parse(source: &str) -> Result<Self, ArgumentError> {
+        Self::parse_with(source, Parser::parse_sum)
+    }
+
+    /// Parses a condition, an expression that can also contain comparisons and logical operators.
+    pub fn parse_condition(source: &str) -> Result<Self, ArgumentError> {
+        Self::parse_with(source, Parser::parse_or)
+    }
+
+    fn parse_with(
+        source: &str,
+        parse: fn(&mut Parser) -> Result<Expr, ArgumentError>,
+    ) -> Result<Self, ArgumentError> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+            parse_parenthesized: parse,
+        };
+        let expr = parse(&mut parser)?;
+        if parser.position < parser.tokens.len() {
+            return Err(invalid("unexpected input after the end of the expression"));
+        }
+        Ok(Self(expr))
+    }
+
+    /// Evaluates the expression against the state of a stopped test.
+    pub fn evaluate(&self, context: &impl EvaluationContext) -> Result<Value, ArgumentError> {
+        self.0.evaluate(context)
+    }
+
+    /// Returns true if the expression evaluates to a word that is not zero.
+    pub fn is_true(&self, context: &impl EvaluationContext) -> Result<bool, ArgumentError> {
+        self.0.evaluate_word(context).map(|value| value != 0)
+    }
+}
+
+/// The value of an evaluated expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A word, along with the address it was read from if it was read from memory.
+    Word {
+        value: u64,
+        address: Option<u64>,
+    },
+    /// Bytes read from memory at `address`.
+    Bytes {
+        address: u64,
+        bytes: Vec<u8>,
+    },
+    Text(String),
+}
+
+impl Value {
+    /// Formats the value for display, e.g. `255 (0xFF)` for a word or `0x0123abcd` for bytes.
+    pub fn format(&self) -> String {
+        match self {
+            Value::Word { value, .. } => util::format_word(*value),
+            Value::Bytes { bytes, .. } => std::iter::once("0x".to_string())
+                .chain(bytes.iter().map(|byte| format!("{byte:02x}")))
+                .collect(),
+            Value::Text(text) => format!("{text:?}"),
+        }
+    }
+
+    /// Returns the memory reference of the memory the value was read from, if any.
+    pub fn memory_reference(&self) -> Option<String> {
+        match self {
+            Value::Word {
+                address: Some(address),
+                ..
+            }
+            | Value::Bytes { address, .. } => Some(util::format_memory_reference(*address as i64)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Literal(u64),
+    Register(usize),
+    GasUsed,
+    TestName,
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    Compare(Box<Expr>, Comparison, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Memory {
+        address: Box<Expr>,
+        length: Option<Box<Expr>>,
+    },
+}
+
+impl Expr {
+    fn evaluate(&self, context: &impl EvaluationContext) -> Result<Value, ArgumentError> {
+        let word = |value| Value::Word {
+            value,
+            address: None,
+        };
+        match self {
+            Expr::Literal(value) => Ok(word(*value)),
+            Expr::Register(index) => Ok(word(context.registers()[*index])),
+            Expr::GasUsed => Ok(word(context.gas_used())),
+            Expr::TestName => Ok(Value::Text(context.test_name().to_string())),
+            Expr::Binary(lhs, op, rhs) => {
+                let value = op.apply(lhs.evaluate_word(context)?, rhs.evaluate_word(context)?)?;
+                Ok(word(value))
+            }
+            Expr::Compare(lhs, comparison, rhs) => {
+                let holds =
+                    comparison.compare(lhs.evaluate_word(context)?, rhs.evaluate_word(context)?);
+                Ok(word(holds as u64))
+            }
+            Expr::And(lhs, rhs) => {
+                let holds = lhs.evaluate_word(context)? != 0 && rhs.evaluate_word(context)? != 0;
+                Ok(word(holds as u64))
+            }
+            Expr::Or(lhs, rhs) => {
+                let holds = lhs.evaluate_word(context)? != 0 || rhs.evaluate_word(context)? != 0;
+                Ok(word(holds as u64))
+            }
+            Expr::Memory { address, length } => {
+                let address = address.evaluate_word(context)?;
+                let length = match length {
+                    Some(length) => length.evaluate_word(context)?,
+                    None => WORD_SIZE,
+                };
+                if length > MAX_READ_LENGTH {
+                    return Err(invalid(format!(
+                        "cannot read more than {MAX_READ_LENGTH} bytes at once"
+                    )));
+                }
+                let bytes = context.read_memory(address, length);
+                if (bytes.len() as u64) < length {
+                    return Err(invalid(format!(
+                        "memory at 0x{:x} is not readable",
+                        address + bytes.len() as u64
+                    )));
+                }
+                Ok(match <[u8; WORD_SIZE as usize]>::try_from(bytes) {
+                    Ok(word) if length == WORD_SIZE => Value::Word {
+                        value: u64::from_be_bytes(word),
+                        address: Some(address),
+                    },
+                    _ => Value::Bytes {
+                        address,
+                        bytes: bytes.to_vec(),
+                    },
+                })
+            }
+        }
+    }
+
+    /// Evaluates an operand of arithmetic, of a comparison or of a memory read, which must be a word.
+    fn evaluate_word(&self, context: &impl EvaluationContext) -> Result<u64, ArgumentError> {
+        match self.evaluate(context)? {
+            Value::Word { value, .. } => Ok(value),
+            _ => Err(invalid(
+                "only words can be used in arithmetic and as addresses",
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    fn apply(&self, lhs: u64, rhs: u64) -> Result<u64, ArgumentError> {
+        let result = match self {
+            BinaryOp::Add => lhs.checked_add(rhs),
+            BinaryOp::Sub => lhs.checked_sub(rhs),
+            BinaryOp::Mul => lhs.checked_mul(rhs),
+            BinaryOp::Div | BinaryOp::Rem if rhs == 0 => return Err(invalid("division by zero")),
+            BinaryOp::Div => lhs.checked_div(rhs),
+            BinaryOp::Rem => lhs.checked_rem(rhs),
+        };
+        result.ok_or_else(|| invalid("arithmetic overflow"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    pub(crate) fn compare(&self, lhs: u64, rhs: u64) -> bool {
+        match self {
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ne => lhs != rhs,
+            Comparison::Lt => lhs < rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Gt => lhs > rhs,
+            Comparison::Ge => lhs >= rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Literal(u64),
+    Register(usize),
+    GasUsed,
+    TestName,
+    Op(BinaryOp),
+    Comparison(Comparison),
+    And,
+    Or,
+    Open,
+    Close,
+    OpenBracket,
+    CloseBracket,
+    Semicolon,
+}
+
+fn invalid(reason: impl Into<String>) -> ArgumentError {
+    ArgumentError::Invalid(format!("expression: {}", reason.into()))
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ArgumentError> {
+    let mut tokens = vec![];
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '$' || c == '_' || c.is_ascii_alphanumeric() {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c != '$' && c != '_' && !c.is_ascii_alphanumeric() {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(parse_word(&word)?);
+            continue;
+        }
+
+        chars.next();
+        let mut followed_by = |next: char| chars.next_if_eq(&next).is_some();
+        tokens.push(match c {
+            '+' => Token::Op(BinaryOp::Add),
+            '-' => Token::Op(BinaryOp::Sub),
+            '*' => Token::Op(BinaryOp::Mul),
+            '/' => Token::Op(BinaryOp::Div),
+            '%' => Token::Op(BinaryOp::Rem),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            ';' => Token::Semicolon,
+            '&' if followed_by('&') => Token::And,
+            '|' if followed_by('|') => Token::Or,
+            '=' if followed_by('=') => Token::Comparison(Comparison::Eq),
+            '!' if followed_by('=') => Token::Comparison(Comparison::Ne),
+            '<' if followed_by('=') => Token::Comparison(Comparison::Le),
+            '<' => Token::Comparison(Comparison::Lt),
+            '>' if followed_by('=') => Token::Comparison(Comparison::Ge),
+            '>' => Token::Comparison(Comparison::Gt),
+            _ => return Err(invalid(format!("unexpected character `{c}`"))),
+        });
+    }
+    Ok(tokens)
+}
+
+fn parse_word(word: &str) -> Result<Token, ArgumentError> {
+    if word.starts_with(|c: char| c.is_ascii_digit()) {
+        let digits = word.replace('_', "");
+        let value = match digits.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => digits.parse(),
+        };
+        return value
+            .map(Token::Literal)
+            .map_err(|_| invalid(format!("invalid integer `{word}`")));
+    }
+
+    match word {
+        "gas_used" | "gas" => Ok(Token::GasUsed),
+        "test_name" => Ok(Token::TestName),
+        "and" => Ok(Token::And),
+        "or" => Ok(Token::Or),
+        _ => parse_register(word).map(Token::Register).ok_or_else(|| {
+            invalid(format!(
+                "unknown name `{word}`, expected a register, `gas_used` or `test_name`"
+            ))
+        }),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// Parses the contents of parentheses, which are of the same kind as the whole source.
+    parse_parenthesized: fn(&mut Parser) -> Result<Expr, ArgumentError>,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).copied();
+        self.position += 1;
+        token
+    }
+
+    fn next_op(&mut self, ops: &[BinaryOp]) -> Option<BinaryOp> {
+        match self.tokens.get(self.position) {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                self.position += 1;
+                Some(*op)
+            }
+            _ => None,
+        }
+    }
+
+    fn next_if(&mut self, token: Token) -> bool {
+        let matches = self.tokens.get(self.position) == Some(&token);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn expect(&mut self, token: Token, description: &str) -> Result<(), ArgumentError> {
+        match self.next() {
+            Some(next) if next == token => Ok(()),
+            _ => Err(invalid(format!("expected {description}"))),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ArgumentError> {
+        let mut expr = self.parse_and()?;
+        while self.next_if(Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ArgumentError> {
+        let mut expr = self.parse_comparison()?;
+        while self.next_if(Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_comparison()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ArgumentError> {
+        let lhs = self.parse_sum()?;
+        match self.tokens.get(self.position) {
+            Some(&Token::Comparison(comparison)) => {
+                self.position += 1;
+                let rhs = self.parse_sum()?;
+                Ok(Expr::Compare(Box::new(lhs), comparison, Box::new(rhs)))
+            }
+            _ => Ok(lhs),
+        }
+    }
+
+    fn parse_sum(&mut self) -> Result<Expr, ArgumentError> {
+        let mut expr = self.parse_product()?;
+        while let Some(op) = self.next_op(&[BinaryOp::Add, BinaryOp::Sub]) {
+            expr = Expr::Binary(Box::new(expr), op, Box::new(self.parse_product()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_product(&mut self) -> Result<Expr, ArgumentError> {
+        let mut expr = self.parse_term()?;
+        while let Some(op) = self.next_op(&[BinaryOp::Mul, BinaryOp::Div, BinaryOp::Rem]) {
+            expr = Expr::Binary(Box::new(expr), op, Box::new(self.parse_term()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, ArgumentError> {
+        match self.next() {
+            Some(Token::Literal(value)) => Ok(Expr::Literal(value)),
+            Some(Token::Register(index)) => Ok(Expr::Register(index)),
+            Some(Token::GasUsed) => Ok(Expr::GasUsed),
+            Some(Token::TestName) => Ok(Expr::TestName),
+            Some(Token::Open) => {
+                let expr = (self.parse_parenthesized)(self)?;
+                self.expect(Token::Close, "`)`")?;
+                Ok(expr)
+            }
+            Some(Token::OpenBracket) => {
+                let address = Box::new(self.parse_sum()?);
+                let length = match self.tokens.get(self.position) {
+                    Some(Token::Semicolon) => {
+                        self.position += 1;
+                        Some(Box::new(self.parse_sum()?))
+                    }
+                    _ => None,
+                };
+                self.expect(Token::CloseBracket, "`]` or `;` followed by a length")?;
+                Ok(Expr::Memory { address, length })
+            }
+            Some(_) => Err(invalid(
+                "expected a register, an integer, a memory read or `(`",
+            )),
+            None => Err(invalid("unexpected end of the expression")),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use fuel_vm::consts::VM_REGISTER_COUNT;
+
+    /// The address the readable memory of [TestContext] starts at.
+    const MEMORY_START: u64 = 0x100;
+
+    /// An [EvaluationContext] with the given registers and a few words of readable memory at [MEMORY_START].
+    pub(crate) struct TestContext {
+        registers: Vec<u64>,
+        memory: Vec<u8>,
+    }
+
+    impl TestContext {
+        fn new() -> Self {
+            let mut registers = vec![0; VM_REGISTER_COUNT];
+            registers[parse_register("pc").unwrap()] = 0x120;
+            registers[5] = MEMORY_START;
+            registers[12] = 3;
+            Self::with_registers(registers)
+        }
+
+        pub(crate) fn with_registers(registers: Vec<u64>) -> Self {
+            let mut memory = 0x0123456789ABCDEF_u64.to_be_bytes().to_vec();
+            memory.extend([0xAA; 8]);
+            Self { registers, memory }
+        }
+    }
+
+    impl EvaluationContext for TestContext {
+        fn registers(&self) -> &[u64] {
+            &self.registers
+        }
+
+        fn read_memory(&self, address: u64, count: u64) -> &[u8] {
+            let Some(start) = address.checked_sub(MEMORY_START) else {
+                return &[];
+            };
+            let start = (start as usize).min(self.memory.len());
+            let end = (start + count as usize).min(self.memory.len());
+            &self.memory[start..end]
+        }
+
+        fn gas_used(&self) -> u64 {
+            42
+        }
+
+        fn test_name(&self) -> &str {
+            "test_memory"
+        }
+    }
+
+    fn evaluate(source: &str) -> Result<Value, ArgumentError> {
+        Expression::parse(source).and_then(|expression| expression.evaluate(&TestContext::new()))
+    }
+
+    fn word(source: &str) -> u64 {
+        match evaluate(source).expect(source) {
+            Value::Word { value, .. } => value,
+            other => panic!("Expected a word for `{source}`, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_registers_and_literals() {
+        assert_eq!(word("$pc"), 0x120);
+        assert_eq!(word("pc"), 0x120);
+        assert_eq!(word("reg12"), 3);
+        assert_eq!(word("$reg12"), 3);
+        assert_eq!(word("0x1_0"), 16);
+        assert_eq!(word("1_000"), 1000);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(word("$pc + 8"), 0x128);
+        assert_eq!(word("$pc - reg12 * 2"), 0x11A);
+        assert_eq!(word("($pc - reg12) * 2"), 0x23A);
+        assert_eq!(word("10 / reg12"), 3);
+        assert_eq!(word("10 % reg12"), 1);
+        assert_eq!(word("10 - 2 - 3"), 5);
+    }
+
+    #[test]
+    fn test_memory_reads() {
+        assert_eq!(
+            evaluate("[reg5]").unwrap(),
+            Value::Word {
+                value: 0x0123456789ABCDEF,
+                address: Some(MEMORY_START),
+            }
+        );
+        let value = evaluate("[reg5 + 6; 4]").unwrap();
+        assert_eq!(value.format(), "0xcdefaaaa");
+        assert_eq!(value.memory_reference().as_deref(), Some("0x106"));
+        assert_eq!(word("[reg5 + 8] - 1"), 0xAAAAAAAAAAAAAAA9);
+        assert_eq!(word("[reg5; 8]"), 0x0123456789ABCDEF);
+    }
+
+    #[test]
+    fn test_pseudo_variables() {
+        assert_eq!(word("gas_used"), 42);
+        assert_eq!(word("gas_used + 1"), 43);
+        let value = evaluate("test_name").unwrap();
+        assert_eq!(value.format(), "\"test_memory\"");
+        assert_eq!(value.memory_reference(), None);
+    }
+
+    #[test]
+    fn test_formatting() {
+        let value = evaluate("$pc").unwrap();
+        assert_eq!(value.format(), "288 (0x120)");
+        assert_eq!(value.memory_reference(), None);
+    }
+
+    #[test]
+    fn test_conditions() {
+        let holds = |source: &str| {
+            Expression::parse_condition(source)
+                .and_then(|condition| condition.is_true(&TestContext::new()))
+                .expect(source)
+        };
+        assert!(holds("$pc + 8 == 0x128"));
+        assert!(holds("[reg5; 8] != 0 && (reg12 < 2 || reg12 >= 3)"));
+        assert!(holds("reg12 % 2"));
+        assert!(!holds("reg12 > 3 or 0"));
+        assert!(Expression::parse_condition("$pc == 1 &&").is_err());
+        assert!(Expression::parse_condition("test_name == 1")
+            .and_then(|condition| condition.is_true(&TestContext::new()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for source in [
+            "", "foo", "reg64", "0xZZ", "$pc +", "($pc", "$pc)", "[$pc", "[$pc; ]", "$pc == 1",
+            "{$pc}",
+        ] {
+            let err = Expression::parse(source).expect_err(source);
+            assert!(matches!(err, ArgumentError::Invalid(_)), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_evaluation_errors() {
+        for source in [
+            "1 / 0",
+            "reg12 % 0",
+            "0 - 1",
+            "0xFFFFFFFFFFFFFFFF + 1",
+            "[0]",
+            "[reg5 + 12]",
+            "[reg5; 5000]",
+            "test_name + 1",
+            "[reg5; 2] + 1",
+        ] {
+            let err = evaluate(source).expect_err(source);
+            assert!(matches!(err, ArgumentError::Invalid(_)), "{source}");
+        }
+    }
+}
@@ -1,13 +1,15 @@
-use crate::server::{AdapterError, DapServer, HandlerResult};
+use crate::server::{util, AdapterError, DapServer, HandlerResult};
 use dap::{
-    requests::BreakpointLocationsArguments, responses::ResponseBody, types::BreakpointLocation,
+    requests::BreakpointLocationsArguments,
+    responses::ResponseBody,
+    types::{BreakpointLocation, StartDebuggingRequestKind},
 };
-use std::path::PathBuf;
+use std::path::Path;
 
 impl DapServer {
     /// Handles a `breakpoint_locations` request. Returns the list of [BreakpointLocation]s.
     pub(crate) fn handle_breakpoint_locations_command(
-        &self,
+        &mut self,
         args: &BreakpointLocationsArguments,
     ) -> HandlerResult {
         let result = self.breakpoint_locations(args).map(|breakpoints| {
@@ -21,29 +23,35 @@ impl DapServer {
         }
     }
 
+    /// Lists the lines and columns in the range of lines in `args` that breakpoints can be set on, which are the
+    /// starts of the spans of instructions.
     fn breakpoint_locations(
-        &self,
+        &mut self,
         args: &BreakpointLocationsArguments,
     ) -> Result<Vec<BreakpointLocation>, AdapterError> {
+        // Build the source maps so we can find the locations
+        if let Some(StartDebuggingRequestKind::Launch) = self.state.mode {
+            let _ = self.build_tests()?;
+        }
+
         let source_path = args
             .source
             .path
             .as_ref()
             .ok_or(AdapterError::MissingSourcePathArgument)?;
 
-        let existing_breakpoints = self
+        let end_line = args.end_line.unwrap_or(args.line);
+        let breakpoints = self
             .state
-            .breakpoints
-            .get(&PathBuf::from(source_path))
-            .ok_or(AdapterError::MissingBreakpointLocation)?;
-
-        let breakpoints = existing_breakpoints
-            .iter()
-            .filter_map(|bp| {
-                bp.line.map(|line| BreakpointLocation {
-                    line,
-                    ..Default::default()
-                })
+            .breakpoint_locations(
+                &util::normalize_path(Path::new(source_path)),
+                args.line..=end_line,
+            )
+            .into_iter()
+            .map(|(line, column)| BreakpointLocation {
+                line,
+                column: Some(column),
+                ..Default::default()
             })
             .collect();
 
@@ -54,53 +62,72 @@ impl DapServer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
+    use sway_core::source_map::{LocationRange, PathIndex, SourceMap, SourceMapSpan};
+    use sway_types::LineCol;
 
     const MOCK_SOURCE_PATH: &str = "some/path";
-    const MOCK_BP_ID: i64 = 1;
     const MOCK_LINE: i64 = 1;
 
-    #[test]
-    fn test_handle_breakpoint_locations_success() {
-        let mut server = DapServer::default();
-        server.state.breakpoints.insert(
-            PathBuf::from(MOCK_SOURCE_PATH),
-            vec![dap::types::Breakpoint {
-                id: Some(MOCK_BP_ID),
-                line: Some(MOCK_LINE),
-                ..Default::default()
-            }],
-        );
-        let args = BreakpointLocationsArguments {
+    fn span(line: usize, col: usize) -> SourceMapSpan {
+        SourceMapSpan {
+            path: PathIndex(0),
+            range: LocationRange {
+                start: LineCol { line, col },
+                end: LineCol { line, col: 20 },
+            },
+        }
+    }
+
+    fn get_test_args(end_line: Option<i64>) -> BreakpointLocationsArguments {
+        BreakpointLocationsArguments {
             source: dap::types::Source {
                 path: Some(MOCK_SOURCE_PATH.into()),
                 ..Default::default()
             },
+            line: MOCK_LINE,
+            end_line,
             ..Default::default()
-        };
-        let result = server.breakpoint_locations(&args).expect("success");
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].line, MOCK_LINE);
+        }
     }
 
     #[test]
-    #[should_panic(expected = "MissingSourcePathArgument")]
-    fn test_handle_breakpoint_locations_missing_argument() {
-        let server = DapServer::default();
-        let args = BreakpointLocationsArguments::default();
-        server.breakpoint_locations(&args).unwrap();
+    fn test_handle_breakpoint_locations_success() {
+        let mut server = DapServer::default();
+        let mut map = SourceMap::new();
+        map.paths.push(PathBuf::from(MOCK_SOURCE_PATH));
+        map.map.insert(1, span(1, 5));
+        map.map.insert(2, span(1, 12));
+        map.map.insert(3, span(2, 1));
+        server.state.set_source_map(map);
+
+        let result = server
+            .breakpoint_locations(&get_test_args(None))
+            .expect("success");
+        let locations: Vec<_> = result.iter().map(|bp| (bp.line, bp.column)).collect();
+        assert_eq!(locations, vec![(MOCK_LINE, Some(5)), (MOCK_LINE, Some(12))]);
+
+        let result = server
+            .breakpoint_locations(&get_test_args(Some(MOCK_LINE + 1)))
+            .expect("success");
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[2].line, MOCK_LINE + 1);
     }
 
     #[test]
-    #[should_panic(expected = "MissingBreakpointLocation")]
-    fn test_handle_breakpoint_locations_missing_breakpoint() {
-        let server = DapServer::default();
-        let args = BreakpointLocationsArguments {
-            source: dap::types::Source {
-                path: Some(MOCK_SOURCE_PATH.into()),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
+    fn test_handle_breakpoint_locations_not_built() {
+        let mut server = DapServer::default();
+        let result = server
+            .breakpoint_locations(&get_test_args(None))
+            .expect("success");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "MissingSourcePathArgument")]
+    fn test_handle_breakpoint_locations_missing_argument() {
+        let mut server = DapServer::default();
+        let args = BreakpointLocationsArguments::default();
         server.breakpoint_locations(&args).unwrap();
     }
 }
@@ -0,0 +1,94 @@
+use crate::server::{storage, AdapterError, DapServer, HandlerResult};
+use dap::{
+    requests::DataBreakpointInfoArguments,
+    responses::{DataBreakpointInfoResponse, ResponseBody},
+    types::{DataBreakpointAccessType, StartDebuggingRequestKind},
+};
+
+impl DapServer {
+    /// Handles a `data_breakpoint_info` request. Returns the ID of a data breakpoint on the storage slot named in
+    /// `args`, which is either a storage key or the path of a storage field of a contract being debugged.
+    pub(crate) fn handle_data_breakpoint_info_command(
+        &mut self,
+        args: &DataBreakpointInfoArguments,
+    ) -> HandlerResult {
+        match self.data_breakpoint_info(args) {
+            Ok(response) => HandlerResult::ok(ResponseBody::DataBreakpointInfo(response)),
+            Err(e) => HandlerResult::err_with_exit(e, 1),
+        }
+    }
+
+    fn data_breakpoint_info(
+        &mut self,
+        args: &DataBreakpointInfoArguments,
+    ) -> Result<DataBreakpointInfoResponse, AdapterError> {
+        // Build the tests so storage fields can be found among the storage slots of the contracts
+        if let Some(StartDebuggingRequestKind::Launch) = self.state.mode {
+            let _ = self.build_tests()?;
+        }
+
+        let name = args.name.trim();
+        let (key, description) = match storage::parse_storage_key(name) {
+            Some(key) => (Some(key), format!("Storage slot {name}")),
+            None => match storage::storage_field_key(name).filter(|key| {
+                self.state.packages.iter().any(|package| {
+                    package
+                        .built
+                        .storage_slots
+                        .iter()
+                        .any(|slot| slot.key() == key)
+                })
+            }) {
+                Some(key) => (
+                    Some(key),
+                    format!(
+                        "{name} (storage slot {})",
+                        storage::format_storage_key(&key)
+                    ),
+                ),
+                None => (None, format!("{name} is not a storage field or key")),
+            },
+        };
+        Ok(DataBreakpointInfoResponse {
+            data_id: key.as_ref().map(storage::format_storage_key),
+            description,
+            access_types: key.map(|_| vec![DataBreakpointAccessType::Write]),
+            can_persist: Some(true),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0x00000000000000000000000000000000000000000000000000000000000000ff";
+
+    fn args(name: &str) -> DataBreakpointInfoArguments {
+        DataBreakpointInfoArguments {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_handle_data_breakpoint_info_key() {
+        let mut server = DapServer::default();
+        let response = server.data_breakpoint_info(&args(KEY)).expect("success");
+        assert_eq!(response.data_id.as_deref(), Some(KEY));
+        assert_eq!(
+            response.access_types,
+            Some(vec![DataBreakpointAccessType::Write])
+        );
+    }
+
+    #[test]
+    fn test_handle_data_breakpoint_info_unknown_field() {
+        let mut server = DapServer::default();
+        let response = server
+            .data_breakpoint_info(&args("count"))
+            .expect("success");
+        assert_eq!(response.data_id, None);
+        assert_eq!(response.description, "count is not a storage field or key");
+    }
+}
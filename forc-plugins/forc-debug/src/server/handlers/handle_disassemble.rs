@@ -0,0 +1,94 @@
+use crate::{
+    server::{
+        handlers::handle_variables::format_instruction, util, AdapterError, DapServer,
+        HandlerResult,
+    },
+    types::Instruction,
+};
+use dap::{
+    requests::DisassembleArguments, responses::ResponseBody, types::DisassembledInstruction,
+};
+use fuel_tx::field::Script;
+use fuel_vm::fuel_asm::{self, RawInstruction};
+
+/// The size of a VM instruction in bytes.
+const INSTRUCTION_SIZE: i64 = 4;
+
+impl DapServer {
+    /// Handles a `disassemble` request. Returns the list of [DisassembledInstruction]s requested.
+    pub(crate) fn handle_disassemble_command(&self, args: &DisassembleArguments) -> HandlerResult {
+        let result = self.disassemble(args).map(|instructions| {
+            ResponseBody::Disassemble(dap::responses::DisassembleResponse { instructions })
+        });
+        match result {
+            Ok(result) => HandlerResult::ok(result),
+            Err(e) => HandlerResult::err(e),
+        }
+    }
+
+    /// Disassembles the script of the selected thread's test, starting at the memory reference offset by the byte and instruction
+    /// offsets in `args`. Addresses outside of the script are returned as invalid instructions, so that exactly
+    /// the requested number of instructions is returned.
+    fn disassemble(
+        &self,
+        args: &DisassembleArguments,
+    ) -> Result<Vec<DisassembledInstruction>, AdapterError> {
+        let thread = self
+            .state
+            .thread()
+            .ok_or(AdapterError::NoActiveTestExecutor)?;
+        let script = thread.executor.tx.transaction().script();
+
+        let start = util::parse_memory_reference(&args.memory_reference)?
+            + args.offset.unwrap_or_default()
+            + args.instruction_offset.unwrap_or_default() * INSTRUCTION_SIZE;
+
+        let instructions = (0..args.instruction_count.max(0))
+            .map(|index| {
+                let address = start + index * INSTRUCTION_SIZE;
+                let bytes = usize::try_from(address)
+                    .ok()
+                    .and_then(|address| script.get(address..address + INSTRUCTION_SIZE as usize))
+                    .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok());
+                match bytes {
+                    Some(bytes) => {
+                        self.disassembled_instruction(thread.package, address as Instruction, bytes)
+                    }
+                    None => DisassembledInstruction {
+                        address: util::format_memory_reference(address),
+                        instruction: "<invalid>".into(),
+                        ..Default::default()
+                    },
+                }
+            })
+            .collect();
+        Ok(instructions)
+    }
+
+    /// Disassembles the instruction at a VM program counter in a package, annotated with its source location if it
+    /// is mapped.
+    fn disassembled_instruction(
+        &self,
+        package: usize,
+        pc: Instruction,
+        bytes: [u8; 4],
+    ) -> DisassembledInstruction {
+        let instruction = fuel_asm::Instruction::try_from(RawInstruction::from_be_bytes(bytes))
+            .map(format_instruction)
+            .unwrap_or_else(|_| "<unknown>".into());
+        let location = self.state.vm_pc_to_source_location(package, pc).ok();
+        DisassembledInstruction {
+            address: util::format_memory_reference(pc as i64),
+            instruction_bytes: Some(bytes.iter().map(|byte| format!("{byte:02x}")).collect()),
+            instruction,
+            location: location
+                .as_ref()
+                .map(|location| util::path_into_source(&location.path)),
+            line: location.as_ref().map(|location| location.line),
+            column: location.as_ref().map(|location| location.column),
+            end_line: location.as_ref().and_then(|location| location.end_line),
+            end_column: location.as_ref().and_then(|location| location.end_column),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::server::{state::GotoTarget, util, AdapterError, DapServer, HandlerResult};
+use dap::{
+    requests::GotoTargetsArguments,
+    responses::{GotoTargetsResponse, ResponseBody},
+    types::{self, StartDebuggingRequestKind},
+};
+use std::path::Path;
+
+impl DapServer {
+    /// Handles a `goto_targets` request. Returns the [types::GotoTarget]s on the line in `args`, one for each
+    /// package with instructions on it, which the tests can be run to with a `goto` request.
+    pub(crate) fn handle_goto_targets_command(
+        &mut self,
+        args: &GotoTargetsArguments,
+    ) -> HandlerResult {
+        match self.goto_targets(args) {
+            Ok(targets) => {
+                HandlerResult::ok(ResponseBody::GotoTargets(GotoTargetsResponse { targets }))
+            }
+            Err(e) => HandlerResult::err_with_exit(e, 1),
+        }
+    }
+
+    fn goto_targets(
+        &mut self,
+        args: &GotoTargetsArguments,
+    ) -> Result<Vec<types::GotoTarget>, AdapterError> {
+        // Build the source maps so we can find the instructions on the line
+        if let Some(StartDebuggingRequestKind::Launch) = self.state.mode {
+            let _ = self.build_tests()?;
+        }
+
+        let source_path = args
+            .source
+            .path
+            .as_ref()
+            .ok_or(AdapterError::MissingSourcePathArgument)?;
+        let source_path = util::normalize_path(Path::new(source_path));
+
+        // A target on a column starts at the expression there, like a breakpoint on it.
+        let column = args.column.and_then(|column| {
+            self.state
+                .resolve_breakpoint_column(&source_path, args.line, column)
+        });
+        let targets: Vec<GotoTarget> = (0..self.state.packages.len())
+            .filter_map(|package| {
+                let pcs = match column {
+                    Some(column) => self
+                        .state
+                        .column_pcs(package, &source_path, args.line, column),
+                    None => self.state.line_pcs(package, &source_path, args.line),
+                };
+                (!pcs.is_empty()).then(|| GotoTarget {
+                    id: package as i64 + 1,
+                    package,
+                    pcs: pcs.to_vec(),
+                })
+            })
+            .collect();
+
+        let responses = targets
+            .iter()
+            .map(|target| {
+                let member_name = self
+                    .state
+                    .packages
+                    .get(target.package)
+                    .and_then(|package| package.member_name.as_ref());
+                let label = match member_name {
+                    Some(member_name) => format!("Line {} in {member_name}", args.line),
+                    None => format!("Line {}", args.line),
+                };
+                types::GotoTarget {
+                    id: target.id,
+                    label,
+                    line: args.line,
+                    column,
+                    end_line: None,
+                    end_column: None,
+                    instruction_pointer_reference: None,
+                }
+            })
+            .collect();
+        self.state.goto_targets = targets;
+        Ok(responses)
+    }
+}
@@ -0,0 +1,65 @@
+use crate::server::{util, AdapterError, DapServer, HandlerResult};
+use dap::{
+    responses::ResponseBody,
+    types::{Source, StartDebuggingRequestKind},
+};
+
+impl DapServer {
+    /// Handles a `loaded_sources` request. Returns the [Source]s of the packages being debugged and their
+    /// dependencies, including the standard library.
+    pub(crate) fn handle_loaded_sources_command(&mut self) -> HandlerResult {
+        let result = self.loaded_sources().map(|sources| {
+            ResponseBody::LoadedSources(dap::responses::LoadedSourcesResponse { sources })
+        });
+        match result {
+            Ok(result) => HandlerResult::ok(result),
+            Err(e) => HandlerResult::err_with_exit(e, 1),
+        }
+    }
+
+    fn loaded_sources(&mut self) -> Result<Vec<Source>, AdapterError> {
+        // Build the source maps so we can list their sources
+        if let Some(StartDebuggingRequestKind::Launch) = self.state.mode {
+            let _ = self.build_tests()?;
+        }
+
+        let sources = self
+            .state
+            .source_paths()
+            .iter()
+            .map(|path| Source {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned()),
+                ..util::path_into_source(path)
+            })
+            .collect();
+        Ok(sources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use sway_core::source_map::SourceMap;
+
+    #[test]
+    fn test_handle_loaded_sources() {
+        let mut server = DapServer::default();
+        assert!(server.loaded_sources().expect("success").is_empty());
+
+        let mut map = SourceMap::new();
+        map.paths.push(PathBuf::from("some/main.sw"));
+        map.paths.push(PathBuf::from("std/src/assert.sw"));
+        server.state.set_source_map(map);
+
+        let sources = server.loaded_sources().expect("success");
+        let names: Vec<_> = sources
+            .iter()
+            .map(|source| source.name.as_deref())
+            .collect();
+        assert_eq!(names, vec![Some("main.sw"), Some("assert.sw")]);
+        assert_eq!(sources[1].path.as_deref(), Some("std/src/assert.sw"));
+    }
+}
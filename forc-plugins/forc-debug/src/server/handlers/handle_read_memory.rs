@@ -0,0 +1,40 @@
+use crate::server::{util, AdapterError, DapServer, HandlerResult};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use dap::{
+    requests::ReadMemoryArguments,
+    responses::{ReadMemoryResponse, ResponseBody},
+};
+
+impl DapServer {
+    /// Handles a `readMemory` request. Returns the bytes of VM memory that are readable at the requested address.
+    pub(crate) fn handle_read_memory_command(&self, args: &ReadMemoryArguments) -> HandlerResult {
+        let result = self.read_memory(args).map(ResponseBody::ReadMemory);
+        match result {
+            Ok(result) => HandlerResult::ok(result),
+            Err(e) => HandlerResult::err(e),
+        }
+    }
+
+    /// Reads the memory of the selected thread's test, starting at the memory reference offset by the byte offset in `args`.
+    /// The requested bytes following the readable ones are reported as unreadable.
+    fn read_memory(&self, args: &ReadMemoryArguments) -> Result<ReadMemoryResponse, AdapterError> {
+        let thread = self
+            .state
+            .thread()
+            .ok_or(AdapterError::NoActiveTestExecutor)?;
+        let address =
+            util::parse_memory_reference(&args.memory_reference)? + args.offset.unwrap_or_default();
+        let count = args.count.max(0);
+
+        let bytes = match u64::try_from(address) {
+            Ok(start) => util::read_memory(&thread.executor, start, count as u64),
+            Err(_) => &[],
+        };
+
+        Ok(ReadMemoryResponse {
+            address: util::format_memory_reference(address),
+            unreadable_bytes: Some(count - bytes.len() as i64),
+            data: Some(BASE64.encode(bytes)),
+        })
+    }
+}
@@ -1,10 +1,17 @@
-use crate::server::{AdapterError, DapServer, HandlerResult};
+use crate::{
+    server::{
+        condition::{BreakpointCondition, HitCondition, LogMessage},
+        util, AdapterError, DapServer, HandlerResult,
+    },
+    types::ClientBreakpoint,
+};
 use dap::{
+    events::{self, Event},
     requests::SetBreakpointsArguments,
     responses::ResponseBody,
-    types::{Breakpoint, StartDebuggingRequestKind},
+    types::{self, Breakpoint, StartDebuggingRequestKind},
 };
-use std::path::PathBuf;
+use std::path::Path;
 
 impl DapServer {
     /// Handles a `set_breakpoints` request. Returns the list of [Breakpoint]s for the path provided in `args`.
@@ -36,7 +43,7 @@ impl DapServer {
             .as_ref()
             .ok_or(AdapterError::MissingSourcePathArgument)?;
 
-        let source_path_buf = PathBuf::from(source_path);
+        let source_path_buf = util::normalize_path(Path::new(source_path));
 
         let existing_breakpoints = self
             .state
@@ -51,47 +58,175 @@ impl DapServer {
             .unwrap_or_default()
             .iter()
             .map(|source_bp| {
-                // Check if there are any instructions mapped to this line in the source
-                let verified = self.state.source_map.map.iter().any(|(pc, _)| {
-                    if let Some((path, range)) = self.state.source_map.addr_to_span(*pc) {
-                        path == source_path_buf && range.start.line as i64 == source_bp.line
-                    } else {
-                        false
-                    }
-                });
-                if let Some(existing_bp) = existing_breakpoints
+                // Move the breakpoint to the first line with instructions mapped to it, if it has none, and to the
+                // start of an expression if it is set on a column.
+                let resolved_location = self.state.resolve_breakpoint_location(
+                    &source_path_buf,
+                    source_bp.line,
+                    source_bp.column,
+                );
+                let (line, column) =
+                    resolved_location.unwrap_or((source_bp.line, source_bp.column));
+                // Conditions that fail to parse reject the breakpoint, with the reason as its message.
+                let non_empty = |condition: &&str| !condition.trim().is_empty();
+                let conditions = source_bp
+                    .condition
+                    .as_deref()
+                    .filter(non_empty)
+                    .map(BreakpointCondition::parse)
+                    .transpose()
+                    .and_then(|condition| {
+                        let hit_condition = source_bp
+                            .hit_condition
+                            .as_deref()
+                            .filter(non_empty)
+                            .map(HitCondition::parse)
+                            .transpose()?;
+                        let log_message = source_bp
+                            .log_message
+                            .as_deref()
+                            .filter(non_empty)
+                            .map(LogMessage::parse)
+                            .transpose()?;
+                        Ok((condition, hit_condition, log_message))
+                    });
+                let tests_built = self.state.tests_built();
+                let ((condition, hit_condition, log_message), message) =
+                    match (conditions, resolved_location) {
+                        (Ok(conditions), Some(_)) => (conditions, None),
+                        // Breakpoints set before the tests are built are bound once they are.
+                        (Ok(conditions), None) if !tests_built => {
+                            (conditions, Some(PENDING_MESSAGE.into()))
+                        }
+                        (Ok(_), None) => (
+                            (None, None, None),
+                            Some(no_code_message(self.state.optimized)),
+                        ),
+                        (Err(err), _) => ((None, None, None), Some(err.to_string())),
+                    };
+                let verified = message.is_none();
+                let breakpoint = if let Some(existing_bp) = existing_breakpoints
                     .iter()
-                    .find(|bp| (bp.line == Some(source_bp.line)))
+                    .find(|bp| bp.line == Some(line) && bp.column == column)
                 {
                     Breakpoint {
                         verified,
-                        ..existing_bp.clone()
+                        message,
+                        ..existing_bp.breakpoint.clone()
                     }
                 } else {
-                    let id = Some(self.breakpoint_id_gen.next());
+                    // Breakpoints set on a line again, after being removed or rejected, or across restarts, keep
+                    // their ID.
+                    let id = Some(
+                        *self
+                            .state
+                            .breakpoint_ids
+                            .entry((source_path_buf.clone(), line, column))
+                            .or_insert_with(|| self.breakpoint_id_gen.next()),
+                    );
                     Breakpoint {
                         id,
                         verified,
-                        line: Some(source_bp.line),
+                        message,
+                        line: Some(line),
+                        column,
                         source: Some(args.source.clone()),
                         ..Default::default()
                     }
+                };
+                ClientBreakpoint {
+                    breakpoint,
+                    condition,
+                    hit_condition,
+                    log_message,
                 }
             })
             .collect::<Vec<_>>();
 
-        self.state
-            .breakpoints
-            .insert(source_path_buf, breakpoints.clone());
+        let response = breakpoints.iter().map(|bp| bp.breakpoint.clone()).collect();
+        // The hit counts start over whenever the breakpoints of a file are set.
+        for bp in &existing_breakpoints {
+            if let Some(id) = bp.id {
+                self.state.breakpoint_hits().remove(&id);
+            }
+        }
+
+        // Breakpoints that can't be set are reported back to the client with the reason, but never stored.
+        let breakpoints = breakpoints
+            .into_iter()
+            .filter(|bp| bp.verified || bp.message.as_deref() == Some(PENDING_MESSAGE))
+            .collect();
+        self.state.breakpoints.insert(source_path_buf, breakpoints);
+        self.state.breakpoints_need_update = true;
+
+        Ok(response)
+    }
+
+    /// Binds the breakpoints that were set before the tests were built, now that they are, and sends `breakpoint`
+    /// events to let the client know whether they could be set.
+    pub(crate) fn bind_pending_breakpoints(&mut self) {
+        let mut changed = vec![];
+        let mut all_breakpoints = std::mem::take(&mut self.state.breakpoints);
+        for (source_path, breakpoints) in all_breakpoints.iter_mut() {
+            for bp in breakpoints.iter_mut().filter(|bp| !bp.verified) {
+                let resolved_location = bp.line.and_then(|line| {
+                    self.state
+                        .resolve_breakpoint_location(source_path, line, bp.column)
+                });
+                match resolved_location {
+                    Some((line, column)) => {
+                        if let Some(id) = bp.id {
+                            self.state
+                                .breakpoint_ids
+                                .insert((source_path.clone(), line, column), id);
+                        }
+                        bp.breakpoint.line = Some(line);
+                        bp.breakpoint.column = column;
+                        bp.breakpoint.verified = true;
+                        bp.breakpoint.message = None;
+                    }
+                    None => bp.breakpoint.message = Some(no_code_message(self.state.optimized)),
+                }
+                changed.push(bp.breakpoint.clone());
+            }
+            breakpoints.retain(|bp| bp.verified);
+        }
+        self.state.breakpoints = all_breakpoints;
         self.state.breakpoints_need_update = true;
 
-        Ok(breakpoints)
+        for breakpoint in changed {
+            let _ = self
+                .server
+                .send_event(Event::Breakpoint(events::BreakpointEventBody {
+                    reason: types::BreakpointEventReason::Changed,
+                    breakpoint,
+                }));
+        }
+    }
+}
+
+/// The message of a breakpoint set before the tests are built, which is bound once they are.
+const PENDING_MESSAGE: &str = "The breakpoint will be set once the tests are built";
+
+/// Returns the message of a breakpoint on a line without code, which can't be set. Lines of optimized builds can
+/// lose their code to optimizations.
+fn no_code_message(optimized: bool) -> String {
+    let message = "No code is generated for this line or the lines after it";
+    if optimized {
+        format!(
+            "{message}, it may have been optimized away. Build the tests with the debug profile to set \
+             breakpoints on any line."
+        )
+    } else {
+        message.into()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::server::expression::tests::TestContext;
+    use std::path::PathBuf;
     use sway_core::source_map::{LocationRange, PathIndex, SourceMap, SourceMapSpan};
     use sway_types::LineCol;
 
@@ -122,7 +257,7 @@ mod tests {
                     },
                 },
             );
-            server.state.source_map = map;
+            server.state.set_source_map(map);
         }
         if existing_bp {
             server.state.breakpoints.insert(
@@ -136,7 +271,8 @@ mod tests {
                         ..Default::default()
                     }),
                     ..Default::default()
-                }],
+                }
+                .into()],
             );
         }
         server
@@ -200,6 +336,168 @@ mod tests {
         assert!(result[0].verified);
     }
 
+    #[test]
+    fn test_handle_set_breakpoints_condition() {
+        let mut server = get_test_server(true, true);
+        let mut args = get_test_args();
+        args.breakpoints.as_mut().unwrap()[0].condition = Some("$pc == 0x4".into());
+        let result = server.set_breakpoints(&args).expect("success");
+        assert!(result[0].verified);
+        assert_eq!(result[0].message, None);
+
+        let stored = &server.state.breakpoints[&PathBuf::from(MOCK_SOURCE_PATH)];
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].id, Some(MOCK_BP_ID));
+        let condition = stored[0].condition.as_ref().expect("condition");
+        let mut registers = vec![0; 64];
+        assert!(!condition.evaluate(&TestContext::with_registers(registers.clone())));
+        registers[3] = 4;
+        assert!(condition.evaluate(&TestContext::with_registers(registers)));
+    }
+
+    #[test]
+    fn test_handle_set_breakpoints_invalid_condition() {
+        let mut server = get_test_server(true, true);
+        let mut args = get_test_args();
+        args.breakpoints.as_mut().unwrap()[0].condition = Some("$pc === 4".into());
+        let result = server.set_breakpoints(&args).expect("success");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, Some(MOCK_BP_ID));
+        assert!(!result[0].verified);
+        assert!(result[0]
+            .message
+            .as_ref()
+            .expect("message")
+            .contains("breakpoint condition"));
+        assert!(server.state.breakpoints[&PathBuf::from(MOCK_SOURCE_PATH)].is_empty());
+    }
+
+    #[test]
+    fn test_handle_set_breakpoints_keeps_id() {
+        let mut server = get_test_server(true, false);
+        let mut args = get_test_args();
+        let id = server.set_breakpoints(&args).expect("success")[0].id;
+
+        // Removing the breakpoint and setting it again keeps its ID, as does rejecting it
+        args.breakpoints = Some(vec![]);
+        server.set_breakpoints(&args).expect("success");
+        args.breakpoints = get_test_args().breakpoints;
+        args.breakpoints.as_mut().unwrap()[0].condition = Some("$pc ==".into());
+        let result = server.set_breakpoints(&args).expect("success");
+        assert!(!result[0].verified);
+        assert_eq!(result[0].id, id);
+
+        server.state.reset();
+        let result = server.set_breakpoints(&get_test_args()).expect("success");
+        assert!(result[0].verified);
+        assert_eq!(result[0].id, id);
+    }
+
+    #[test]
+    fn test_handle_set_breakpoints_column() {
+        let mut server = get_test_server(true, false);
+        let mut args = get_test_args();
+        args.breakpoints.as_mut().unwrap()[0].column = Some(4);
+        let result = server.set_breakpoints(&args).expect("success");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].line, Some(MOCK_LINE));
+        assert_eq!(result[0].column, Some(0));
+        assert!(result[0].verified);
+
+        // A breakpoint on the whole line is another breakpoint
+        let line_result = server.set_breakpoints(&get_test_args()).expect("success");
+        assert_eq!(line_result[0].column, None);
+        assert_ne!(line_result[0].id, result[0].id);
+    }
+
+    #[test]
+    fn test_handle_set_breakpoints_moved_to_next_line() {
+        let mut server = get_test_server(true, false);
+        let mut args = get_test_args();
+        args.breakpoints.as_mut().unwrap()[0].line = MOCK_LINE - 1;
+        args.breakpoints.as_mut().unwrap()[0].column = Some(4);
+        let result = server.set_breakpoints(&args).expect("success");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].line, Some(MOCK_LINE));
+        assert_eq!(result[0].column, None);
+        assert!(result[0].verified);
+
+        let stored = &server.state.breakpoints[&PathBuf::from(MOCK_SOURCE_PATH)];
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].line, Some(MOCK_LINE));
+    }
+
+    #[test]
+    fn test_handle_set_breakpoints_no_code() {
+        let mut server = get_test_server(true, false);
+        let mut args = get_test_args();
+        args.breakpoints.as_mut().unwrap()[0].line = MOCK_LINE + 1;
+        let result = server.set_breakpoints(&args).expect("success");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].line, Some(MOCK_LINE + 1));
+        assert!(!result[0].verified);
+        assert!(result[0].message.is_some());
+        assert!(server.state.breakpoints[&PathBuf::from(MOCK_SOURCE_PATH)].is_empty());
+    }
+
+    #[test]
+    fn test_handle_set_breakpoints_before_build() {
+        let mut server = get_test_server(false, false);
+        let mut args = get_test_args();
+        args.breakpoints.as_mut().unwrap()[0].line = MOCK_LINE - 1;
+        let result = server.set_breakpoints(&args).expect("success");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].line, Some(MOCK_LINE - 1));
+        assert!(!result[0].verified);
+        assert_eq!(result[0].message.as_deref(), Some(PENDING_MESSAGE));
+
+        // The breakpoint is moved to the next line with code once the tests are built.
+        let server_with_map = get_test_server(true, false);
+        server
+            .state
+            .set_source_map(server_with_map.state.source_map(0).unwrap().clone());
+        server.bind_pending_breakpoints();
+        let stored = &server.state.breakpoints[&PathBuf::from(MOCK_SOURCE_PATH)];
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].line, Some(MOCK_LINE));
+        assert!(stored[0].verified);
+        assert_eq!(stored[0].message, None);
+    }
+
+    #[test]
+    fn test_handle_set_breakpoints_before_build_no_code() {
+        let mut server = get_test_server(false, false);
+        let mut args = get_test_args();
+        args.breakpoints.as_mut().unwrap()[0].line = MOCK_LINE + 1;
+        server.set_breakpoints(&args).expect("success");
+        assert_eq!(
+            server.state.breakpoints[&PathBuf::from(MOCK_SOURCE_PATH)].len(),
+            1
+        );
+
+        let server_with_map = get_test_server(true, false);
+        server
+            .state
+            .set_source_map(server_with_map.state.source_map(0).unwrap().clone());
+        server.bind_pending_breakpoints();
+        assert!(server.state.breakpoints[&PathBuf::from(MOCK_SOURCE_PATH)].is_empty());
+    }
+
+    #[test]
+    fn test_handle_set_breakpoints_no_code_optimized() {
+        let mut server = get_test_server(true, false);
+        server.state.optimized = true;
+        let mut args = get_test_args();
+        args.breakpoints.as_mut().unwrap()[0].line = MOCK_LINE + 1;
+        let result = server.set_breakpoints(&args).expect("success");
+        assert!(!result[0].verified);
+        assert!(result[0]
+            .message
+            .as_ref()
+            .expect("message")
+            .contains("optimized away"));
+    }
+
     #[test]
     #[should_panic(expected = "MissingSourcePathArgument")]
     fn test_handle_breakpoint_locations_missing_argument() {
@@ -0,0 +1,113 @@
+use crate::server::{
+    storage::{self, DataBreakpoint},
+    DapServer, HandlerResult,
+};
+use dap::{
+    requests::SetDataBreakpointsArguments,
+    responses::{ResponseBody, SetDataBreakpointsResponse},
+    types::{Breakpoint, DataBreakpointAccessType},
+};
+
+impl DapServer {
+    /// Handles a `set_data_breakpoints` request. Replaces the data breakpoints, which stop a test after it writes
+    /// one of the watched storage slots.
+    pub(crate) fn handle_set_data_breakpoints(
+        &mut self,
+        args: &SetDataBreakpointsArguments,
+    ) -> HandlerResult {
+        let breakpoints = self.set_data_breakpoints(args);
+        HandlerResult::ok(ResponseBody::SetDataBreakpoints(
+            SetDataBreakpointsResponse { breakpoints },
+        ))
+    }
+
+    fn set_data_breakpoints(&mut self, args: &SetDataBreakpointsArguments) -> Vec<Breakpoint> {
+        let mut data_breakpoints = vec![];
+        let mut breakpoints = vec![];
+        for breakpoint in &args.breakpoints {
+            let Some(key) = storage::parse_storage_key(&breakpoint.data_id) else {
+                breakpoints.push(Breakpoint {
+                    verified: false,
+                    message: Some("Invalid storage key".into()),
+                    ..Default::default()
+                });
+                continue;
+            };
+            if matches!(
+                breakpoint.access_type,
+                Some(DataBreakpointAccessType::Read | DataBreakpointAccessType::ReadWrite)
+            ) {
+                breakpoints.push(Breakpoint {
+                    verified: false,
+                    message: Some("Only writes to storage slots can be watched".into()),
+                    ..Default::default()
+                });
+                continue;
+            }
+
+            // A slot that stays watched keeps the ID of its breakpoint.
+            let id = self
+                .state
+                .data_breakpoints
+                .iter()
+                .find(|data_breakpoint| data_breakpoint.key == key)
+                .map(|data_breakpoint| data_breakpoint.id)
+                .unwrap_or_else(|| self.breakpoint_id_gen.next());
+            data_breakpoints.push(DataBreakpoint { id, key });
+            breakpoints.push(Breakpoint {
+                id: Some(id),
+                verified: true,
+                ..Default::default()
+            });
+        }
+        self.state.data_breakpoints = data_breakpoints;
+        breakpoints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0x00000000000000000000000000000000000000000000000000000000000000ff";
+
+    fn args(
+        breakpoints: &[(&str, Option<DataBreakpointAccessType>)],
+    ) -> SetDataBreakpointsArguments {
+        SetDataBreakpointsArguments {
+            breakpoints: breakpoints
+                .iter()
+                .map(|(data_id, access_type)| dap::types::DataBreakpoint {
+                    data_id: data_id.to_string(),
+                    access_type: access_type.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_handle_set_data_breakpoints() {
+        let mut server = DapServer::default();
+        let breakpoints = server.set_data_breakpoints(&args(&[
+            (KEY, Some(DataBreakpointAccessType::Write)),
+            ("count", None),
+            (KEY, Some(DataBreakpointAccessType::Read)),
+        ]));
+        assert!(breakpoints[0].verified);
+        assert!(!breakpoints[1].verified);
+        assert_eq!(
+            breakpoints[2].message.as_deref(),
+            Some("Only writes to storage slots can be watched")
+        );
+        assert_eq!(server.state.data_breakpoints.len(), 1);
+
+        // Setting the breakpoint again keeps its ID.
+        let id = breakpoints[0].id;
+        let breakpoints = server.set_data_breakpoints(&args(&[(KEY, None)]));
+        assert_eq!(breakpoints[0].id, id);
+        let breakpoints = server.set_data_breakpoints(&args(&[]));
+        assert!(breakpoints.is_empty());
+        assert!(server.state.data_breakpoints.is_empty());
+    }
+}
@@ -1,12 +1,23 @@
-use crate::server::{util, AdapterError, DapServer, HandlerResult};
+use crate::{
+    server::{
+        state::SourceLocation, thread::DebugThread, util, AdapterError, DapServer, HandlerResult,
+    },
+    types::Instruction,
+};
 use dap::{
+    requests::StackTraceArguments,
     responses::ResponseBody,
     types::{StackFrame, StackFramePresentationhint},
 };
 
 impl DapServer {
-    /// Handles a `stack_trace` request. Returns the list of [StackFrame]s for the current execution state.
-    pub(crate) fn handle_stack_trace_command(&self) -> HandlerResult {
+    /// Handles a `stack_trace` request. Returns the list of [StackFrame]s of the requested thread, and selects it
+    /// so the scopes and variables requested next are the thread's.
+    pub(crate) fn handle_stack_trace_command(
+        &mut self,
+        args: &StackTraceArguments,
+    ) -> HandlerResult {
+        self.state.select_thread(args.thread_id);
         let result = self.stack_trace().map(|stack_frames| {
             ResponseBody::StackTrace(dap::responses::StackTraceResponse {
                 stack_frames,
@@ -20,49 +31,85 @@ impl DapServer {
     }
 
     fn stack_trace(&self) -> Result<Vec<StackFrame>, AdapterError> {
-        let executor = self
+        let thread = self
             .state
-            .executors
-            .first()
+            .thread()
             .ok_or(AdapterError::NoActiveTestExecutor)?;
-        let name = executor.name.clone();
+        let pc = util::current_instruction(thread.executor.interpreter.registers());
 
-        let source_location = match self.state.stopped_on_breakpoint_id {
-            // If we stopped on a breakpoint, use the breakpoint's source location.
-            Some(breakpoint_id) => self.state.breakpoints.iter().find_map(|(_, breakpoints)| {
-                breakpoints.iter().find_map(|bp| {
-                    if Some(breakpoint_id) == bp.id {
-                        if let Some(bp_line) = bp.line {
-                            return Some((bp.source.clone(), bp_line));
-                        }
-                    }
-                    None
-                })
-            }),
-            // Otherwise, use the current instruction's source location.
-            None => self
-                .state
-                .vm_pc_to_source_location(util::current_instruction(
-                    executor.interpreter.registers(),
-                ))
-                .ok()
-                .map(|(source_path, line)| (Some(util::path_into_source(&source_path)), line)),
-        };
+        // The innermost frame is at the current instruction, and every other frame is at the call it made.
+        let frame_pcs =
+            std::iter::once(pc).chain(thread.call_stack.iter().rev().map(|frame| frame.call_pc));
+        // Each frame runs the function entered by the call recorded above it, and the outermost one runs the test.
+        let function_names = thread
+            .call_stack
+            .iter()
+            .rev()
+            .map(|frame| {
+                self.state
+                    .function_name(thread.package, frame.function_pc)
+                    .unwrap_or_else(|| "<unknown>".into())
+            })
+            .chain(std::iter::once(thread.name().to_string()));
 
-        // For now, we only return 1 stack frame.
-        let stack_frames = source_location
-            .map(|(source, line)| {
-                vec![StackFrame {
-                    id: 0,
-                    name,
-                    source,
-                    line,
-                    column: 0,
-                    presentation_hint: Some(StackFramePresentationhint::Normal),
-                    ..Default::default()
-                }]
+        let stack_frames = frame_pcs
+            .zip(function_names)
+            .enumerate()
+            .map(|(id, (pc, name))| {
+                let id = id as i64;
+                let instruction_pointer_reference = Some(format!("0x{pc:x}"));
+                match self.frame_source_location(thread, id, pc) {
+                    Some(location) => StackFrame {
+                        id,
+                        name,
+                        source: Some(util::path_into_source(&location.path)),
+                        line: location.line,
+                        column: location.column,
+                        end_line: location.end_line,
+                        end_column: location.end_column,
+                        instruction_pointer_reference,
+                        presentation_hint: Some(StackFramePresentationhint::Normal),
+                        ..Default::default()
+                    },
+                    None => StackFrame {
+                        id,
+                        name: format!("<unknown> @ 0x{pc:x}"),
+                        instruction_pointer_reference,
+                        presentation_hint: Some(StackFramePresentationhint::Subtle),
+                        ..Default::default()
+                    },
+                }
             })
-            .unwrap_or_default();
+            .collect();
         Ok(stack_frames)
     }
+
+    /// Finds the source location of the stack frame of a thread with the given id, stopped at the given program
+    /// counter.
+    fn frame_source_location(
+        &self,
+        thread: &DebugThread,
+        id: i64,
+        pc: Instruction,
+    ) -> Option<SourceLocation> {
+        // Use the span of the frame's instruction, so the expression it executes is highlighted.
+        let instruction_location = self.state.vm_pc_to_source_location(thread.package, pc).ok();
+        // Otherwise, if we stopped on a breakpoint that is still set, use its location for the innermost frame.
+        instruction_location.or_else(|| {
+            let breakpoint_id = thread.stopped_on_breakpoint_id.filter(|_| id == 0)?;
+            self.state
+                .breakpoints
+                .iter()
+                .find_map(|(source_path, breakpoints)| {
+                    let bp = breakpoints.iter().find(|bp| bp.id == Some(breakpoint_id))?;
+                    Some(SourceLocation {
+                        path: source_path.clone(),
+                        line: bp.line?,
+                        column: bp.column.unwrap_or_default(),
+                        end_line: None,
+                        end_column: None,
+                    })
+                })
+        })
+    }
 }
@@ -1,15 +1,16 @@
 use crate::{
-    names::register_name,
+    names::{register_name, REGISTERS},
     server::{
-        AdapterError, DapServer, HandlerResult, INSTRUCTIONS_VARIABLE_REF, REGISTERS_VARIABLE_REF,
+        util, AdapterError, DapServer, HandlerResult, EXECUTION_VARIABLE_REF,
+        INSTRUCTIONS_VARIABLE_REF, REGISTERS_VARIABLE_REF,
     },
 };
 use dap::{requests::VariablesArguments, responses::ResponseBody, types::Variable};
-use fuel_vm::fuel_asm::{Imm06, Imm12, Imm18, Imm24, Instruction, RawInstruction, RegId};
+use fuel_vm::fuel_asm::{Imm06, Imm12, Imm18, Imm24, Instruction, RegId};
 
 impl DapServer {
     /// Processes a variables request, returning all variables and their current values.
-    pub(crate) fn handle_variables_command(&self, args: &VariablesArguments) -> HandlerResult {
+    pub(crate) fn handle_variables_command(&mut self, args: &VariablesArguments) -> HandlerResult {
         let result = self.get_variables(args).map(|variables| {
             ResponseBody::Variables(dap::responses::VariablesResponse { variables })
         });
@@ -19,65 +20,101 @@ impl DapServer {
         }
     }
 
-    /// Returns the list of [Variable]s for the current execution state.
+    /// Returns the list of [Variable]s for the execution state of the selected thread's test.
     pub(crate) fn get_variables(
-        &self,
+        &mut self,
         args: &VariablesArguments,
     ) -> Result<Vec<Variable>, AdapterError> {
-        let executor = self
+        let executor = &self
             .state
-            .executors
-            .first()
-            .ok_or(AdapterError::NoActiveTestExecutor)?;
+            .thread()
+            .ok_or(AdapterError::NoActiveTestExecutor)?
+            .executor;
 
-        let register_variables = executor
-            .interpreter
-            .registers()
-            .iter()
-            .enumerate()
-            .map(|(index, value)| Variable {
-                name: register_name(index),
-                value: format!("0x{value:X?}"),
-                ..Default::default()
-            })
-            .collect::<Vec<_>>();
-
-        // Slice out current opcode pc-4..pc and then parse using fuel-asm
-        // to return the opcode and its arguments.
-        let pc = executor.interpreter.registers()[RegId::PC] as usize;
-        let instruction_variables = match Instruction::try_from(RawInstruction::from_be_bytes(
-            executor.interpreter.memory()[pc..pc + 4]
-                .try_into()
-                .unwrap(),
-        )) {
-            Ok(instruction) => vec![
-                ("Opcode", Some(format!("{:?}", instruction.opcode()))),
-                ("rA", ra(instruction)),
-                ("rB", rb(instruction)),
-                ("rC", rc(instruction)),
-                ("rD", rd(instruction)),
-                ("imm", imm(instruction)),
-            ]
-            .iter()
-            .filter_map(|(name, value)| {
-                value.as_ref().map(|value| Variable {
-                    name: (*name).to_string(),
-                    value: value.to_string(),
-                    ..Default::default()
+        let variables = match args.variables_reference {
+            REGISTERS_VARIABLE_REF => executor
+                .interpreter
+                .registers()
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    // Label the special registers the way they are written in assembly.
+                    let name = if index < REGISTERS.len() {
+                        format!("${}", register_name(index))
+                    } else {
+                        register_name(index)
+                    };
+                    let mut variable = variable(name, util::format_word(*value));
+                    if POINTER_REGISTERS.contains(&index) {
+                        variable.memory_reference =
+                            Some(util::format_memory_reference(*value as i64));
+                    }
+                    variable
                 })
-            })
-            .collect(),
-            Err(_) => vec![],
+                .collect(),
+            INSTRUCTIONS_VARIABLE_REF => match util::decode_instruction_at_pc(executor) {
+                Some(instruction) => vec![
+                    ("Opcode", Some(format!("{:?}", instruction.opcode()))),
+                    ("rA", ra(instruction)),
+                    ("rB", rb(instruction)),
+                    ("rC", rc(instruction)),
+                    ("rD", rd(instruction)),
+                    ("imm", imm(instruction)),
+                ]
+                .into_iter()
+                .filter_map(|(name, value)| value.map(|value| variable(name, value)))
+                .collect(),
+                None => vec![],
+            },
+            EXECUTION_VARIABLE_REF => {
+                let gas_used = util::gas_used(executor);
+                let instruction = util::decode_instruction_at_pc(executor)
+                    .map(format_instruction)
+                    .unwrap_or_else(|| "<unknown>".into());
+                vec![
+                    variable("Test", executor.name.clone()),
+                    variable("Context", format!("{:?}", executor.interpreter.context())),
+                    variable("Gas used", util::format_word(gas_used)),
+                    variable("Instruction", instruction),
+                ]
+            }
+            _ => vec![],
         };
+        Ok(variables)
+    }
+}
 
-        match args.variables_reference {
-            REGISTERS_VARIABLE_REF => Ok(register_variables),
-            INSTRUCTIONS_VARIABLE_REF => Ok(instruction_variables),
-            _ => Ok(vec![]),
-        }
+/// The registers that point into VM memory, which can be inspected with a `readMemory` request.
+const POINTER_REGISTERS: [usize; 4] = [
+    RegId::SSP.to_u8() as usize,
+    RegId::SP.to_u8() as usize,
+    RegId::FP.to_u8() as usize,
+    RegId::HP.to_u8() as usize,
+];
+
+fn variable(name: impl Into<String>, value: String) -> Variable {
+    Variable {
+        name: name.into(),
+        value,
+        ..Default::default()
     }
 }
 
+/// Formats an instruction as its opcode followed by its operands, e.g. `SW reg59 one 0x1`.
+pub(crate) fn format_instruction(instruction: Instruction) -> String {
+    std::iter::once(Some(format!("{:?}", instruction.opcode())))
+        .chain([
+            ra(instruction),
+            rb(instruction),
+            rc(instruction),
+            rd(instruction),
+            imm(instruction),
+        ])
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn reg_id_to_string(reg_id: Option<RegId>) -> Option<String> {
     reg_id.map(|reg_id| register_name(reg_id.into()))
 }
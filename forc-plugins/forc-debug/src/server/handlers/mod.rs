@@ -1,19 +1,31 @@
 use crate::{
     error::AdapterError,
     server::{
-        AdditionalData, DapServer, HandlerResult, INSTRUCTIONS_VARIABLE_REF,
-        REGISTERS_VARIABLE_REF, THREAD_ID,
+        expression::Expression, state::ExceptionFilter, AdditionalData, DapServer,
+        ExecutionContextArgs, HandlerResult, Step, EXECUTION_VARIABLE_REF,
+        INSTRUCTIONS_VARIABLE_REF, REGISTERS_VARIABLE_REF,
     },
+    types::TestFilter,
 };
 use dap::{
     prelude::*,
-    types::{Scope, StartDebuggingRequestKind},
+    types::{Scope, StartDebuggingRequestKind, SteppingGranularity},
+};
+use requests::{
+    ContinueArguments, EvaluateArguments, GotoArguments, InitializeArguments,
+    LaunchRequestArguments, NextArguments, SetExceptionBreakpointsArguments, StepInArguments,
+    StepOutArguments,
 };
-use requests::{EvaluateArguments, LaunchRequestArguments};
 use std::path::PathBuf;
 
 pub(crate) mod handle_breakpoint_locations;
+pub(crate) mod handle_data_breakpoint_info;
+pub(crate) mod handle_disassemble;
+pub(crate) mod handle_goto_targets;
+pub(crate) mod handle_loaded_sources;
+pub(crate) mod handle_read_memory;
 pub(crate) mod handle_set_breakpoints;
+pub(crate) mod handle_set_data_breakpoints;
 pub(crate) mod handle_stack_trace;
 pub(crate) mod handle_variables;
 
@@ -24,10 +36,31 @@ impl DapServer {
         HandlerResult::ok_with_exit(ResponseBody::Attach, 0)
     }
 
-    pub(crate) fn handle_initialize(&mut self) -> HandlerResult {
+    /// Handles an `initialize` request. Records the client's capabilities and returns the server's.
+    pub(crate) fn handle_initialize(&mut self, args: &InitializeArguments) -> HandlerResult {
+        self.state.supports_progress_reporting = args.supports_progress_reporting.unwrap_or(false);
         HandlerResult::ok(ResponseBody::Initialize(types::Capabilities {
             supports_breakpoint_locations_request: Some(true),
             supports_configuration_done_request: Some(true),
+            supports_data_breakpoints: Some(true),
+            supports_stepping_granularity: Some(true),
+            supports_disassemble_request: Some(true),
+            supports_goto_targets_request: Some(true),
+            supports_loaded_sources_request: Some(true),
+            supports_read_memory_request: Some(true),
+            supports_restart_request: Some(true),
+            supports_terminate_request: Some(true),
+            exception_breakpoint_filters: Some(
+                ExceptionFilter::ALL
+                    .into_iter()
+                    .map(|filter| types::ExceptionBreakpointsFilter {
+                        filter: filter.id().into(),
+                        label: filter.label().into(),
+                        default: Some(false),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
             ..Default::default()
         }))
     }
@@ -42,33 +75,91 @@ impl DapServer {
         if let Some(additional_data) = &args.additional_data {
             if let Ok(data) = serde_json::from_value::<AdditionalData>(additional_data.clone()) {
                 self.state.program_path = PathBuf::from(data.program);
+                let execution_context = match data.execution_context {
+                    Some(value) => match ExecutionContextArgs::parse(value) {
+                        Ok(execution_context) => execution_context,
+                        Err(e) => return HandlerResult::err_with_exit(e, 1),
+                    },
+                    None => Default::default(),
+                };
+                if self.state.build_profile != data.build_profile
+                    || self.state.execution_context != execution_context
+                {
+                    // The tests need to be built again with the other profile, and set up again in the other
+                    // context.
+                    self.state.build_profile = data.build_profile;
+                    self.state.execution_context = execution_context;
+                    self.state.packages.clear();
+                }
+                self.state.test_filter = None;
+                if let Some(test_filter) = data.test_filter {
+                    if let Err(e) = self.set_test_filter(TestFilter::new(&test_filter)) {
+                        return HandlerResult::err_with_exit(e, 1);
+                    }
+                }
                 return HandlerResult::ok(ResponseBody::Launch);
             }
         }
         HandlerResult::err_with_exit(AdapterError::MissingConfiguration, 1)
     }
 
-    /// Handles a `next` request. Returns true if the server should continue running.
-    pub(crate) fn handle_next(&mut self) -> HandlerResult {
-        match self.continue_debugging_tests(true) {
-            Ok(true) => HandlerResult::ok(ResponseBody::Next),
+    /// Handles a `next` request. Steps the thread over the current line.
+    pub(crate) fn handle_next(&mut self, args: &NextArguments) -> HandlerResult {
+        self.handle_step(
+            args.thread_id,
+            Step::Over,
+            args.granularity.as_ref(),
+            ResponseBody::Next,
+        )
+    }
+
+    /// Handles a `stepIn` request. Steps the thread to the next line, entering any function called on the current
+    /// line.
+    pub(crate) fn handle_step_in(&mut self, args: &StepInArguments) -> HandlerResult {
+        self.handle_step(
+            args.thread_id,
+            Step::In,
+            args.granularity.as_ref(),
+            ResponseBody::StepIn,
+        )
+    }
+
+    /// Handles a `stepOut` request. Steps the thread until the current function returns.
+    pub(crate) fn handle_step_out(&mut self, args: &StepOutArguments) -> HandlerResult {
+        self.handle_step(
+            args.thread_id,
+            Step::Out,
+            args.granularity.as_ref(),
+            ResponseBody::StepOut,
+        )
+    }
+
+    fn handle_step(
+        &mut self,
+        thread_id: i64,
+        step: Step,
+        granularity: Option<&SteppingGranularity>,
+        response: ResponseBody,
+    ) -> HandlerResult {
+        match self.step_tests(thread_id, step, granularity) {
+            Ok(true) => HandlerResult::ok(response),
             Ok(false) => {
                 // The tests finished executing
-                HandlerResult::ok_with_exit(ResponseBody::Next, 0)
+                HandlerResult::ok_with_exit(response, 0)
             }
             Err(e) => HandlerResult::err_with_exit(e, 1),
         }
     }
 
-    /// Handles a `continue` request. Returns true if the server should continue running.
-    pub(crate) fn handle_continue(&mut self) -> HandlerResult {
-        match self.continue_debugging_tests(false) {
+    /// Handles a `continue` request. Only the requested thread continues, the other tests stay stopped.
+    pub(crate) fn handle_continue(&mut self, args: &ContinueArguments) -> HandlerResult {
+        match self.continue_debugging_tests(args.thread_id) {
             Ok(true) => HandlerResult::ok(ResponseBody::Continue(responses::ContinueResponse {
-                all_threads_continued: Some(true),
+                all_threads_continued: Some(false),
             })),
             Ok(false) => HandlerResult::ok_with_exit(
                 ResponseBody::Continue(responses::ContinueResponse {
-                    all_threads_continued: Some(true),
+                    all_threads_continued: Some(false),
                 }),
                 0,
             ),
@@ -76,30 +167,84 @@ impl DapServer {
         }
     }
 
+    /// Handles a `goto` request. Runs the thread to the goto target, stopping earlier on breakpoints and
+    /// exceptions. The other tests stay stopped.
+    pub(crate) fn handle_goto(&mut self, args: &GotoArguments) -> HandlerResult {
+        match self.goto_tests(args.thread_id, args.target_id) {
+            Ok(true) => HandlerResult::ok(ResponseBody::Goto),
+            Ok(false) => HandlerResult::ok_with_exit(ResponseBody::Goto, 0),
+            Err(e @ AdapterError::UnknownGotoTarget { .. }) => HandlerResult::err(e),
+            Err(e) => HandlerResult::err_with_exit(e, 1),
+        }
+    }
+
+    /// Handles an `evaluate` request. Expressions from the debug console and watch expressions are evaluated
+    /// against the test of the selected thread, and errors are returned as the result so they are shown to the user.
     pub(crate) fn handle_evaluate(&mut self, args: &EvaluateArguments) -> HandlerResult {
-        let result = match args.context {
-            Some(types::EvaluateArgumentsContext::Variables) => args.expression.clone(),
-            _ => "Evaluate expressions not supported in this context".into(),
+        let response = match args.context {
+            Some(
+                types::EvaluateArgumentsContext::Repl | types::EvaluateArgumentsContext::Watch,
+            ) => self.evaluate_expression(&args.expression),
+            Some(types::EvaluateArgumentsContext::Variables) => responses::EvaluateResponse {
+                result: args.expression.clone(),
+                ..Default::default()
+            },
+            _ => responses::EvaluateResponse {
+                result: "Evaluate expressions not supported in this context".into(),
+                ..Default::default()
+            },
         };
-        HandlerResult::ok(ResponseBody::Evaluate(responses::EvaluateResponse {
-            result,
-            ..Default::default()
-        }))
+        HandlerResult::ok(ResponseBody::Evaluate(response))
     }
 
-    pub(crate) fn handle_pause(&mut self) -> HandlerResult {
-        // TODO: interpreter pause function
-        if let Some(executor) = self.state.executor() {
-            executor.interpreter.set_single_stepping(true);
+    fn evaluate_expression(&self, source: &str) -> responses::EvaluateResponse {
+        let Some(thread) = self.state.thread() else {
+            return responses::EvaluateResponse {
+                result: AdapterError::NoActiveTestExecutor.to_string(),
+                ..Default::default()
+            };
+        };
+        match Expression::parse(source).and_then(|expression| expression.evaluate(&thread.executor))
+        {
+            Ok(value) => responses::EvaluateResponse {
+                result: value.format(),
+                memory_reference: value.memory_reference(),
+                ..Default::default()
+            },
+            Err(e) => responses::EvaluateResponse {
+                result: e.to_string(),
+                ..Default::default()
+            },
         }
+    }
+
+    /// Handles a `pause` request. Running tests are paused as soon as the request is read, so by the time it is
+    /// handled they have already stopped, and pausing while stopped does nothing.
+    pub(crate) fn handle_pause(&mut self) -> HandlerResult {
+        self.take_pause_request();
         HandlerResult::ok(ResponseBody::Pause)
     }
 
+    /// Handles a `restart` request. The tests are built and launched again once the response is sent, with the
+    /// breakpoints set on the new threads.
     pub(crate) fn handle_restart(&mut self) -> HandlerResult {
         self.state.reset();
+        self.take_pause_request();
         HandlerResult::ok(ResponseBody::Restart)
     }
 
+    /// Handles a `disconnect` request. Drops the running tests and exits once the response is sent.
+    pub(crate) fn handle_disconnect(&mut self) -> HandlerResult {
+        self.state.reset();
+        HandlerResult::ok_with_exit(ResponseBody::Disconnect, 0)
+    }
+
+    /// Handles a `terminate` request. Drops the running tests and exits once the response is sent.
+    pub(crate) fn handle_terminate(&mut self) -> HandlerResult {
+        self.state.reset();
+        HandlerResult::ok_with_exit(ResponseBody::Terminate, 0)
+    }
+
     pub(crate) fn handle_scopes(&mut self) -> HandlerResult {
         HandlerResult::ok(ResponseBody::Scopes(responses::ScopesResponse {
             scopes: vec![
@@ -115,16 +260,55 @@ impl DapServer {
                     variables_reference: REGISTERS_VARIABLE_REF,
                     ..Default::default()
                 },
+                Scope {
+                    name: "Execution".into(),
+                    variables_reference: EXECUTION_VARIABLE_REF,
+                    ..Default::default()
+                },
             ],
         }))
     }
 
+    /// Handles a `setExceptionBreakpoints` request. Replaces the exceptions to stop on.
+    pub(crate) fn handle_set_exception_breakpoints(
+        &mut self,
+        args: &SetExceptionBreakpointsArguments,
+    ) -> HandlerResult {
+        let filters: Vec<_> = args
+            .filters
+            .iter()
+            .map(|id| ExceptionFilter::from_id(id))
+            .collect();
+        self.state.exception_filters = filters.iter().flatten().copied().collect();
+        let breakpoints = filters
+            .iter()
+            .map(|filter| types::Breakpoint {
+                verified: filter.is_some(),
+                message: filter
+                    .is_none()
+                    .then(|| "Unknown exception filter".to_string()),
+                ..Default::default()
+            })
+            .collect();
+        HandlerResult::ok(ResponseBody::SetExceptionBreakpoints(
+            responses::SetExceptionBreakpointsResponse {
+                breakpoints: Some(breakpoints),
+            },
+        ))
+    }
+
+    /// Handles a `threads` request. Returns a thread for each test that hasn't finished, named after the test.
     pub(crate) fn handle_threads(&mut self) -> HandlerResult {
         HandlerResult::ok(ResponseBody::Threads(responses::ThreadsResponse {
-            threads: vec![types::Thread {
-                id: THREAD_ID,
-                name: "main".into(),
-            }],
+            threads: self
+                .state
+                .threads
+                .iter()
+                .map(|thread| types::Thread {
+                    id: thread.id,
+                    name: thread.name().into(),
+                })
+                .collect(),
         }))
     }
 }
@@ -0,0 +1,139 @@
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+/// Reads the messages from the client ahead of the server on a separate thread, and passes them on unchanged.
+///
+/// While the server is busy running tests it can't handle requests, so `pause` requests are detected as soon as
+/// they are read and set the pause flag, which the running tests check after every instruction.
+pub(crate) struct InputReader {
+    messages: mpsc::Receiver<Vec<u8>>,
+    message: Vec<u8>,
+    position: usize,
+}
+
+impl InputReader {
+    /// Starts reading messages from `input`, setting `pause_requested` whenever a `pause` request is read.
+    pub(crate) fn spawn(input: Box<dyn Read + Send>, pause_requested: Arc<AtomicBool>) -> Self {
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            let mut input = BufReader::new(input);
+            while let Ok(Some((message, content_start))) = read_message(&mut input) {
+                if is_pause_request(&message[content_start..]) {
+                    pause_requested.store(true, Ordering::SeqCst);
+                }
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            messages,
+            message: vec![],
+            position: 0,
+        }
+    }
+}
+
+impl Read for InputReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.message.len() {
+            match self.messages.recv() {
+                Ok(message) => {
+                    self.message = message;
+                    self.position = 0;
+                }
+                // The input has ended.
+                Err(_) => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.message.len() - self.position);
+        buf[..len].copy_from_slice(&self.message[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Reads a message along with its headers. Returns the message and the index its content starts at, or `None` if
+/// the input has ended.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<(Vec<u8>, usize)>> {
+    let mut message = vec![];
+    let mut content_length = 0;
+    loop {
+        let line_start = message.len();
+        if input.read_until(b'\n', &mut message)? == 0 {
+            return Ok(None);
+        }
+        let line = String::from_utf8_lossy(&message[line_start..]);
+        let Some((name, value)) = line.split_once(':') else {
+            // The headers end with an empty line.
+            if line.trim().is_empty() {
+                break;
+            }
+            continue;
+        };
+        if name.trim().eq_ignore_ascii_case("Content-Length") {
+            content_length = value.trim().parse().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid Content-Length header")
+            })?;
+        }
+    }
+
+    let content_start = message.len();
+    message.resize(content_start + content_length, 0);
+    input.read_exact(&mut message[content_start..])?;
+    Ok(Some((message, content_start)))
+}
+
+fn is_pause_request(content: &[u8]) -> bool {
+    serde_json::from_slice::<serde_json::Value>(content)
+        .is_ok_and(|request| request["command"] == "pause")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> String {
+        format!("Content-Length: {}\r\n\r\n{content}", content.len())
+    }
+
+    #[test]
+    fn test_input_reader_detects_pause_requests() {
+        let next = message(r#"{"seq":1,"type":"request","command":"next"}"#);
+        let pause = message(r#"{"seq":2,"type":"request","command":"pause"}"#);
+        let input = format!("{next}{pause}");
+
+        let pause_requested = Arc::new(AtomicBool::new(false));
+        let mut reader = InputReader::spawn(
+            Box::new(io::Cursor::new(input.clone().into_bytes())),
+            pause_requested.clone(),
+        );
+        let mut output = String::new();
+        reader.read_to_string(&mut output).unwrap();
+
+        assert_eq!(output, input);
+        assert!(pause_requested.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_input_reader_ignores_other_requests() {
+        let input = message(r#"{"seq":1,"type":"request","command":"continue"}"#);
+
+        let pause_requested = Arc::new(AtomicBool::new(false));
+        let mut reader = InputReader::spawn(
+            Box::new(io::Cursor::new(input.clone().into_bytes())),
+            pause_requested.clone(),
+        );
+        let mut output = String::new();
+        reader.read_to_string(&mut output).unwrap();
+
+        assert_eq!(output, input);
+        assert!(!pause_requested.load(Ordering::SeqCst));
+    }
+}
@@ -1,42 +1,127 @@
+pub mod condition;
+pub mod expression;
 mod handlers;
+mod input;
+mod report;
 mod state;
+mod storage;
+mod thread;
 mod util;
 
 use crate::{
     error::{self, AdapterError, Error},
-    server::{state::ServerState, util::IdGenerator},
-    types::{ExitCode, Instruction},
+    server::{
+        input::InputReader,
+        state::{DebugPackage, ServerState},
+        thread::{Resume, RunContext, RunResult, ThreadMessage},
+        util::{self, IdGenerator},
+    },
+    types::{ExitCode, TestFilter},
 };
 use dap::{
-    events::{ExitedEventBody, OutputEventBody, StoppedEventBody},
+    events::{
+        ExitedEventBody, OutputEventBody, ProgressEndEventBody, ProgressStartEventBody,
+        ProgressUpdateEventBody,
+    },
     prelude::*,
-    types::StartDebuggingRequestKind,
+    types::{StartDebuggingRequestKind, SteppingGranularity},
 };
 use forc_pkg::{
-    manifest::GenericManifestFile,
+    manifest::{GenericManifestFile, ManifestFile},
     source::IPFSNode,
-    {self, BuildProfile, Built, BuiltPackage, PackageManifestFile},
-};
-use forc_test::{
-    execute::{DebugResult, TestExecutor},
-    setup::TestSetup,
-    BuiltTests,
+    {self, BuildProfile, Built, PackageManifestFile},
 };
+use forc_test::{execute::TestExecutor, BuiltTests, ExecutionContext, TestResult};
 use serde::{Deserialize, Serialize};
 use std::{
     io::{BufReader, BufWriter, Read, Write},
     process,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
 };
 use sway_core::BuildTarget;
 
-pub const THREAD_ID: i64 = 0;
 pub const REGISTERS_VARIABLE_REF: i64 = 1;
 pub const INSTRUCTIONS_VARIABLE_REF: i64 = 2;
+pub const EXECUTION_VARIABLE_REF: i64 = 3;
+
+/// The kinds of source-level steps the client can request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Step {
+    /// Runs to the next line of the current function, stepping over calls.
+    Over,
+    /// Runs to the next line, stepping into calls.
+    In,
+    /// Runs until the current function returns.
+    Out,
+}
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AdditionalData {
     pub program: String,
+    /// Only debug the tests whose names match this filter, see [TestFilter].
+    #[serde(
+        default,
+        rename = "testFilter",
+        alias = "testName",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub test_filter: Option<String>,
+    /// The build profile to build the tests with, such as `release` for optimized builds. Defaults to `debug`.
+    #[serde(
+        default,
+        rename = "buildProfile",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_profile: Option<String>,
+    /// The chain context to run the tests in, to reproduce a failure, see [ExecutionContextArgs].
+    #[serde(
+        default,
+        rename = "executionContext",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub execution_context: Option<serde_json::Value>,
+}
+
+/// The chain context to run the tests in, as given in the launch arguments. The values that aren't given keep the
+/// ones `forc test` uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExecutionContextArgs {
+    /// The height of the block the tests run in.
+    pub block_height: Option<u32>,
+    /// The amount of the base asset coin spent by the test transactions.
+    pub coin_amount: Option<u64>,
+    /// The seed of the random metadata of the test transactions, such as the key signing their coin input.
+    pub seed: Option<u64>,
+}
+
+impl ExecutionContextArgs {
+    /// Parses and validates the execution context given in the launch arguments.
+    pub fn parse(value: serde_json::Value) -> Result<ExecutionContext, AdapterError> {
+        let args: Self =
+            serde_json::from_value(value).map_err(|err| AdapterError::InvalidExecutionContext {
+                reason: err.to_string(),
+            })?;
+        // The transactions have a maturity of 1 and spend their coin, so neither can be 0.
+        if args.block_height == Some(0) {
+            return Err(AdapterError::InvalidExecutionContext {
+                reason: "`blockHeight` must be at least 1".into(),
+            });
+        }
+        if args.coin_amount == Some(0) {
+            return Err(AdapterError::InvalidExecutionContext {
+                reason: "`coinAmount` must be at least 1".into(),
+            });
+        }
+        Ok(ExecutionContext {
+            seed: args.seed,
+            block_height: args.block_height,
+            coin_amount: args.coin_amount,
+        })
+    }
 }
 
 /// This struct is a stateful representation of a Debug Adapter Protocol (DAP) server. It holds everything
@@ -50,6 +135,8 @@ pub struct DapServer {
     server: Server<Box<dyn Read>, Box<dyn Write>>,
     /// Used to generate unique breakpoint IDs.
     breakpoint_id_gen: IdGenerator,
+    /// Set when the client requests to pause the running tests.
+    pause_requested: Arc<AtomicBool>,
     /// The server state.
     pub state: ServerState,
 }
@@ -66,12 +153,15 @@ impl DapServer {
     /// # Arguments
     /// * `input` - Source of DAP protocol messages (usually stdin)
     /// * `output` - Destination for DAP protocol messages (usually stdout)
-    pub fn new(input: Box<dyn Read>, output: Box<dyn Write>) -> Self {
+    pub fn new(input: Box<dyn Read + Send>, output: Box<dyn Write>) -> Self {
+        let pause_requested = Arc::new(AtomicBool::new(false));
+        let input: Box<dyn Read> = Box::new(InputReader::spawn(input, pause_requested.clone()));
         let server = Server::new(BufReader::new(input), BufWriter::new(output));
         DapServer {
             server,
             state: ServerState::default(),
             breakpoint_id_gen: IdGenerator::default(),
+            pause_requested,
         }
     }
 
@@ -84,8 +174,11 @@ impl DapServer {
             };
 
             // Handle the request and send response
-            let response = self.handle_request(req)?;
+            let (response, exit_code) = self.handle_request(req)?;
             self.server.respond(response)?;
+            if let Some(exit_code) = exit_code {
+                self.exit(exit_code);
+            }
 
             // Handle one-time initialization
             if !self.state.initialized_event_sent {
@@ -108,20 +201,18 @@ impl DapServer {
         }
     }
 
-    /// Processes a debug adapter request and generates appropriate response.
-    fn handle_request(&mut self, req: Request) -> error::Result<Response> {
+    /// Processes a debug adapter request and generates appropriate response, along with the exit code if the
+    /// server should exit once the response is sent.
+    fn handle_request(&mut self, req: Request) -> error::Result<(Response, Option<ExitCode>)> {
         let (result, exit_code) = self.handle_command(&req.command).into_tuple();
         let response = match result {
-            Ok(rsp) => Ok(req.success(rsp)),
+            Ok(rsp) => req.success(rsp),
             Err(e) => {
                 self.error(format!("{e:?}"));
-                Ok(req.error(&format!("{e:?}")))
+                req.error(&format!("{e:?}"))
             }
         };
-        if let Some(exit_code) = exit_code {
-            self.exit(exit_code);
-        }
-        response
+        Ok((response, exit_code))
     }
 
     /// Handles a command and returns the result and exit code, if any.
@@ -132,26 +223,30 @@ impl DapServer {
                 self.handle_breakpoint_locations_command(args)
             }
             Command::ConfigurationDone => self.handle_configuration_done(),
-            Command::Continue(_) => self.handle_continue(),
-            Command::Disconnect(_) => HandlerResult::ok_with_exit(ResponseBody::Disconnect, 0),
+            Command::Continue(ref args) => self.handle_continue(args),
+            Command::DataBreakpointInfo(ref args) => self.handle_data_breakpoint_info_command(args),
+            Command::Disassemble(ref args) => self.handle_disassemble_command(args),
+            Command::Disconnect(_) => self.handle_disconnect(),
             Command::Evaluate(args) => self.handle_evaluate(args),
-            Command::Initialize(_) => self.handle_initialize(),
+            Command::Goto(ref args) => self.handle_goto(args),
+            Command::GotoTargets(ref args) => self.handle_goto_targets_command(args),
+            Command::Initialize(ref args) => self.handle_initialize(args),
             Command::Launch(ref args) => self.handle_launch(args),
-            Command::Next(_) => self.handle_next(),
+            Command::LoadedSources => self.handle_loaded_sources_command(),
+            Command::Next(ref args) => self.handle_next(args),
             Command::Pause(_) => self.handle_pause(),
+            Command::ReadMemory(ref args) => self.handle_read_memory_command(args),
             Command::Restart(_) => self.handle_restart(),
             Command::Scopes(_) => self.handle_scopes(),
             Command::SetBreakpoints(ref args) => self.handle_set_breakpoints_command(args),
-            Command::StackTrace(_) => self.handle_stack_trace_command(),
-            Command::StepIn(_) => {
-                self.error("This feature is not currently supported.".into());
-                HandlerResult::ok(ResponseBody::StepIn)
-            }
-            Command::StepOut(_) => {
-                self.error("This feature is not currently supported.".into());
-                HandlerResult::ok(ResponseBody::StepOut)
+            Command::SetDataBreakpoints(ref args) => self.handle_set_data_breakpoints(args),
+            Command::SetExceptionBreakpoints(ref args) => {
+                self.handle_set_exception_breakpoints(args)
             }
-            Command::Terminate(_) => HandlerResult::ok_with_exit(ResponseBody::Terminate, 0),
+            Command::StackTrace(ref args) => self.handle_stack_trace_command(args),
+            Command::StepIn(ref args) => self.handle_step_in(args),
+            Command::StepOut(ref args) => self.handle_step_out(args),
+            Command::Terminate(_) => self.handle_terminate(),
             Command::TerminateThreads(_) => {
                 HandlerResult::ok_with_exit(ResponseBody::TerminateThreads, 0)
             }
@@ -187,97 +282,181 @@ impl DapServer {
         }));
     }
 
-    /// Logs test execution results in a cargo-test-like format, showing duration and gas usage for each test.
+    /// Logs a summary table of the duration, gas and instructions used by each test, once all tests have finished.
     fn log_test_results(&mut self) {
-        if !self.state.executors.is_empty() {
+        if !self.state.threads.is_empty() {
+            return;
+        }
+        self.end_progress();
+        self.log(report::summary(&self.state.test_reports));
+    }
+
+    /// Starts reporting the progress of the tests, if the client supports progress events. The progress of a
+    /// previous run that didn't finish is ended first.
+    fn start_progress(&mut self) {
+        if !self.state.supports_progress_reporting {
+            return;
+        }
+        self.end_progress();
+        let _ = self
+            .server
+            .send_event(Event::ProgressStart(ProgressStartEventBody {
+                progress_id: report::TEST_PROGRESS_ID.into(),
+                title: "Debugging tests".into(),
+                request_id: None,
+                cancellable: None,
+                message: None,
+                percentage: Some(0),
+            }));
+        self.state.progress_started = true;
+    }
+
+    /// Reports the progress of the tests as the test of the thread at `index` starts.
+    fn report_test_started(&mut self, index: usize) {
+        let Some(thread) = self.state.threads.get(index) else {
+            return;
+        };
+        if !self.state.progress_started {
+            return;
+        }
+        let total = self.state.threads.len();
+        let message = report::progress_message(thread.name(), index, total);
+        let _ = self
+            .server
+            .send_event(Event::ProgressUpdate(ProgressUpdateEventBody {
+                progress_id: report::TEST_PROGRESS_ID.into(),
+                message: Some(message),
+                percentage: Some((index * 100 / total) as i64),
+            }));
+    }
+
+    /// Ends the progress of the tests, if it is being reported.
+    fn end_progress(&mut self) {
+        if !std::mem::take(&mut self.state.progress_started) {
             return;
         }
-        let test_results = &self.state.test_results;
-        let test_lines = test_results
+        let _ = self
+            .server
+            .send_event(Event::ProgressEnd(ProgressEndEventBody {
+                progress_id: report::TEST_PROGRESS_ID.into(),
+                message: Some(report::progress_end_message(&self.state.test_reports)),
+            }));
+    }
+
+    /// Records the result of the test of a thread, which has finished, and reports it to the client with an output
+    /// event whose `data` is a [report::TEST_RESULT_EVENT] payload. A failed test is also reported with an output
+    /// event whose `data` is a [report::TEST_FAILED_EVENT] payload, describing why it failed.
+    fn test_complete(&mut self, thread_id: i64, result: TestResult) {
+        let Some(index) = self
+            .state
+            .threads
             .iter()
-            .map(|r| {
-                let outcome = if r.passed() { "ok" } else { "failed" };
-                format!(
-                    "test {} ... {} ({}ms, {} gas)",
-                    r.name,
-                    outcome,
-                    r.duration.as_millis(),
-                    r.gas_used
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        let passed = test_results.iter().filter(|r| r.passed()).count();
-        let final_outcome = if passed == test_results.len() {
-            "OK"
-        } else {
-            "FAILED"
+            .position(|thread| thread.id == thread_id)
+        else {
+            return;
         };
+        let thread = self.state.threads.remove(index);
+        let report = self
+            .state
+            .test_complete(&result, thread.instructions_executed);
+        let passed = report.passed;
+        let body = OutputEventBody {
+            output: report.output(),
+            data: Some(report.payload()),
+            ..Default::default()
+        };
+        let _ = self.server.send_event(Event::Output(body));
+        if passed {
+            return;
+        }
 
-        self.log(format!(
-            "{test_lines}\nResult: {final_outcome}. {passed} passed. {} failed.\n",
-            test_results.len() - passed
-        ));
+        let receipts = thread.executor.interpreter.receipts().to_vec();
+        let location = util::find_revert_pc(&receipts)
+            .and_then(|pc| self.state.vm_pc_to_source_location(thread.package, pc).ok());
+        let failure = report::TestFailure::new(&result, receipts, location);
+        let body = OutputEventBody {
+            output: failure.output(),
+            data: Some(failure.payload()),
+            ..Default::default()
+        };
+        let _ = self.server.send_event(Event::Output(body));
     }
 
     /// Handles a `launch` request. Returns true if the server should continue running.
     pub fn launch(&mut self) -> Result<bool, AdapterError> {
         // Build tests for the given path.
-        let (pkg_to_debug, test_setup) = self.build_tests()?;
-        let entries = pkg_to_debug.bytecode.entries.iter().filter_map(|entry| {
-            if let Some(test_entry) = entry.kind.test() {
-                return Some((entry, test_entry));
-            }
-            None
-        });
+        let packages = self.build_tests()?;
 
-        // Construct a TestExecutor for each test and store it
-        let executors: Vec<TestExecutor> = entries
-            .filter_map(|(entry, test_entry)| {
-                let offset = u32::try_from(entry.finalized.imm)
-                    .expect("test instruction offset out of range");
-                let name = entry.finalized.fn_name.clone();
-                if test_entry.file_path.as_path() != self.state.program_path.as_path() {
-                    return None;
-                }
-
-                TestExecutor::build(
-                    &pkg_to_debug.bytecode.bytes,
-                    offset,
-                    test_setup.clone(),
-                    test_entry,
-                    name.clone(),
+        // Construct a TestExecutor for each selected test and run it on its own thread, along with the package it
+        // tests
+        let state = &self.state;
+        let executors: Vec<(usize, TestExecutor)> = packages
+            .iter()
+            .enumerate()
+            .flat_map(|(package_index, package)| {
+                package.tests(&state.program_path).into_iter().filter_map(
+                    move |(name, entry, test_entry)| {
+                        let offset = u32::try_from(entry.finalized.imm)
+                            .expect("test instruction offset out of range");
+                        if let Some(test_filter) = &state.test_filter {
+                            if !test_filter.matches(&name) {
+                                return None;
+                            }
+                        }
+
+                        TestExecutor::build_with_context(
+                            &package.built.bytecode.bytes,
+                            offset,
+                            package.test_setup.clone(),
+                            test_entry,
+                            name,
+                            &state.execution_context,
+                        )
+                        .ok()
+                        .map(|executor| (package_index, executor))
+                    },
                 )
-                .ok()
             })
             .collect();
-        self.state.init_executors(executors);
+        self.state.init_threads(executors);
+        self.start_progress();
 
         // Start debugging
-        self.start_debugging_tests(false)
+        self.start_debugging_tests()
+    }
+
+    /// Sets the filter selecting the tests to debug, checking that it matches at least one of the tests.
+    pub fn set_test_filter(&mut self, test_filter: TestFilter) -> Result<(), AdapterError> {
+        let packages = self.build_tests()?;
+        let test_names: Vec<String> = packages
+            .iter()
+            .flat_map(|package| package.tests(&self.state.program_path))
+            .map(|(name, _, _)| name)
+            .collect();
+        if !test_names.iter().any(|name| test_filter.matches(name)) {
+            return Err(AdapterError::NoMatchingTests {
+                filter: test_filter.to_string(),
+                available: test_names,
+            });
+        }
+        self.state.test_filter = Some(test_filter);
+        Ok(())
     }
 
     /// Builds the tests at the given [PathBuf] and stores the source maps.
-    pub fn build_tests(&mut self) -> Result<(BuiltPackage, TestSetup), AdapterError> {
-        if let Some(pkg) = &self.state.built_package {
-            if let Some(setup) = &self.state.test_setup {
-                return Ok((pkg.clone(), setup.clone()));
-            }
+    ///
+    /// If the path is in a workspace rather than in a package, the tests of all of the workspace members are built.
+    pub fn build_tests(&mut self) -> Result<Vec<DebugPackage>, AdapterError> {
+        if !self.state.packages.is_empty() {
+            return Ok(self.state.packages.clone());
         }
 
         // 1. Build the packages
-        let manifest_file = forc_pkg::manifest::ManifestFile::from_dir(&self.state.program_path)
-            .map_err(|err| AdapterError::BuildFailed {
+        let manifest_file = ManifestFile::from_dir(&self.state.program_path).map_err(|err| {
+            AdapterError::BuildFailed {
                 reason: format!("read manifest file: {err:?}"),
-            })?;
-        let pkg_manifest: PackageManifestFile =
-            manifest_file
-                .clone()
-                .try_into()
-                .map_err(|err: anyhow::Error| AdapterError::BuildFailed {
-                    reason: format!("package manifest: {err:?}"),
-                })?;
+            }
+        })?;
         let member_manifests =
             manifest_file
                 .member_manifests()
@@ -300,23 +479,42 @@ impl DapServer {
             reason: format!("build plan: {err:?}"),
         })?;
 
-        let project_name = pkg_manifest.project_name();
+        let (pkg_manifests, is_workspace): (Vec<PackageManifestFile>, _) = match &manifest_file {
+            ManifestFile::Package(pkg_manifest) => (vec![(**pkg_manifest).clone()], false),
+            ManifestFile::Workspace(_) => (member_manifests.values().cloned().collect(), true),
+        };
 
-        let outputs = std::iter::once(build_plan.find_member_index(project_name).ok_or(
-            AdapterError::BuildFailed {
-                reason: format!("find built project: {project_name}"),
-            },
-        )?)
-        .collect();
+        let outputs = pkg_manifests
+            .iter()
+            .map(|pkg_manifest| {
+                let project_name = pkg_manifest.project_name();
+                build_plan
+                    .find_member_index(project_name)
+                    .ok_or(AdapterError::BuildFailed {
+                        reason: format!("find built project: {project_name}"),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let profile_name = self
+            .state
+            .build_profile
+            .as_deref()
+            .unwrap_or(BuildProfile::DEFAULT);
+        let mut build_profile = build_plan
+            .build_profiles()
+            .find(|(name, _)| name == profile_name)
+            .map(|(_, profile)| profile)
+            .ok_or_else(|| AdapterError::BuildFailed {
+                reason: format!("build profile not found: {profile_name}"),
+            })?;
+        build_profile.include_tests = true;
+        self.state.optimized = build_profile.optimization_level != sway_core::OptLevel::Opt0;
 
         let built_packages = forc_pkg::build(
             &build_plan,
             BuildTarget::default(),
-            &BuildProfile {
-                optimization_level: sway_core::OptLevel::Opt0,
-                include_tests: true,
-                ..Default::default()
-            },
+            &build_profile,
             &outputs,
             &[],
             &[],
@@ -325,40 +523,59 @@ impl DapServer {
             reason: format!("build packages: {err:?}"),
         })?;
 
-        // 2. Store the source maps and find debug package
-        let pkg_to_debug = built_packages
+        // 2. Find the packages to debug and build their tests
+        let execution_context = self.state.execution_context;
+        let packages = pkg_manifests
             .iter()
-            .find(|(_, pkg)| pkg.descriptor.manifest_file == pkg_manifest)
-            .map(|(_, pkg)| pkg)
-            .ok_or(AdapterError::BuildFailed {
-                reason: format!("find package: {project_name}"),
-            })?;
-
-        self.state.source_map = pkg_to_debug.source_map.clone();
-
-        // 3. Build the tests
-        let built = Built::Package(Arc::from(pkg_to_debug.clone()));
-
-        let built_tests = BuiltTests::from_built(built, &build_plan).map_err(|err| {
-            AdapterError::BuildFailed {
-                reason: format!("build tests: {err:?}"),
-            }
-        })?;
+            .map(|pkg_manifest| {
+                let project_name = pkg_manifest.project_name();
+                let pkg_to_debug = built_packages
+                    .iter()
+                    .find(|(_, pkg)| pkg.descriptor.manifest_file == *pkg_manifest)
+                    .map(|(_, pkg)| pkg)
+                    .ok_or(AdapterError::BuildFailed {
+                        reason: format!("find package: {project_name}"),
+                    })?;
+                let built = Built::Package(Arc::from(pkg_to_debug.clone()));
+
+                let built_tests = BuiltTests::from_built(built, &build_plan).map_err(|err| {
+                    AdapterError::BuildFailed {
+                        reason: format!("build tests: {err:?}"),
+                    }
+                })?;
 
-        let pkg_tests = match built_tests {
-            BuiltTests::Package(pkg_tests) => pkg_tests,
-            BuiltTests::Workspace(_) => {
-                return Err(AdapterError::BuildFailed {
-                    reason: "package tests: workspace tests not supported".into(),
+                let pkg_tests = match built_tests {
+                    BuiltTests::Package(pkg_tests) => pkg_tests,
+                    BuiltTests::Workspace(_) => {
+                        return Err(AdapterError::BuildFailed {
+                            reason: format!("package tests: {project_name} built as a workspace"),
+                        })
+                    }
+                };
+                let test_setup =
+                    pkg_tests
+                        .setup_with_context(&execution_context)
+                        .map_err(|err| AdapterError::BuildFailed {
+                            reason: format!("test setup: {err:?}"),
+                        })?;
+                Ok(DebugPackage {
+                    member_name: is_workspace.then(|| project_name.to_string()),
+                    built: pkg_to_debug.clone(),
+                    test_setup,
                 })
-            }
-        };
-        let test_setup = pkg_tests.setup().map_err(|err| AdapterError::BuildFailed {
-            reason: format!("test setup: {err:?}"),
-        })?;
-        self.state.built_package = Some(pkg_to_debug.clone());
-        self.state.test_setup = Some(test_setup.clone());
-        Ok((pkg_to_debug.clone(), test_setup))
+            })
+            .collect::<Result<Vec<_>, AdapterError>>()?;
+
+        // 3. Store the source maps
+        self.state.set_source_maps(
+            packages
+                .iter()
+                .map(|package| package.built.source_map.clone())
+                .collect(),
+        );
+        self.state.packages = packages.clone();
+        self.bind_pending_breakpoints();
+        Ok(packages)
     }
 
     /// Sends the 'exited' event to the client and kills the server process.
@@ -369,78 +586,176 @@ impl DapServer {
         process::exit(exit_code as i32);
     }
 
-    fn stop(&mut self, pc: Instruction) -> Result<bool, AdapterError> {
-        let (hit_breakpoint_ids, reason) =
-            if let Ok(breakpoint_id) = self.state.vm_pc_to_breakpoint_id(pc) {
-                self.state.stopped_on_breakpoint_id = Some(breakpoint_id);
-                (
-                    Some(vec![breakpoint_id]),
-                    types::StoppedEventReason::Breakpoint,
-                )
-            } else {
-                self.state.stopped_on_breakpoint_id = None;
-                (None, types::StoppedEventReason::Step)
-            };
+    /// Reports the stop of a thread that hasn't been reported yet, preferring the threads in `thread_ids`, and
+    /// selects the thread. The stops of the other threads are reported as the reported thread finishes, so the
+    /// client is told about one stop at a time.
+    ///
+    /// Returns true if a test is still running or false if all tests have finished.
+    fn report_stop(&mut self, thread_ids: &[i64]) -> bool {
+        let index = self
+            .state
+            .threads
+            .iter()
+            .position(|thread| thread.unreported_stop.is_some() && thread_ids.contains(&thread.id))
+            .or_else(|| {
+                self.state
+                    .threads
+                    .iter()
+                    .position(|thread| thread.unreported_stop.is_some())
+            });
+        let Some(index) = index else {
+            self.log_test_results();
+            return !self.state.threads.is_empty();
+        };
+        let thread = &mut self.state.threads[index];
+        let thread_id = thread.id;
+        let body = thread
+            .unreported_stop
+            .take()
+            .expect("the thread has stopped")
+            .event_body(thread_id, thread.stopped_on_breakpoint_id);
+        self.state.selected_thread = thread_id;
+
+        let _ = self.server.send_event(Event::Stopped(body));
+        true
+    }
 
-        let _ = self.server.send_event(Event::Stopped(StoppedEventBody {
-            reason,
-            hit_breakpoint_ids,
-            description: None,
-            thread_id: Some(THREAD_ID),
-            preserve_focus_hint: None,
-            text: None,
-            all_threads_stopped: None,
-        }));
-        Ok(true)
+    /// Checks whether the client has requested to pause, and clears the request.
+    pub(crate) fn take_pause_request(&self) -> bool {
+        self.pause_requested.swap(false, Ordering::SeqCst)
     }
 
-    /// Starts debugging all tests.
-    /// `single_stepping` indicates whether the VM should break after one instruction.
+    /// Starts debugging all tests, each on its own thread.
     ///
-    /// Returns true if it has stopped on a breakpoint or false if all tests have finished.
-    fn start_debugging_tests(&mut self, single_stepping: bool) -> Result<bool, AdapterError> {
-        self.state.update_vm_breakpoints();
+    /// Returns true if a test has stopped or false if all tests have finished.
+    fn start_debugging_tests(&mut self) -> Result<bool, AdapterError> {
+        for index in 0..self.state.threads.len() {
+            self.report_test_started(index);
+        }
+        let thread_ids: Vec<_> = self.state.threads.iter().map(|thread| thread.id).collect();
+        self.run_threads(&thread_ids, Resume::Start)
+    }
 
-        while let Some(executor) = self.state.executors.first_mut() {
-            executor.interpreter.set_single_stepping(single_stepping);
-            match executor.start_debugging()? {
-                DebugResult::TestComplete(result) => {
-                    self.state.test_complete(result);
-                }
-                DebugResult::Breakpoint(pc) => {
-                    executor.interpreter.set_single_stepping(false);
-                    return self.stop(pc);
-                }
-            };
+    /// Continues debugging the test of a thread until it hits a breakpoint or finishes. The other tests stay
+    /// stopped.
+    ///
+    /// Returns true if a test is still running or false if all tests have finished.
+    fn continue_debugging_tests(&mut self, thread_id: i64) -> Result<bool, AdapterError> {
+        let Some(thread_id) = self.state.select_thread(thread_id) else {
+            self.log_test_results();
+            return Ok(false);
+        };
+        self.run_threads(&[thread_id], Resume::Continue)
+    }
+
+    /// Continues debugging the test of a thread until it reaches the goto target with the given ID, as when running
+    /// to the cursor. The test stops earlier on breakpoints and exceptions, and the target is removed once it stops.
+    /// The other tests stay stopped.
+    ///
+    /// Returns true if a test is still running or false if all tests have finished.
+    fn goto_tests(&mut self, thread_id: i64, target_id: i64) -> Result<bool, AdapterError> {
+        let Some(thread_id) = self.state.select_thread(thread_id) else {
+            self.log_test_results();
+            return Ok(false);
+        };
+        let thread = self
+            .state
+            .threads
+            .iter()
+            .find(|thread| thread.id == thread_id)
+            .expect("the thread was selected");
+        let pcs = self
+            .state
+            .goto_targets
+            .iter()
+            .find(|target| target.id == target_id && target.package == thread.package)
+            .map(|target| target.pcs.clone())
+            .ok_or(AdapterError::UnknownGotoTarget { target_id })?;
+        if let Some(thread) = self.state.thread_mut(thread_id) {
+            thread.set_goto_target(pcs);
         }
-        self.log_test_results();
-        Ok(false)
+        self.run_threads(&[thread_id], Resume::Continue)
+    }
+
+    /// Steps the test of a thread until the given [Step] is complete, or for a single instruction if the
+    /// `granularity` is [SteppingGranularity::Instruction]. The other tests stay stopped.
+    ///
+    /// Returns true if a test is still running or false if all tests have finished.
+    fn step_tests(
+        &mut self,
+        thread_id: i64,
+        step: Step,
+        granularity: Option<&SteppingGranularity>,
+    ) -> Result<bool, AdapterError> {
+        let Some(thread_id) = self.state.select_thread(thread_id) else {
+            self.log_test_results();
+            return Ok(false);
+        };
+        let instruction = matches!(granularity, Some(SteppingGranularity::Instruction));
+        self.run_threads(&[thread_id], Resume::Step { step, instruction })
     }
 
-    /// Continues debugging the current test and starts the next one if no breakpoint is hit.
-    /// `single_stepping` indicates whether the VM should break after one instruction.
+    /// Resumes the threads with the given IDs, each on a worker thread, and waits until all of them have stopped or
+    /// finished. The messages of logpoints are logged as the workers send them. Finished tests are reported in the
+    /// order of their threads, followed by a stop, if any test has stopped.
     ///
-    /// Returns true if it has stopped on a breakpoint or false if all tests have finished.
-    fn continue_debugging_tests(&mut self, single_stepping: bool) -> Result<bool, AdapterError> {
+    /// Returns true if a test is still running or false if all tests have finished.
+    fn run_threads(&mut self, thread_ids: &[i64], resume: Resume) -> Result<bool, AdapterError> {
         self.state.update_vm_breakpoints();
 
-        if let Some(executor) = self.state.executors.first_mut() {
-            executor.interpreter.set_single_stepping(single_stepping);
-            match executor.continue_debugging()? {
-                DebugResult::TestComplete(result) => {
-                    self.state.test_complete(result);
-                    // The current test has finished, but there could be more tests to run. Start debugging the
-                    // remaining tests.
-                    return self.start_debugging_tests(single_stepping);
+        let mut threads = std::mem::take(&mut self.state.threads);
+        let (sender, receiver) = mpsc::channel();
+        let mut results = vec![];
+        let state = &self.state;
+        let pause_requested = &*self.pause_requested;
+        let server = &mut self.server;
+        std::thread::scope(|scope| {
+            for thread in threads
+                .iter_mut()
+                .filter(|thread| thread_ids.contains(&thread.id))
+            {
+                let context = RunContext {
+                    state,
+                    pause_requested,
+                    messages: sender.clone(),
+                };
+                scope.spawn(move || {
+                    let result = thread.run(&context, resume);
+                    let _ = context
+                        .messages
+                        .send((thread.id, ThreadMessage::Done(result)));
+                });
+            }
+            drop(sender);
+
+            for (thread_id, message) in receiver {
+                match message {
+                    ThreadMessage::Log(output) => {
+                        let _ = server.send_event(Event::Output(OutputEventBody {
+                            output,
+                            ..Default::default()
+                        }));
+                    }
+                    ThreadMessage::Done(result) => results.push((thread_id, result)),
                 }
-                DebugResult::Breakpoint(pc) => {
-                    executor.interpreter.set_single_stepping(false);
-                    return self.stop(pc);
+            }
+        });
+        self.state.threads = threads;
+        // Every running test has paused by now.
+        self.take_pause_request();
+
+        results.sort_by_key(|(thread_id, _)| *thread_id);
+        for (thread_id, result) in results {
+            match result? {
+                RunResult::Finished(result) => self.test_complete(thread_id, result),
+                RunResult::Stopped(stop) => {
+                    if let Some(thread) = self.state.thread_mut(thread_id) {
+                        thread.unreported_stop = Some(stop);
+                    }
                 }
             }
         }
-        self.log_test_results();
-        Ok(false)
+        Ok(self.report_stop(thread_ids))
     }
 }
 
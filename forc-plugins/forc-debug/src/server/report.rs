@@ -0,0 +1,361 @@
+//! Reports of how the debugged tests performed, sent to the client as each test finishes and summarized once
+//! all of them have.
+
+use crate::server::{
+    state::SourceLocation,
+    util::{self, format_word},
+};
+use forc_test::TestResult;
+use fuel_tx::Receipt;
+use serde_json::json;
+use std::time::Duration;
+
+/// The name of the event carried by the `data` of the output event sent when a test finishes.
+pub const TEST_RESULT_EVENT: &str = "forc-test/result";
+
+/// The name of the event carried by the `data` of the output event sent when a test fails, after its
+/// [TEST_RESULT_EVENT].
+pub const TEST_FAILED_EVENT: &str = "forc-test/failed";
+
+/// The ID of the progress reported while the tests are debugged.
+pub const TEST_PROGRESS_ID: &str = "forc-test/progress";
+
+/// The outcome of a finished test, along with the work it did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestReport {
+    pub name: String,
+    pub passed: bool,
+    /// Why the test failed, if it did.
+    pub failure: Option<String>,
+    pub gas_used: u64,
+    /// The number of instructions the test executed.
+    pub instructions: u64,
+    /// The wall time the test ran for, including the time spent stopped in the debugger.
+    pub duration: Duration,
+}
+
+impl TestReport {
+    pub fn new(result: &TestResult, instructions: u64) -> Self {
+        let passed = result.passed();
+        let failure = (!passed).then(|| match result.revert_code() {
+            Some(code) => format!("reverted with code {}", format_word(code)),
+            None => "did not revert".into(),
+        });
+        Self {
+            name: result.name.clone(),
+            passed,
+            failure,
+            gas_used: result.gas_used,
+            instructions,
+            duration: result.duration,
+        }
+    }
+
+    fn outcome(&self) -> &'static str {
+        if self.passed {
+            "ok"
+        } else {
+            "failed"
+        }
+    }
+
+    /// Formats the report as a line of output, e.g. `test test_1 ... ok (3ms, 1234 gas, 567 instructions)`. The
+    /// reason a test failed follows the outcome.
+    pub fn output(&self) -> String {
+        let failure = self
+            .failure
+            .as_ref()
+            .map(|failure| format!(": {failure}"))
+            .unwrap_or_default();
+        format!(
+            "test {} ... {}{failure} ({}ms, {} gas, {} instructions)\n",
+            self.name,
+            self.outcome(),
+            self.duration.as_millis(),
+            self.gas_used,
+            self.instructions
+        )
+    }
+
+    /// Returns the payload of the [TEST_RESULT_EVENT] for the report.
+    pub fn payload(&self) -> serde_json::Value {
+        json!({
+            "event": TEST_RESULT_EVENT,
+            "name": self.name,
+            "passed": self.passed,
+            "failure": self.failure,
+            "gasUsed": self.gas_used,
+            "instructions": self.instructions,
+            "durationMs": self.duration.as_millis() as u64,
+        })
+    }
+}
+
+/// Why a test failed, along with the receipts it produced, so the failure can be inspected without running the test
+/// again outside the debugger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFailure {
+    pub name: String,
+    /// The code the test reverted with, if it reverted.
+    pub revert_code: Option<u64>,
+    /// The error signal the revert code decodes to, such as a failed assertion, or else why the test failed.
+    pub reason: String,
+    pub gas_used: u64,
+    /// Where in the source the test reverted or panicked, if the source map has it.
+    pub location: Option<SourceLocation>,
+    pub receipts: Vec<Receipt>,
+}
+
+impl TestFailure {
+    pub fn new(
+        result: &TestResult,
+        receipts: Vec<Receipt>,
+        location: Option<SourceLocation>,
+    ) -> Self {
+        let revert_code = result.revert_code();
+        let reason = match util::find_panic(&receipts) {
+            Some((_, panic)) => panic,
+            None => match (result.error_signal(), revert_code) {
+                (Ok(signal), _) => signal.to_string(),
+                (Err(_), Some(code)) => format!("reverted with code {}", format_word(code)),
+                (Err(_), None) => "did not revert".into(),
+            },
+        };
+        Self {
+            name: result.name.clone(),
+            revert_code,
+            reason,
+            gas_used: result.gas_used,
+            location,
+            receipts,
+        }
+    }
+
+    /// Formats the failure as lines of output, e.g. `test test_1 failed: assertion failed` followed by where the
+    /// test reverted, the revert code, the gas used and the receipts.
+    pub fn output(&self) -> String {
+        let mut output = format!("test {} failed: {}\n", self.name, self.reason);
+        if let Some(location) = &self.location {
+            output.push_str(&format!(
+                "    at {}:{}:{}\n",
+                location.path.display(),
+                location.line,
+                location.column
+            ));
+        }
+        if let Some(code) = self.revert_code {
+            output.push_str(&format!("    revert code: {}\n", format_word(code)));
+        }
+        output.push_str(&format!("    gas used: {}\n    receipts:\n", self.gas_used));
+        for receipt in &self.receipts {
+            output.push_str(&format!("        {receipt:?}\n"));
+        }
+        output
+    }
+
+    /// Returns the payload of the [TEST_FAILED_EVENT] for the failure. The revert code is given in hexadecimal, as
+    /// codes such as those of error signals don't fit in a JSON number.
+    pub fn payload(&self) -> serde_json::Value {
+        json!({
+            "event": TEST_FAILED_EVENT,
+            "name": self.name,
+            "revertCode": self.revert_code.map(|code| format!("0x{code:x}")),
+            "reason": self.reason,
+            "gasUsed": self.gas_used,
+            "location": self.location.as_ref().map(|location| json!({
+                "path": location.path,
+                "line": location.line,
+                "column": location.column,
+            })),
+            "receipts": self.receipts,
+        })
+    }
+}
+
+/// Formats the progress message shown when a test starts, e.g. `test_1 (1/3)`, where `index` is the index of the
+/// test among all `total` tests.
+pub fn progress_message(name: &str, index: usize, total: usize) -> String {
+    format!("{name} ({}/{total})", index + 1)
+}
+
+/// Formats the progress message shown once all tests have finished, e.g. `2 passed. 1 failed.`.
+pub fn progress_end_message(reports: &[TestReport]) -> String {
+    let passed = reports.iter().filter(|report| report.passed).count();
+    format!("{passed} passed. {} failed.", reports.len() - passed)
+}
+
+/// Formats a table of the reports of all tests, followed by the overall result in a cargo-test-like format.
+pub fn summary(reports: &[TestReport]) -> String {
+    let header = ["test", "result", "gas", "instructions", "time"].map(String::from);
+    let rows = reports.iter().map(|report| {
+        [
+            report.name.clone(),
+            report.outcome().to_string(),
+            report.gas_used.to_string(),
+            report.instructions.to_string(),
+            format!("{}ms", report.duration.as_millis()),
+        ]
+    });
+    let rows = std::iter::once(header).chain(rows).collect::<Vec<_>>();
+    let widths = (0..5)
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+    let table = rows
+        .iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        })
+        .collect::<String>();
+
+    let passed = reports.iter().filter(|report| report.passed).count();
+    let outcome = if passed == reports.len() {
+        "OK"
+    } else {
+        "FAILED"
+    };
+    format!(
+        "{table}Result: {outcome}. {passed} passed. {} failed.\n",
+        reports.len() - passed
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reports() -> Vec<TestReport> {
+        vec![
+            TestReport {
+                name: "test_transfer".into(),
+                passed: true,
+                failure: None,
+                gas_used: 12_345,
+                instructions: 678,
+                duration: Duration::from_millis(3),
+            },
+            TestReport {
+                name: "test_revert".into(),
+                passed: false,
+                failure: Some("reverted with code 42 (0x2A)".into()),
+                gas_used: 90,
+                instructions: 12,
+                duration: Duration::from_millis(1_250),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_report_output() {
+        let reports = reports();
+        assert_eq!(
+            reports[0].output(),
+            "test test_transfer ... ok (3ms, 12345 gas, 678 instructions)\n"
+        );
+        assert_eq!(
+            reports[1].output(),
+            "test test_revert ... failed: reverted with code 42 (0x2A) (1250ms, 90 gas, 12 instructions)\n"
+        );
+    }
+
+    #[test]
+    fn test_report_payload() {
+        assert_eq!(
+            reports()[0].payload(),
+            json!({
+                "event": "forc-test/result",
+                "name": "test_transfer",
+                "passed": true,
+                "failure": null,
+                "gasUsed": 12345,
+                "instructions": 678,
+                "durationMs": 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_failure_output_and_payload() {
+        let receipts = vec![Receipt::revert(
+            Default::default(),
+            0xffff_ffff_ffff_0004,
+            40,
+            0,
+        )];
+        let failure = TestFailure {
+            name: "test_assert".into(),
+            revert_code: Some(0xffff_ffff_ffff_0004),
+            reason: "assertion failed".into(),
+            gas_used: 90,
+            location: Some(SourceLocation {
+                path: "src/main.sw".into(),
+                line: 11,
+                column: 5,
+                end_line: Some(11),
+                end_column: Some(24),
+            }),
+            receipts: receipts.clone(),
+        };
+        assert_eq!(
+            failure.output(),
+            format!(
+                "\
+test test_assert failed: assertion failed
+    at src/main.sw:11:5
+    revert code: 18446744073709486084 (0xFFFFFFFFFFFF0004)
+    gas used: 90
+    receipts:
+        {:?}
+",
+                receipts[0]
+            )
+        );
+        assert_eq!(
+            failure.payload(),
+            json!({
+                "event": "forc-test/failed",
+                "name": "test_assert",
+                "revertCode": "0xffffffffffff0004",
+                "reason": "assertion failed",
+                "gasUsed": 90,
+                "location": { "path": "src/main.sw", "line": 11, "column": 5 },
+                "receipts": receipts,
+            })
+        );
+    }
+
+    #[test]
+    fn test_progress_messages() {
+        assert_eq!(
+            progress_message("test_transfer", 0, 2),
+            "test_transfer (1/2)"
+        );
+        assert_eq!(progress_end_message(&reports()), "1 passed. 1 failed.");
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            summary(&reports()),
+            "\
+test           result  gas    instructions  time
+test_transfer  ok      12345  678           3ms
+test_revert    failed  90     12            1250ms
+Result: FAILED. 1 passed. 1 failed.
+"
+        );
+        assert_eq!(
+            summary(&reports()[..1]),
+            "\
+test           result  gas    instructions  time
+test_transfer  ok      12345  678           3ms
+Result: OK. 1 passed. 0 failed.
+"
+        );
+    }
+}
@@ -1,14 +1,222 @@
 use crate::{
     error::AdapterError,
-    types::{Breakpoints, Instruction},
+    server::{
+        report::TestReport,
+        storage::DataBreakpoint,
+        thread::DebugThread,
+        util::{self, current_instruction, normalize_path},
+    },
+    types::{Breakpoints, Instruction, TestFilter},
 };
 use dap::types::StartDebuggingRequestKind;
-use forc_pkg::BuiltPackage;
-use forc_test::{execute::TestExecutor, setup::TestSetup, TestResult};
-use std::path::PathBuf;
-use sway_core::source_map::SourceMap;
+use forc_pkg::{BuiltPackage, PkgEntry, PkgTestEntry};
+use forc_test::{execute::TestExecutor, setup::TestSetup, ExecutionContext, TestResult};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+use sway_core::source_map::{LocationRange, PathIndex, SourceMap, SourceMapSpan};
+use sway_types::LineCol;
+
+/// A function call made by a test, recorded so the call stack can be reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallFrame {
+    /// The program counter of the instruction that made the call.
+    pub call_pc: Instruction,
+    /// The program counter of the first instruction of the called function.
+    pub function_pc: Instruction,
+}
+
+/// The location in the source of the span an instruction is mapped to. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub path: PathBuf,
+    pub line: i64,
+    /// The column the span starts at, or 0 if it is unknown.
+    pub column: i64,
+    pub end_line: Option<i64>,
+    pub end_column: Option<i64>,
+}
+
+/// A location the tests can be run to, as returned by a `gotoTargets` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GotoTarget {
+    pub id: i64,
+    /// The index in `packages` of the package whose tests can be run to the target.
+    pub package: usize,
+    /// The program counters of the target's instructions.
+    pub pcs: Vec<Instruction>,
+}
 
+/// A built package whose tests are being debugged.
+#[derive(Debug, Clone)]
+pub struct DebugPackage {
+    /// The name of the package if it is debugged as a member of a workspace, which prefixes its test names.
+    pub member_name: Option<String>,
+    pub built: BuiltPackage,
+    pub test_setup: TestSetup,
+}
+
+impl DebugPackage {
+    /// Returns the entries of the tests to debug, along with their names.
+    ///
+    /// All tests of a workspace member are debugged, named `member::test_name`. Only the tests declared in the
+    /// source file at `program_path` are debugged in a single package.
+    pub fn tests<'a>(
+        &'a self,
+        program_path: &Path,
+    ) -> Vec<(String, &'a PkgEntry, &'a PkgTestEntry)> {
+        match &self.member_name {
+            Some(member_name) => self
+                .built
+                .bytecode
+                .entries
+                .iter()
+                .filter_map(|entry| {
+                    let test_entry = entry.kind.test()?;
+                    let name = format!("{member_name}::{}", entry.finalized.fn_name);
+                    Some((name, entry, test_entry))
+                })
+                .collect(),
+            None => util::test_entries(&self.built, program_path)
+                .map(|(entry, test_entry)| (entry.finalized.fn_name.clone(), entry, test_entry))
+                .collect(),
+        }
+    }
+}
+
+/// The source map of a package, along with the program counters of the instructions mapped to each line, and to
+/// each column their spans start at on the line, by normalized source path and line.
 #[derive(Default, Debug, Clone)]
+struct IndexedSourceMap {
+    source_map: SourceMap,
+    /// The normalized paths of the source files of the package and its dependencies.
+    source_paths: Vec<PathBuf>,
+    line_pcs: HashMap<PathBuf, BTreeMap<i64, Vec<Instruction>>>,
+    column_pcs: HashMap<PathBuf, BTreeMap<i64, BTreeMap<i64, Vec<Instruction>>>>,
+}
+
+impl IndexedSourceMap {
+    fn new(source_map: SourceMap) -> Self {
+        let mut line_pcs: HashMap<_, BTreeMap<_, Vec<_>>> = HashMap::new();
+        let mut column_pcs: HashMap<_, BTreeMap<_, BTreeMap<_, Vec<_>>>> = HashMap::new();
+        let mut normalized_paths = HashMap::new();
+        for opcode_index in source_map.map.keys() {
+            if let Some((path, range)) = source_map.addr_to_span(*opcode_index) {
+                let path = normalized_paths
+                    .entry(path)
+                    .or_insert_with_key(|path| normalize_path(path))
+                    .clone();
+                let (line, column) = (range.start.line as i64, range.start.col as i64);
+                let pc = *opcode_index as Instruction * 4;
+                line_pcs
+                    .entry(path.clone())
+                    .or_default()
+                    .entry(line)
+                    .or_default()
+                    .push(pc);
+                column_pcs
+                    .entry(path)
+                    .or_default()
+                    .entry(line)
+                    .or_default()
+                    .entry(column)
+                    .or_default()
+                    .push(pc);
+            }
+        }
+        line_pcs
+            .values_mut()
+            .flat_map(BTreeMap::values_mut)
+            .chain(
+                column_pcs
+                    .values_mut()
+                    .flat_map(BTreeMap::values_mut)
+                    .flat_map(BTreeMap::values_mut),
+            )
+            .for_each(|pcs| pcs.sort_unstable());
+        // Resolve the paths of dependencies the same way as the paths of spans.
+        let range = LocationRange {
+            start: LineCol { line: 0, col: 0 },
+            end: LineCol { line: 0, col: 0 },
+        };
+        let source_paths = (0..source_map.paths.len())
+            .map(|index| {
+                let span = SourceMapSpan {
+                    path: PathIndex(index),
+                    range,
+                };
+                let (path, _) = span.to_span(&source_map.paths, &source_map.dependency_paths);
+                normalized_paths
+                    .entry(path)
+                    .or_insert_with_key(|path| normalize_path(path))
+                    .clone()
+            })
+            .collect();
+        Self {
+            source_map,
+            source_paths,
+            line_pcs,
+            column_pcs,
+        }
+    }
+
+    /// Returns the columns that the spans of the instructions mapped to a source line start at.
+    fn columns(&self, path: &Path, line: i64) -> impl Iterator<Item = i64> + '_ {
+        self.column_pcs
+            .get(path)
+            .and_then(|lines| lines.get(&line))
+            .into_iter()
+            .flat_map(BTreeMap::keys)
+            .copied()
+    }
+}
+
+/// What to do when a breakpoint is hit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakpointHit {
+    /// Stop execution.
+    Stop,
+    /// Log the rendered message of a logpoint and continue execution.
+    Log(String),
+}
+
+/// The exceptions the client can ask to stop on, with the exception breakpoint filters of the DAP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExceptionFilter {
+    /// Stops before a test executes a revert.
+    Revert,
+    /// Stops after a test panics.
+    Panic,
+}
+
+impl ExceptionFilter {
+    pub const ALL: [Self; 2] = [Self::Revert, Self::Panic];
+
+    /// Returns the ID of the filter in the DAP.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Revert => "revert",
+            Self::Panic => "panic",
+        }
+    }
+
+    /// Returns the name of the filter shown by the client.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Revert => "Reverts",
+            Self::Panic => "Panics",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|filter| filter.id() == id)
+    }
+}
+
+#[derive(Default, Debug)]
 /// The state of the DAP server.
 pub struct ServerState {
     // DAP state
@@ -18,123 +226,493 @@ pub struct ServerState {
     pub started_debugging: bool,
     pub configuration_done: bool,
     pub breakpoints_need_update: bool,
-    pub stopped_on_breakpoint_id: Option<i64>,
+    /// Whether the client shows the progress reported with progress events.
+    pub supports_progress_reporting: bool,
+    /// Whether the progress of the tests being debugged is being reported.
+    pub progress_started: bool,
     pub breakpoints: Breakpoints,
+    /// The exceptions to stop on.
+    pub exception_filters: HashSet<ExceptionFilter>,
+    /// The IDs given to breakpoints by source path, line and column. They are kept across resets, so a breakpoint
+    /// set on the same location again keeps its ID.
+    pub breakpoint_ids: HashMap<(PathBuf, i64, Option<i64>), i64>,
+    /// The program counters of the breakpoints set in the VM, mapped to the breakpoint IDs, by package in the order
+    /// of `packages`.
+    pub breakpoint_pcs: Vec<HashMap<Instruction, i64>>,
+    /// The number of times each breakpoint has been hit by any of the tests, by breakpoint ID. The tests count hits
+    /// from their worker threads, see [ServerState::breakpoint_hits].
+    breakpoint_hits: Mutex<HashMap<i64, u64>>,
+    /// The data breakpoints, which watch contract storage slots. They are kept across resets.
+    pub data_breakpoints: Vec<DataBreakpoint>,
+    /// The targets returned by the last `gotoTargets` request.
+    pub goto_targets: Vec<GotoTarget>,
 
     // Build state
-    pub source_map: SourceMap,
-    pub built_package: Option<BuiltPackage>,
+    /// The name of the build profile to build the tests with, or `None` for the default one.
+    pub build_profile: Option<String>,
+    /// Whether the tests were built with optimizations, so some lines may have no instructions mapped to them.
+    pub optimized: bool,
+    /// The packages whose tests are debugged, which are all members of a workspace when debugging one.
+    pub packages: Vec<DebugPackage>,
+    /// The source maps of the packages, in the order of `packages`.
+    source_maps: Vec<IndexedSourceMap>,
 
     // Test state
-    pub test_setup: Option<TestSetup>,
-    pub test_results: Vec<forc_test::TestResult>,
-    pub executors: Vec<TestExecutor>,
-    original_executors: Vec<TestExecutor>,
+    /// The reports of the tests that have finished.
+    pub test_reports: Vec<TestReport>,
+    /// The tests that haven't finished yet, in the order they were started.
+    pub threads: Vec<DebugThread>,
+    /// The ID of the thread that the client inspected or that was reported stopped last. Requests that don't
+    /// name a thread, such as `variables`, target it.
+    pub selected_thread: i64,
+    /// The filter selecting the tests to debug. It is kept on reset, so a restart debugs the same tests.
+    pub test_filter: Option<TestFilter>,
+    /// The chain context the tests run in. It is kept on reset, so a restart reproduces the same run.
+    pub execution_context: ExecutionContext,
 }
 
 impl ServerState {
     /// Resets the data for a new run of the tests.
+    ///
+    /// The threads are dropped along with the packages and their test setups, so the next run builds the tests
+    /// again and starts from fresh contract storage. The breakpoints and their IDs are kept, and set in the VM again
+    /// once the tests are rebuilt.
     pub fn reset(&mut self) {
         self.started_debugging = false;
-        self.executors.clone_from(&self.original_executors);
-        self.built_package = None;
-        self.test_setup = None;
-        self.test_results = vec![];
-        self.stopped_on_breakpoint_id = None;
+        self.threads.clear();
+        self.packages.clear();
+        self.test_reports = vec![];
         self.breakpoints_need_update = true;
+        self.breakpoint_pcs.clear();
+        self.breakpoint_hits().clear();
+    }
+
+    /// Returns the source map of a package, by its index in `packages`, if the tests have been built.
+    pub fn source_map(&self, package: usize) -> Option<&SourceMap> {
+        self.source_maps.get(package).map(|map| &map.source_map)
     }
 
-    /// Initializes the executor stores.
-    pub fn init_executors(&mut self, executors: Vec<TestExecutor>) {
-        self.executors.clone_from(&executors);
-        self.original_executors = executors;
+    /// Returns the number of times each breakpoint has been hit, by breakpoint ID.
+    pub fn breakpoint_hits(&self) -> MutexGuard<'_, HashMap<i64, u64>> {
+        self.breakpoint_hits
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
     }
 
-    /// Returns the active [TestExecutor], if any.
-    pub fn executor(&mut self) -> Option<&mut TestExecutor> {
-        self.executors.first_mut()
+    /// Replaces the source map of the package being debugged, and indexes the instructions mapped to each line.
+    pub fn set_source_map(&mut self, source_map: SourceMap) {
+        self.set_source_maps(vec![source_map]);
+    }
+
+    /// Replaces the source maps of the packages being debugged, in the order of `packages`, and indexes the
+    /// instructions mapped to each line.
+    pub fn set_source_maps(&mut self, source_maps: Vec<SourceMap>) {
+        self.source_maps = source_maps.into_iter().map(IndexedSourceMap::new).collect();
+        self.breakpoints_need_update = true;
+    }
+
+    /// Returns the normalized paths of the source files of the packages and their dependencies, in order.
+    pub fn source_paths(&self) -> Vec<PathBuf> {
+        let paths: BTreeSet<_> = self
+            .source_maps
+            .iter()
+            .flat_map(|map| map.source_paths.iter().cloned())
+            .collect();
+        paths.into_iter().collect()
+    }
+
+    /// Returns true once the tests have been built, and the lines of breakpoints can be resolved.
+    pub fn tests_built(&self) -> bool {
+        !self.source_maps.is_empty()
+    }
+
+    /// Returns the VM program counters of the instructions mapped to a source line in a package, by its index in
+    /// `packages`, in order.
+    pub fn line_pcs(&self, package: usize, path: &Path, line: i64) -> &[Instruction] {
+        self.source_maps
+            .get(package)
+            .and_then(|map| map.line_pcs.get(&normalize_path(path)))
+            .and_then(|lines| lines.get(&line))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the VM program counters of the instructions whose spans start at a source line and column in a
+    /// package, by its index in `packages`, in order.
+    pub fn column_pcs(
+        &self,
+        package: usize,
+        path: &Path,
+        line: i64,
+        column: i64,
+    ) -> &[Instruction] {
+        self.source_maps
+            .get(package)
+            .and_then(|map| map.column_pcs.get(&normalize_path(path)))
+            .and_then(|lines| lines.get(&line))
+            .and_then(|columns| columns.get(&column))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Resolves the column a breakpoint requested on a source line and column is set on, that is, the last column a
+    /// span starts at up to the requested one, or the first one after it if there is none, in any of the packages.
+    pub fn resolve_breakpoint_column(&self, path: &Path, line: i64, column: i64) -> Option<i64> {
+        let path = normalize_path(path);
+        let columns: BTreeSet<_> = self
+            .source_maps
+            .iter()
+            .flat_map(|map| map.columns(&path, line))
+            .collect();
+        columns
+            .range(..=column)
+            .next_back()
+            .or_else(|| columns.range(column..).next())
+            .copied()
     }
 
-    /// Finds the source location matching a VM program counter.
+    /// Resolves the line and column a breakpoint requested on a source line, and optionally a column, is set on.
+    /// Breakpoints moved to another line are set on the whole line.
+    pub fn resolve_breakpoint_location(
+        &self,
+        path: &Path,
+        line: i64,
+        column: Option<i64>,
+    ) -> Option<(i64, Option<i64>)> {
+        let resolved_line = self.resolve_breakpoint_line(path, line)?;
+        let column = column
+            .filter(|_| resolved_line == line)
+            .and_then(|column| self.resolve_breakpoint_column(path, line, column));
+        Some((resolved_line, column))
+    }
+
+    /// Returns the lines and columns in a range of lines where breakpoints can be set, that is, where the spans of
+    /// instructions start in any of the packages, in order.
+    pub fn breakpoint_locations(&self, path: &Path, lines: RangeInclusive<i64>) -> Vec<(i64, i64)> {
+        let path = normalize_path(path);
+        let locations: BTreeSet<_> = self
+            .source_maps
+            .iter()
+            .filter_map(|map| map.column_pcs.get(&path))
+            .flat_map(|map_lines| map_lines.range(lines.clone()))
+            .flat_map(|(line, columns)| columns.keys().map(|column| (*line, *column)))
+            .collect();
+        locations.into_iter().collect()
+    }
+
+    /// Resolves the line a breakpoint requested on a source line is set on, that is, the first line at or after it
+    /// that has instructions mapped to it in any of the packages.
+    pub fn resolve_breakpoint_line(&self, path: &Path, line: i64) -> Option<i64> {
+        let path = normalize_path(path);
+        self.source_maps
+            .iter()
+            .filter_map(|map| {
+                let lines = map.line_pcs.get(&path)?;
+                lines.range(line..).next().map(|(line, _)| *line)
+            })
+            .min()
+    }
+
+    /// Creates a thread for each executor, with the index in `packages` of the package the executor tests. The
+    /// threads are numbered from 1 in order, and the first one is selected.
+    pub fn init_threads(&mut self, executors: Vec<(usize, TestExecutor)>) {
+        self.threads = executors
+            .into_iter()
+            .zip(1..)
+            .map(|((package, executor), id)| DebugThread::new(id, package, executor))
+            .collect();
+        self.selected_thread = 1;
+        self.breakpoints_need_update = true;
+    }
+
+    /// Returns the selected thread, or the first one if it has finished.
+    pub fn thread(&self) -> Option<&DebugThread> {
+        self.threads
+            .iter()
+            .find(|thread| thread.id == self.selected_thread)
+            .or_else(|| self.threads.first())
+    }
+
+    /// Returns the thread with the given ID, if it hasn't finished.
+    pub fn thread_mut(&mut self, thread_id: i64) -> Option<&mut DebugThread> {
+        self.threads
+            .iter_mut()
+            .find(|thread| thread.id == thread_id)
+    }
+
+    /// Selects the thread with the given ID, if it hasn't finished. Returns the ID of the selected thread, which
+    /// is the first one if the requested one has finished, or `None` if all tests have finished.
+    pub fn select_thread(&mut self, thread_id: i64) -> Option<i64> {
+        if self.threads.iter().any(|thread| thread.id == thread_id) {
+            self.selected_thread = thread_id;
+        }
+        self.thread().map(|thread| thread.id)
+    }
+
+    /// Finds the source location matching a VM program counter in a package, by its index in `packages`.
     pub fn vm_pc_to_source_location(
         &self,
+        package: usize,
         pc: Instruction,
-    ) -> Result<(PathBuf, i64), AdapterError> {
+    ) -> Result<SourceLocation, AdapterError> {
         // Convert PC to instruction index (divide by 4 for byte offset)
         let instruction_idx = (pc / 4) as usize;
-        if let Some((path, range)) = self.source_map.addr_to_span(instruction_idx) {
-            Ok((path, range.start.line as i64))
+        let span = self
+            .source_map(package)
+            .and_then(|source_map| source_map.addr_to_span(instruction_idx));
+        if let Some((path, range)) = span {
+            Ok(SourceLocation {
+                path,
+                line: range.start.line as i64,
+                column: range.start.col as i64,
+                end_line: Some(range.end.line as i64),
+                end_column: Some(range.end.col as i64),
+            })
         } else {
             Err(AdapterError::MissingSourceMap { pc })
         }
     }
 
-    /// Updates the breakpoints in the VM for all remaining [TestExecutor]s.
+    /// Finds the source path and line of a thread's current instruction, if it is mapped.
+    pub fn current_source_location(&self, thread: &DebugThread) -> Option<(PathBuf, i64)> {
+        let pc = current_instruction(thread.executor.interpreter.registers());
+        self.vm_pc_to_source_location(thread.package, pc)
+            .ok()
+            .map(|location| (location.path, location.line))
+    }
+
+    /// Finds the name of the function starting at a VM program counter in a package, by reading it from the
+    /// function's signature in the source.
+    pub fn function_name(&self, package: usize, pc: Instruction) -> Option<String> {
+        let SourceLocation { path, line, .. } = self.vm_pc_to_source_location(package, pc).ok()?;
+        let source = std::fs::read_to_string(path).ok()?;
+        let signature = source
+            .lines()
+            .nth(usize::try_from(line).ok()?.checked_sub(1)?)?;
+        let (_, rest) = signature.split_once("fn ")?;
+        let name: String = rest
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// Updates the breakpoints in the VM of all remaining threads.
     pub(crate) fn update_vm_breakpoints(&mut self) {
         if !self.breakpoints_need_update {
             return;
         }
 
-        // Convert breakpoints to instruction offsets using the source map
-        let opcode_indexes = self
-            .breakpoints
-            .iter()
-            .flat_map(|(source_path, breakpoints)| {
-                breakpoints
+        // Set the breakpoints on every instruction mapped to their lines, which are at different program counters
+        // in each package
+        let breakpoint_pcs: Vec<HashMap<_, _>> = (0..self.source_maps.len())
+            .map(|package| {
+                self.breakpoints
                     .iter()
-                    .filter_map(|bp| {
-                        bp.line.and_then(|line| {
-                            // Find any instruction that maps to this line in the source map
-                            self.source_map.map.iter().find_map(|(pc, _)| {
-                                self.source_map
-                                    .addr_to_span(*pc)
-                                    .filter(|(path, range)| {
-                                        path == source_path && range.start.line as i64 == line
-                                    })
-                                    .map(|_| pc)
+                    .flat_map(|(source_path, breakpoints)| {
+                        breakpoints
+                            .iter()
+                            .filter_map(|bp| Some((bp.id?, bp.line?, bp.column)))
+                            .flat_map(move |(id, line, column)| {
+                                let pcs = match column {
+                                    Some(column) => {
+                                        self.column_pcs(package, source_path, line, column)
+                                    }
+                                    None => self.line_pcs(package, source_path, line),
+                                };
+                                pcs.iter().map(move |pc| (*pc, id))
                             })
-                        })
                     })
-                    .collect::<Vec<_>>()
-            });
-
-        // Set breakpoints in the VM
-        self.executors.iter_mut().for_each(|executor| {
-            let bps: Vec<_> = opcode_indexes
-                .clone()
-                .map(|opcode_index| fuel_vm::state::Breakpoint::script(*opcode_index as u64))
+                    .collect()
+            })
+            .collect();
+
+        // Set the breakpoints of each test's package in its VM
+        for thread in &mut self.threads {
+            let bps: Vec<_> = breakpoint_pcs
+                .get(thread.package)
+                .into_iter()
+                .flat_map(HashMap::keys)
+                .map(|pc| fuel_vm::state::Breakpoint::script(pc / 4))
                 .collect();
-            executor.interpreter.overwrite_breakpoints(&bps);
-        });
+            thread.executor.interpreter.overwrite_breakpoints(&bps);
+        }
 
+        self.breakpoint_pcs = breakpoint_pcs;
         self.breakpoints_need_update = false;
     }
 
-    /// Finds the breakpoint matching a VM program counter.
-    pub fn vm_pc_to_breakpoint_id(&self, pc: u64) -> Result<i64, AdapterError> {
-        let (source_path, source_line) = self.vm_pc_to_source_location(pc)?;
+    /// Finds the breakpoint matching a VM program counter in a package, by its index in `packages`, if there is
+    /// one.
+    pub fn vm_pc_to_breakpoint_id(&self, package: usize, pc: Instruction) -> Option<i64> {
+        self.breakpoint_pcs.get(package)?.get(&pc).copied()
+    }
 
-        // Find the breakpoint ID matching the source location.
-        let source_bps = self
-            .breakpoints
-            .get(&source_path)
-            .ok_or(AdapterError::UnknownBreakpoint { pc })?;
-        let breakpoint_id = source_bps
-            .iter()
-            .find_map(|bp| {
-                if bp.line == Some(source_line) {
-                    bp.id
-                } else {
-                    None
-                }
-            })
-            .ok_or(AdapterError::UnknownBreakpoint { pc })?;
+    /// Records the result of a finished test, which executed the given number of instructions. Returns the report
+    /// of the test.
+    pub(crate) fn test_complete(&mut self, result: &TestResult, instructions: u64) -> &TestReport {
+        self.test_reports
+            .push(TestReport::new(result, instructions));
+        self.test_reports.last().expect("the report was just added")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(breakpoint_id)
+    const MOCK_SOURCE_PATH: &str = "some/path";
+
+    fn span(line: usize) -> SourceMapSpan {
+        column_span(line, 0)
     }
 
-    pub(crate) fn test_complete(&mut self, result: TestResult) {
-        self.test_results.push(result);
-        self.executors.remove(0);
+    fn column_span(line: usize, col: usize) -> SourceMapSpan {
+        SourceMapSpan {
+            path: PathIndex(0),
+            range: LocationRange {
+                start: LineCol { line, col },
+                end: LineCol { line, col: 10 },
+            },
+        }
+    }
+
+    #[test]
+    fn test_breakpoints_set_on_every_instruction_of_line() {
+        let mut state = ServerState::default();
+        let mut map = SourceMap::new();
+        map.paths.push(PathBuf::from(MOCK_SOURCE_PATH));
+        map.map.insert(3, span(2));
+        map.map.insert(1, span(1));
+        map.map.insert(2, span(1));
+        state.set_source_map(map);
+
+        let path = PathBuf::from(MOCK_SOURCE_PATH);
+        assert_eq!(state.line_pcs(0, &path, 1), &[4, 8]);
+        assert_eq!(state.line_pcs(0, &path, 2), &[12]);
+        assert!(state.line_pcs(0, &path, 3).is_empty());
+
+        state.breakpoints.insert(
+            path,
+            vec![dap::types::Breakpoint {
+                id: Some(7),
+                line: Some(1),
+                ..Default::default()
+            }
+            .into()],
+        );
+        state.update_vm_breakpoints();
+        assert_eq!(state.breakpoint_pcs[0], HashMap::from([(4, 7), (8, 7)]));
+        assert_eq!(state.vm_pc_to_breakpoint_id(0, 8), Some(7));
+        assert_eq!(state.vm_pc_to_breakpoint_id(0, 12), None);
+    }
+
+    #[test]
+    fn test_pc_without_breakpoint() {
+        let mut state = ServerState::default();
+        let mut map = SourceMap::new();
+        map.paths.push(PathBuf::from(MOCK_SOURCE_PATH));
+        map.map.insert(1, span(1));
+        map.map.insert(2, span(2));
+        state.set_source_map(map);
+        state.breakpoints.insert(
+            PathBuf::from(MOCK_SOURCE_PATH),
+            vec![dap::types::Breakpoint {
+                id: Some(7),
+                line: Some(1),
+                ..Default::default()
+            }
+            .into()],
+        );
+        state.update_vm_breakpoints();
+
+        // A PC that maps to the source but not to a breakpoint has a location and no breakpoint ID
+        assert_eq!(state.vm_pc_to_breakpoint_id(0, 8), None);
+        let location = state.vm_pc_to_source_location(0, 8).unwrap();
+        assert_eq!(location.line, 2);
+
+        // A PC that maps to neither has no location either, so it is only known by its address
+        assert_eq!(state.vm_pc_to_breakpoint_id(0, 40), None);
+        assert!(matches!(
+            state.vm_pc_to_source_location(0, 40),
+            Err(AdapterError::MissingSourceMap { pc: 40 })
+        ));
+        // Nor does a PC of a package without a source map
+        assert_eq!(state.vm_pc_to_breakpoint_id(1, 4), None);
+        assert!(state.vm_pc_to_source_location(1, 4).is_err());
+    }
+
+    #[test]
+    fn test_breakpoints_set_on_column() {
+        let mut state = ServerState::default();
+        let mut map = SourceMap::new();
+        map.paths.push(PathBuf::from(MOCK_SOURCE_PATH));
+        map.map.insert(1, column_span(1, 5));
+        map.map.insert(2, column_span(1, 9));
+        map.map.insert(3, column_span(1, 5));
+        map.map.insert(4, column_span(3, 1));
+        state.set_source_map(map);
+
+        let path = PathBuf::from(MOCK_SOURCE_PATH);
+        assert_eq!(
+            state.breakpoint_locations(&path, 1..=3),
+            vec![(1, 5), (1, 9), (3, 1)]
+        );
+        assert_eq!(state.breakpoint_locations(&path, 2..=2), vec![]);
+        assert_eq!(state.resolve_breakpoint_column(&path, 1, 1), Some(5));
+        assert_eq!(state.resolve_breakpoint_column(&path, 1, 8), Some(5));
+        assert_eq!(state.resolve_breakpoint_column(&path, 1, 12), Some(9));
+        assert_eq!(state.resolve_breakpoint_column(&path, 2, 1), None);
+
+        state.breakpoints.insert(
+            path.clone(),
+            vec![dap::types::Breakpoint {
+                id: Some(7),
+                line: Some(1),
+                column: Some(9),
+                ..Default::default()
+            }
+            .into()],
+        );
+        state.update_vm_breakpoints();
+        assert_eq!(state.breakpoint_pcs[0], HashMap::from([(8, 7)]));
+
+        let location = state.vm_pc_to_source_location(0, 12).unwrap();
+        assert_eq!(
+            location,
+            SourceLocation {
+                path,
+                line: 1,
+                column: 5,
+                end_line: Some(1),
+                end_column: Some(10),
+            }
+        );
+    }
+
+    #[test]
+    fn test_breakpoints_set_again_after_reset() {
+        let mut state = ServerState::default();
+        let mut map = SourceMap::new();
+        map.paths.push(PathBuf::from(MOCK_SOURCE_PATH));
+        map.map.insert(1, span(1));
+        state.set_source_map(map.clone());
+        state.breakpoints.insert(
+            PathBuf::from(MOCK_SOURCE_PATH),
+            vec![dap::types::Breakpoint {
+                id: Some(7),
+                line: Some(1),
+                ..Default::default()
+            }
+            .into()],
+        );
+        state.update_vm_breakpoints();
+        assert_eq!(state.vm_pc_to_breakpoint_id(0, 4), Some(7));
+
+        // The breakpoints of the previous run are dropped along with it, and set again once the tests are rebuilt
+        state.reset();
+        assert_eq!(state.vm_pc_to_breakpoint_id(0, 4), None);
+        state.set_source_map(map);
+        state.update_vm_breakpoints();
+        assert_eq!(state.vm_pc_to_breakpoint_id(0, 4), Some(7));
     }
 }
@@ -0,0 +1,203 @@
+//! Data breakpoints, which watch contract storage slots and stop a test once it writes one.
+//!
+//! A data breakpoint is identified by the key of the slot it watches, written as a `0x`-prefixed b256. The key of a
+//! storage field can be derived from its path, e.g. `count`, `storage.count` or `storage::namespace.count`. Writes
+//! to the slot by any contract stop the test.
+
+use crate::server::util;
+use forc_test::execute::TestExecutor;
+use fuel_types::{Bytes32, ContractId};
+use fuel_vm::{
+    fuel_asm::{self, RegId},
+    storage::{InterpreterStorage, MemoryStorage},
+};
+
+/// A data breakpoint on a contract storage slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataBreakpoint {
+    pub id: i64,
+    /// The key of the watched slot.
+    pub key: Bytes32,
+}
+
+/// Parses a storage key, a `0x`-prefixed b256 such as the ID of a data breakpoint.
+pub fn parse_storage_key(key: &str) -> Option<Bytes32> {
+    let digits = key.trim().strip_prefix("0x")?;
+    if digits.len() != 64 || !digits.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    for (byte, pair) in bytes.iter_mut().zip(digits.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(Bytes32::new(bytes))
+}
+
+/// Formats a storage key as the ID of a data breakpoint, e.g. `0x02dac9…`.
+pub fn format_storage_key(key: &Bytes32) -> String {
+    format!("{key:#x}")
+}
+
+/// Returns the key of the slot a storage field is stored in, given its path, e.g. `count`, `storage.count` or
+/// `storage::namespace.count`.
+pub fn storage_field_key(path: &str) -> Option<Bytes32> {
+    let path = path.trim();
+    let path = path
+        .strip_prefix("storage.")
+        .or_else(|| path.strip_prefix("storage::"))
+        .unwrap_or(path);
+    let names: Vec<String> = path
+        .split("::")
+        .flat_map(|names| names.split('.'))
+        .map(String::from)
+        .collect();
+    let is_identifier = |name: &String| {
+        name.chars().next().is_some_and(|c| !c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    if !names.iter().all(is_identifier) {
+        return None;
+    }
+    Some(sway_core::ir_generation::storage::get_storage_key(
+        names, None,
+    ))
+}
+
+/// The contract storage slots an instruction writes, which are `slots` consecutive slots starting at `start_key`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageWrite {
+    pub contract_id: ContractId,
+    pub start_key: Bytes32,
+    pub slots: u64,
+}
+
+impl StorageWrite {
+    /// Returns the storage write made by the instruction at the program counter of the test, if it is `SWW`,
+    /// `SWWQ` or `SCWQ` and runs in a contract.
+    pub fn at_pc(executor: &TestExecutor) -> Option<Self> {
+        let registers = executor.interpreter.registers();
+        let (key_register, slots) = match util::decode_instruction_at_pc(executor)? {
+            fuel_asm::Instruction::SWW(sww) => (sww.unpack().0, 1),
+            fuel_asm::Instruction::SWWQ(swwq) => {
+                let (key_register, _, _, slots_register) = swwq.unpack();
+                (key_register, registers[slots_register])
+            }
+            fuel_asm::Instruction::SCWQ(scwq) => {
+                let (key_register, _, slots_register) = scwq.unpack();
+                (key_register, registers[slots_register])
+            }
+            _ => return None,
+        };
+        // Storage is only accessible from a contract call, whose frame starts with the ID of the contract.
+        let frame_pointer = registers[RegId::FP];
+        if frame_pointer == 0 {
+            return None;
+        }
+        let contract_id = util::read_memory(executor, frame_pointer, 32)
+            .try_into()
+            .ok()?;
+        let start_key = util::read_memory(executor, registers[key_register], 32)
+            .try_into()
+            .ok()?;
+        Some(Self {
+            contract_id: ContractId::new(contract_id),
+            start_key: Bytes32::new(start_key),
+            slots,
+        })
+    }
+
+    /// Returns whether the slot with the given key is one of the written slots.
+    pub fn writes(&self, key: &Bytes32) -> bool {
+        // The slots are consecutive, so the key is written if it is less than `slots` past the start key.
+        let mut offset = [0u8; 32];
+        let mut borrow = false;
+        for i in (0..32).rev() {
+            let (difference, borrow_start) = key[i].overflowing_sub(self.start_key[i]);
+            let (difference, borrow_previous) = difference.overflowing_sub(borrow as u8);
+            offset[i] = difference;
+            borrow = borrow_start || borrow_previous;
+        }
+        let (high, low) = offset.split_at(24);
+        !borrow
+            && high.iter().all(|byte| *byte == 0)
+            && u64::from_be_bytes(low.try_into().expect("8 bytes")) < self.slots
+    }
+}
+
+/// Returns the value of a contract's storage slot, or `None` if the slot is unset.
+pub fn storage_value(
+    executor: &TestExecutor,
+    contract_id: &ContractId,
+    key: &Bytes32,
+) -> Option<Vec<u8>> {
+    let storage: &MemoryStorage = executor.interpreter.as_ref();
+    let value = storage.contract_state(contract_id, key).ok()??;
+    Some(AsRef::<[u8]>::as_ref(&*value).to_vec())
+}
+
+/// Formats the value of a storage slot as hexadecimal, e.g. `0x0000000000000001…`, or `unset` if the slot is unset.
+pub fn format_storage_value(value: Option<&[u8]>) -> String {
+    match value {
+        Some(bytes) => {
+            let digits: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+            format!("0x{digits}")
+        }
+        None => "unset".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0x00000000000000000000000000000000000000000000000000000000000000ff";
+
+    #[test]
+    fn test_parse_storage_key() {
+        let key = parse_storage_key(KEY).expect("valid key");
+        assert_eq!(key[31], 0xff);
+        assert_eq!(format_storage_key(&key), KEY);
+        assert_eq!(parse_storage_key(&KEY[2..]), None);
+        assert_eq!(parse_storage_key(&KEY[..64]), None);
+        assert_eq!(parse_storage_key("0xzz"), None);
+    }
+
+    #[test]
+    fn test_storage_field_key() {
+        let key = storage_field_key("count").expect("valid path");
+        assert_eq!(storage_field_key("storage.count"), Some(key));
+        assert_ne!(storage_field_key("total"), Some(key));
+        assert_eq!(
+            storage_field_key("storage::namespace.count"),
+            storage_field_key("namespace.count")
+        );
+        assert_ne!(storage_field_key("namespace.count"), Some(key));
+        assert_eq!(storage_field_key("count + 1"), None);
+        assert_eq!(storage_field_key(""), None);
+    }
+
+    #[test]
+    fn test_storage_write_writes() {
+        let start_key = parse_storage_key(KEY).unwrap();
+        let write = StorageWrite {
+            contract_id: ContractId::zeroed(),
+            start_key,
+            slots: 2,
+        };
+        let key = |last_bytes: [u8; 2]| {
+            let mut key = [0u8; 32];
+            key[30..].copy_from_slice(&last_bytes);
+            Bytes32::new(key)
+        };
+        assert!(write.writes(&start_key));
+        assert!(write.writes(&key([0x01, 0x00])));
+        assert!(!write.writes(&key([0x01, 0x01])));
+        assert!(!write.writes(&key([0x00, 0xfe])));
+    }
+
+    #[test]
+    fn test_format_storage_value() {
+        assert_eq!(format_storage_value(Some(&[0, 1, 0xab])), "0x0001ab");
+        assert_eq!(format_storage_value(None), "unset");
+    }
+}
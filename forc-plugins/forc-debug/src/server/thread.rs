@@ -0,0 +1,470 @@
+//! The tests being debugged, each of which is reported to the client as a thread named after the test.
+//!
+//! Tests run on worker threads while they are resumed, so a test stopped in the debugger doesn't hold up the
+//! others. Workers send the messages of the logpoints they hit, and how each run ended, to the server loop through
+//! a channel.
+
+use crate::{
+    error::AdapterError,
+    server::{
+        state::{BreakpointHit, CallFrame, ExceptionFilter, ServerState},
+        storage::{self, StorageWrite},
+        util::{self, FrameChange},
+        Step,
+    },
+    types::Instruction,
+};
+use dap::{events::StoppedEventBody, types::StoppedEventReason};
+use forc_test::{
+    execute::{DebugResult, TestExecutor},
+    TestResult,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::Sender,
+};
+
+/// How a [DebugThread] is resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Resume {
+    /// Starts the test, running it until it stops.
+    Start,
+    /// Runs until a breakpoint is hit.
+    Continue,
+    /// Runs until the [Step] is complete, or for a single instruction if `instruction` is set.
+    Step { step: Step, instruction: bool },
+}
+
+/// Why a [DebugThread] stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThreadStop {
+    /// A breakpoint was hit.
+    Breakpoint,
+    /// A step was completed.
+    Step,
+    /// The test is about to revert or has panicked, as described.
+    Exception(String),
+    /// A data breakpoint was hit, by writing a storage slot as described.
+    DataBreakpoint(String),
+    /// The goto target the test was run to was reached.
+    Goto,
+    /// The client requested to pause.
+    Pause,
+}
+
+impl ThreadStop {
+    /// Returns the body of the stopped event reporting the stop of the thread with the given ID, which stopped on
+    /// the breakpoint with the given ID, if any.
+    ///
+    /// Tests routinely stop where there is no breakpoint, such as after a step or a pause, so the breakpoint ID is
+    /// only reported for stops on breakpoints. A breakpoint whose ID can't be found, for example because it was
+    /// removed while the test ran, is still reported as a breakpoint, without an ID.
+    pub fn event_body(self, thread_id: i64, breakpoint_id: Option<i64>) -> StoppedEventBody {
+        let (reason, hit_breakpoint_ids, description, text) = match self {
+            ThreadStop::Breakpoint => (
+                StoppedEventReason::Breakpoint,
+                breakpoint_id.map(|id| vec![id]),
+                None,
+                None,
+            ),
+            ThreadStop::Step => (StoppedEventReason::Step, None, None, None),
+            ThreadStop::Exception(text) => (
+                StoppedEventReason::Exception,
+                None,
+                Some("Paused on exception".into()),
+                Some(text),
+            ),
+            ThreadStop::DataBreakpoint(text) => (
+                StoppedEventReason::Data,
+                breakpoint_id.map(|id| vec![id]),
+                Some("Paused on data breakpoint".into()),
+                Some(text),
+            ),
+            ThreadStop::Goto => (StoppedEventReason::Goto, None, None, None),
+            ThreadStop::Pause => (StoppedEventReason::Pause, None, None, None),
+        };
+        StoppedEventBody {
+            reason,
+            hit_breakpoint_ids,
+            description,
+            thread_id: Some(thread_id),
+            preserve_focus_hint: None,
+            text,
+            all_threads_stopped: Some(false),
+        }
+    }
+}
+
+/// How a run of a [DebugThread] ended.
+#[derive(Debug)]
+pub(crate) enum RunResult {
+    Stopped(ThreadStop),
+    Finished(TestResult),
+}
+
+/// A message sent from a worker to the server loop, along with the ID of the thread it runs.
+#[derive(Debug)]
+pub(crate) enum ThreadMessage {
+    /// The rendered message of a logpoint that was hit.
+    Log(String),
+    /// The run ended, or failed.
+    Done(Result<RunResult, AdapterError>),
+}
+
+/// What a worker shares with the server loop while it runs a [DebugThread].
+pub(crate) struct RunContext<'a> {
+    pub state: &'a ServerState,
+    /// Set when the client requests to pause the running tests.
+    pub pause_requested: &'a AtomicBool,
+    pub messages: Sender<(i64, ThreadMessage)>,
+}
+
+/// The result of executing a single instruction.
+enum Executed {
+    /// Execution continues at the program counter.
+    Next(Instruction),
+    Finished(TestResult),
+}
+
+/// A test being debugged.
+#[derive(Debug)]
+pub struct DebugThread {
+    /// The ID of the thread reported to the client.
+    pub id: i64,
+    /// The index in `packages` of the package the test is in.
+    pub package: usize,
+    pub executor: TestExecutor,
+    /// The calls the test is inside of, outermost first.
+    pub call_stack: Vec<CallFrame>,
+    /// The number of instructions the test has executed so far.
+    pub instructions_executed: u64,
+    /// The ID of the breakpoint the test is stopped on, if any.
+    pub stopped_on_breakpoint_id: Option<i64>,
+    /// Why the test stopped, until the stop is reported to the client.
+    pub unreported_stop: Option<ThreadStop>,
+    /// The result of the test if it has panicked, while it is stopped on the panic.
+    panicked_test: Option<TestResult>,
+    /// The ID of the breakpoint on the line of the last executed instruction, if any.
+    last_breakpoint_id: Option<i64>,
+    /// The ID of the data breakpoint on the storage slot the last executed instruction wrote, if any, along with a
+    /// description of the write.
+    data_breakpoint_hit: Option<(i64, String)>,
+    /// The program counters of the goto target the test is running to, if any. The target acts as a breakpoint
+    /// that is removed once the test stops.
+    goto_target: Option<Vec<Instruction>>,
+    /// Whether the last executed instruction is one of the goto target's, so the target is only reached when
+    /// execution enters it.
+    in_goto_target: bool,
+}
+
+impl DebugThread {
+    pub fn new(id: i64, package: usize, executor: TestExecutor) -> Self {
+        Self {
+            id,
+            package,
+            executor,
+            call_stack: vec![],
+            instructions_executed: 0,
+            stopped_on_breakpoint_id: None,
+            unreported_stop: None,
+            panicked_test: None,
+            last_breakpoint_id: None,
+            data_breakpoint_hit: None,
+            goto_target: None,
+            in_goto_target: false,
+        }
+    }
+
+    /// Returns the name of the test.
+    pub fn name(&self) -> &str {
+        &self.executor.name
+    }
+
+    /// Sets the program counters of a goto target to run the test to the next time it is resumed.
+    pub fn set_goto_target(&mut self, pcs: Vec<Instruction>) {
+        let pc = util::current_instruction(self.executor.interpreter.registers());
+        self.in_goto_target = pcs.contains(&pc);
+        self.goto_target = Some(pcs);
+    }
+
+    /// Runs the test until it stops or finishes.
+    pub(crate) fn run(
+        &mut self,
+        context: &RunContext,
+        resume: Resume,
+    ) -> Result<RunResult, AdapterError> {
+        self.stopped_on_breakpoint_id = None;
+        let result = match resume {
+            Resume::Start => self.start(context),
+            Resume::Continue => self.continue_running(context),
+            Resume::Step { step, instruction } => self.step(context, step, instruction),
+        };
+        // The goto target is only run to once, even if the test stopped before reaching it.
+        self.goto_target = None;
+        result
+    }
+
+    fn start(&mut self, context: &RunContext) -> Result<RunResult, AdapterError> {
+        // Break on the first instruction of the test, so the rest of it runs one instruction at a time and the
+        // call stack can be tracked.
+        self.executor.interpreter.set_single_stepping(true);
+        match self.executor.start_debugging()? {
+            DebugResult::TestComplete(result) => Ok(RunResult::Finished(result)),
+            DebugResult::Breakpoint(pc) => {
+                self.executor.interpreter.set_single_stepping(false);
+                if let Some(stop) = self.check_stop(context, pc) {
+                    return Ok(RunResult::Stopped(stop));
+                }
+                self.continue_running(context)
+            }
+        }
+    }
+
+    fn continue_running(&mut self, context: &RunContext) -> Result<RunResult, AdapterError> {
+        loop {
+            let pc = match self.step_instruction(context.state)? {
+                Executed::Next(pc) => pc,
+                Executed::Finished(result) => return Ok(RunResult::Finished(result)),
+            };
+            if let Some(stop) = self.check_stop(context, pc) {
+                return Ok(RunResult::Stopped(stop));
+            }
+            if context.pause_requested.load(Ordering::SeqCst) {
+                return Ok(RunResult::Stopped(ThreadStop::Pause));
+            }
+        }
+    }
+
+    /// Steps the test until the given [Step] is complete, or for a single instruction if `instruction` is set.
+    fn step(
+        &mut self,
+        context: &RunContext,
+        step: Step,
+        instruction: bool,
+    ) -> Result<RunResult, AdapterError> {
+        let start_location = context.state.current_source_location(self);
+        let start_depth = self.call_stack.len();
+        // Lines can't be stepped from instructions that aren't mapped to one, which are common in optimized
+        // builds, so those are stepped one instruction at a time.
+        let instruction = instruction || start_location.is_none();
+        loop {
+            let pc = match self.step_instruction(context.state)? {
+                Executed::Next(pc) => pc,
+                Executed::Finished(result) => return Ok(RunResult::Finished(result)),
+            };
+            if let Some(stop) = self.check_stop(context, pc) {
+                return Ok(RunResult::Stopped(stop));
+            }
+            if instruction {
+                return Ok(RunResult::Stopped(ThreadStop::Step));
+            }
+            if context.pause_requested.load(Ordering::SeqCst) {
+                return Ok(RunResult::Stopped(ThreadStop::Pause));
+            }
+
+            // Only stop on instructions that map to a line in the source.
+            let location = context.state.current_source_location(self);
+            if location.is_none() {
+                continue;
+            }
+            let line_changed = location != start_location;
+            let depth = self.call_stack.len();
+            let step_complete = match step {
+                Step::Over => line_changed && depth <= start_depth,
+                Step::In => line_changed,
+                Step::Out => depth < start_depth,
+            };
+            if step_complete {
+                return Ok(RunResult::Stopped(ThreadStop::Step));
+            }
+        }
+    }
+
+    /// Checks whether the test should stop on an exception, a data breakpoint, a breakpoint or the goto target at
+    /// the given program counter. A breakpoint on the goto target is reported as the breakpoint.
+    fn check_stop(&mut self, context: &RunContext, pc: Instruction) -> Option<ThreadStop> {
+        if let Some(exception) = self.check_exception(context.state) {
+            return Some(ThreadStop::Exception(exception));
+        }
+        if let Some((id, text)) = self.data_breakpoint_hit.take() {
+            // The line is entered all the same, so a breakpoint on it isn't hit once the test is resumed.
+            self.last_breakpoint_id = context.state.vm_pc_to_breakpoint_id(self.package, pc);
+            self.stopped_on_breakpoint_id = Some(id);
+            return Some(ThreadStop::DataBreakpoint(text));
+        }
+        match self.breakpoint_hit(context.state, pc) {
+            Some(BreakpointHit::Stop) => {
+                self.stopped_on_breakpoint_id =
+                    context.state.vm_pc_to_breakpoint_id(self.package, pc);
+                return Some(ThreadStop::Breakpoint);
+            }
+            Some(BreakpointHit::Log(message)) => {
+                let _ = context
+                    .messages
+                    .send((self.id, ThreadMessage::Log(format!("{message}\n"))));
+            }
+            None => {}
+        }
+        let goto_target = self.goto_target.as_ref()?;
+        let in_goto_target = goto_target.contains(&pc);
+        let entered = in_goto_target && !self.in_goto_target;
+        self.in_goto_target = in_goto_target;
+        entered.then_some(ThreadStop::Goto)
+    }
+
+    /// Checks whether the test is about to revert or has panicked, and the client asked to stop on it.
+    ///
+    /// Returns a description of the exception if execution should stop.
+    fn check_exception(&self, state: &ServerState) -> Option<String> {
+        if self.panicked_test.is_some() {
+            return util::find_panic(self.executor.interpreter.receipts()).map(|(_, text)| text);
+        }
+        if !state.exception_filters.contains(&ExceptionFilter::Revert) {
+            return None;
+        }
+        util::revert_description(&self.executor)
+    }
+
+    /// Checks whether a breakpoint at a VM program counter is hit by the test, that is, whether there is one and
+    /// its conditions, if any, hold.
+    ///
+    /// Every time the breakpoint's condition holds counts as a hit, which its hit condition is checked against.
+    /// A breakpoint is only hit when execution enters its line, not again for each of the line's instructions.
+    pub fn breakpoint_hit(
+        &mut self,
+        state: &ServerState,
+        pc: Instruction,
+    ) -> Option<BreakpointHit> {
+        let line_breakpoint_id = state.vm_pc_to_breakpoint_id(self.package, pc);
+        let previous_breakpoint_id =
+            std::mem::replace(&mut self.last_breakpoint_id, line_breakpoint_id);
+        let breakpoint_id = line_breakpoint_id.filter(|id| previous_breakpoint_id != Some(*id))?;
+        let Some(breakpoint) = state
+            .breakpoints
+            .values()
+            .flatten()
+            .find(|bp| bp.id == Some(breakpoint_id))
+        else {
+            return Some(BreakpointHit::Stop);
+        };
+        if let Some(condition) = &breakpoint.condition {
+            if !condition.evaluate(&self.executor) {
+                return None;
+            }
+        }
+
+        let hits = {
+            let mut breakpoint_hits = state.breakpoint_hits();
+            let hits = breakpoint_hits.entry(breakpoint_id).or_default();
+            *hits += 1;
+            *hits
+        };
+        if let Some(hit_condition) = &breakpoint.hit_condition {
+            if !hit_condition.is_met(hits) {
+                return None;
+            }
+        }
+
+        match &breakpoint.log_message {
+            Some(log_message) => Some(BreakpointHit::Log(log_message.render(&self.executor))),
+            None => Some(BreakpointHit::Stop),
+        }
+    }
+
+    /// Executes a single instruction of the test and keeps track of the calls it makes and the watched storage
+    /// slots it writes.
+    ///
+    /// If the test panics and the client asked to stop on panics, the test is kept running until it is resumed,
+    /// and the program counter of the faulting instruction is returned.
+    fn step_instruction(&mut self, state: &ServerState) -> Result<Executed, AdapterError> {
+        if let Some(result) = self.panicked_test.take() {
+            return Ok(Executed::Finished(result));
+        }
+
+        let registers_before = self.executor.interpreter.registers().to_vec();
+        let instruction = util::decode_instruction_at_pc(&self.executor);
+        // The value of a watched slot is read before it is written, so the write can be described.
+        let watched_write = StorageWrite::at_pc(&self.executor).and_then(|write| {
+            let breakpoint = state
+                .data_breakpoints
+                .iter()
+                .find(|breakpoint| write.writes(&breakpoint.key))?;
+            let old_value =
+                storage::storage_value(&self.executor, &write.contract_id, &breakpoint.key);
+            Some((*breakpoint, write.contract_id, old_value))
+        });
+
+        self.executor.interpreter.set_single_stepping(true);
+        let result = self.executor.continue_debugging()?;
+        self.executor.interpreter.set_single_stepping(false);
+        self.instructions_executed += 1;
+        if let DebugResult::TestComplete(result) = result {
+            let panic_pc = util::find_panic(self.executor.interpreter.receipts()).map(|(pc, _)| pc);
+            match panic_pc {
+                Some(pc) if state.exception_filters.contains(&ExceptionFilter::Panic) => {
+                    // Keep the test running, so it can be inspected while stopped on the panic.
+                    self.panicked_test = Some(result);
+                    return Ok(Executed::Next(pc));
+                }
+                _ => {}
+            }
+            return Ok(Executed::Finished(result));
+        }
+
+        let registers = self.executor.interpreter.registers();
+        let pc = util::current_instruction(registers);
+        match util::frame_change(instruction, &registers_before, registers) {
+            Some(FrameChange::Call) => self.call_stack.push(CallFrame {
+                call_pc: util::current_instruction(&registers_before),
+                function_pc: pc,
+            }),
+            Some(FrameChange::Return) => {
+                self.call_stack.pop();
+            }
+            None => {}
+        }
+
+        if let Some((breakpoint, contract_id, old_value)) = watched_write {
+            let new_value = storage::storage_value(&self.executor, &contract_id, &breakpoint.key);
+            let text = format!(
+                "Storage slot {} of contract {contract_id:#x} written: {} -> {}",
+                storage::format_storage_key(&breakpoint.key),
+                storage::format_storage_value(old_value.as_deref()),
+                storage::format_storage_value(new_value.as_deref()),
+            );
+            self.data_breakpoint_hit = Some((breakpoint.id, text));
+        }
+        Ok(Executed::Next(pc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_event_body_on_breakpoint() {
+        let body = ThreadStop::Breakpoint.event_body(2, Some(7));
+        assert!(matches!(body.reason, StoppedEventReason::Breakpoint));
+        assert_eq!(body.hit_breakpoint_ids, Some(vec![7]));
+        assert_eq!(body.thread_id, Some(2));
+        assert_eq!(body.all_threads_stopped, Some(false));
+
+        // A breakpoint that can't be found is reported without an ID
+        let body = ThreadStop::Breakpoint.event_body(2, None);
+        assert!(matches!(body.reason, StoppedEventReason::Breakpoint));
+        assert_eq!(body.hit_breakpoint_ids, None);
+    }
+
+    #[test]
+    fn test_stop_event_body_without_breakpoint() {
+        for stop in [ThreadStop::Step, ThreadStop::Pause, ThreadStop::Goto] {
+            let body = stop.event_body(1, None);
+            assert_eq!(body.hit_breakpoint_ids, None);
+            assert_eq!(body.text, None);
+        }
+        let body = ThreadStop::Exception("Test reverted".into()).event_body(1, None);
+        assert!(matches!(body.reason, StoppedEventReason::Exception));
+        assert_eq!(body.text.as_deref(), Some("Test reverted"));
+    }
+}
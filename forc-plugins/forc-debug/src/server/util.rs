@@ -1,7 +1,16 @@
-use crate::types::Instruction;
+use crate::{error::AdapterError, types::Instruction};
 use dap::types::Source;
-use fuel_vm::fuel_asm::RegId;
-use std::path::Path;
+use forc_pkg::{BuiltPackage, PkgEntry, PkgTestEntry};
+use forc_test::execute::TestExecutor;
+use fuel_tx::{field::ScriptGasLimit, Receipt};
+use fuel_vm::{
+    consts::VM_MAX_RAM,
+    fuel_asm::{self, RawInstruction, RegId},
+};
+use std::path::{Path, PathBuf};
+
+/// The register holding the return address of a Sway function call (`$$reta`).
+const RETURN_ADDRESS_REGISTER: usize = 62;
 
 #[derive(Debug, Clone)]
 /// Utility for generating unique, incremental IDs.
@@ -37,8 +46,155 @@ pub(crate) fn path_into_source(path: &Path) -> Source {
     }
 }
 
+/// Normalizes a source path, so paths from the client and from the source map can be compared.
+///
+/// Paths are canonicalized if they exist, and lowercased on platforms with case-insensitive file systems.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if cfg!(any(windows, target_os = "macos")) {
+        PathBuf::from(path.to_string_lossy().to_lowercase())
+    } else {
+        path
+    }
+}
+
 pub(crate) fn current_instruction(registers: &[u64]) -> Instruction {
     let pc = registers[RegId::PC];
     let is = registers[RegId::IS];
     pc - is
 }
+
+/// Parses a memory reference given by the client, which is a hexadecimal address, e.g. `0x1f4`.
+pub(crate) fn parse_memory_reference(memory_reference: &str) -> Result<i64, AdapterError> {
+    let memory_reference = memory_reference.trim();
+    let (sign, digits) = match memory_reference.strip_prefix('-') {
+        Some(digits) => (-1, digits),
+        None => (1, memory_reference),
+    };
+    i64::from_str_radix(digits.trim_start_matches("0x"), 16)
+        .map(|address| sign * address)
+        .map_err(|_| AdapterError::InvalidMemoryReference {
+            memory_reference: memory_reference.to_string(),
+        })
+}
+
+/// Formats an address as a memory reference, e.g. `0x1f4`.
+pub(crate) fn format_memory_reference(address: i64) -> String {
+    if address < 0 {
+        format!("-0x{:x}", address.unsigned_abs())
+    } else {
+        format!("0x{address:x}")
+    }
+}
+
+/// Formats a VM word as both decimal and hexadecimal, e.g. `255 (0xFF)`.
+pub(crate) fn format_word(value: u64) -> String {
+    format!("{value} (0x{value:X})")
+}
+
+/// Returns the gas the test has used so far.
+pub(crate) fn gas_used(executor: &TestExecutor) -> u64 {
+    let gas_limit = executor.tx.transaction().script_gas_limit();
+    gas_limit.saturating_sub(executor.interpreter.registers()[RegId::GGAS])
+}
+
+/// Reads up to `count` bytes of the test's memory at `address`.
+///
+/// Only the stack, below `$sp`, and the heap, from `$hp`, are readable. The read stops at the end of the region
+/// it starts in, so fewer bytes than requested are returned if the rest are unreadable.
+pub(crate) fn read_memory(executor: &TestExecutor, address: u64, count: u64) -> &[u8] {
+    let registers = executor.interpreter.registers();
+    let region_end = if address < registers[RegId::SP] {
+        registers[RegId::SP]
+    } else if address >= registers[RegId::HP] && address < VM_MAX_RAM {
+        VM_MAX_RAM
+    } else {
+        return &[];
+    };
+    let end = address.saturating_add(count).min(region_end);
+    &executor.interpreter.memory()[address as usize..end as usize]
+}
+
+/// Decodes the instruction at the program counter, if it is a valid one.
+pub(crate) fn decode_instruction_at_pc(executor: &TestExecutor) -> Option<fuel_asm::Instruction> {
+    let pc = executor.interpreter.registers()[RegId::PC] as usize;
+    let bytes = executor.interpreter.memory()[pc..pc + 4].try_into().ok()?;
+    fuel_asm::Instruction::try_from(RawInstruction::from_be_bytes(bytes)).ok()
+}
+
+/// Describes the revert the active test is about to make, if the instruction at its program counter is `RVRT`.
+pub(crate) fn revert_description(executor: &TestExecutor) -> Option<String> {
+    let fuel_asm::Instruction::RVRT(rvrt) = decode_instruction_at_pc(executor)? else {
+        return None;
+    };
+    let code = executor.interpreter.registers()[rvrt.unpack()];
+    Some(format!("Test reverted with code {}", format_word(code)))
+}
+
+/// Finds the panic a finished test stopped with in its receipts, returning the program counter of the faulting
+/// instruction and a description of the panic.
+pub(crate) fn find_panic(receipts: &[Receipt]) -> Option<(Instruction, String)> {
+    receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Panic { reason, pc, is, .. } => Some((
+            pc - is,
+            format!("Test panicked with reason {:?}", reason.reason()),
+        )),
+        _ => None,
+    })
+}
+
+/// Finds the program counter of the instruction a finished test reverted or panicked on in its receipts.
+pub(crate) fn find_revert_pc(receipts: &[Receipt]) -> Option<Instruction> {
+    receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Revert { pc, is, .. } | Receipt::Panic { pc, is, .. } => Some(pc - is),
+        _ => None,
+    })
+}
+
+/// A change to the call stack caused by executing an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FrameChange {
+    Call,
+    Return,
+}
+
+/// Returns how executing `instruction` changed the call stack, given the registers before and after it ran.
+///
+/// Contract calls go through the `CALL` and `RET`/`RETD` opcodes. Sway functions are called with a plain
+/// jump after `$$reta` is set to the instruction following the jump, and return with a `JMP` to `$$reta`.
+pub(crate) fn frame_change(
+    instruction: Option<fuel_asm::Instruction>,
+    before: &[u64],
+    after: &[u64],
+) -> Option<FrameChange> {
+    let instruction_index = |registers: &[u64]| current_instruction(registers) / 4;
+    match instruction {
+        Some(fuel_asm::Instruction::CALL(_)) => Some(FrameChange::Call),
+        Some(fuel_asm::Instruction::RET(_) | fuel_asm::Instruction::RETD(_)) => {
+            Some(FrameChange::Return)
+        }
+        Some(fuel_asm::Instruction::JMP(_))
+            if instruction_index(after) == before[RETURN_ADDRESS_REGISTER] =>
+        {
+            Some(FrameChange::Return)
+        }
+        _ if after[RegId::PC] != before[RegId::PC] + 4
+            && after[RETURN_ADDRESS_REGISTER] == instruction_index(before) + 1 =>
+        {
+            Some(FrameChange::Call)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the test entries of a built package that are declared in the source file at `program_path`.
+pub(crate) fn test_entries<'a>(
+    pkg: &'a BuiltPackage,
+    program_path: &Path,
+) -> impl Iterator<Item = (&'a PkgEntry, &'a PkgTestEntry)> + 'a {
+    let program_path = normalize_path(program_path);
+    pkg.bytecode.entries.iter().filter_map(move |entry| {
+        let test_entry = entry.kind.test()?;
+        (normalize_path(&test_entry.file_path) == program_path).then_some((entry, test_entry))
+    })
+}
@@ -1,4 +1,7 @@
-use crate::error::{Error, Result};
+use crate::{
+    error::{Error, Result},
+    server::condition::{BreakpointCondition, HitCondition, LogMessage},
+};
 use dap::types::Breakpoint;
 use fuel_types::ContractId;
 use std::{
@@ -10,7 +13,73 @@ use sway_core::asm_generation::ProgramABI;
 
 pub type ExitCode = i64;
 pub type Instruction = u64;
-pub type Breakpoints = HashMap<PathBuf, Vec<Breakpoint>>;
+pub type Breakpoints = HashMap<PathBuf, Vec<ClientBreakpoint>>;
+
+/// A breakpoint set by the client, along with the conditions that must hold for it to stop execution.
+///
+/// Breakpoints with a log message are logpoints, which log the message instead of stopping.
+#[derive(Debug, Clone, Default)]
+pub struct ClientBreakpoint {
+    pub breakpoint: Breakpoint,
+    pub condition: Option<BreakpointCondition>,
+    pub hit_condition: Option<HitCondition>,
+    pub log_message: Option<LogMessage>,
+}
+
+impl From<Breakpoint> for ClientBreakpoint {
+    fn from(breakpoint: Breakpoint) -> Self {
+        Self {
+            breakpoint,
+            condition: None,
+            hit_condition: None,
+            log_message: None,
+        }
+    }
+}
+
+impl Deref for ClientBreakpoint {
+    type Target = Breakpoint;
+    fn deref(&self) -> &Self::Target {
+        &self.breakpoint
+    }
+}
+
+/// A filter selecting the tests to debug by name.
+///
+/// Filters containing glob wildcards (`*`, `?` or `[...]`) must match the whole test name, other filters match any
+/// test whose name contains them.
+#[derive(Debug, Clone)]
+pub struct TestFilter {
+    filter: String,
+    pattern: Option<glob::Pattern>,
+}
+
+impl TestFilter {
+    pub fn new(filter: &str) -> Self {
+        let pattern = filter
+            .contains(['*', '?', '['])
+            .then(|| glob::Pattern::new(filter).ok())
+            .flatten();
+        Self {
+            filter: filter.to_string(),
+            pattern,
+        }
+    }
+
+    /// Returns true if the test with the given name should be debugged.
+    pub fn matches(&self, test_name: &str) -> bool {
+        match &self.pattern {
+            Some(pattern) => pattern.matches(test_name),
+            None => test_name.contains(&self.filter),
+        }
+    }
+}
+
+impl std::fmt::Display for TestFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.filter)
+    }
+}
 
 /// A map storing ABIs for contracts, capable of fetching ABIs from the registry for unknown contracts.
 #[derive(Debug, Default)]
@@ -0,0 +1,10 @@
+script;
+
+use std::block::height;
+
+fn main() {}
+
+#[test]
+fn test_block_height() {
+    assert(height() == 1000);
+}
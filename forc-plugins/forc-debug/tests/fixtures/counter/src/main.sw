@@ -0,0 +1,31 @@
+contract;
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment();
+    #[storage(read)]
+    fn get() -> u64;
+}
+
+storage {
+    count: u64 = 0,
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment() {
+        storage.count.write(storage.count.read() + 1);
+    }
+
+    #[storage(read)]
+    fn get() -> u64 {
+        storage.count.read()
+    }
+}
+
+#[test]
+fn test_increment() {
+    let counter = abi(Counter, CONTRACT_ID);
+    counter.increment();
+    assert_eq(counter.get(), 1);
+}
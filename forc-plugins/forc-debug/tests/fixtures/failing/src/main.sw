@@ -0,0 +1,12 @@
+script;
+
+fn main() {}
+
+fn is_even(value: u64) -> bool {
+    value % 2 == 0
+}
+
+#[test]
+fn test_assert() {
+    assert(is_even(3));
+}
@@ -0,0 +1,12 @@
+script;
+
+fn main() {}
+
+#[test]
+fn test_loop() {
+    let mut i = 0;
+    while i < 10 {
+        i += 1;
+    }
+    assert_eq(i, 10);
+}
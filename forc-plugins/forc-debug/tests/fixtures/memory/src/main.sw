@@ -0,0 +1,10 @@
+script;
+
+fn main() {}
+
+#[test]
+fn test_memory() {
+    let value: u64 = 0x0123456789ABCDEF;
+    let array = [value, value];
+    assert_eq(array[1], value);
+}
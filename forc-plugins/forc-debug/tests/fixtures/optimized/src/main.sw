@@ -0,0 +1,15 @@
+script;
+
+fn main() {}
+
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+#[test]
+fn test_optimized() {
+    let a = 1;
+    let b = 2;
+    let _unused = a * 10;
+    assert_eq(add(a, b), 3);
+}
@@ -0,0 +1,23 @@
+script;
+
+fn main() {}
+
+fn check(value: u64) {
+    if value > 1 {
+        revert(42);
+    }
+}
+
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+#[test(should_revert)]
+fn test_revert() {
+    check(2);
+}
+
+#[test(should_revert)]
+fn test_panic() {
+    let _ = add(u64::max(), 1);
+}
@@ -0,0 +1,12 @@
+script;
+
+fn main() {}
+
+#[test]
+fn test_spin() {
+    let mut i = 0;
+    while i < 1_000_000_000 {
+        i += 1;
+    }
+    assert_eq(i, 1_000_000_000);
+}
@@ -0,0 +1,9 @@
+script;
+
+fn main() {}
+
+#[test]
+fn test_a() {
+    let x = 2;
+    assert_eq(x + 2, 4);
+}
@@ -0,0 +1,10 @@
+script;
+
+fn main() {}
+
+#[test]
+fn test_b() {
+    let mut x = 1;
+    x = x * 3;
+    assert_eq(x, 3);
+}
@@ -1,21 +1,33 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use dap::{
     events::{Event, OutputEventBody},
-    requests::{Command, LaunchRequestArguments, SetBreakpointsArguments, VariablesArguments},
-    responses::ResponseBody,
+    requests::{
+        BreakpointLocationsArguments, Command, ContinueArguments, DataBreakpointInfoArguments,
+        DisassembleArguments, EvaluateArguments, GotoArguments, GotoTargetsArguments,
+        InitializeArguments, LaunchRequestArguments, NextArguments, ReadMemoryArguments,
+        SetBreakpointsArguments, SetDataBreakpointsArguments, SetExceptionBreakpointsArguments,
+        StackTraceArguments, VariablesArguments,
+    },
+    responses::{EvaluateResponse, ReadMemoryResponse, ResponseBody},
     types::{
-        OutputEventCategory, Source, SourceBreakpoint, StartDebuggingRequestKind,
+        Breakpoint, DataBreakpoint, EvaluateArgumentsContext, OutputEventCategory, Source,
+        SourceBreakpoint, StackFrame, StartDebuggingRequestKind, SteppingGranularity,
         StoppedEventReason, Variable,
     },
 };
 use forc_debug::server::{
-    AdditionalData, DapServer, INSTRUCTIONS_VARIABLE_REF, REGISTERS_VARIABLE_REF,
+    AdditionalData, DapServer, EXECUTION_VARIABLE_REF, INSTRUCTIONS_VARIABLE_REF,
+    REGISTERS_VARIABLE_REF,
 };
+use fuel_abi_types::error_codes::ErrorSignal;
+use fuel_tx::Receipt;
 use std::{
     collections::BTreeMap,
     env,
-    io::Write,
+    io::{Read, Write},
     path::PathBuf,
-    sync::{Arc, Mutex},
+    sync::{mpsc, Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 pub fn sway_workspace_dir() -> PathBuf {
@@ -30,6 +42,8 @@ pub fn test_fixtures_dir() -> PathBuf {
 /// A simple struct to capture event output from the server for testing purposes.
 struct EventCapture {
     pub output: Arc<Mutex<String>>,
+    /// The payloads of the test result events skipped by [EventCapture::take_event].
+    pub test_results: Arc<Mutex<Vec<serde_json::Value>>>,
 }
 
 impl Write for EventCapture {
@@ -44,19 +58,54 @@ impl Write for EventCapture {
     }
 }
 
+/// Client input for the server, which blocks until a message is sent through the channel.
+struct ChannelInput(mpsc::Receiver<Vec<u8>>);
+
+impl ChannelInput {
+    fn new() -> (mpsc::Sender<Vec<u8>>, Self) {
+        let (sender, receiver) = mpsc::channel();
+        (sender, Self(receiver))
+    }
+}
+
+impl Read for ChannelInput {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.0.recv() {
+            Ok(message) => {
+                buf[..message.len()].copy_from_slice(&message);
+                Ok(message.len())
+            }
+            Err(_) => Ok(0),
+        }
+    }
+}
+
 impl EventCapture {
+    /// Takes the next event, skipping the output events reporting the result of each test, which are collected
+    /// to be checked with [EventCapture::take_test_results].
     pub fn take_event(&self) -> Option<Event> {
         let mut lock = self.output.lock().unwrap();
         while !lock.is_empty() {
             let cloned = lock.clone();
             let (line, rest) = cloned.split_once('\n')?;
             *lock = rest.to_string();
-            if let Ok(event) = serde_json::from_str::<Event>(line) {
-                return Some(event);
+            match serde_json::from_str::<Event>(line) {
+                Ok(Event::Output(OutputEventBody {
+                    data: Some(data), ..
+                })) if data["event"] == "forc-test/result" => {
+                    self.test_results.lock().unwrap().push(data);
+                }
+                Ok(event) => return Some(event),
+                Err(_) => {}
             }
         }
         None
     }
+
+    /// Takes the payloads of the test result events skipped so far, in the order the tests finished.
+    pub fn take_test_results(&self) -> Vec<serde_json::Value> {
+        std::mem::take(&mut self.test_results.lock().unwrap())
+    }
 }
 
 #[test]
@@ -102,6 +151,7 @@ fn test_server_launch_mode() {
     // Launch request
     let additional_data = serde_json::to_value(AdditionalData {
         program: source_str.clone(),
+        ..Default::default()
     })
     .unwrap();
     let (result, exit_code) = server
@@ -157,11 +207,16 @@ fn test_server_launch_mode() {
     assert!(keep_running);
     assert_stopped_breakpoint_event(output_capture.take_event(), 0);
 
-    // Threads request
+    // Threads request, each test should be a thread as all of them have stopped on a breakpoint
     let (result, exit_code) = server.handle_command(&Command::Threads).into_tuple();
     match result.expect("threads result") {
         ResponseBody::Threads(res) => {
-            assert_eq!(res.threads.len(), 1);
+            let names: Vec<_> = res
+                .threads
+                .iter()
+                .map(|thread| thread.name.as_str())
+                .collect();
+            assert_eq!(names, vec!["test_1", "test_2", "test_3"]);
         }
         other => panic!("Expected Threads response, got {:?}", other),
     }
@@ -185,7 +240,8 @@ fn test_server_launch_mode() {
         .into_tuple();
     match result.expect("scopes result") {
         ResponseBody::Scopes(res) => {
-            assert_eq!(res.scopes.len(), 2);
+            assert_eq!(res.scopes.len(), 3);
+            assert_eq!(res.scopes[2].name, "Execution");
         }
         other => panic!("Expected Scopes response, got {:?}", other),
     }
@@ -201,6 +257,13 @@ fn test_server_launch_mode() {
     match result.expect("registers variables result") {
         ResponseBody::Variables(res) => {
             assert_eq!(res.variables.len(), 64);
+            assert_eq!(res.variables[0].name, "$zero");
+            assert_eq!(res.variables[0].value, "0 (0x0)");
+            assert_eq!(res.variables[1].name, "$one");
+            assert_eq!(res.variables[1].value, "1 (0x1)");
+            assert_eq!(res.variables[3].name, "$pc");
+            assert_eq!(res.variables[9].name, "$ggas");
+            assert_eq!(res.variables[16].name, "reg16");
         }
         other => panic!("Expected Variables response, got {:?}", other),
     }
@@ -227,6 +290,31 @@ fn test_server_launch_mode() {
     }
     assert!(exit_code.is_none());
 
+    // Variables request - execution state
+    let (result, exit_code) = server
+        .handle_command(&Command::Variables(VariablesArguments {
+            variables_reference: EXECUTION_VARIABLE_REF,
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("execution variables result") {
+        ResponseBody::Variables(res) => {
+            let names = res
+                .variables
+                .iter()
+                .map(|v| v.name.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(names, vec!["Test", "Context", "Gas used", "Instruction"]);
+            assert_eq!(res.variables[0].value, "test_1");
+            assert!(res.variables[1].value.starts_with("Script"));
+            let gas_used = res.variables[2].value.split_once(' ').unwrap().0;
+            assert!(gas_used.parse::<u64>().unwrap() > 0);
+            assert_eq!(res.variables[3].value, "SW reg59 one 0x1");
+        }
+        other => panic!("Expected Variables response, got {:?}", other),
+    }
+    assert!(exit_code.is_none());
+
     // Next request
     let (result, exit_code) = server
         .handle_command(&Command::Next(Default::default()))
@@ -235,21 +323,50 @@ fn test_server_launch_mode() {
     assert!(exit_code.is_none());
     assert_stopped_next_event(output_capture.take_event());
 
-    // Step In request
+    // Step In request, should stop on the line calling `helper`
     let (result, exit_code) = server
         .handle_command(&Command::StepIn(Default::default()))
         .into_tuple();
-    assert!(result.is_ok());
+    assert!(matches!(result, Ok(ResponseBody::StepIn)));
     assert!(exit_code.is_none());
-    assert_not_supported_event(output_capture.take_event());
+    assert_stopped_next_event(output_capture.take_event());
+    assert_eq!(stopped_line(&mut server), 23);
+
+    // Step In request, should stop inside `helper`
+    let (result, exit_code) = server
+        .handle_command(&Command::StepIn(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::StepIn)));
+    assert!(exit_code.is_none());
+    assert_stopped_next_event(output_capture.take_event());
+    assert!((11..=17).contains(&stopped_line(&mut server)));
 
-    // Step Out request
+    // Stack Trace request, should include the frame of `test_1` calling `helper`
+    let frames = stack_frames(&mut server);
+    assert_eq!(frames.len(), 2);
+    assert_eq!((frames[0].id, frames[0].name.as_str()), (0, "helper"));
+    assert_eq!((frames[1].id, frames[1].name.as_str()), (1, "test_1"));
+    assert_eq!(frames[1].line, 23);
+
+    // Step Out request, should return to `test_1`
     let (result, exit_code) = server
         .handle_command(&Command::StepOut(Default::default()))
         .into_tuple();
-    assert!(result.is_ok());
+    assert!(matches!(result, Ok(ResponseBody::StepOut)));
     assert!(exit_code.is_none());
-    assert_not_supported_event(output_capture.take_event());
+    assert_stopped_next_event(output_capture.take_event());
+    assert!((23..=24).contains(&stopped_line(&mut server)));
+
+    // Next request with instruction granularity, should stop after a single instruction
+    let (result, exit_code) = server
+        .handle_command(&Command::Next(NextArguments {
+            granularity: Some(SteppingGranularity::Instruction),
+            ..Default::default()
+        }))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Next)));
+    assert!(exit_code.is_none());
+    assert_stopped_next_event(output_capture.take_event());
 
     // Continue request, should hit 2nd breakpoint
     let (result, exit_code) = server
@@ -277,92 +394,1639 @@ fn test_server_launch_mode() {
     // Test results should be logged
     let body = assert_output_event_body(output_capture.take_event());
     assert!(body.category.is_none());
-    assert!(body.output.contains("test test_1 ... ok"));
-    assert!(body.output.contains("test test_2 ... ok"));
-    assert!(body.output.contains("test test_3 ... ok"));
+    assert!(body.output.starts_with("test    result  gas"));
+    for name in ["test_1", "test_2", "test_3"] {
+        assert!(body.output.lines().any(|line| line.starts_with(name)));
+    }
     assert!(body.output.contains("Result: OK. 3 passed. 0 failed"));
+
+    // Each test should have been reported as it finished
+    let test_results = output_capture.take_test_results();
+    assert_eq!(test_results.len(), 3);
+    for (test_result, name) in test_results.iter().zip(["test_1", "test_2", "test_3"]) {
+        assert_eq!(test_result["name"], name);
+        assert_eq!(test_result["passed"], true);
+        assert!(test_result["gasUsed"].as_u64().unwrap() > 0);
+        assert!(test_result["instructions"].as_u64().unwrap() > 0);
+        assert!(test_result["durationMs"].is_u64());
+    }
 }
 
 #[test]
-fn test_sourcemap_build() {
-    let mut server = DapServer::new(Box::new(std::io::stdin()), Box::new(std::io::sink()));
+fn test_server_conditional_breakpoints() {
+    let (mut server, output_capture, breakpoints) = launch_with_breakpoints(
+        "simple",
+        vec![
+            SourceBreakpoint {
+                line: 21,
+                condition: Some("$one == 0".into()),
+                ..Default::default()
+            },
+            SourceBreakpoint {
+                line: 30,
+                condition: Some("$one == 1 && ($zero == 0 || $pc == 0)".into()),
+                ..Default::default()
+            },
+            SourceBreakpoint {
+                line: 39,
+                condition: Some("$one ==".into()),
+                ..Default::default()
+            },
+        ],
+    );
+    assert!(breakpoints[0].verified && breakpoints[1].verified);
+    assert!(!breakpoints[2].verified);
+    assert!(breakpoints[2].message.is_some());
 
-    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+    // Launch, should skip the 1st breakpoint because its condition is false
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 1);
 
-    // Initialize and set the program path
-    server.handle_command(&Command::Initialize(Default::default()));
-    server.state.program_path = program_path.clone();
-    server.state.mode = Some(StartDebuggingRequestKind::Launch);
+    // Continue request, should skip the rejected 3rd breakpoint and exit cleanly
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(result.is_ok());
+    assert_eq!(exit_code, Some(0));
+}
 
-    // Explicitly build the tests
-    server.build_tests().expect("Failed to build tests");
+#[test]
+fn test_server_hit_count_breakpoints() {
+    let (mut server, output_capture, breakpoints) = launch_with_breakpoints(
+        "looping",
+        vec![SourceBreakpoint {
+            line: 9,
+            hit_condition: Some("% 3".into()),
+            ..Default::default()
+        }],
+    );
+    assert!(breakpoints[0].verified);
 
-    // Group instructions by line number
-    let mut line_to_instructions: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
-    let source_map = &server.state.source_map;
+    // The loop body runs 10 times, so the breakpoint should stop on the 3rd, 6th and 9th hits
+    let mut keep_running = server.launch().expect("launched without error");
+    let mut stops = 0;
+    while keep_running {
+        assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+        stops += 1;
+        assert_eq!(server.state.breakpoint_hits().get(&0), Some(&(stops * 3)));
 
-    for pc in source_map.map.keys() {
-        if let Some((path, range)) = source_map.addr_to_span(*pc) {
-            if path == program_path {
-                line_to_instructions
-                    .entry(range.start.line as i64)
-                    .or_default()
-                    .push(*pc);
-            }
-        }
+        let (result, exit_code) = server
+            .handle_command(&Command::Continue(Default::default()))
+            .into_tuple();
+        assert!(result.is_ok());
+        keep_running = exit_code.is_none();
     }
+    assert_eq!(stops, 3);
+    assert_eq!(server.state.breakpoint_hits().get(&0), Some(&10));
+}
 
-    // Verify essential source locations are mapped correctly
-    let key_locations = [
-        // Main function and its contents
-        (3, 4, "main function parameters"), // Should have 4 instructions
-        (4, 2, "addition operation"),       // Should have 2 instructions (add operation)
-        // Helper function and its contents
-        (11, 4, "helper function parameters"), // Should have 4 instructions
-        (12, 2, "helper addition operation"),  // Should have 2 instructions
-        // Test functions (identical patterns)
-        (21, 1, "test_1 first line"),  // Each test line should have
-        (22, 1, "test_1 second line"), // exactly one instruction
-        (23, 1, "test_1 helper call"),
-        (24, 1, "test_1 assertion"),
-    ];
+#[test]
+fn test_server_logpoints() {
+    let (mut server, output_capture, breakpoints) = launch_with_breakpoints(
+        "looping",
+        vec![SourceBreakpoint {
+            line: 9,
+            log_message: Some("loop at {$pc}, {gas} gas used".into()),
+            ..Default::default()
+        }],
+    );
+    assert!(breakpoints[0].verified);
 
-    for (line, expected_count, description) in key_locations {
-        let instructions = line_to_instructions
-            .get(&line)
-            .unwrap_or_else(|| panic!("Missing mapping for line {}: {}", line, description));
-        assert_eq!(
-            instructions.len(),
-            expected_count,
-            "Line {} ({}): Expected {} instructions, found {:?}",
-            line,
-            description,
-            expected_count,
-            instructions
-        );
+    // Launch, should log a message for each of the 10 iterations without stopping
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+    for _ in 0..10 {
+        let body = assert_output_event_body(output_capture.take_event());
+        assert!(body.category.is_none());
+        assert!(body.output.starts_with("loop at "));
+        assert!(body.output.ends_with(" gas used\n"));
     }
+
+    // Test results should be logged
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 1 passed. 0 failed"));
 }
 
-/// Asserts that the given event is a Stopped event with a breakpoint reason and the given breakpoint ID.
-fn assert_stopped_breakpoint_event(event: Option<Event>, breakpoint_id: i64) {
-    match event.expect("received event") {
-        Event::Stopped(body) => {
-            assert!(matches!(body.reason, StoppedEventReason::Breakpoint));
-            assert_eq!(body.hit_breakpoint_ids, Some(vec![breakpoint_id]));
+#[test]
+fn test_server_remove_breakpoints() {
+    let (mut server, output_capture, _) = launch_with_breakpoints(
+        "simple",
+        vec![SourceBreakpoint {
+            line: 21,
+            ..Default::default()
+        }],
+    );
+
+    // Launch, should hit the breakpoint in the 1st test
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+
+    // Remove the breakpoint
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source: Source {
+                path: Some(program_path.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            breakpoints: Some(vec![]),
+            ..Default::default()
+        }))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::SetBreakpoints(_))));
+    assert_eq!(stopped_line(&mut server), 21);
+
+    // Continue, should run the remaining tests to completion without stopping again
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Continue(_))));
+    assert_eq!(exit_code, Some(0));
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 3 passed. 0 failed"));
+}
+
+#[test]
+fn test_server_goto() {
+    let (mut server, output_capture, _) = launch_with_breakpoints(
+        "simple",
+        vec![
+            SourceBreakpoint {
+                line: 21,
+                ..Default::default()
+            },
+            SourceBreakpoint {
+                line: 23,
+                ..Default::default()
+            },
+        ],
+    );
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+    let goto_target = |server: &mut DapServer, line: i64| {
+        let (result, _) = server
+            .handle_command(&Command::GotoTargets(GotoTargetsArguments {
+                source: Source {
+                    path: Some(program_path.to_string_lossy().to_string()),
+                    ..Default::default()
+                },
+                line,
+                column: None,
+            }))
+            .into_tuple();
+        match result.expect("goto targets result") {
+            ResponseBody::GotoTargets(res) => {
+                assert_eq!(res.targets.len(), 1);
+                assert_eq!(res.targets[0].line, line);
+                assert_eq!(res.targets[0].label, format!("Line {line}"));
+                res.targets[0].id
+            }
+            other => panic!("Expected GotoTargets response, got {:?}", other),
         }
-        other => panic!("Expected Stopped event, got {:?}", other),
     };
-}
 
-/// Asserts that the given event is a Stopped event with the right reason and no breakpoint ID.
-fn assert_stopped_next_event(event: Option<Event>) {
-    match event.expect("received event") {
+    // Launch, should stop on the 1st breakpoint in the 1st test
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    let thread_id = stopped_thread(output_capture.take_event(), 0);
+
+    // Run to line 24, should stop on the breakpoint on line 23 first
+    let target_id = goto_target(&mut server, 24);
+    let (result, exit_code) = server
+        .handle_command(&Command::Goto(GotoArguments {
+            thread_id,
+            target_id,
+        }))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Goto)));
+    assert!(exit_code.is_none());
+    assert_eq!(stopped_thread(output_capture.take_event(), 1), thread_id);
+    assert_eq!(stopped_line(&mut server), 23);
+
+    // Run to line 24 again, should reach it
+    let target_id = goto_target(&mut server, 24);
+    let (result, _) = server
+        .handle_command(&Command::Goto(GotoArguments {
+            thread_id,
+            target_id,
+        }))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Goto)));
+    match output_capture.take_event().expect("received event") {
         Event::Stopped(body) => {
-            assert!(matches!(body.reason, StoppedEventReason::Step));
+            assert!(matches!(body.reason, StoppedEventReason::Goto));
             assert_eq!(body.hit_breakpoint_ids, None);
+            assert_eq!(body.thread_id, Some(thread_id));
         }
         other => panic!("Expected Stopped event, got {:?}", other),
-    };
+    }
+    assert_eq!(stopped_line(&mut server), 24);
+
+    // An unknown target should be rejected without ending the session
+    let (result, exit_code) = server
+        .handle_command(&Command::Goto(GotoArguments {
+            thread_id,
+            target_id: 42,
+        }))
+        .into_tuple();
+    assert_eq!(
+        result.expect_err("goto should fail").to_string(),
+        "Unknown goto target: 42"
+    );
+    assert!(exit_code.is_none());
+
+    // Continue, the test should finish as the target was removed
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Continue(_))));
+    assert_eq!(exit_code, Some(0));
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 3 passed. 0 failed"));
+}
+
+#[test]
+fn test_server_threads() {
+    let (mut server, output_capture, _) = launch_with_breakpoints(
+        "simple",
+        vec![
+            SourceBreakpoint {
+                line: 21,
+                ..Default::default()
+            },
+            SourceBreakpoint {
+                line: 30,
+                ..Default::default()
+            },
+        ],
+    );
+
+    // Launch, the 1st test should be reported stopped while the 3rd one finishes
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_eq!(stopped_thread(output_capture.take_event(), 0), 1);
+    let results = output_capture.take_test_results();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["name"], "test_3");
+
+    // Threads request, should list the stopped tests
+    let (result, _) = server.handle_command(&Command::Threads).into_tuple();
+    match result.expect("threads result") {
+        ResponseBody::Threads(res) => {
+            let threads: Vec<_> = res
+                .threads
+                .iter()
+                .map(|thread| (thread.id, thread.name.as_str()))
+                .collect();
+            assert_eq!(threads, vec![(1, "test_1"), (2, "test_2")]);
+        }
+        other => panic!("Expected Threads response, got {:?}", other),
+    }
+
+    // Stack Trace request for the 2nd thread, should select it for the requests that follow
+    let (result, _) = server
+        .handle_command(&Command::StackTrace(StackTraceArguments {
+            thread_id: 2,
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("stack trace result") {
+        ResponseBody::StackTrace(res) => assert_eq!(res.stack_frames[0].line, 30),
+        other => panic!("Expected StackTrace response, got {:?}", other),
+    }
+    let (result, _) = server
+        .handle_command(&Command::Variables(VariablesArguments {
+            variables_reference: EXECUTION_VARIABLE_REF,
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("execution variables result") {
+        ResponseBody::Variables(res) => assert_eq!(res.variables[0].value, "test_2"),
+        other => panic!("Expected Variables response, got {:?}", other),
+    }
+
+    // Continue the 1st thread, it should finish and the stop of the 2nd thread should be reported
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(ContinueArguments {
+            thread_id: 1,
+            ..Default::default()
+        }))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Continue(_))));
+    assert!(exit_code.is_none());
+    assert_eq!(stopped_thread(output_capture.take_event(), 1), 2);
+    let results = output_capture.take_test_results();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["name"], "test_1");
+
+    // Continue the 2nd thread, all tests should have finished
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(ContinueArguments {
+            thread_id: 2,
+            ..Default::default()
+        }))
+        .into_tuple();
+    assert!(result.is_ok());
+    assert_eq!(exit_code, Some(0));
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 3 passed. 0 failed"));
+}
+
+#[test]
+fn test_server_breakpoints_before_launch() {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+    let source_str = program_path.to_string_lossy().to_string();
+    server.handle_command(&Command::Initialize(Default::default()));
+
+    // Breakpoints set before the launch request can't be bound yet
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source: Source {
+                path: Some(source_str.clone()),
+                ..Default::default()
+            },
+            breakpoints: Some(vec![
+                SourceBreakpoint {
+                    line: 20,
+                    ..Default::default()
+                },
+                SourceBreakpoint {
+                    line: 1000,
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("set breakpoints result") {
+        ResponseBody::SetBreakpoints(res) => {
+            assert_eq!(res.breakpoints.len(), 2);
+            assert!(res.breakpoints.iter().all(|bp| !bp.verified));
+        }
+        other => panic!("Expected SetBreakpoints response, got {:?}", other),
+    }
+
+    let additional_data = serde_json::to_value(AdditionalData {
+        program: source_str,
+        ..Default::default()
+    })
+    .unwrap();
+    server.handle_command(&Command::Launch(LaunchRequestArguments {
+        additional_data: Some(additional_data),
+        ..Default::default()
+    }));
+    server.handle_command(&Command::ConfigurationDone);
+
+    // They are bound once the tests are built, moved to the next line with code if needed
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    match output_capture.take_event().expect("received event") {
+        Event::Breakpoint(body) => {
+            assert_eq!(body.breakpoint.id, Some(0));
+            assert_eq!(body.breakpoint.line, Some(21));
+            assert!(body.breakpoint.verified);
+        }
+        other => panic!("Expected Breakpoint event, got {:?}", other),
+    }
+    match output_capture.take_event().expect("received event") {
+        Event::Breakpoint(body) => {
+            assert_eq!(body.breakpoint.id, Some(1));
+            assert!(!body.breakpoint.verified);
+            assert!(body.breakpoint.message.is_some());
+        }
+        other => panic!("Expected Breakpoint event, got {:?}", other),
+    }
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+}
+
+#[test]
+fn test_server_column_breakpoints() {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+    let source = Source {
+        path: Some(program_path.to_string_lossy().to_string()),
+        ..Default::default()
+    };
+    server.handle_command(&Command::Initialize(Default::default()));
+    let additional_data = serde_json::to_value(AdditionalData {
+        program: program_path.to_string_lossy().to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+    server.handle_command(&Command::Launch(LaunchRequestArguments {
+        additional_data: Some(additional_data),
+        ..Default::default()
+    }));
+
+    // The breakpoint locations of a line are the columns its expressions start at
+    let (result, _) = server
+        .handle_command(&Command::BreakpointLocations(
+            BreakpointLocationsArguments {
+                source: source.clone(),
+                line: 21,
+                ..Default::default()
+            },
+        ))
+        .into_tuple();
+    let locations = match result.expect("breakpoint locations result") {
+        ResponseBody::BreakpointLocations(res) => res.breakpoints,
+        other => panic!("Expected BreakpointLocations response, got {:?}", other),
+    };
+    assert!(!locations.is_empty());
+    assert!(locations.iter().all(|location| location.line == 21));
+    let column = locations.last().unwrap().column.expect("column");
+
+    // A breakpoint on a column stops on the expression starting there
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source,
+            breakpoints: Some(vec![SourceBreakpoint {
+                line: 21,
+                column: Some(column),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("set breakpoints result") {
+        ResponseBody::SetBreakpoints(res) => {
+            assert!(res.breakpoints[0].verified);
+            assert_eq!(res.breakpoints[0].column, Some(column));
+        }
+        other => panic!("Expected SetBreakpoints response, got {:?}", other),
+    }
+    server.handle_command(&Command::ConfigurationDone);
+
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+    let frame = stack_frames(&mut server).remove(0);
+    assert_eq!((frame.line, frame.column), (21, column));
+    assert!(frame.end_line.is_some() && frame.end_column.is_some());
+}
+
+#[test]
+fn test_server_loaded_sources() {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+    let std_assert_path = sway_workspace_dir()
+        .parent()
+        .unwrap()
+        .join("sway-lib-std/src/assert.sw")
+        .canonicalize()
+        .unwrap();
+    server.handle_command(&Command::Initialize(Default::default()));
+    let additional_data = serde_json::to_value(AdditionalData {
+        program: program_path.to_string_lossy().to_string(),
+        test_filter: Some("test_1".into()),
+        ..Default::default()
+    })
+    .unwrap();
+    server.handle_command(&Command::Launch(LaunchRequestArguments {
+        additional_data: Some(additional_data),
+        ..Default::default()
+    }));
+
+    // The sources should include the files of the standard library
+    let (result, _) = server.handle_command(&Command::LoadedSources).into_tuple();
+    let sources = match result.expect("loaded sources result") {
+        ResponseBody::LoadedSources(res) => res.sources,
+        other => panic!("Expected LoadedSources response, got {:?}", other),
+    };
+    let paths: Vec<_> = sources
+        .iter()
+        .map(|source| PathBuf::from(source.path.clone().expect("path")))
+        .collect();
+    assert!(paths.contains(&program_path.canonicalize().unwrap()));
+    assert!(paths.contains(&std_assert_path));
+
+    // A breakpoint in `assert_eq` of the standard library should be hit when the test calls it
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source: Source {
+                path: Some(std_assert_path.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            breakpoints: Some(vec![SourceBreakpoint {
+                line: 66,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }))
+        .into_tuple();
+    let breakpoint = match result.expect("set breakpoints result") {
+        ResponseBody::SetBreakpoints(res) => res.breakpoints[0].clone(),
+        other => panic!("Expected SetBreakpoints response, got {:?}", other),
+    };
+    assert!(breakpoint.verified);
+    server.handle_command(&Command::ConfigurationDone);
+
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), breakpoint.id.unwrap());
+    let frame = stack_frames(&mut server).remove(0);
+    let frame_path = PathBuf::from(frame.source.and_then(|source| source.path).unwrap());
+    assert_eq!(frame_path.canonicalize().unwrap(), std_assert_path);
+    assert_eq!(Some(frame.line), breakpoint.line);
+}
+
+#[test]
+fn test_server_disassemble() {
+    let (mut server, output_capture, _) = launch_with_breakpoints(
+        "simple",
+        vec![SourceBreakpoint {
+            line: 21,
+            ..Default::default()
+        }],
+    );
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+    let instruction_pointer = stack_frames(&mut server)[0]
+        .instruction_pointer_reference
+        .clone()
+        .expect("instruction pointer reference");
+
+    // Disassemble around the current instruction
+    let disassemble = |server: &mut DapServer, instruction_offset: i64| {
+        let (result, exit_code) = server
+            .handle_command(&Command::Disassemble(DisassembleArguments {
+                memory_reference: instruction_pointer.clone(),
+                instruction_offset: Some(instruction_offset),
+                instruction_count: 5,
+                ..Default::default()
+            }))
+            .into_tuple();
+        assert!(exit_code.is_none());
+        match result.expect("disassemble result") {
+            ResponseBody::Disassemble(res) => res.instructions,
+            other => panic!("Expected Disassemble response, got {:?}", other),
+        }
+    };
+    let instructions = disassemble(&mut server, -2);
+    assert_eq!(instructions.len(), 5);
+    assert_eq!(instructions[2].address, instruction_pointer);
+    assert_eq!(instructions[2].line, Some(21));
+    assert!(instructions[2].location.is_some());
+    assert_eq!(
+        instructions[2].instruction_bytes.as_ref().map(String::len),
+        Some(8)
+    );
+    assert!(instructions
+        .iter()
+        .all(|instruction| instruction.instruction != "<invalid>"));
+
+    // Instructions past the end of the script are invalid
+    let instructions = disassemble(&mut server, 1_000_000);
+    assert_eq!(instructions.len(), 5);
+    assert!(instructions
+        .iter()
+        .all(|instruction| instruction.instruction == "<invalid>"));
+}
+
+#[test]
+fn test_server_read_memory() {
+    let (mut server, output_capture, _) = launch_with_breakpoints(
+        "memory",
+        vec![SourceBreakpoint {
+            line: 9,
+            ..Default::default()
+        }],
+    );
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+
+    // The stack pointer registers are exposed as memory references
+    let (result, _) = server
+        .handle_command(&Command::Variables(VariablesArguments {
+            variables_reference: REGISTERS_VARIABLE_REF,
+            ..Default::default()
+        }))
+        .into_tuple();
+    let registers = match result.expect("registers variables result") {
+        ResponseBody::Variables(res) => res.variables,
+        other => panic!("Expected Variables response, got {:?}", other),
+    };
+    let memory_reference = |name: &str| {
+        let register = registers
+            .iter()
+            .find(|register| register.name == name)
+            .expect("register");
+        register.memory_reference.clone().expect("memory reference")
+    };
+    let parse_address = |memory_reference: &str| {
+        i64::from_str_radix(memory_reference.trim_start_matches("0x"), 16).expect("address")
+    };
+    let ssp = memory_reference("$ssp");
+    let sp = parse_address(&memory_reference("$sp"));
+    assert!(registers[0].memory_reference.is_none());
+
+    // The local variables of the test are on the stack
+    let response = read_memory(&mut server, &ssp, None, sp - parse_address(&ssp));
+    assert_eq!(response.address, ssp);
+    assert_eq!(response.unreadable_bytes, Some(0));
+    let data = BASE64
+        .decode(response.data.expect("data"))
+        .expect("base64 data");
+    assert_eq!(data.len() as i64, sp - parse_address(&ssp));
+    let value = 0x0123456789ABCDEF_u64.to_be_bytes();
+    assert!(data
+        .windows(16)
+        .any(|bytes| bytes[..8] == value && bytes[8..] == value));
+
+    // Reads are clamped to the end of the stack
+    let response = read_memory(&mut server, &format!("0x{:x}", sp), Some(-4), 16);
+    assert_eq!(response.unreadable_bytes, Some(12));
+    let data = BASE64
+        .decode(response.data.expect("data"))
+        .expect("base64 data");
+    assert_eq!(data.len(), 4);
+
+    // Invalid memory references are rejected
+    let (result, exit_code) = server
+        .handle_command(&Command::ReadMemory(ReadMemoryArguments {
+            memory_reference: "not an address".into(),
+            count: 8,
+            ..Default::default()
+        }))
+        .into_tuple();
+    assert!(result.is_err());
+    assert!(exit_code.is_none());
+}
+
+#[test]
+fn test_server_evaluate() {
+    let (mut server, output_capture, _) = launch_with_breakpoints(
+        "memory",
+        vec![SourceBreakpoint {
+            line: 9,
+            ..Default::default()
+        }],
+    );
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+
+    let response = evaluate(&mut server, "test_name");
+    assert_eq!(response.result, "\"test_memory\"");
+    assert!(response.memory_reference.is_none());
+
+    let response = evaluate(&mut server, "[$ssp; $sp - $ssp]");
+    assert!(response.result.contains("0123456789abcdef0123456789abcdef"));
+    assert_eq!(
+        response.memory_reference,
+        evaluate(&mut server, "$ssp")
+            .result
+            .split_once("(0x")
+            .map(|(_, hex)| format!("0x{}", hex.trim_end_matches(')').to_lowercase()))
+    );
+
+    // Malformed expressions are reported in the result
+    let response = evaluate(&mut server, "$sp +");
+    assert!(response.result.contains("unexpected end of the expression"));
+}
+
+#[test]
+fn test_server_progress() {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+    server.handle_command(&Command::Initialize(InitializeArguments {
+        adapter_id: "sway".into(),
+        supports_progress_reporting: Some(true),
+        ..Default::default()
+    }));
+    let additional_data = serde_json::to_value(AdditionalData {
+        program: program_path.to_string_lossy().to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+    server.handle_command(&Command::Launch(LaunchRequestArguments {
+        additional_data: Some(additional_data),
+        ..Default::default()
+    }));
+    server.handle_command(&Command::ConfigurationDone);
+
+    // Launch, the progress should be reported as each test starts
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+    match output_capture.take_event().expect("received event") {
+        Event::ProgressStart(body) => assert_eq!(body.percentage, Some(0)),
+        other => panic!("Expected ProgressStart event, got {:?}", other),
+    }
+    for (message, percentage) in [
+        ("test_1 (1/3)", 0),
+        ("test_2 (2/3)", 33),
+        ("test_3 (3/3)", 66),
+    ] {
+        match output_capture.take_event().expect("received event") {
+            Event::ProgressUpdate(body) => {
+                assert_eq!(body.message.as_deref(), Some(message));
+                assert_eq!(body.percentage, Some(percentage));
+            }
+            other => panic!("Expected ProgressUpdate event, got {:?}", other),
+        }
+    }
+    match output_capture.take_event().expect("received event") {
+        Event::ProgressEnd(body) => {
+            assert_eq!(body.message.as_deref(), Some("3 passed. 0 failed."))
+        }
+        other => panic!("Expected ProgressEnd event, got {:?}", other),
+    }
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 3 passed. 0 failed"));
+
+    // Each test should be reported as it finishes
+    let names: Vec<_> = output_capture
+        .take_test_results()
+        .iter()
+        .map(|result| result["name"].clone())
+        .collect();
+    assert_eq!(names, vec!["test_1", "test_2", "test_3"]);
+}
+
+#[test]
+fn test_server_restart() {
+    let (mut server, output_capture, _) = launch_with_breakpoints(
+        "counter",
+        vec![SourceBreakpoint {
+            line: 30,
+            ..Default::default()
+        }],
+    );
+
+    // Launch, should stop after the test has incremented the counter in storage
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+
+    // Restart, should drop the running test
+    let (result, exit_code) = server
+        .handle_command(&Command::Restart(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Restart)));
+    assert!(exit_code.is_none());
+    assert!(server.state.threads.is_empty());
+
+    // Launch again, should stop on the breakpoint again
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+
+    // Continue, the test should pass as the counter was only incremented once in the fresh storage
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Continue(_))));
+    assert_eq!(exit_code, Some(0));
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 1 passed. 0 failed"));
+}
+
+#[test]
+fn test_server_restart_keeps_breakpoint_ids() {
+    let (mut server, output_capture, _) = launch_with_breakpoints(
+        "simple",
+        vec![SourceBreakpoint {
+            line: 21,
+            ..Default::default()
+        }],
+    );
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+
+    // Restart, and set the breakpoints again as clients do, adding one
+    server.handle_command(&Command::Restart(Default::default()));
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source: Source {
+                path: Some(program_path.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            breakpoints: Some(vec![
+                SourceBreakpoint {
+                    line: 30,
+                    ..Default::default()
+                },
+                SourceBreakpoint {
+                    line: 21,
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("set breakpoints result") {
+        ResponseBody::SetBreakpoints(res) => {
+            let ids: Vec<_> = res.breakpoints.iter().map(|bp| bp.id).collect();
+            assert_eq!(ids, vec![Some(1), Some(0)]);
+        }
+        other => panic!("Expected SetBreakpoints response, got {:?}", other),
+    }
+
+    // Launch again, should stop on the unchanged breakpoint with the same ID
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+    let (result, _) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(result.is_ok());
+    assert_stopped_breakpoint_event(output_capture.take_event(), 1);
+}
+
+#[test]
+fn test_server_pause() {
+    let output_capture = EventCapture::default();
+    let (input_sender, input) = ChannelInput::new();
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(Box::new(input), output);
+
+    let program_path = test_fixtures_dir().join("spinning/src/main.sw");
+    server.handle_command(&Command::Initialize(Default::default()));
+    let additional_data = serde_json::to_value(AdditionalData {
+        program: program_path.to_string_lossy().to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+    server.handle_command(&Command::Launch(LaunchRequestArguments {
+        additional_data: Some(additional_data),
+        ..Default::default()
+    }));
+    server.build_tests().expect("built tests");
+    server.handle_command(&Command::ConfigurationDone);
+
+    // Pause while the test is spinning
+    let pause = r#"{"seq":1,"type":"request","command":"pause","arguments":{"threadId":0}}"#;
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(200));
+        let message = format!("Content-Length: {}\r\n\r\n{pause}", pause.len());
+        input_sender.send(message.into_bytes()).unwrap();
+    });
+
+    // Launch, should stop when the pause request is received
+    let start = Instant::now();
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert!(start.elapsed() < Duration::from_secs(30));
+    match output_capture.take_event().expect("received event") {
+        Event::Stopped(body) => {
+            assert!(matches!(body.reason, StoppedEventReason::Pause));
+            assert_eq!(body.hit_breakpoint_ids, None);
+        }
+        other => panic!("Expected Stopped event, got {:?}", other),
+    };
+    assert!(!stack_frames(&mut server).is_empty());
+
+    // Pausing while stopped does nothing
+    let (result, exit_code) = server
+        .handle_command(&Command::Pause(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Pause)));
+    assert!(exit_code.is_none());
+    assert!(output_capture.take_event().is_none());
+}
+
+#[test]
+fn test_server_optimized_build() {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+
+    let program_path = test_fixtures_dir().join("optimized/src/main.sw");
+    server.handle_command(&Command::Initialize(Default::default()));
+    let additional_data = serde_json::to_value(AdditionalData {
+        program: program_path.to_string_lossy().to_string(),
+        build_profile: Some("release".into()),
+        ..Default::default()
+    })
+    .unwrap();
+    let (result, _) = server
+        .handle_command(&Command::Launch(LaunchRequestArguments {
+            additional_data: Some(additional_data),
+            ..Default::default()
+        }))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Launch)));
+
+    // Breakpoints on lines whose code was optimized away are rejected with a reason
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source: Source {
+                path: Some(program_path.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            breakpoints: Some(
+                (11..=14)
+                    .map(|line| SourceBreakpoint {
+                        line,
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("set breakpoints result") {
+        ResponseBody::SetBreakpoints(res) => {
+            assert_eq!(res.breakpoints.len(), 4);
+            assert!(res
+                .breakpoints
+                .iter()
+                .all(|bp| bp.verified || bp.message.is_some()));
+        }
+        other => panic!("Expected SetBreakpoints response, got {:?}", other),
+    }
+    assert!(server.state.optimized);
+    server.handle_command(&Command::ConfigurationDone);
+
+    // Step through the test until it finishes, whether or not its instructions are mapped to lines
+    let mut keep_running = server.launch().expect("launched without error");
+    let mut steps = 0;
+    while keep_running {
+        let frames = stack_frames(&mut server);
+        assert!(!frames.is_empty());
+        let (result, exit_code) = server
+            .handle_command(&Command::Next(NextArguments::default()))
+            .into_tuple();
+        assert!(result.is_ok());
+        keep_running = exit_code.is_none();
+        steps += 1;
+        assert!(steps < 10_000, "the test should finish");
+    }
+    while output_capture.take_event().is_some() {}
+    let results = output_capture.take_test_results();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["passed"], true);
+}
+
+#[test]
+fn test_server_exception_breakpoints() {
+    let (mut server, output_capture, _) = launch_with_breakpoints("reverting", vec![]);
+    let (result, _) = server
+        .handle_command(&Command::SetExceptionBreakpoints(
+            SetExceptionBreakpointsArguments {
+                filters: vec!["revert".into(), "panic".into(), "unknown".into()],
+                ..Default::default()
+            },
+        ))
+        .into_tuple();
+    match result.expect("set exception breakpoints result") {
+        ResponseBody::SetExceptionBreakpoints(res) => {
+            let verified: Vec<_> = res
+                .breakpoints
+                .expect("breakpoints")
+                .iter()
+                .map(|bp| bp.verified)
+                .collect();
+            assert_eq!(verified, vec![true, true, false]);
+        }
+        other => panic!("Expected SetExceptionBreakpoints response, got {:?}", other),
+    }
+
+    // Launch, should stop before the 1st test reverts
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    let text = assert_stopped_exception_event(output_capture.take_event());
+    assert_eq!(text, "Test reverted with code 42 (0x2A)");
+
+    // Continue, should stop after the 2nd test panics
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Continue(_))));
+    assert!(exit_code.is_none());
+    let text = assert_stopped_exception_event(output_capture.take_event());
+    assert!(text.contains("ArithmeticOverflow"));
+
+    // Continue, should finish both tests
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Continue(_))));
+    assert_eq!(exit_code, Some(0));
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 2 passed. 0 failed"));
+}
+
+#[test]
+fn test_server_data_breakpoints() {
+    let (mut server, output_capture, _) = launch_with_breakpoints("counter", vec![]);
+
+    // The storage field's slot is found by its name
+    let (result, _) = server
+        .handle_command(&Command::DataBreakpointInfo(DataBreakpointInfoArguments {
+            name: "storage.count".into(),
+            ..Default::default()
+        }))
+        .into_tuple();
+    let data_id = match result.expect("data breakpoint info result") {
+        ResponseBody::DataBreakpointInfo(res) => res.data_id.expect("data ID"),
+        other => panic!("Expected DataBreakpointInfo response, got {:?}", other),
+    };
+    let (result, _) = server
+        .handle_command(&Command::DataBreakpointInfo(DataBreakpointInfoArguments {
+            name: "total".into(),
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("data breakpoint info result") {
+        ResponseBody::DataBreakpointInfo(res) => assert_eq!(res.data_id, None),
+        other => panic!("Expected DataBreakpointInfo response, got {:?}", other),
+    }
+
+    let (result, _) = server
+        .handle_command(&Command::SetDataBreakpoints(SetDataBreakpointsArguments {
+            breakpoints: vec![DataBreakpoint {
+                data_id,
+                ..Default::default()
+            }],
+        }))
+        .into_tuple();
+    match result.expect("set data breakpoints result") {
+        ResponseBody::SetDataBreakpoints(res) => {
+            assert!(res.breakpoints[0].verified);
+            assert_eq!(res.breakpoints[0].id, Some(0));
+        }
+        other => panic!("Expected SetDataBreakpoints response, got {:?}", other),
+    }
+
+    // Launch, should stop once `increment` has written the incremented count
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    match output_capture.take_event().expect("received event") {
+        Event::Stopped(body) => {
+            assert!(matches!(body.reason, StoppedEventReason::Data));
+            assert_eq!(body.hit_breakpoint_ids, Some(vec![0]));
+            let text = body.text.expect("write description");
+            let old_value = format!("0x{}", "0".repeat(64));
+            let new_value = format!("0x{:016x}{}", 1, "0".repeat(48));
+            assert!(text.ends_with(&format!("written: {old_value} -> {new_value}")));
+        }
+        other => panic!("Expected Stopped event, got {:?}", other),
+    }
+    assert_eq!(stopped_line(&mut server), 17);
+
+    // Continue, the test should pass
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(matches!(result, Ok(ResponseBody::Continue(_))));
+    assert_eq!(exit_code, Some(0));
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 1 passed. 0 failed"));
+}
+
+#[test]
+fn test_server_failed_test() {
+    let (mut server, output_capture, _) = launch_with_breakpoints("failing", vec![]);
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+
+    // The failure is reported after the test's result
+    let results = output_capture.take_test_results();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["passed"], false);
+    let reason = ErrorSignal::try_from_revert_code(0xffff_ffff_ffff_0004)
+        .expect("assert signal")
+        .to_string();
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body
+        .output
+        .starts_with(&format!("test test_assert failed: {reason}\n    at ")));
+    let mut failure = body.data.expect("failure payload");
+
+    // The receipts end with the revert and the script result
+    let receipts = failure["receipts"].take();
+    let receipts: Vec<Receipt> = serde_json::from_value(receipts).expect("receipts");
+    assert!(matches!(
+        receipts.as_slice(),
+        [
+            ..,
+            Receipt::Revert {
+                ra: 0xffff_ffff_ffff_0004,
+                ..
+            },
+            Receipt::ScriptResult { .. }
+        ]
+    ));
+    let location = failure["location"].take();
+    assert!(location["line"].as_i64().is_some_and(|line| line > 0));
+    assert!(failure["gasUsed"]
+        .take()
+        .as_u64()
+        .is_some_and(|gas| gas > 0));
+    assert_eq!(
+        failure,
+        serde_json::json!({
+            "event": "forc-test/failed",
+            "name": "test_assert",
+            "revertCode": "0xffffffffffff0004",
+            "reason": reason,
+            "gasUsed": null,
+            "location": null,
+            "receipts": null,
+        })
+    );
+
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: FAILED. 0 passed. 1 failed"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_server_symlinked_project() {
+    // Debug the `simple` fixture through a symlink to its directory.
+    let symlink_dir = test_fixtures_dir().join("simple_symlink");
+    let _ = std::fs::remove_file(&symlink_dir);
+    std::os::unix::fs::symlink(test_fixtures_dir().join("simple"), &symlink_dir)
+        .expect("created symlink");
+
+    let (mut server, output_capture, breakpoints) = launch_with_breakpoints(
+        "simple_symlink",
+        vec![SourceBreakpoint {
+            line: 21,
+            ..Default::default()
+        }],
+    );
+    assert!(breakpoints[0].verified);
+
+    // Launch, should hit the breakpoint in the symlinked source file
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+    assert_eq!(stopped_line(&mut server), 21);
+
+    std::fs::remove_file(&symlink_dir).expect("removed symlink");
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+#[test]
+fn test_server_mixed_case_path() {
+    // Refer to the `simple` fixture with a path in a different case than the one on disk.
+    let fixture = test_fixtures_dir().join("simple");
+    let mixed_case_dir = PathBuf::from(fixture.to_string_lossy().to_uppercase());
+    assert!(mixed_case_dir.exists());
+    let source_str = mixed_case_dir
+        .join("src/main.sw")
+        .to_string_lossy()
+        .to_string();
+
+    let (mut server, output_capture, _) = launch_with_breakpoints("simple", vec![]);
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source: Source {
+                path: Some(source_str),
+                ..Default::default()
+            },
+            breakpoints: Some(vec![SourceBreakpoint {
+                line: 21,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("set breakpoints result") {
+        ResponseBody::SetBreakpoints(res) => assert!(res.breakpoints[0].verified),
+        other => panic!("Expected SetBreakpoints response, got {:?}", other),
+    }
+
+    // Launch, should hit the breakpoint set through the mixed-case path
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+}
+
+#[test]
+fn test_server_test_filter() {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+
+    server.handle_command(&Command::Initialize(Default::default()));
+    let launch = |server: &mut DapServer, test_filter: &str| {
+        let additional_data = serde_json::to_value(AdditionalData {
+            program: program_path.to_string_lossy().to_string(),
+            test_filter: Some(test_filter.to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        server
+            .handle_command(&Command::Launch(LaunchRequestArguments {
+                additional_data: Some(additional_data),
+                ..Default::default()
+            }))
+            .into_tuple()
+    };
+
+    // A filter matching no tests should be rejected, listing the available tests
+    let (result, exit_code) = launch(&mut server, "test_4");
+    let err = result.expect_err("launch should fail").to_string();
+    assert_eq!(
+        err,
+        "No tests match the filter `test_4`, available tests: test_1, test_2, test_3"
+    );
+    assert_eq!(exit_code, Some(1));
+
+    // A glob filter should only debug the matching tests
+    let (result, exit_code) = launch(&mut server, "test_[13]");
+    assert!(matches!(result, Ok(ResponseBody::Launch)));
+    assert!(exit_code.is_none());
+    server.handle_command(&Command::ConfigurationDone);
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.lines().any(|line| line.starts_with("test_1 ")));
+    assert!(!body.output.contains("test_2"));
+    assert!(body.output.contains("Result: OK. 2 passed. 0 failed"));
+
+    // Restarting should debug the same tests
+    server.handle_command(&Command::Restart(Default::default()));
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 2 passed. 0 failed"));
+
+    // A plain filter should debug the tests whose names contain it
+    let (result, _) = launch(&mut server, "_2");
+    assert!(matches!(result, Ok(ResponseBody::Launch)));
+    server.handle_command(&Command::Restart(Default::default()));
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.lines().any(|line| line.starts_with("test_2 ")));
+    assert!(body.output.contains("Result: OK. 1 passed. 0 failed"));
+}
+
+#[test]
+fn test_server_execution_context() {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+    let program_path = test_fixtures_dir().join("context/src/main.sw");
+
+    server.handle_command(&Command::Initialize(Default::default()));
+    let launch = |server: &mut DapServer, execution_context: serde_json::Value| {
+        let additional_data = serde_json::to_value(AdditionalData {
+            program: program_path.to_string_lossy().to_string(),
+            execution_context: Some(execution_context),
+            ..Default::default()
+        })
+        .unwrap();
+        server
+            .handle_command(&Command::Launch(LaunchRequestArguments {
+                additional_data: Some(additional_data),
+                ..Default::default()
+            }))
+            .into_tuple()
+    };
+
+    // Unknown fields should be rejected, listing the accepted ones
+    let (result, exit_code) = launch(&mut server, serde_json::json!({ "height": 1000 }));
+    let err = result.expect_err("launch should fail").to_string();
+    assert!(err.starts_with("Invalid execution context: unknown field `height`"));
+    assert!(err.ends_with("The accepted fields are `blockHeight`, `coinAmount` and `seed`"));
+    assert_eq!(exit_code, Some(1));
+    let (result, _) = launch(&mut server, serde_json::json!({ "blockHeight": 0 }));
+    let err = result.expect_err("launch should fail").to_string();
+    assert!(err.contains("`blockHeight` must be at least 1"));
+
+    // The test should run at the given block height
+    let (result, exit_code) = launch(
+        &mut server,
+        serde_json::json!({ "blockHeight": 1000, "coinAmount": 5, "seed": 7 }),
+    );
+    assert!(matches!(result, Ok(ResponseBody::Launch)));
+    assert!(exit_code.is_none());
+    server.handle_command(&Command::ConfigurationDone);
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 1 passed. 0 failed"));
+
+    // Restarting should run the test in the same context
+    server.handle_command(&Command::Restart(Default::default()));
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("Result: OK. 1 passed. 0 failed"));
+}
+
+#[test]
+fn test_server_workspace() {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+    let workspace_dir = test_fixtures_dir().join("workspace");
+    let member_b_path = workspace_dir.join("member_b/src/main.sw");
+
+    server.handle_command(&Command::Initialize(Default::default()));
+    let launch = |server: &mut DapServer, test_filter: Option<&str>| {
+        let additional_data = serde_json::to_value(AdditionalData {
+            program: workspace_dir.to_string_lossy().to_string(),
+            test_filter: test_filter.map(str::to_string),
+            ..Default::default()
+        })
+        .unwrap();
+        server
+            .handle_command(&Command::Launch(LaunchRequestArguments {
+                additional_data: Some(additional_data),
+                ..Default::default()
+            }))
+            .into_tuple()
+    };
+
+    // The tests of all members should be available, prefixed with the member name
+    let (result, _) = launch(&mut server, Some("test_c"));
+    let err = result.expect_err("launch should fail").to_string();
+    assert_eq!(
+        err,
+        "No tests match the filter `test_c`, available tests: member_a::test_a, member_b::test_b"
+    );
+
+    // Breakpoints in any member should be bound
+    let (result, _) = launch(&mut server, None);
+    assert!(matches!(result, Ok(ResponseBody::Launch)));
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source: Source {
+                path: Some(member_b_path.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            breakpoints: Some(vec![SourceBreakpoint {
+                line: 9,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }))
+        .into_tuple();
+    match result.expect("set breakpoints result") {
+        ResponseBody::SetBreakpoints(res) => assert!(res.breakpoints[0].verified),
+        other => panic!("Expected SetBreakpoints response, got {:?}", other),
+    }
+    server.handle_command(&Command::ConfigurationDone);
+
+    // Launch, the test of member_a should pass and the test of member_b should stop on the breakpoint
+    let keep_running = server.launch().expect("launched without error");
+    assert!(keep_running);
+    assert_stopped_breakpoint_event(output_capture.take_event(), 0);
+    let frame = stack_frames(&mut server).remove(0);
+    assert_eq!(frame.line, 9);
+    let source_path = frame.source.and_then(|source| source.path).expect("path");
+    assert!(PathBuf::from(source_path).ends_with("member_b/src/main.sw"));
+
+    // Continue, both tests should pass
+    let (result, exit_code) = server
+        .handle_command(&Command::Continue(Default::default()))
+        .into_tuple();
+    assert!(result.is_ok());
+    assert_eq!(exit_code, Some(0));
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(body.output.contains("member_a::test_a"));
+    assert!(body.output.contains("member_b::test_b"));
+    assert!(body.output.contains("Result: OK. 2 passed. 0 failed"));
+
+    // A filter with the member name should only debug the matching member's tests
+    let (result, _) = launch(&mut server, Some("member_b::*"));
+    assert!(matches!(result, Ok(ResponseBody::Launch)));
+    server.handle_command(&Command::Restart(Default::default()));
+    server.state.breakpoints.clear();
+    let keep_running = server.launch().expect("launched without error");
+    assert!(!keep_running);
+    let body = assert_output_event_body(output_capture.take_event());
+    assert!(!body.output.contains("member_a::test_a"));
+    assert!(body.output.contains("Result: OK. 1 passed. 0 failed"));
+}
+
+#[test]
+fn test_sourcemap_build() {
+    let mut server = DapServer::new(Box::new(std::io::stdin()), Box::new(std::io::sink()));
+
+    let program_path = test_fixtures_dir().join("simple/src/main.sw");
+
+    // Initialize and set the program path
+    server.handle_command(&Command::Initialize(Default::default()));
+    server.state.program_path = program_path.clone();
+    server.state.mode = Some(StartDebuggingRequestKind::Launch);
+
+    // Explicitly build the tests
+    server.build_tests().expect("Failed to build tests");
+
+    // Group instructions by line number
+    let mut line_to_instructions: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
+    let source_map = server.state.source_map(0).expect("source map");
+
+    for pc in source_map.map.keys() {
+        if let Some((path, range)) = source_map.addr_to_span(*pc) {
+            if path == program_path {
+                line_to_instructions
+                    .entry(range.start.line as i64)
+                    .or_default()
+                    .push(*pc);
+            }
+        }
+    }
+
+    // Verify essential source locations are mapped correctly
+    let key_locations = [
+        // Main function and its contents
+        (3, 4, "main function parameters"), // Should have 4 instructions
+        (4, 2, "addition operation"),       // Should have 2 instructions (add operation)
+        // Helper function and its contents
+        (11, 4, "helper function parameters"), // Should have 4 instructions
+        (12, 2, "helper addition operation"),  // Should have 2 instructions
+        // Test functions (identical patterns)
+        (21, 1, "test_1 first line"),  // Each test line should have
+        (22, 1, "test_1 second line"), // exactly one instruction
+        (23, 1, "test_1 helper call"),
+        (24, 1, "test_1 assertion"),
+    ];
+
+    for (line, expected_count, description) in key_locations {
+        let instructions = line_to_instructions
+            .get(&line)
+            .unwrap_or_else(|| panic!("Missing mapping for line {}: {}", line, description));
+        assert_eq!(
+            instructions.len(),
+            expected_count,
+            "Line {} ({}): Expected {} instructions, found {:?}",
+            line,
+            description,
+            expected_count,
+            instructions
+        );
+    }
+}
+
+/// Asserts that the given event is a Stopped event with a breakpoint reason and the given breakpoint ID.
+fn assert_stopped_breakpoint_event(event: Option<Event>, breakpoint_id: i64) {
+    match event.expect("received event") {
+        Event::Stopped(body) => {
+            assert!(matches!(body.reason, StoppedEventReason::Breakpoint));
+            assert_eq!(body.hit_breakpoint_ids, Some(vec![breakpoint_id]));
+        }
+        other => panic!("Expected Stopped event, got {:?}", other),
+    };
+}
+
+/// Asserts that the given event is a Stopped event on the breakpoint with the given ID, and returns the ID of the
+/// thread that stopped.
+fn stopped_thread(event: Option<Event>, breakpoint_id: i64) -> i64 {
+    match event.expect("received event") {
+        Event::Stopped(body) => {
+            assert!(matches!(body.reason, StoppedEventReason::Breakpoint));
+            assert_eq!(body.hit_breakpoint_ids, Some(vec![breakpoint_id]));
+            assert_eq!(body.all_threads_stopped, Some(false));
+            body.thread_id.expect("thread ID")
+        }
+        other => panic!("Expected Stopped event, got {:?}", other),
+    }
+}
+
+/// Asserts that the given event is a Stopped event on an exception, and returns the exception's description.
+fn assert_stopped_exception_event(event: Option<Event>) -> String {
+    match event.expect("received event") {
+        Event::Stopped(body) => {
+            assert!(matches!(body.reason, StoppedEventReason::Exception));
+            assert_eq!(body.hit_breakpoint_ids, None);
+            body.text.expect("exception text")
+        }
+        other => panic!("Expected Stopped event, got {:?}", other),
+    }
+}
+
+/// Launches a server debugging the given fixture with the given breakpoints set on its source file.
+///
+/// Returns the server, ready for tests to be started with [DapServer::launch], the captured events, and the
+/// breakpoints returned by the SetBreakpoints request.
+fn launch_with_breakpoints(
+    fixture: &str,
+    breakpoints: Vec<SourceBreakpoint>,
+) -> (DapServer, EventCapture, Vec<Breakpoint>) {
+    let output_capture = EventCapture::default();
+    let input = Box::new(std::io::stdin());
+    let output = Box::new(output_capture.clone());
+    let mut server = DapServer::new(input, output);
+
+    let program_path = test_fixtures_dir().join(fixture).join("src/main.sw");
+    let source_str = program_path.to_string_lossy().to_string();
+
+    server.handle_command(&Command::Initialize(Default::default()));
+    let additional_data = serde_json::to_value(AdditionalData {
+        program: source_str.clone(),
+        ..Default::default()
+    })
+    .unwrap();
+    server.handle_command(&Command::Launch(LaunchRequestArguments {
+        additional_data: Some(additional_data),
+        ..Default::default()
+    }));
+    let (result, _) = server
+        .handle_command(&Command::SetBreakpoints(SetBreakpointsArguments {
+            source: Source {
+                path: Some(source_str),
+                ..Default::default()
+            },
+            breakpoints: Some(breakpoints),
+            ..Default::default()
+        }))
+        .into_tuple();
+    let breakpoints = match result.expect("set breakpoints result") {
+        ResponseBody::SetBreakpoints(res) => res.breakpoints,
+        other => panic!("Expected SetBreakpoints response, got {:?}", other),
+    };
+    server.handle_command(&Command::ConfigurationDone);
+    (server, output_capture, breakpoints)
+}
+
+/// Asserts that the given event is a Stopped event with the right reason and no breakpoint ID.
+fn assert_stopped_next_event(event: Option<Event>) {
+    match event.expect("received event") {
+        Event::Stopped(body) => {
+            assert!(matches!(body.reason, StoppedEventReason::Step));
+            assert_eq!(body.hit_breakpoint_ids, None);
+        }
+        other => panic!("Expected Stopped event, got {:?}", other),
+    };
+}
+
+/// Returns the stack frames reported by a StackTrace request, innermost first.
+fn stack_frames(server: &mut DapServer) -> Vec<StackFrame> {
+    let (result, _) = server
+        .handle_command(&Command::StackTrace(Default::default()))
+        .into_tuple();
+    match result {
+        Ok(ResponseBody::StackTrace(res)) => res.stack_frames,
+        other => panic!("Expected StackTrace response, got {:?}", other),
+    }
+}
+
+/// Evaluates an expression in the debug console.
+fn evaluate(server: &mut DapServer, expression: &str) -> EvaluateResponse {
+    let (result, exit_code) = server
+        .handle_command(&Command::Evaluate(EvaluateArguments {
+            expression: expression.into(),
+            context: Some(EvaluateArgumentsContext::Repl),
+            ..Default::default()
+        }))
+        .into_tuple();
+    assert!(exit_code.is_none());
+    match result {
+        Ok(ResponseBody::Evaluate(res)) => res,
+        other => panic!("Expected Evaluate response, got {:?}", other),
+    }
+}
+
+/// Reads `count` bytes of VM memory at the given memory reference and byte offset.
+fn read_memory(
+    server: &mut DapServer,
+    memory_reference: &str,
+    offset: Option<i64>,
+    count: i64,
+) -> ReadMemoryResponse {
+    let (result, exit_code) = server
+        .handle_command(&Command::ReadMemory(ReadMemoryArguments {
+            memory_reference: memory_reference.into(),
+            offset,
+            count,
+        }))
+        .into_tuple();
+    assert!(exit_code.is_none());
+    match result {
+        Ok(ResponseBody::ReadMemory(res)) => res,
+        other => panic!("Expected ReadMemory response, got {:?}", other),
+    }
+}
+
+/// Returns the source line the server is stopped on, as reported by a StackTrace request.
+fn stopped_line(server: &mut DapServer) -> i64 {
+    stack_frames(server).first().expect("stack frame").line
 }
 
 fn assert_output_event_body(event: Option<Event>) -> OutputEventBody {
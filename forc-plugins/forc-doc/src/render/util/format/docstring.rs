@@ -27,13 +27,12 @@ impl DocStrings for Attributes {
     }
     fn to_raw_string(&self) -> String {
         let mut docs = String::new();
-        // TODO: Change this logic once https://github.com/FuelLabs/sway/issues/6938 gets implemented.
-        for arg in self
+        for line in self
             .of_kind(AttributeKind::DocComment)
-            .flat_map(|attribute| &attribute.args)
+            .filter_map(|attribute| attribute.doc_comment_text())
         {
-            writeln!(docs, "{}", arg.name.as_str())
-                .expect("problem appending `arg.name.as_str()` to `docs` with `writeln` macro.");
+            writeln!(docs, "{line}")
+                .expect("problem appending doc comment line to `docs` with `writeln` macro.");
         }
         docs
     }
@@ -370,7 +370,8 @@ where
 
 impl WithName for Attribute {
     fn with_name<N: AsRef<str> + ?Sized>(&self, name: &N) -> bool {
-        self.name.as_str() == name.as_ref()
+        // Namespaced attributes, e.g., `#[fuels::test]`, are matched by their full path.
+        self.path_str() == name.as_ref()
     }
 }
 
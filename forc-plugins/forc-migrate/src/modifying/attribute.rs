@@ -1,5 +1,8 @@
 use sway_ast::{
-    attribute::{Attribute, AttributeArg, CFG_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME},
+    attribute::{
+        Attribute, AttributeArg, CFG_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME,
+        DOC_COMMENT_TEXT_ARG_NAME,
+    },
     brackets::SquareBrackets,
     keywords::{HashBangToken, HashToken, Token},
     AttributeDecl, Literal, Parens, Punctuated,
@@ -33,6 +36,7 @@ impl New {
         assert_insert_span!(insert_span);
 
         Attribute {
+            namespace: vec![],
             name: Ident::new_with_override(attribute_name.as_ref().into(), insert_span.clone()),
             args: Some(Parens {
                 inner: Punctuated {
@@ -43,6 +47,7 @@ impl New {
                             insert_span.clone(),
                         ),
                         value,
+                        args: None,
                     })),
                 },
                 span: insert_span,
@@ -128,8 +133,11 @@ impl New {
                     final_value_opt: Some(Box::new(New::attribute_with_arg(
                         insert_span.clone(),
                         DOC_COMMENT_ATTRIBUTE_NAME,
-                        &format!(" {}", comment.as_ref()),
-                        None,
+                        DOC_COMMENT_TEXT_ARG_NAME,
+                        Some(New::literal_string(
+                            insert_span.clone(),
+                            &format!(" {}", comment.as_ref()),
+                        )),
                     ))),
                 },
                 span: insert_span,
@@ -1,5 +1,5 @@
 use sway_ast::{
-    literal::{LitBool, LitBoolType},
+    literal::{LitBool, LitBoolType, LitString},
     Literal,
 };
 use sway_types::Span;
@@ -22,4 +22,14 @@ impl New {
             },
         })
     }
+
+    /// Creates a [Literal] representing string `value`.
+    pub(crate) fn literal_string<S: AsRef<str> + ?Sized>(insert_span: Span, value: &S) -> Literal {
+        assert_insert_span!(insert_span);
+
+        Literal::String(LitString {
+            span: insert_span,
+            parsed: value.as_ref().into(),
+        })
+    }
 }
@@ -0,0 +1,7 @@
+fn main() {
+    // The target triple is part of the user agent of the http requests.
+    println!(
+        "cargo:rustc-env=TARGET={}",
+        std::env::var("TARGET").expect("cargo sets TARGET for build scripts")
+    );
+}
@@ -0,0 +1,554 @@
+use super::{is_metadata_file, validate_config_files};
+use crate::consts::{
+    CURRENT_VERSION_POINTER, MAX_CONFIG_VERSIONS, PINNED_REF_FILE_NAME, SHA_MANIFEST_FILE_NAME,
+    SOURCE_FILE_NAME, SOURCE_REPO_FILE_NAME,
+};
+use anyhow::Result;
+use chrono::Utc;
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Returns the path of a folder next to `dir`, named `<dir name>.<suffix>`.
+pub(crate) fn sibling_dir(dir: &Path, suffix: &str) -> PathBuf {
+    let mut name = dir.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{suffix}"));
+    dir.with_file_name(name)
+}
+
+/// Returns the name of the version of the configuration in `network_dir` that
+/// is in use, or `None` if the configuration has no versions, e.g., because it
+/// was downloaded by an older version of forc-node, which kept the files
+/// directly in `network_dir`.
+pub(crate) fn current_version(network_dir: &Path) -> Option<String> {
+    let pointer = network_dir.join(CURRENT_VERSION_POINTER);
+    #[cfg(unix)]
+    let version = fs::read_link(pointer).ok()?.to_str()?.to_string();
+    #[cfg(not(unix))]
+    let version = fs::read_to_string(pointer).ok()?.trim().to_string();
+    network_dir.join(&version).is_dir().then_some(version)
+}
+
+/// Returns the folder with the files of the configuration in `network_dir`,
+/// resolved through the pointer to its current version.
+pub(crate) fn current_config_dir(network_dir: &Path) -> PathBuf {
+    match current_version(network_dir) {
+        Some(version) => network_dir.join(version),
+        None => network_dir.to_path_buf(),
+    }
+}
+
+/// Points the configuration in `network_dir` to its `version`. The pointer is
+/// replaced atomically, so the configuration always resolves to a complete
+/// version.
+pub(crate) fn set_current_version(network_dir: &Path, version: &str) -> Result<()> {
+    let pointer = network_dir.join(CURRENT_VERSION_POINTER);
+    let new_pointer = network_dir.join(format!(
+        ".{CURRENT_VERSION_POINTER}.tmp-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&new_pointer);
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(version, &new_pointer)?;
+    #[cfg(not(unix))]
+    fs::write(&new_pointer, version)?;
+    fs::rename(&new_pointer, &pointer)?;
+    Ok(())
+}
+
+/// Returns an unused name for a new version of the configuration in
+/// `network_dir`: the `git_ref` the configuration is pinned to, or the current
+/// date and time.
+fn new_version_name(network_dir: &Path, git_ref: Option<&str>) -> String {
+    let timestamp = Utc::now().format("%Y-%m-%dT%H-%M-%SZ").to_string();
+    let name = git_ref
+        .map(|git_ref| {
+            git_ref
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c,
+                    _ => '-',
+                })
+                .collect::<String>()
+                .trim_start_matches('.')
+                .to_string()
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or(timestamp);
+    (0..)
+        .map(|n| match n {
+            0 => name.clone(),
+            n => format!("{name}-{n}"),
+        })
+        .find(|name| name != CURRENT_VERSION_POINTER && !network_dir.join(name).exists())
+        .expect("there is an unused version name")
+}
+
+/// Returns when the version in `version_dir` was installed, that is, when the
+/// hashes of its files were recorded.
+pub(crate) fn installed_at(version_dir: &Path) -> Option<SystemTime> {
+    fs::metadata(version_dir.join(SHA_MANIFEST_FILE_NAME))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Returns the names of the versions of the configuration in `network_dir`,
+/// from the oldest to the newest installed.
+pub(crate) fn versions_of(network_dir: &Path) -> Result<Vec<String>> {
+    // Without a current version, the folders are nested configuration folders.
+    if current_version(network_dir).is_none() {
+        return Ok(vec![]);
+    }
+    let mut versions = vec![];
+    for entry in fs::read_dir(network_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        // The pointer is a symlink, so it is not a directory entry.
+        if entry.file_type()?.is_dir() && !name.starts_with('.') {
+            versions.push((installed_at(&entry.path()), name));
+        }
+    }
+    versions.sort();
+    Ok(versions.into_iter().map(|(_, name)| name).collect())
+}
+
+/// Moves the files of a configuration downloaded by an older version of
+/// forc-node, which kept them directly in `network_dir`, into a version, and
+/// makes it the current one.
+fn version_unversioned_files(network_dir: &Path) -> Result<()> {
+    // The source, the pin, and the repository apply to all the versions.
+    let is_shared = |name: &OsStr| {
+        name == SOURCE_FILE_NAME || name == PINNED_REF_FILE_NAME || name == SOURCE_REPO_FILE_NAME
+    };
+    let entries = fs::read_dir(network_dir)?
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<io::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|name| !is_shared(name))
+        .collect::<Vec<_>>();
+    if entries.iter().all(|name| is_metadata_file(name)) {
+        return Ok(());
+    }
+
+    let version = new_version_name(network_dir, None);
+    let version_dir = network_dir.join(&version);
+    fs::create_dir(&version_dir)?;
+    for name in entries {
+        fs::rename(network_dir.join(&name), version_dir.join(&name))?;
+    }
+    set_current_version(network_dir, &version)
+}
+
+/// Moves `staging_dir` into `network_dir` as a new version, and makes it the
+/// current one. Unless `keep_previous` is false, the previous versions are
+/// kept, up to [MAX_CONFIG_VERSIONS] in total, to switch back to.
+///
+/// Returns the name of the new version.
+pub(crate) fn install_config_version(
+    staging_dir: &Path,
+    network_dir: &Path,
+    git_ref: Option<&str>,
+    keep_previous: bool,
+) -> Result<String> {
+    fs::create_dir_all(network_dir)?;
+    if current_version(network_dir).is_none() {
+        version_unversioned_files(network_dir)?;
+    }
+    let previous = current_version(network_dir);
+
+    let version = new_version_name(network_dir, git_ref);
+    fs::rename(staging_dir, network_dir.join(&version))?;
+    set_current_version(network_dir, &version)?;
+
+    match previous {
+        Some(_) if keep_previous => prune_versions(network_dir, MAX_CONFIG_VERSIONS)?,
+        // The new version is in place, a leftover previous version is harmless.
+        Some(previous) => {
+            let _ = fs::remove_dir_all(network_dir.join(previous));
+        }
+        None => {}
+    }
+    Ok(version)
+}
+
+/// The default local network configuration, embedded at build time.
+#[cfg(feature = "embedded-local-config")]
+static EMBEDDED_LOCAL_CONFIG: include_dir::Dir<'_> =
+    include_dir::include_dir!("$CARGO_MANIFEST_DIR/local-config");
+
+/// Writes the embedded local network configuration to `network_dir`, as its
+/// current version. Returns false if forc-node was built without it.
+pub(crate) fn unpack_embedded_local_config(network_dir: &Path) -> Result<bool> {
+    #[cfg(feature = "embedded-local-config")]
+    {
+        let staging_dir = sibling_dir(network_dir, &format!("tmp-{}", std::process::id()));
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir)?;
+        }
+        fs::create_dir_all(&staging_dir)?;
+        EMBEDDED_LOCAL_CONFIG.extract(&staging_dir)?;
+        validate_config_files(&staging_dir)?;
+        install_config_version(&staging_dir, network_dir, None, false)?;
+        Ok(true)
+    }
+    #[cfg(not(feature = "embedded-local-config"))]
+    {
+        let _ = network_dir;
+        Ok(false)
+    }
+}
+
+/// Removes the oldest versions of the configuration in `network_dir`, except
+/// the current one, so that at most `keep` are left.
+///
+/// Returns the names of the removed versions.
+pub(crate) fn prune_versions(network_dir: &Path, keep: usize) -> Result<Vec<String>> {
+    let current = current_version(network_dir);
+    let versions = versions_of(network_dir)?;
+    let excess = versions.len().saturating_sub(keep.max(1));
+    let removed = versions
+        .into_iter()
+        .filter(|version| Some(version) != current.as_ref())
+        .take(excess)
+        .collect::<Vec<_>>();
+    for version in &removed {
+        fs::remove_dir_all(network_dir.join(version))?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chain_config::{
+            source::user_agent,
+            test_utils::{mount_testnet_listing, CHAIN_CONFIG, NEW_CHAIN_CONFIG, STATE_CONFIG},
+            ChainConfig, ConfigFetcher,
+        },
+        consts::TESTNET_CONFIG_FOLDER_NAME,
+    };
+    use tempfile::TempDir;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[tokio::test]
+    async fn test_failed_download_keeps_existing_files() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[
+                ("chain_config.json", "new chain"),
+                ("state_config.json", "new state"),
+            ],
+            &config_path,
+        )
+        .await;
+
+        let old_files: [(&str, &[u8]); 2] = [
+            ("chain_config.json", b"old chain\n\x00\xff"),
+            ("state_config.json", b"old state"),
+        ];
+        fs::create_dir_all(&test_folder).unwrap();
+        for (name, content) in &old_files {
+            fs::write(test_folder.join(name), content).unwrap();
+        }
+
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("new chain"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/raw/state_config.json"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        let err = fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("state_config.json"));
+
+        for (name, content) in &old_files {
+            assert_eq!(fs::read(test_folder.join(name)).unwrap(), *content);
+        }
+        // The staging folder is removed as well.
+        assert_eq!(fs::read_dir(&config_path).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_empty_download_keeps_existing_files() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", "new chain")],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), "old chain").unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        assert!(fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .is_err());
+        assert_eq!(
+            fs::read_to_string(test_folder.join("chain_config.json")).unwrap(),
+            "old chain"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_successful_download_replaces_existing_files() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", NEW_CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), "old chain").unwrap();
+        fs::write(test_folder.join("removed.json"), "removed").unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .and(header("User-Agent", user_agent().as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_string(NEW_CHAIN_CONFIG))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        let config_dir = fetcher.config_dir(&ChainConfig::Testnet);
+        assert_eq!(
+            fs::read_to_string(config_dir.join("chain_config.json")).unwrap(),
+            NEW_CHAIN_CONFIG
+        );
+        assert!(!config_dir.join("removed.json").exists());
+
+        // The previous files are kept as a version of their own, within the folder.
+        let versions = fetcher.list_versions(&ChainConfig::Testnet).unwrap();
+        assert_eq!(versions.len(), 2);
+        assert!(!versions[0].current);
+        assert!(versions[1].current);
+        assert_eq!(versions[1].path, config_dir);
+        assert_eq!(
+            fs::read_to_string(versions[0].path.join("chain_config.json")).unwrap(),
+            "old chain"
+        );
+        assert_eq!(
+            fs::read_to_string(versions[0].path.join("removed.json")).unwrap(),
+            "removed"
+        );
+        assert_eq!(fs::read_dir(&config_path).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_oldest_versions_are_pruned() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", NEW_CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        // Versions without recorded hashes are ordered by their name.
+        for day in 1..=MAX_CONFIG_VERSIONS {
+            let version_dir = test_folder.join(format!("2024-01-0{day}T00-00-00Z"));
+            fs::create_dir_all(&version_dir).unwrap();
+            fs::write(
+                version_dir.join("chain_config.json"),
+                format!("chain {day}"),
+            )
+            .unwrap();
+        }
+        set_current_version(
+            &test_folder,
+            &format!("2024-01-0{MAX_CONFIG_VERSIONS}T00-00-00Z"),
+        )
+        .unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(NEW_CHAIN_CONFIG))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        let versions = fetcher
+            .list_versions(&ChainConfig::Testnet)
+            .unwrap()
+            .iter()
+            .map(|version| fs::read_to_string(version.path.join("chain_config.json")).unwrap())
+            .collect::<Vec<_>>();
+        let mut expected = (2..=MAX_CONFIG_VERSIONS)
+            .map(|day| format!("chain {day}"))
+            .collect::<Vec<_>>();
+        expected.push(NEW_CHAIN_CONFIG.to_string());
+        assert_eq!(versions, expected);
+    }
+
+    #[tokio::test]
+    async fn test_switching_versions_does_not_download_or_remove_files() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", NEW_CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        fs::write(test_folder.join("state_config.json"), STATE_CONFIG).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(NEW_CHAIN_CONFIG))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+        let versions = fetcher.list_versions(&ChainConfig::Testnet).unwrap();
+        let (previous, updated) = (&versions[0].name, &versions[1].name);
+
+        fetcher
+            .switch_version(&ChainConfig::Testnet, previous)
+            .unwrap();
+        let config_dir = fetcher.config_dir(&ChainConfig::Testnet);
+        assert_eq!(config_dir, test_folder.join(previous));
+        assert_eq!(
+            fs::read_to_string(config_dir.join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+        assert_eq!(
+            fs::read_to_string(config_dir.join("state_config.json")).unwrap(),
+            STATE_CONFIG
+        );
+        assert!(fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+
+        // Both versions are kept, so the switch can be undone.
+        fetcher
+            .switch_version(&ChainConfig::Testnet, updated)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(
+                fetcher
+                    .config_dir(&ChainConfig::Testnet)
+                    .join("chain_config.json")
+            )
+            .unwrap(),
+            NEW_CHAIN_CONFIG
+        );
+        assert!(fetcher
+            .switch_version(&ChainConfig::Testnet, "unknown")
+            .is_err());
+
+        assert_eq!(
+            fetcher.prune_versions(&ChainConfig::Testnet, 1).unwrap(),
+            vec![previous.clone()]
+        );
+        let versions = fetcher.list_versions(&ChainConfig::Testnet).unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(&versions[0].name, updated);
+    }
+
+    #[test]
+    fn test_invalid_version_is_not_switched_to() {
+        let test_dir = TempDir::new().unwrap();
+        let fetcher = ConfigFetcher::with_test_config(
+            "http://127.0.0.1:0".to_string(),
+            test_dir.path().to_path_buf(),
+        );
+        let network_dir = test_dir.path().join(TESTNET_CONFIG_FOLDER_NAME);
+        for (version, content) in [("v1", CHAIN_CONFIG), ("v2", "not json")] {
+            fs::create_dir_all(network_dir.join(version)).unwrap();
+            fs::write(network_dir.join(version).join("chain_config.json"), content).unwrap();
+        }
+        set_current_version(&network_dir, "v1").unwrap();
+
+        assert!(fetcher.switch_version(&ChainConfig::Testnet, "v2").is_err());
+        assert_eq!(
+            fetcher.config_dir(&ChainConfig::Testnet),
+            network_dir.join("v1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unparsable_download_keeps_existing_files() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let truncated = &NEW_CHAIN_CONFIG[..NEW_CHAIN_CONFIG.len() / 2];
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", truncated)],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(truncated))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        let err = fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid configuration file chain_config.json: EOF"));
+        assert_eq!(
+            fs::read_to_string(test_folder.join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+        fetcher.validate_config(&ChainConfig::Testnet).unwrap();
+    }
+}
@@ -0,0 +1,1923 @@
+use super::{
+    backup::{
+        current_config_dir, current_version, install_config_version, installed_at, prune_versions,
+        set_current_version, sibling_dir, versions_of,
+    },
+    changed_top_level_keys, check_fuel_core_compatibility, config_files_size, copy_config_files,
+    manifest::{git_blob_sha, hash_config_files, read_sha_manifest, sha256_hex, ShaManifest},
+    read_update_info,
+    source::{ConfigSource, GithubSource, HttpClient, RemoteFile, RemoteFolder, RetryPolicy},
+    validate_config_files, ChainConfig, ConfigChangeSet, ConfigFileStatus, ConfigRepository,
+    ConfigStatus, ConfigVersion, CustomSource, FuelCoreCompatibility, StoredSource, UpdateInfo,
+};
+use crate::{
+    consts::{
+        CHECKSUMS_FILE_NAME, CONFIG_FOLDER, CUSTOM_CONFIG_FOLDER_NAME, MAX_CONCURRENT_DOWNLOADS,
+        PINNED_REF_FILE_NAME, SHA_MANIFEST_FILE_NAME, SOURCE_FILE_NAME, SOURCE_REPO_FILE_NAME,
+        UPDATE_INFO_FILE_NAME,
+    },
+    util::get_fuel_core_version,
+};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use forc_tracing::{println_action_green, println_warning};
+use forc_util::user_forc_directory;
+use futures::{StreamExt, TryStreamExt};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tar::Archive;
+
+/// Formats a number of bytes in a human readable way, e.g., `1.5 MiB`.
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Reports the progress of downloading a file. If the size of the file is
+/// known, the progress is reported at every quarter of it.
+struct DownloadProgress<'a> {
+    name: &'a str,
+    total: Option<u64>,
+    downloaded: u64,
+    reported_quarters: u64,
+}
+
+impl<'a> DownloadProgress<'a> {
+    fn start(name: &'a str, total: Option<u64>) -> Self {
+        let size = total.map(|total| format!(" ({})", format_bytes(total)));
+        println_action_green(
+            "Downloading",
+            &format!("{name}{}", size.unwrap_or_default()),
+        );
+        Self {
+            name,
+            total,
+            downloaded: 0,
+            reported_quarters: 0,
+        }
+    }
+
+    fn advance(&mut self, bytes: usize) {
+        self.downloaded += bytes as u64;
+        let Some(total) = self.total.filter(|total| *total > 0) else {
+            return;
+        };
+        let quarters = (self.downloaded * 4 / total).min(4);
+        if quarters > self.reported_quarters && quarters < 4 {
+            println_action_green(
+                "Downloading",
+                &format!(
+                    "{} {}/{}",
+                    self.name,
+                    format_bytes(self.downloaded),
+                    format_bytes(total)
+                ),
+            );
+        }
+        self.reported_quarters = quarters;
+    }
+
+    fn finish(self) {
+        println_action_green(
+            "Downloaded",
+            &format!("{} ({})", self.name, format_bytes(self.downloaded)),
+        );
+    }
+}
+
+/// Writes a downloaded file to `writer`, reporting the [DownloadProgress].
+struct ProgressWriter<'a, W> {
+    writer: W,
+    progress: DownloadProgress<'a>,
+}
+
+impl<W: Write> Write for ProgressWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.progress.advance(written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// `ConfigFetcher` is responsible for the configuration operations. The
+/// configurations hosted in git repositories are listed and downloaded from
+/// its [ConfigSource], the [GithubSource] by default.
+/// Basically checks remote hash of the corresponding chain configuration.
+/// If there is a mismatch between local and remote instance, overrides the
+/// local instance with remote changes for testnet and mainnet configurations.
+///
+/// For local chain configuration, we only check for existence of it locally.
+/// If the local chain configuration is missing in user's local,
+/// `ConfigFetcher` fetches it but remote updates are not tracked for it.
+pub struct ConfigFetcher {
+    source: Box<dyn ConfigSource>,
+    /// Downloads the tarballs of custom configurations.
+    http: HttpClient,
+    pub(crate) config_vault: PathBuf,
+    config_repo: ConfigRepository,
+    verify_hashes: bool,
+    fuel_core_compatibility: FuelCoreCompatibility,
+}
+
+impl Default for ConfigFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigFetcher {
+    /// Creates a new fetcher to interact with github.
+    /// By default user's chain configuration vault is at: `~/.forc/chainspecs`
+    pub fn new() -> Self {
+        Self::with_source(GithubSource::new())
+    }
+
+    /// Creates a new fetcher that lists and downloads the configurations
+    /// hosted in git repositories from `source`.
+    pub fn with_source(source: impl ConfigSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            http: HttpClient::default(),
+            config_vault: user_forc_directory().join(CONFIG_FOLDER),
+            config_repo: ConfigRepository::default(),
+            verify_hashes: false,
+            fuel_core_compatibility: FuelCoreCompatibility::default(),
+        }
+    }
+
+    /// Downloads the built-in chain configurations from `config_repo`,
+    /// instead of the [ConfigRepository::default].
+    pub fn with_config_repo(self, config_repo: ConfigRepository) -> Self {
+        Self {
+            config_repo,
+            ..self
+        }
+    }
+
+    /// Keeps the chain configurations in `config_vault`, instead of the
+    /// user's vault.
+    pub fn with_config_vault(self, config_vault: PathBuf) -> Self {
+        Self {
+            config_vault,
+            ..self
+        }
+    }
+
+    /// Retries the requests for the tarballs of custom configurations
+    /// according to `retry_policy`. The requests to the [ConfigSource] are
+    /// retried by the source.
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            http: HttpClient {
+                retry_policy,
+                ..self.http
+            },
+            ..self
+        }
+    }
+
+    #[cfg(test)]
+    /// Override the base url, to be used in tests.
+    pub fn with_base_url(base_url: String) -> Self {
+        Self::with_source(
+            GithubSource::new()
+                .with_raw_url(format!("{base_url}/raw"))
+                .with_base_url(base_url)
+                .with_github_token(None),
+        )
+    }
+
+    #[cfg(test)]
+    pub fn with_test_config(base_url: String, config_vault: PathBuf) -> Self {
+        // Retry without waiting, to keep the tests fast.
+        let retry_policy = RetryPolicy {
+            initial_backoff: Duration::ZERO,
+            ..Default::default()
+        };
+        Self::with_source(
+            GithubSource::new()
+                .with_raw_url(format!("{base_url}/raw"))
+                .with_base_url(base_url)
+                .with_github_token(None)
+                .with_retry_policy(retry_policy),
+        )
+        .with_config_vault(config_vault)
+        .with_retry_policy(retry_policy)
+        // Downloads in tests do not depend on the installed fuel-core.
+        .with_fuel_core_compatibility(FuelCoreCompatibility::Skip)
+    }
+
+    /// If `verify_hashes` is true, all the local configuration files are
+    /// hashed when checking for updates. Otherwise, the recorded hashes of the
+    /// files that did not change since the last update are used.
+    pub fn with_hash_verification(self, verify_hashes: bool) -> Self {
+        Self {
+            verify_hashes,
+            ..self
+        }
+    }
+
+    /// Sets what happens when a downloaded configuration requires a fuel-core
+    /// version other than the installed one.
+    pub fn with_fuel_core_compatibility(
+        self,
+        fuel_core_compatibility: FuelCoreCompatibility,
+    ) -> Self {
+        Self {
+            fuel_core_compatibility,
+            ..self
+        }
+    }
+
+    /// Returns the folder of the git repository that hosts the files of
+    /// `conf`, at its pinned ref, or `None` if the files are not hosted in one.
+    fn remote_folder(&self, conf: &ChainConfig) -> Option<RemoteFolder> {
+        let (owner, repo, path) = conf.repository_folder(&self.config_repo)?;
+        Some(RemoteFolder {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            path: path.to_string(),
+            git_ref: self.pinned_ref(conf),
+        })
+    }
+
+    /// Returns where the files of `conf` are downloaded from, for its
+    /// [UpdateInfo].
+    fn update_source(&self, conf: &ChainConfig) -> (String, Option<String>) {
+        if let Some(folder) = self.remote_folder(conf) {
+            return (format!("{}/{}", folder.owner, folder.repo), folder.git_ref);
+        }
+        match conf {
+            ChainConfig::Custom {
+                source: CustomSource::Directory(source_dir),
+                ..
+            } => (source_dir.display().to_string(), None),
+            ChainConfig::Custom {
+                source: CustomSource::Tarball(url),
+                ..
+            } => (url.clone(), None),
+            _ => (conf.to_string(), None),
+        }
+    }
+
+    /// Returns the folder of `conf` in the vault, which holds its versions.
+    pub(crate) fn network_dir(&self, conf: &ChainConfig) -> PathBuf {
+        self.config_vault.join(conf.folder())
+    }
+
+    /// Returns the folder with the files of the current version of `conf`.
+    pub fn config_dir(&self, conf: &ChainConfig) -> PathBuf {
+        current_config_dir(&self.network_dir(conf))
+    }
+
+    /// Returns the git ref `conf` is pinned to, or `None` if it follows the
+    /// default branch of its repository.
+    pub fn pinned_ref(&self, conf: &ChainConfig) -> Option<String> {
+        let pin_file = self.network_dir(conf).join(PINNED_REF_FILE_NAME);
+        let git_ref = fs::read_to_string(pin_file).ok()?;
+        let git_ref = git_ref.trim();
+        (!git_ref.is_empty()).then(|| git_ref.to_string())
+    }
+
+    /// Pins `conf` to `git_ref`, a tag, a branch, or a commit sha, so that
+    /// its files are checked and updated against the ones at that ref.
+    /// If `git_ref` is `None`, `conf` follows the default branch again.
+    pub fn pin_ref(&self, conf: &ChainConfig, git_ref: Option<&str>) -> Result<()> {
+        if conf.repository_folder(&self.config_repo).is_none() {
+            bail!("The {conf} configuration is not hosted on github, so it cannot be pinned");
+        }
+        let network_dir = self.network_dir(conf);
+        let pin_file = network_dir.join(PINNED_REF_FILE_NAME);
+        match git_ref {
+            Some(git_ref) => {
+                fs::create_dir_all(&network_dir)?;
+                fs::write(pin_file, git_ref)?;
+            }
+            None if pin_file.exists() => fs::remove_file(pin_file)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the repository the files of `conf` were downloaded from, if it
+    /// differs from the one it is checked for updates against, e.g., because
+    /// the repository was overridden since.
+    fn other_recorded_repository(&self, conf: &ChainConfig) -> Option<String> {
+        let folder = self.remote_folder(conf)?;
+        let recorded =
+            fs::read_to_string(self.network_dir(conf).join(SOURCE_REPO_FILE_NAME)).ok()?;
+        let recorded = recorded.trim();
+        (recorded != format!("{}/{}", folder.owner, folder.repo)).then(|| recorded.to_string())
+    }
+
+    /// Reads the [StoredSource] of `conf`, if there is one.
+    fn read_stored_source(&self, conf: &ChainConfig) -> Option<StoredSource> {
+        let source_file = self.network_dir(conf).join(SOURCE_FILE_NAME);
+        let stored_source = fs::read(source_file).ok()?;
+        serde_json::from_slice(&stored_source).ok()
+    }
+
+    /// Lists the remote files of `conf`, to get their hashes and download
+    /// urls. The files of the built-in configurations are hosted at:
+    /// https://github.com/FuelLabs/chain-configuration/
+    pub(crate) async fn check_remote_files(
+        &self,
+        conf: &ChainConfig,
+    ) -> anyhow::Result<Vec<RemoteFile>> {
+        let Some(folder) = self.remote_folder(conf) else {
+            bail!("The {conf} configuration is not hosted on github");
+        };
+        self.source
+            .list_folder(&folder, &self.config_dir(conf))
+            .await
+    }
+
+    /// Calculates the hash for the local configuration instance.
+    /// The hash calculation is based on github's hash calculation to match the
+    /// github api response.
+    ///
+    /// The files in nested folders are included, keyed by their path relative
+    /// to the configuration folder, e.g., `snapshots/state_config.json`.
+    ///
+    /// Unless the fetcher verifies all the hashes, the files are only hashed if
+    /// their size or modification time differ from the ones recorded in the
+    /// manifest written by the last update.
+    fn check_local_files(&self, conf: &ChainConfig) -> Result<Option<HashMap<String, String>>> {
+        if *conf == ChainConfig::Local {
+            bail!("Local configuration should not be checked");
+        }
+
+        let folder_path = self.config_dir(conf);
+
+        if !folder_path.exists() {
+            return Ok(None);
+        }
+
+        let manifest = if self.verify_hashes {
+            ShaManifest::new()
+        } else {
+            read_sha_manifest(&folder_path)
+        };
+        let files = hash_config_files(&folder_path, &manifest)?
+            .into_iter()
+            .map(|(name, entry)| (name, entry.sha))
+            .collect();
+
+        Ok(Some(files))
+    }
+
+    /// Checks if a fetch is required by comparing the hashes of individual files
+    /// of the given chain config in the local instance to the one in github.
+    /// See [Self::check_changes].
+    pub async fn check_fetch_required(&self, conf: &ChainConfig) -> anyhow::Result<bool> {
+        Ok(!self.check_changes(conf).await?.is_empty())
+    }
+
+    /// Collects the changes of the given chain config by comparing the hashes
+    /// of individual files in the local instance to the one in github by
+    /// utilizing the github content abi.
+    ///
+    /// Custom configurations from a tarball are checked by comparing the ETag
+    /// of the tarball, and the ones from a local directory never need a fetch.
+    pub async fn check_changes(&self, conf: &ChainConfig) -> anyhow::Result<ConfigChangeSet> {
+        match conf {
+            ChainConfig::Local
+            | ChainConfig::Custom {
+                source: CustomSource::Directory(_),
+                ..
+            } => return Ok(ConfigChangeSet::default()),
+            ChainConfig::Custom {
+                source: CustomSource::Tarball(url),
+                ..
+            } => {
+                return Ok(ConfigChangeSet {
+                    unknown_files: self.check_tarball_update_required(conf, url).await?,
+                    ..Default::default()
+                })
+            }
+            _ => {}
+        }
+
+        let local_files = match self.check_local_files(conf)? {
+            Some(files) => files,
+            // No local files, need to fetch
+            None => {
+                return Ok(ConfigChangeSet {
+                    unknown_files: true,
+                    ..Default::default()
+                })
+            }
+        };
+
+        // Mixing the files of two repositories would go unnoticed otherwise.
+        if let Some(recorded) = self.other_recorded_repository(conf) {
+            println_warning(&format!(
+                "The {conf} network configuration was downloaded from {recorded}, but it is \
+                checked for updates against {}",
+                self.config_repo
+            ));
+        }
+        if let Some(changes) = self.check_checksum_changes(conf).await? {
+            return Ok(changes);
+        }
+        let remote_files = self.check_remote_files(conf).await?;
+
+        // Compare files
+        let mut changes = ConfigChangeSet::default();
+        for remote_file in &remote_files {
+            match local_files.get(&remote_file.name) {
+                Some(local_sha) if local_sha == &remote_file.sha => {}
+                Some(_) => {
+                    changes.modified.push(remote_file.name.clone());
+                    changes
+                        .remote_files
+                        .insert(remote_file.name.clone(), remote_file.clone());
+                }
+                None => changes.added.push(remote_file.name.clone()),
+            }
+        }
+
+        // Also check if we have any extra files locally that aren't remote
+        let remote_filenames: HashSet<_> = remote_files.iter().map(|f| &f.name).collect();
+        changes.removed = local_files
+            .into_keys()
+            .filter(|name| !remote_filenames.contains(name))
+            .collect();
+
+        changes.added.sort();
+        changes.removed.sort();
+        changes.modified.sort();
+        Ok(changes)
+    }
+
+    /// Collects the changes of `conf` by comparing the SHA256 of the local
+    /// files to the [CHECKSUMS_FILE_NAME] manifest of its remote folder, which
+    /// is a single download instead of a listing of the folder.
+    ///
+    /// Returns `None` if the remote folder has no manifest.
+    async fn check_checksum_changes(
+        &self,
+        conf: &ChainConfig,
+    ) -> anyhow::Result<Option<ConfigChangeSet>> {
+        let Some(folder) = self.remote_folder(conf) else {
+            bail!("The {conf} configuration is not hosted on github");
+        };
+        let Some(remote_files) = self.source.fetch_checksums(&folder).await? else {
+            return Ok(None);
+        };
+
+        let config_dir = self.config_dir(conf);
+        let manifest = if self.verify_hashes {
+            ShaManifest::new()
+        } else {
+            read_sha_manifest(&config_dir)
+        };
+        let local_files = hash_config_files(&config_dir, &manifest)?
+            .into_iter()
+            .filter(|(name, _)| name != CHECKSUMS_FILE_NAME)
+            .map(|(name, entry)| {
+                let sha256 = match entry.sha256 {
+                    Some(sha256) => sha256,
+                    None => sha256_hex(&fs::read(config_dir.join(&name))?),
+                };
+                Ok((name, sha256))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        let mut changes = ConfigChangeSet::default();
+        for remote_file in &remote_files {
+            match local_files.get(&remote_file.name) {
+                Some(local_sha256) if local_sha256 == &remote_file.sha256 => {}
+                Some(_) => {
+                    changes.modified.push(remote_file.name.clone());
+                    // Only downloaded to compare the keys of JSON files, so
+                    // the git blob SHA1 is not needed.
+                    let remote_file = RemoteFile {
+                        name: remote_file.name.clone(),
+                        sha: String::new(),
+                        size: 0,
+                        download_url: remote_file.download_url.clone(),
+                    };
+                    changes
+                        .remote_files
+                        .insert(remote_file.name.clone(), remote_file);
+                }
+                None => changes.added.push(remote_file.name.clone()),
+            }
+        }
+        let remote_filenames: HashSet<_> = remote_files.iter().map(|f| &f.name).collect();
+        changes.removed = local_files
+            .into_keys()
+            .filter(|name| !remote_filenames.contains(name))
+            .collect();
+
+        changes.added.sort();
+        changes.removed.sort();
+        changes.modified.sort();
+        Ok(Some(changes))
+    }
+
+    /// Returns the top-level keys that differ between the local and the
+    /// remote version of `name`, a modified JSON file of `conf` in `changes`.
+    pub async fn changed_json_keys(
+        &self,
+        conf: &ChainConfig,
+        changes: &ConfigChangeSet,
+        name: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let Some(remote_file) = changes.remote_files.get(name) else {
+            bail!("{name} is not a modified file of the {conf} configuration");
+        };
+        let local_path = self.config_dir(conf).join(name);
+        let local: serde_json::Value = serde_json::from_slice(&fs::read(local_path)?)?;
+
+        let mut remote = vec![];
+        self.source
+            .download_file(remote_file, &mut remote)
+            .await
+            .with_context(|| format!("Failed to download file: {name}"))?;
+        let remote: serde_json::Value = serde_json::from_slice(&remote)?;
+
+        Ok(changed_top_level_keys(&local, &remote))
+    }
+
+    /// Checks if the tarball of a custom configuration changed since it was
+    /// downloaded, by comparing its current ETag to the stored one. Tarballs
+    /// served without an ETag are never updated.
+    async fn check_tarball_update_required(
+        &self,
+        conf: &ChainConfig,
+        url: &str,
+    ) -> anyhow::Result<bool> {
+        let Some(stored_source) = self.read_stored_source(conf) else {
+            return Ok(true); // Never downloaded, need to fetch
+        };
+        let Some(stored_etag) = stored_source.etag else {
+            return Ok(false);
+        };
+
+        let response = self.http.send_with_retry(self.http.head(url)).await?;
+
+        if !response.status().is_success() {
+            bail!("failed to check {url} for updates");
+        }
+
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|etag| etag.to_str().ok());
+        Ok(etag.is_some_and(|etag| etag != stored_etag))
+    }
+
+    /// Download the chain config for given mode. Fetches the corresponding
+    /// directory from: https://github.com/FuelLabs/chain-configuration/,
+    /// or from the source of a custom configuration.
+    ///
+    /// The files are downloaded to `<folder>.tmp-<pid>` first, which becomes a
+    /// new version of the configuration only once all of them are downloaded
+    /// and validated. Then the configuration is pointed to the new version.
+    /// On any failure, the current version is left untouched.
+    pub async fn download_config(&self, conf: &ChainConfig) -> anyhow::Result<()> {
+        let network_dir = self.network_dir(conf);
+
+        // The configuration is selected by the path of its folder in the vault.
+        if let ChainConfig::Custom {
+            source: CustomSource::Directory(source_dir),
+            ..
+        } = conf
+        {
+            let source_dir = source_dir.canonicalize()?;
+            for dir in [self.config_dir(conf), network_dir.clone()] {
+                if dir.exists() && source_dir == dir.canonicalize()? {
+                    return Ok(());
+                }
+            }
+        }
+
+        let staging_dir = sibling_dir(&network_dir, &format!("tmp-{}", std::process::id()));
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir)?;
+        }
+
+        // Files copied from a local directory can always be copied again.
+        let keep_previous = !matches!(
+            conf,
+            ChainConfig::Custom {
+                source: CustomSource::Directory(_),
+                ..
+            }
+        );
+        let install = async {
+            let etag = self.download_config_to(conf, &staging_dir).await?;
+            let (source, git_ref) = self.update_source(conf);
+            let (file_count, total_bytes) = config_files_size(&staging_dir)?;
+            let update_info = UpdateInfo {
+                updated_at: Utc::now(),
+                source,
+                git_ref,
+                file_count,
+                total_bytes,
+            };
+            fs::write(
+                staging_dir.join(UPDATE_INFO_FILE_NAME),
+                serde_json::to_vec_pretty(&update_info)?,
+            )?;
+            install_config_version(
+                &staging_dir,
+                &network_dir,
+                self.pinned_ref(conf).as_deref(),
+                keep_previous,
+            )?;
+            match conf {
+                ChainConfig::Custom { source, .. } => {
+                    let stored_source = StoredSource {
+                        source: source.clone(),
+                        etag,
+                    };
+                    fs::write(
+                        network_dir.join(SOURCE_FILE_NAME),
+                        serde_json::to_vec_pretty(&stored_source)?,
+                    )?;
+                }
+                _ => fs::write(
+                    network_dir.join(SOURCE_REPO_FILE_NAME),
+                    self.config_repo.to_string(),
+                )?,
+            }
+            anyhow::Ok(())
+        };
+        let result = install.await;
+        if result.is_err() && staging_dir.exists() {
+            let _ = fs::remove_dir_all(&staging_dir);
+        }
+        result
+    }
+
+    /// Returns the versions of `conf` in the vault, from the oldest to the
+    /// newest downloaded. A new version is kept whenever the files are updated.
+    ///
+    /// Configurations downloaded by older versions of forc-node have no
+    /// versions, until they are updated.
+    pub fn list_versions(&self, conf: &ChainConfig) -> Result<Vec<ConfigVersion>> {
+        let network_dir = self.network_dir(conf);
+        let current = current_version(&network_dir);
+        Ok(versions_of(&network_dir)?
+            .into_iter()
+            .map(|name| {
+                let path = network_dir.join(&name);
+                ConfigVersion {
+                    current: Some(&name) == current.as_ref(),
+                    installed_at: installed_at(&path).map(DateTime::<Utc>::from),
+                    name,
+                    path,
+                }
+            })
+            .collect())
+    }
+
+    /// Points `conf` to its `version`, one of the [Self::list_versions], once
+    /// its files are validated. Nothing is downloaded, and the other versions
+    /// are kept, so it can be switched back at any time.
+    pub fn switch_version(&self, conf: &ChainConfig, version: &str) -> Result<()> {
+        let network_dir = self.network_dir(conf);
+        if !versions_of(&network_dir)?
+            .iter()
+            .any(|name| name == version)
+        {
+            bail!("The {conf} configuration has no version named `{version}`");
+        }
+        validate_config_files(&network_dir.join(version))?;
+        set_current_version(&network_dir, version)
+    }
+
+    /// Removes the oldest versions of `conf`, except the current one, so that
+    /// at most `keep` versions are left.
+    ///
+    /// Returns the names of the removed versions.
+    pub fn prune_versions(&self, conf: &ChainConfig, keep: usize) -> Result<Vec<String>> {
+        prune_versions(&self.network_dir(conf), keep)
+    }
+
+    /// Returns the built-in chain configurations, followed by the custom ones
+    /// in the vault, sorted by their name.
+    pub fn known_configs(&self) -> Vec<ChainConfig> {
+        let mut custom_configs = fs::read_dir(self.config_vault.join(CUSTOM_CONFIG_FOLDER_NAME))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                // Skip the backups kept by older versions of forc-node and the
+                // folders of downloads in progress.
+                if name.contains(&format!(".{BACKUP_FOLDER_PREFIX}")) || name.contains(".tmp-") {
+                    return None;
+                }
+                let stored_source = fs::read(entry.path().join(SOURCE_FILE_NAME)).ok()?;
+                let stored_source: StoredSource = serde_json::from_slice(&stored_source).ok()?;
+                Some(ChainConfig::Custom {
+                    name,
+                    source: stored_source.source,
+                })
+            })
+            .collect::<Vec<_>>();
+        custom_configs.sort();
+
+        let mut configs = vec![
+            ChainConfig::Local,
+            ChainConfig::Testnet,
+            ChainConfig::Devnet,
+            ChainConfig::Ignition,
+        ];
+        configs.extend(custom_configs);
+        configs
+    }
+
+    /// Returns the state of the `conf` files in the vault, without checking
+    /// for updates.
+    pub fn config_status(&self, conf: &ChainConfig) -> Result<ConfigStatus> {
+        let path = self.config_dir(conf);
+        let mut status = ConfigStatus {
+            name: conf.to_string(),
+            path: path.clone(),
+            installed: path.is_dir(),
+            version: current_version(&self.network_dir(conf)),
+            files: vec![],
+            last_updated: None,
+            update_info: None,
+            pinned_ref: self.pinned_ref(conf),
+            update_available: None,
+        };
+        if !status.installed {
+            return Ok(status);
+        }
+
+        let manifest = if self.verify_hashes {
+            ShaManifest::new()
+        } else {
+            read_sha_manifest(&path)
+        };
+        let files = hash_config_files(&path, &manifest)?;
+        // The manifest is written whenever the files are downloaded. The
+        // files downloaded by older versions have no manifest.
+        let last_updated = fs::metadata(path.join(SHA_MANIFEST_FILE_NAME))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .or_else(|| files.values().map(|entry| entry.modified).max());
+        status.last_updated = last_updated.map(DateTime::<Utc>::from);
+        status.update_info = read_update_info(&path);
+        status.files = files
+            .into_iter()
+            .map(|(name, entry)| ConfigFileStatus {
+                name,
+                size: entry.size,
+                sha: entry.sha,
+            })
+            .collect();
+        status.files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(status)
+    }
+
+    /// Returns the state of the `conf` files in the vault, including whether
+    /// an update is available. Nothing is downloaded or asked, only the cached
+    /// listing of the remote files might be refreshed.
+    pub async fn check_config_status(&self, conf: &ChainConfig) -> Result<ConfigStatus> {
+        let mut status = self.config_status(conf)?;
+        let tracks_updates = !matches!(
+            conf,
+            ChainConfig::Local
+                | ChainConfig::Custom {
+                    source: CustomSource::Directory(_),
+                    ..
+                }
+        );
+        if tracks_updates {
+            status.update_available = Some(self.check_fetch_required(conf).await?);
+        }
+        Ok(status)
+    }
+
+    /// Checks that the files of `conf` in the vault can be parsed.
+    /// See [validate_config_files].
+    pub fn validate_config(&self, conf: &ChainConfig) -> Result<PathBuf> {
+        let config_dir = self.config_dir(conf);
+        if !config_dir.exists() {
+            bail!(
+                "Missing {conf} network configuration at {}",
+                config_dir.display()
+            );
+        }
+        validate_config_files(&config_dir)?;
+        Ok(config_dir)
+    }
+
+    /// Downloads the files of `conf` to the empty `target_dir`, and checks
+    /// that they can be parsed.
+    ///
+    /// Returns the ETag of the tarball of a custom configuration, if it has one.
+    async fn download_config_to(
+        &self,
+        conf: &ChainConfig,
+        target_dir: &Path,
+    ) -> Result<Option<String>> {
+        let etag = match conf {
+            ChainConfig::Custom {
+                source: CustomSource::Directory(source_dir),
+                ..
+            } => {
+                copy_config_files(source_dir, target_dir)?;
+                None
+            }
+            ChainConfig::Custom {
+                source: CustomSource::Tarball(url),
+                ..
+            } => self.download_tarball(url, target_dir).await?,
+            _ => {
+                self.download_remote_files(conf, target_dir).await?;
+                None
+            }
+        };
+        validate_config_files(target_dir)?;
+        check_fuel_core_compatibility(
+            conf,
+            target_dir,
+            self.fuel_core_compatibility,
+            get_fuel_core_version,
+        )?;
+        // The modification times are kept when the folder is moved in place.
+        let manifest = hash_config_files(target_dir, &ShaManifest::new())?;
+        fs::write(
+            target_dir.join(SHA_MANIFEST_FILE_NAME),
+            serde_json::to_vec(&manifest)?,
+        )?;
+        Ok(etag)
+    }
+
+    /// Downloads the files of `conf` hosted in a git repository to `target_dir`.
+    async fn download_remote_files(
+        &self,
+        conf: &ChainConfig,
+        target_dir: &Path,
+    ) -> anyhow::Result<()> {
+        let files = self.check_remote_files(conf).await?;
+
+        // Create config directory if it doesn't exist
+        fs::create_dir_all(target_dir)?;
+
+        // Download the files concurrently. The first failure drops the
+        // downloads that are still in progress.
+        let downloads = files.iter().map(|file| {
+            let file_path = target_dir.join(&file.name);
+            async move {
+                self.download_file(file, &file_path)
+                    .await
+                    .with_context(|| format!("Failed to download file: {}", file.name))
+            }
+        });
+        futures::stream::iter(downloads)
+            .buffer_unordered(MAX_CONCURRENT_DOWNLOADS)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        // Make sure the set of files is complete before it is used.
+        for file in &files {
+            let is_downloaded = fs::metadata(target_dir.join(&file.name))
+                .map(|metadata| metadata.is_file() && metadata.len() > 0)
+                .unwrap_or(false);
+            if !is_downloaded {
+                bail!("Failed to download file: {}", file.name);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Downloads `file` to `file_path`, streaming it to the disk and reporting
+    /// the progress.
+    ///
+    /// The written file is hashed again, to make sure that it is the one in
+    /// the listing, and not, e.g., a truncated or a newer version of it.
+    async fn download_file(&self, file: &RemoteFile, file_path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let total = (file.size > 0).then_some(file.size);
+        let mut writer = ProgressWriter {
+            writer: fs::File::create(file_path)?,
+            progress: DownloadProgress::start(&file.name, total),
+        };
+        self.source.download_file(file, &mut writer).await?;
+        writer.flush()?;
+
+        let sha = git_blob_sha(&fs::read(file_path)?);
+        if sha != file.sha {
+            bail!(
+                "the hash of the downloaded file is {sha}, expected {}",
+                file.sha
+            );
+        }
+        writer.progress.finish();
+        Ok(())
+    }
+
+    /// Downloads the gzipped tarball at `url` and unpacks it to `target_dir`.
+    /// If all the files of the tarball are within a single top-level folder,
+    /// the contents of that folder are unpacked.
+    ///
+    /// Returns the ETag of the tarball, if there is one.
+    async fn download_tarball(
+        &self,
+        url: &str,
+        target_dir: &Path,
+    ) -> anyhow::Result<Option<String>> {
+        let response = self.http.send_with_retry(self.http.get(url)).await?;
+
+        if !response.status().is_success() {
+            bail!("Failed to download chain configuration from {url}");
+        }
+
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(str::to_string);
+        let tarball = response.bytes().await?;
+
+        fs::create_dir_all(target_dir)?;
+        Archive::new(GzDecoder::new(tarball.as_ref())).unpack(target_dir)?;
+
+        let entries = fs::read_dir(target_dir)?.collect::<Result<Vec<_>, _>>()?;
+        if let [entry] = entries.as_slice() {
+            if entry.path().is_dir() {
+                let top_level_dir = entry.path();
+                for nested in fs::read_dir(&top_level_dir)? {
+                    let nested = nested?;
+                    fs::rename(nested.path(), target_dir.join(nested.file_name()))?;
+                }
+                fs::remove_dir(top_level_dir)?;
+            }
+        }
+
+        Ok(etag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chain_config::{
+            source::GithubContentDetails,
+            test_utils::{
+                create_github_response, mount_testnet_listing, CHAIN_CONFIG, NEW_CHAIN_CONFIG,
+                STATE_CONFIG,
+            },
+        },
+        consts::{CHAIN_CONFIG_REPO_NAME, DEVNET_CONFIG_FOLDER_NAME, TESTNET_CONFIG_FOLDER_NAME},
+    };
+    use tempfile::TempDir;
+    use wiremock::{
+        matchers::{method, path, query_param, query_param_is_missing},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[tokio::test]
+    async fn test_fetch_not_required_when_files_match() {
+        let mock_server = MockServer::start().await;
+        let test_files = [
+            ("config.json", "test config content"),
+            ("metadata.json", "test metadata content"),
+        ];
+
+        // Create test directory and files
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+
+        for (name, content) in &test_files {
+            fs::write(test_folder.join(name), content).unwrap();
+        }
+
+        // Setup mock response
+        let github_response = create_github_response(&test_files);
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+
+        let needs_fetch = fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        assert!(
+            !needs_fetch,
+            "Fetch should not be required when files match"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_required_when_files_differ() {
+        let mock_server = MockServer::start().await;
+
+        // Create local test files
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("fuel").join("configs");
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+
+        let local_files = [
+            ("config.json", "old config content"),
+            ("metadata.json", "old metadata content"),
+        ];
+
+        for (name, content) in &local_files {
+            fs::write(test_folder.join(name), content).unwrap();
+        }
+
+        // Setup mock GitHub response with different content
+        let github_files = [
+            ("config.json", "new config content"),
+            ("metadata.json", "new metadata content"),
+        ];
+        let github_response = create_github_response(&github_files);
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_base_url(mock_server.uri());
+
+        let needs_fetch = fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        assert!(needs_fetch, "Fetch should be required when files differ");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_required_when_files_missing() {
+        let mock_server = MockServer::start().await;
+
+        // Create local test files (missing one file)
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("fuel").join("configs");
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+
+        let local_files = [("config.json", "test config content")];
+
+        for (name, content) in &local_files {
+            fs::write(test_folder.join(name), content).unwrap();
+        }
+
+        // Setup mock GitHub response with extra file
+        let github_files = [
+            ("config.json", "test config content"),
+            ("metadata.json", "test metadata content"),
+        ];
+        let github_response = create_github_response(&github_files);
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_base_url(mock_server.uri());
+
+        let needs_fetch = fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        assert!(
+            needs_fetch,
+            "Fetch should be required when files are missing"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_configuration_never_needs_fetch() {
+        let fetcher = ConfigFetcher::default();
+        let needs_fetch = fetcher
+            .check_fetch_required(&ChainConfig::Local)
+            .await
+            .unwrap();
+
+        assert!(!needs_fetch, "Local configuration should never need fetch");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_required_when_extra_local_files() {
+        let mock_server = MockServer::start().await;
+
+        // Create local test files (with extra file)
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("fuel").join("configs");
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+
+        let local_files = [
+            ("config.json", "test config content"),
+            ("metadata.json", "test metadata content"),
+            ("extra.json", "extra file content"),
+        ];
+
+        for (name, content) in &local_files {
+            fs::write(test_folder.join(name), content).unwrap();
+        }
+
+        // Setup mock GitHub response with fewer files
+        let github_files = [
+            ("config.json", "test config content"),
+            ("metadata.json", "test metadata content"),
+        ];
+        let github_response = create_github_response(&github_files);
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_base_url(mock_server.uri());
+
+        let needs_fetch = fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        assert!(
+            needs_fetch,
+            "Fetch should be required when there are extra local files"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_custom_configuration_from_directory_is_copied_to_vault() {
+        let source_dir = TempDir::new().unwrap();
+        fs::write(source_dir.path().join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        fs::write(source_dir.path().join("state_config.json"), STATE_CONFIG).unwrap();
+
+        let vault_dir = TempDir::new().unwrap();
+        let fetcher = ConfigFetcher::with_test_config(
+            "http://127.0.0.1:0".to_string(),
+            vault_dir.path().to_path_buf(),
+        );
+        let conf = ChainConfig::Custom {
+            name: "staging".to_string(),
+            source: CustomSource::Directory(source_dir.path().to_path_buf()),
+        };
+
+        fetcher.download_config(&conf).await.unwrap();
+
+        let target_dir = fetcher.config_dir(&conf);
+        assert_eq!(
+            target_dir.parent().unwrap(),
+            vault_dir
+                .path()
+                .join(CUSTOM_CONFIG_FOLDER_NAME)
+                .join("staging")
+        );
+        assert_eq!(
+            fs::read_to_string(target_dir.join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+        assert_eq!(
+            fs::read_to_string(target_dir.join("state_config.json")).unwrap(),
+            STATE_CONFIG
+        );
+        assert_eq!(
+            fetcher.read_stored_source(&conf).unwrap().source,
+            CustomSource::Directory(source_dir.path().to_path_buf())
+        );
+        assert!(!fetcher.check_fetch_required(&conf).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_custom_configuration_from_tarball_is_unpacked_and_tracked() {
+        let mock_server = MockServer::start().await;
+
+        let mut tarball = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let content = CHAIN_CONFIG.as_bytes();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tarball
+            .append_data(&mut header, "staging/chain_config.json", content)
+            .unwrap();
+        let tarball = tarball.into_inner().unwrap().finish().unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/staging.tar.gz"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"tarball-etag\"")
+                    .set_body_bytes(tarball),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/staging.tar.gz"))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"tarball-etag\""))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let vault_dir = TempDir::new().unwrap();
+        let fetcher =
+            ConfigFetcher::with_test_config(mock_server.uri(), vault_dir.path().to_path_buf());
+        let conf = ChainConfig::Custom {
+            name: "staging".to_string(),
+            source: CustomSource::Tarball(format!("{}/staging.tar.gz", mock_server.uri())),
+        };
+
+        assert!(fetcher.check_fetch_required(&conf).await.unwrap());
+        fetcher.download_config(&conf).await.unwrap();
+
+        let target_dir = fetcher.config_dir(&conf);
+        assert_eq!(
+            target_dir.parent().unwrap(),
+            vault_dir
+                .path()
+                .join(CUSTOM_CONFIG_FOLDER_NAME)
+                .join("staging")
+        );
+        assert_eq!(
+            fs::read_to_string(target_dir.join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+        assert_eq!(
+            fetcher.read_stored_source(&conf).unwrap().etag.as_deref(),
+            Some("\"tarball-etag\"")
+        );
+        assert!(!fetcher.check_fetch_required(&conf).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_custom_configuration_from_github_is_checked_for_updates() {
+        let mock_server = MockServer::start().await;
+        let test_files = [("chain_config.json", "chain")];
+
+        let vault_dir = TempDir::new().unwrap();
+        let target_dir = vault_dir
+            .path()
+            .join(CUSTOM_CONFIG_FOLDER_NAME)
+            .join("staging");
+        fs::create_dir_all(&target_dir).unwrap();
+        for (name, content) in &test_files {
+            fs::write(target_dir.join(name), content).unwrap();
+        }
+
+        let github_response = create_github_response(&test_files);
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/networks/contents/configs/staging"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let fetcher =
+            ConfigFetcher::with_test_config(mock_server.uri(), vault_dir.path().to_path_buf());
+        let conf = ChainConfig::Custom {
+            name: "staging".to_string(),
+            source: CustomSource::Github {
+                owner: "acme".to_string(),
+                repo: "networks".to_string(),
+                path: "configs/staging".to_string(),
+            },
+        };
+
+        assert!(!fetcher.check_fetch_required(&conf).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_changes_list_added_removed_and_modified_files() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[
+                (
+                    "chain_config.json",
+                    "{\"chain_name\": \"new\", \"height\": 1}",
+                ),
+                ("metadata.json", "metadata"),
+                ("state_config.json", "state"),
+            ],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(
+            test_folder.join("chain_config.json"),
+            "{\"chain_name\": \"old\", \"height\": 1, \"removed\": true}",
+        )
+        .unwrap();
+        fs::write(test_folder.join("metadata.json"), "metadata").unwrap();
+        fs::write(test_folder.join("extra.json"), "extra").unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("{\"chain_name\": \"new\", \"height\": 1}"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        let changes = fetcher.check_changes(&ChainConfig::Testnet).await.unwrap();
+
+        assert_eq!(changes.added, vec!["state_config.json"]);
+        assert_eq!(changes.removed, vec!["extra.json"]);
+        assert_eq!(changes.modified, vec!["chain_config.json"]);
+        assert!(!changes.unknown_files);
+        assert!(fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+
+        let keys = fetcher
+            .changed_json_keys(&ChainConfig::Testnet, &changes, "chain_config.json")
+            .await
+            .unwrap();
+        assert_eq!(keys, vec!["chain_name", "removed"]);
+        assert!(fetcher
+            .changed_json_keys(&ChainConfig::Testnet, &changes, "metadata.json")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_nested_folders_are_downloaded_and_checked() {
+        let mock_server = MockServer::start().await;
+        let listing_path = format!(
+            "/repos/FuelLabs/{}/contents/{}",
+            CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+        );
+        let with_mock_download_urls = |files: Vec<GithubContentDetails>, prefix: &str| {
+            files
+                .into_iter()
+                .map(|item| GithubContentDetails {
+                    download_url: Some(format!("{}/raw/{prefix}{}", mock_server.uri(), item.name)),
+                    ..item
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut top_level = with_mock_download_urls(
+            create_github_response(&[("chain_config.json", CHAIN_CONFIG)]),
+            "",
+        );
+        top_level.push(GithubContentDetails {
+            name: "snapshots".to_string(),
+            sha: "tree-sha".to_string(),
+            size: 0,
+            download_url: None,
+            content_type: "dir".to_string(),
+        });
+        let snapshots = with_mock_download_urls(
+            create_github_response(&[("state_config.json", STATE_CONFIG)]),
+            "snapshots/",
+        );
+        Mock::given(method("GET"))
+            .and(path(listing_path.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&top_level))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{listing_path}/snapshots")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&snapshots))
+            .mount(&mock_server)
+            .await;
+        for (file_path, content) in [
+            ("/raw/chain_config.json", CHAIN_CONFIG),
+            ("/raw/snapshots/state_config.json", STATE_CONFIG),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(file_path))
+                .respond_with(ResponseTemplate::new(200).set_body_string(content))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+        let test_folder = fetcher.config_dir(&ChainConfig::Testnet);
+        assert_eq!(
+            fs::read_to_string(test_folder.join("snapshots").join("state_config.json")).unwrap(),
+            STATE_CONFIG
+        );
+        assert!(!fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+
+        fs::write(
+            test_folder.join("snapshots").join("state_config.json"),
+            "edited",
+        )
+        .unwrap();
+        let changes = fetcher.check_changes(&ChainConfig::Testnet).await.unwrap();
+        assert_eq!(changes.modified, vec!["snapshots/state_config.json"]);
+        assert!(changes.added.is_empty());
+        assert!(changes.removed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pinned_configuration_is_checked_and_downloaded_at_its_ref() {
+        let mock_server = MockServer::start().await;
+        let listing_path = format!(
+            "/repos/FuelLabs/{}/contents/{}",
+            CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+        );
+        let listing_at = |content: &str, raw_prefix: &str| {
+            create_github_response(&[("chain_config.json", content)])
+                .into_iter()
+                .map(|item| GithubContentDetails {
+                    download_url: Some(format!(
+                        "{}/raw/{raw_prefix}/{}",
+                        mock_server.uri(),
+                        item.name
+                    )),
+                    ..item
+                })
+                .collect::<Vec<_>>()
+        };
+        Mock::given(method("GET"))
+            .and(path(listing_path.clone()))
+            .and(query_param_is_missing("ref"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(listing_at(NEW_CHAIN_CONFIG, "main")),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(listing_path))
+            .and(query_param("ref", "v1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(listing_at(CHAIN_CONFIG, "v1")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/raw/v1/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CHAIN_CONFIG))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        let conf = ChainConfig::Testnet;
+
+        fetcher.pin_ref(&conf, Some("v1")).unwrap();
+        assert_eq!(fetcher.pinned_ref(&conf).as_deref(), Some("v1"));
+        fetcher.download_config(&conf).await.unwrap();
+        // The version is named after the ref it was downloaded at.
+        assert_eq!(fetcher.config_dir(&conf), test_folder.join("v1"));
+        assert_eq!(
+            fs::read_to_string(test_folder.join("v1").join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+
+        // The files match the pinned ref, even though the default branch moved on.
+        assert_eq!(fetcher.pinned_ref(&conf).as_deref(), Some("v1"));
+        assert!(!fetcher.check_fetch_required(&conf).await.unwrap());
+
+        fetcher.pin_ref(&conf, None).unwrap();
+        assert_eq!(fetcher.pinned_ref(&conf), None);
+        let changes = fetcher.check_changes(&conf).await.unwrap();
+        assert_eq!(changes.modified, vec!["chain_config.json"]);
+    }
+
+    #[test]
+    fn test_configuration_not_hosted_on_github_cannot_be_pinned() {
+        let test_dir = TempDir::new().unwrap();
+        let fetcher = ConfigFetcher::with_test_config(
+            "http://127.0.0.1:0".to_string(),
+            test_dir.path().to_path_buf(),
+        );
+        let conf = ChainConfig::Custom {
+            name: "staging".to_string(),
+            source: CustomSource::Tarball("https://example.com/staging.tar.gz".to_string()),
+        };
+        assert!(fetcher.pin_ref(&conf, Some("v1")).is_err());
+        assert_eq!(fetcher.pinned_ref(&conf), None);
+    }
+
+    #[test]
+    fn test_byte_counts_are_human_readable() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 * 1024), "3072.0 GiB");
+    }
+
+    #[tokio::test]
+    async fn test_download_does_not_touch_the_user_vault() {
+        // Point the user's home at a temporary directory, so that the user's
+        // vault can be checked without reading the real home directory.
+        let home_dir = TempDir::new().unwrap();
+        let previous_home = std::env::var_os("HOME");
+        std::env::set_var("HOME", home_dir.path());
+        let user_vault = user_forc_directory().join(CONFIG_FOLDER);
+        assert!(user_vault.starts_with(home_dir.path()));
+
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CHAIN_CONFIG))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        let result = fetcher.download_config(&ChainConfig::Testnet).await;
+        match previous_home {
+            Some(previous_home) => std::env::set_var("HOME", previous_home),
+            None => std::env::remove_var("HOME"),
+        }
+        result.unwrap();
+
+        let config_dir = fetcher.config_dir(&ChainConfig::Testnet);
+        assert!(config_dir.starts_with(&test_folder));
+        assert_eq!(
+            fs::read_to_string(config_dir.join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+        // Nothing, not even a temporary folder, is written to the user's vault.
+        assert!(!user_vault.exists());
+        assert_eq!(fs::read_dir(home_dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_devnet_fetch_required_when_files_differ() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = config_path.join(DEVNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+
+        let github_response = create_github_response(&[("chain_config.json", NEW_CHAIN_CONFIG)]);
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, DEVNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        let changes = fetcher.check_changes(&ChainConfig::Devnet).await.unwrap();
+
+        assert_eq!(changes.modified, vec!["chain_config.json".to_string()]);
+        assert!(changes.added.is_empty());
+        assert!(changes.removed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_devnet_configuration_is_downloaded_to_its_folder() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+
+        let github_response = create_github_response(&[("chain_config.json", CHAIN_CONFIG)])
+            .into_iter()
+            .map(|item| GithubContentDetails {
+                download_url: Some(format!("{}/raw/{}", mock_server.uri(), item.name)),
+                ..item
+            })
+            .collect::<Vec<_>>();
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, DEVNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CHAIN_CONFIG))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        fetcher.download_config(&ChainConfig::Devnet).await.unwrap();
+
+        let config_dir = fetcher.config_dir(&ChainConfig::Devnet);
+        assert!(config_dir.starts_with(config_path.join(DEVNET_CONFIG_FOLDER_NAME)));
+        assert_eq!(
+            fs::read_to_string(config_dir.join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+        assert!(!config_path.join(TESTNET_CONFIG_FOLDER_NAME).exists());
+        assert!(!fetcher
+            .check_fetch_required(&ChainConfig::Devnet)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_files_deleted_upstream_are_removed_on_update() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        fs::write(test_folder.join("deleted_upstream.json"), STATE_CONFIG).unwrap();
+        fs::write(test_folder.join(PINNED_REF_FILE_NAME), "v1.0.0").unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CHAIN_CONFIG))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        let changes = fetcher.check_changes(&ChainConfig::Testnet).await.unwrap();
+        assert_eq!(changes.removed, vec!["deleted_upstream.json".to_string()]);
+
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        let config_dir = fetcher.config_dir(&ChainConfig::Testnet);
+        assert_eq!(config_dir, test_folder.join("v1.0.0"));
+        assert!(!config_dir.join("deleted_upstream.json").exists());
+        // The files written by forc-node itself are kept.
+        assert_eq!(
+            fs::read_to_string(test_folder.join(PINNED_REF_FILE_NAME)).unwrap(),
+            "v1.0.0"
+        );
+        assert!(!fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_config_status_reports_files_and_updates() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", NEW_CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+
+        let status = fetcher.config_status(&ChainConfig::Testnet).unwrap();
+        assert!(!status.installed);
+        assert!(status.files.is_empty());
+        assert_eq!(status.last_updated, None);
+
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        let status = fetcher
+            .check_config_status(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+        assert!(status.installed);
+        assert_eq!(status.path, test_folder);
+        assert_eq!(
+            status.files,
+            vec![ConfigFileStatus {
+                name: "chain_config.json".to_string(),
+                size: CHAIN_CONFIG.len() as u64,
+                sha: git_blob_sha(CHAIN_CONFIG.as_bytes()),
+            }]
+        );
+        assert!(status.last_updated.is_some());
+        assert_eq!(status.update_available, Some(true));
+        // Checking the status does not change the files.
+        assert_eq!(
+            fs::read_to_string(test_folder.join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+
+        // Updates of the local configuration are not tracked.
+        let status = fetcher
+            .check_config_status(&ChainConfig::Local)
+            .await
+            .unwrap();
+        assert_eq!(status.update_available, None);
+    }
+
+    #[test]
+    fn test_known_configs_include_the_custom_ones_in_the_vault() {
+        let test_dir = TempDir::new().unwrap();
+        let custom_dir = test_dir.path().join(CUSTOM_CONFIG_FOLDER_NAME);
+        let source = StoredSource {
+            source: CustomSource::Tarball("https://example.com/staging.tar.gz".to_string()),
+            etag: None,
+        };
+        for folder in ["staging", "staging.backup-2024-01-01T00:00:00.000000Z"] {
+            fs::create_dir_all(custom_dir.join(folder)).unwrap();
+            fs::write(
+                custom_dir.join(folder).join(SOURCE_FILE_NAME),
+                serde_json::to_vec(&source).unwrap(),
+            )
+            .unwrap();
+        }
+        // Folders without a stored source are not configurations.
+        fs::create_dir_all(custom_dir.join("unknown")).unwrap();
+
+        let fetcher = ConfigFetcher::with_test_config(
+            "http://127.0.0.1:0".to_string(),
+            test_dir.path().to_path_buf(),
+        );
+        assert_eq!(
+            fetcher.known_configs(),
+            vec![
+                ChainConfig::Local,
+                ChainConfig::Testnet,
+                ChainConfig::Devnet,
+                ChainConfig::Ignition,
+                ChainConfig::Custom {
+                    name: "staging".to_string(),
+                    source: source.source,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_overridden_repository_is_downloaded_from_and_recorded() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let github_response = create_github_response(&[("chain_config.json", CHAIN_CONFIG)])
+            .into_iter()
+            .map(|item| GithubContentDetails {
+                download_url: Some(format!("{}/raw/{}", mock_server.uri(), item.name)),
+                ..item
+            })
+            .collect::<Vec<_>>();
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/acme/networks/contents/{}",
+                TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CHAIN_CONFIG))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone())
+            .with_config_repo(ConfigRepository {
+                owner: "acme".to_string(),
+                repo: "networks".to_string(),
+            });
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        let network_dir = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        assert_eq!(
+            fs::read_to_string(network_dir.join(SOURCE_REPO_FILE_NAME)).unwrap(),
+            "acme/networks"
+        );
+        assert_eq!(
+            fetcher.other_recorded_repository(&ChainConfig::Testnet),
+            None
+        );
+
+        // Checking against the default repository again notices the files
+        // came from elsewhere.
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        assert_eq!(
+            fetcher.other_recorded_repository(&ChainConfig::Testnet),
+            Some("acme/networks".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_records_the_update_info() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let files = [
+            ("chain_config.json", CHAIN_CONFIG),
+            ("state_config.json", STATE_CONFIG),
+        ];
+        mount_testnet_listing(&mock_server, &files, &config_path).await;
+        for (name, content) in files {
+            Mock::given(method("GET"))
+                .and(path(format!("/raw/{name}")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(content))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        let update_info = fetcher
+            .config_status(&ChainConfig::Testnet)
+            .unwrap()
+            .update_info
+            .unwrap();
+        assert_eq!(
+            update_info.origin(),
+            format!("FuelLabs/{CHAIN_CONFIG_REPO_NAME}")
+        );
+        assert_eq!(update_info.file_count, 2);
+        assert_eq!(
+            update_info.total_bytes,
+            (CHAIN_CONFIG.len() + STATE_CONFIG.len()) as u64
+        );
+        assert!(Utc::now() - update_info.updated_at < chrono::Duration::minutes(1));
+        // The info is not a part of the configuration.
+        assert!(!fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+
+        // Corrupted info is ignored.
+        let config_dir = fetcher.config_dir(&ChainConfig::Testnet);
+        fs::write(config_dir.join(UPDATE_INFO_FILE_NAME), "{").unwrap();
+        let status = fetcher.config_status(&ChainConfig::Testnet).unwrap();
+        assert_eq!(status.update_info, None);
+        fetcher.validate_config(&ChainConfig::Testnet).unwrap();
+    }
+}
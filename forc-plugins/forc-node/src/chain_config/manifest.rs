@@ -0,0 +1,350 @@
+use super::is_metadata_file;
+use crate::consts::{CHECKSUMS_FILE_NAME, SHA_MANIFEST_FILE_NAME};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Component, Path},
+    time::SystemTime,
+};
+
+/// Calculates the SHA1 of a file with the `content` the same way github does.
+pub(crate) fn git_blob_sha(content: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(b"blob ");
+    hasher.update(content.len().to_string().as_bytes());
+    hasher.update([0]);
+    hasher.update(content);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Calculates the hex encoded SHA256 of the `content` of a file.
+pub(crate) fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Parses a checksum manifest in the format of `sha256sum`, with a line of
+/// `<sha256>  <path>` for each file, into the checksums by the file path.
+pub(crate) fn parse_checksums(manifest: &str) -> Result<BTreeMap<String, String>> {
+    manifest
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let Some((sha256, name)) = line.split_once(char::is_whitespace) else {
+                bail!("Invalid {CHECKSUMS_FILE_NAME} line: {line}");
+            };
+            // `sha256sum` marks the files hashed in binary mode with a `*`.
+            let name = name.trim_start();
+            let name = name.strip_prefix('*').unwrap_or(name);
+            let name = name.strip_prefix("./").unwrap_or(name);
+            let is_relative = Path::new(name)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+            if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                bail!("Invalid {CHECKSUMS_FILE_NAME} line: {line}");
+            }
+            if name.is_empty() || !is_relative {
+                bail!("Invalid path in {CHECKSUMS_FILE_NAME}: {name}");
+            }
+            Ok((name.to_string(), sha256.to_ascii_lowercase()))
+        })
+        .collect()
+}
+
+/// Verifies the files in `config_dir` against its [CHECKSUMS_FILE_NAME]
+/// manifest, if the configuration has one.
+///
+/// Returns the number of verified files, or `None` if there is no manifest.
+pub fn verify_checksums(config_dir: &Path) -> Result<Option<usize>> {
+    let Ok(manifest) = fs::read_to_string(config_dir.join(CHECKSUMS_FILE_NAME)) else {
+        return Ok(None);
+    };
+    let checksums = parse_checksums(&manifest)?;
+    let mismatched = checksums
+        .iter()
+        .filter_map(|(name, sha256)| match fs::read(config_dir.join(name)) {
+            Ok(content) if sha256_hex(&content) == *sha256 => None,
+            Ok(_) => Some(format!("{name} (checksum mismatch)")),
+            Err(_) => Some(format!("{name} (missing)")),
+        })
+        .collect::<Vec<_>>();
+    if !mismatched.is_empty() {
+        bail!(
+            "The files do not match their {CHECKSUMS_FILE_NAME}: {}",
+            mismatched.join(", ")
+        );
+    }
+    Ok(Some(checksums.len()))
+}
+
+/// The hashes of a configuration file, recorded together with the size and
+/// the modification time of the file, so that the file is only hashed again
+/// once it changes.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct ManifestEntry {
+    pub(crate) sha: String,
+    /// The SHA256 of the file, or `None` if it was recorded by an older version
+    /// of forc-node.
+    #[serde(default)]
+    pub(crate) sha256: Option<String>,
+    pub(crate) size: u64,
+    pub(crate) modified: SystemTime,
+}
+
+/// The [ManifestEntry]s of the files of a configuration, by the file path
+/// relative to the configuration folder.
+pub(crate) type ShaManifest = HashMap<String, ManifestEntry>;
+
+/// Hashes the configuration files in `config_dir`, including the ones in
+/// nested folders, and returns their manifest. The files whose size and
+/// modification time match their entry in `manifest` are not hashed again.
+pub(crate) fn hash_config_files(config_dir: &Path, manifest: &ShaManifest) -> Result<ShaManifest> {
+    let mut files = HashMap::new();
+    let mut pending = vec![(String::new(), config_dir.to_path_buf())];
+    while let Some((prefix, dir)) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let name = format!("{prefix}{}", entry.file_name().to_string_lossy());
+            if entry.path().is_dir() {
+                pending.push((format!("{name}/"), entry.path()));
+            } else if entry.path().is_file() && !is_metadata_file(&entry.file_name()) {
+                let metadata = entry.metadata()?;
+                let (size, modified) = (metadata.len(), metadata.modified()?);
+                let (sha, sha256) = match manifest.get(&name) {
+                    Some(recorded) if recorded.size == size && recorded.modified == modified => {
+                        (recorded.sha.clone(), recorded.sha256.clone())
+                    }
+                    _ => {
+                        let content = fs::read(entry.path())?;
+                        (git_blob_sha(&content), Some(sha256_hex(&content)))
+                    }
+                };
+                files.insert(
+                    name,
+                    ManifestEntry {
+                        sha,
+                        sha256,
+                        size,
+                        modified,
+                    },
+                );
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Reads the manifest of the configuration in `config_dir`. A missing or
+/// invalid manifest is treated as empty, so that all the files are hashed.
+pub(crate) fn read_sha_manifest(config_dir: &Path) -> ShaManifest {
+    fs::read(config_dir.join(SHA_MANIFEST_FILE_NAME))
+        .ok()
+        .and_then(|manifest| serde_json::from_slice(&manifest).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chain_config::{
+            source::user_agent,
+            test_utils::{mount_testnet_listing, CHAIN_CONFIG, NEW_CHAIN_CONFIG, STATE_CONFIG},
+            ChainConfig, ConfigFetcher,
+        },
+        consts::{CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME},
+    };
+    use std::time::Duration;
+    use tempfile::TempDir;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[tokio::test]
+    async fn test_recorded_hashes_are_trusted_until_files_change() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CHAIN_CONFIG))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+        let test_folder = fetcher.config_dir(&ChainConfig::Testnet);
+        let manifest = read_sha_manifest(&test_folder);
+        assert_eq!(
+            manifest["chain_config.json"].sha,
+            git_blob_sha(CHAIN_CONFIG.as_bytes())
+        );
+        assert!(!fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+
+        // A recorded hash is used as long as the size and modification time
+        // of the file match, so a wrong hash shows up as a modification.
+        let mut stale_manifest = manifest.clone();
+        stale_manifest.get_mut("chain_config.json").unwrap().sha = "0".repeat(40);
+        fs::write(
+            test_folder.join(SHA_MANIFEST_FILE_NAME),
+            serde_json::to_vec(&stale_manifest).unwrap(),
+        )
+        .unwrap();
+        assert!(fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+
+        // Verifying the hashes ignores the manifest.
+        let verifying_fetcher =
+            ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone())
+                .with_hash_verification(true);
+        assert!(!verifying_fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+
+        // A file that was touched since the manifest was written is hashed again.
+        fs::File::options()
+            .write(true)
+            .open(test_folder.join("chain_config.json"))
+            .unwrap()
+            .set_modified(manifest["chain_config.json"].modified + Duration::from_secs(1))
+            .unwrap();
+        assert!(!fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+    }
+
+    #[test]
+    fn test_files_with_a_different_size_are_hashed_again() {
+        let test_dir = TempDir::new().unwrap();
+        fs::write(test_dir.path().join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        let manifest = hash_config_files(test_dir.path(), &ShaManifest::new()).unwrap();
+
+        fs::write(test_dir.path().join("chain_config.json"), NEW_CHAIN_CONFIG).unwrap();
+        let mut stale_manifest = manifest.clone();
+        let entry = stale_manifest.get_mut("chain_config.json").unwrap();
+        entry.modified = fs::metadata(test_dir.path().join("chain_config.json"))
+            .unwrap()
+            .modified()
+            .unwrap();
+
+        let rehashed = hash_config_files(test_dir.path(), &stale_manifest).unwrap();
+        assert_eq!(
+            rehashed["chain_config.json"].sha,
+            git_blob_sha(NEW_CHAIN_CONFIG.as_bytes())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checksum_manifest_is_used_instead_of_the_listing() {
+        let mock_server = MockServer::start().await;
+        let checksums = format!(
+            "{}  chain_config.json\n{} *state_config.json\n",
+            sha256_hex(NEW_CHAIN_CONFIG.as_bytes()),
+            sha256_hex(STATE_CONFIG.as_bytes())
+        );
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/raw/FuelLabs/{}/HEAD/{}/{CHECKSUMS_FILE_NAME}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .and(header("User-Agent", user_agent().as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_string(checksums))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        fs::write(test_folder.join("extra.json"), "extra").unwrap();
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+
+        let changes = fetcher.check_changes(&ChainConfig::Testnet).await.unwrap();
+        assert_eq!(changes.added, vec!["state_config.json"]);
+        assert_eq!(changes.removed, vec!["extra.json"]);
+        assert_eq!(changes.modified, vec!["chain_config.json"]);
+        assert_eq!(
+            changes.remote_files["chain_config.json"].download_url,
+            format!(
+                "{}/raw/FuelLabs/{}/HEAD/{}/chain_config.json",
+                mock_server.uri(),
+                CHAIN_CONFIG_REPO_NAME,
+                TESTNET_CONFIG_FOLDER_NAME
+            )
+        );
+
+        // The downloaded manifest itself is not a change.
+        fs::write(test_folder.join("chain_config.json"), NEW_CHAIN_CONFIG).unwrap();
+        fs::write(test_folder.join("state_config.json"), STATE_CONFIG).unwrap();
+        fs::write(test_folder.join(CHECKSUMS_FILE_NAME), "").unwrap();
+        fs::remove_file(test_folder.join("extra.json")).unwrap();
+        assert!(fetcher
+            .check_changes(&ChainConfig::Testnet)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_installed_files_are_verified_against_their_checksums() {
+        let test_dir = TempDir::new().unwrap();
+        let config_dir = test_dir.path();
+        assert_eq!(verify_checksums(config_dir).unwrap(), None);
+
+        fs::create_dir_all(config_dir.join("snapshots")).unwrap();
+        fs::write(config_dir.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        fs::write(config_dir.join("snapshots/state_config.json"), STATE_CONFIG).unwrap();
+        let checksums = format!(
+            "# Generated by sha256sum\n{}  ./chain_config.json\n{}  snapshots/state_config.json\n",
+            sha256_hex(CHAIN_CONFIG.as_bytes()),
+            sha256_hex(STATE_CONFIG.as_bytes()).to_uppercase()
+        );
+        fs::write(config_dir.join(CHECKSUMS_FILE_NAME), &checksums).unwrap();
+        assert_eq!(verify_checksums(config_dir).unwrap(), Some(2));
+
+        fs::write(config_dir.join("chain_config.json"), NEW_CHAIN_CONFIG).unwrap();
+        fs::remove_file(config_dir.join("snapshots/state_config.json")).unwrap();
+        let err = verify_checksums(config_dir).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The files do not match their CHECKSUMS: chain_config.json (checksum mismatch), \
+            snapshots/state_config.json (missing)"
+        );
+
+        let outside = format!("{}  ../chain_config.json", sha256_hex(b""));
+        assert!(parse_checksums(&outside).is_err());
+        assert!(parse_checksums("not-a-checksum  chain_config.json").is_err());
+    }
+}
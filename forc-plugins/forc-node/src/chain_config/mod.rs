@@ -0,0 +1,1397 @@
+mod backup;
+mod fetcher;
+mod manifest;
+mod source;
+#[cfg(test)]
+mod test_utils;
+
+pub use fetcher::ConfigFetcher;
+pub use manifest::verify_checksums;
+pub use source::{
+    ChecksummedFile, ConfigSource, GithubSource, RemoteFile, RemoteFolder, RetryPolicy,
+};
+
+pub(crate) use fetcher::format_bytes;
+
+use crate::{
+    consts::{
+        CHAIN_CONFIG_FILE_NAME, CHAIN_CONFIG_REPO_NAME, CHAIN_CONFIG_REPO_OWNER,
+        CHAIN_CONFIG_REQUIRED_FIELDS, CONFIG_FOLDER, CUSTOM_CONFIG_FOLDER_NAME,
+        DEVNET_CONFIG_FOLDER_NAME, FUEL_CORE_VERSION_FILE_NAME, IGNITION_CONFIG_FOLDER_NAME,
+        LISTING_CACHE_FILE_NAME, LOCAL_CONFIG_FOLDER_NAME, PINNED_REF_FILE_NAME,
+        SHA_MANIFEST_FILE_NAME, SOURCE_FILE_NAME, SOURCE_REPO_FILE_NAME,
+        TESTNET_CONFIG_FOLDER_NAME, UPDATE_INFO_FILE_NAME,
+    },
+    util::ask_user_yes_no_question,
+};
+use anyhow::{anyhow, bail, Result};
+use backup::{current_config_dir, installed_at, unpack_embedded_local_config};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use forc_tracing::{
+    println_action_green, println_action_red, println_action_yellow, println_warning,
+};
+use forc_util::user_forc_directory;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap},
+    ffi::OsStr,
+    fmt::Display,
+    fs,
+    io::{stdin, IsTerminal},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Different chain configuration options.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ChainConfig {
+    Local,
+    Testnet,
+    Devnet,
+    Ignition,
+    /// A chain configuration provided by the user, e.g., for a private network.
+    /// Its files are stored in the vault at `custom/<name>`.
+    Custom {
+        name: String,
+        source: CustomSource,
+    },
+}
+
+/// Where the files of a [ChainConfig::Custom] come from.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum CustomSource {
+    /// A local directory. Its files are copied to the vault on every start,
+    /// so there are no updates to check for.
+    Directory(PathBuf),
+    /// A URL of a gzipped tarball with the configuration files.
+    Tarball(String),
+    /// A folder of a github repository.
+    Github {
+        owner: String,
+        repo: String,
+        path: String,
+    },
+}
+
+/// The source of a [ChainConfig::Custom], persisted next to its files, so that
+/// the configuration can later be selected by its name.
+#[derive(Serialize, Deserialize, Debug)]
+struct StoredSource {
+    source: CustomSource,
+    /// The ETag of the downloaded tarball, if the source is a tarball.
+    etag: Option<String>,
+}
+
+impl ChainConfig {
+    /// Returns the path of the configuration folder, relative to the vault.
+    fn folder(&self) -> PathBuf {
+        match self {
+            ChainConfig::Local => PathBuf::from(LOCAL_CONFIG_FOLDER_NAME),
+            ChainConfig::Testnet => PathBuf::from(TESTNET_CONFIG_FOLDER_NAME),
+            ChainConfig::Devnet => PathBuf::from(DEVNET_CONFIG_FOLDER_NAME),
+            ChainConfig::Ignition => PathBuf::from(IGNITION_CONFIG_FOLDER_NAME),
+            ChainConfig::Custom { name, .. } => Path::new(CUSTOM_CONFIG_FOLDER_NAME).join(name),
+        }
+    }
+
+    /// Returns the owner, the name, and the folder of the git repository that
+    /// hosts the files, or `None` if the files are not hosted in one. The
+    /// built-in configurations are hosted in `repository`.
+    fn repository_folder<'a>(
+        &'a self,
+        repository: &'a ConfigRepository,
+    ) -> Option<(&'a str, &'a str, &'a str)> {
+        let folder = match self {
+            ChainConfig::Local => LOCAL_CONFIG_FOLDER_NAME,
+            ChainConfig::Testnet => TESTNET_CONFIG_FOLDER_NAME,
+            ChainConfig::Devnet => DEVNET_CONFIG_FOLDER_NAME,
+            ChainConfig::Ignition => IGNITION_CONFIG_FOLDER_NAME,
+            ChainConfig::Custom {
+                source: CustomSource::Github { owner, repo, path },
+                ..
+            } => return Some((owner.as_str(), repo.as_str(), path.as_str())),
+            ChainConfig::Custom { .. } => return None,
+        };
+        Some((&repository.owner, &repository.repo, folder))
+    }
+}
+
+/// The github repository that hosts the built-in chain configurations, e.g.,
+/// a fork of the official one that maintains its own network parameters.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConfigRepository {
+    pub owner: String,
+    pub repo: String,
+}
+
+impl Default for ConfigRepository {
+    fn default() -> Self {
+        Self {
+            owner: CHAIN_CONFIG_REPO_OWNER.to_string(),
+            repo: CHAIN_CONFIG_REPO_NAME.to_string(),
+        }
+    }
+}
+
+impl Display for ConfigRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.owner, self.repo)
+    }
+}
+
+impl Display for ChainConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChainConfig::Local => write!(f, "local"),
+            ChainConfig::Testnet => write!(f, "testnet"),
+            ChainConfig::Devnet => write!(f, "devnet"),
+            ChainConfig::Ignition => write!(f, "ignition"),
+            ChainConfig::Custom { name, .. } => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Resolves to the folder of the current version of the configuration.
+impl From<ChainConfig> for PathBuf {
+    fn from(value: ChainConfig) -> Self {
+        current_config_dir(
+            &user_forc_directory()
+                .join(CONFIG_FOLDER)
+                .join(value.folder()),
+        )
+    }
+}
+
+impl FromStr for ChainConfig {
+    type Err = anyhow::Error;
+
+    /// Parses one of:
+    /// - `local`, `testnet`, `devnet` or `ignition` for the built-in
+    ///   configurations,
+    /// - a path to a local directory,
+    /// - an `http(s)://` URL of a gzipped tarball,
+    /// - `github:<owner>/<repo>/<path>` for a folder of a github repository,
+    /// - the name of a custom configuration that was used before.
+    ///
+    /// The name of a new custom configuration is the last segment of its
+    /// source, e.g., `staging` for `github:acme/networks/configs/staging`.
+    ///
+    /// The names of custom configurations used before take precedence over
+    /// directories of the same name, which can be given as, e.g., `./staging`.
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_in_vault(s, &user_forc_directory().join(CONFIG_FOLDER))
+    }
+}
+
+impl ChainConfig {
+    /// Parses `s` like [ChainConfig::from_str], looking up the custom
+    /// configurations used before in the vault at `vault_dir`.
+    pub(crate) fn parse_in_vault(s: &str, vault_dir: &Path) -> Result<Self> {
+        let is_valid_name =
+            |name: &str| !(name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']));
+        let custom = |name: &str, source: CustomSource| -> Result<Self> {
+            if !is_valid_name(name) {
+                bail!("Cannot use `{name}` as the name of the chain configuration `{s}`");
+            }
+            Ok(ChainConfig::Custom {
+                name: name.to_string(),
+                source,
+            })
+        };
+
+        match s {
+            "local" => return Ok(ChainConfig::Local),
+            "testnet" => return Ok(ChainConfig::Testnet),
+            "devnet" => return Ok(ChainConfig::Devnet),
+            "ignition" => return Ok(ChainConfig::Ignition),
+            _ => {}
+        }
+
+        // The name of a custom configuration used before.
+        if is_valid_name(s) {
+            let source_file = vault_dir
+                .join(CUSTOM_CONFIG_FOLDER_NAME)
+                .join(s)
+                .join(SOURCE_FILE_NAME);
+            if let Ok(stored_source) = fs::read(source_file) {
+                let stored_source: StoredSource = serde_json::from_slice(&stored_source)?;
+                return custom(s, stored_source.source);
+            }
+        }
+
+        let path = Path::new(s);
+        if path.is_dir() {
+            let path = path.canonicalize()?;
+            let name = path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+            return custom(name, CustomSource::Directory(path.clone()));
+        }
+        if s.starts_with("http://") || s.starts_with("https://") {
+            let file_name = s
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default();
+            let name = [".tar.gz", ".tgz"]
+                .iter()
+                .find_map(|extension| file_name.strip_suffix(extension))
+                .unwrap_or(file_name);
+            return custom(name, CustomSource::Tarball(s.to_string()));
+        }
+        if let Some(github) = s.strip_prefix("github:") {
+            let mut parts = github.trim_matches('/').splitn(3, '/');
+            let (Some(owner), Some(repo), Some(path)) = (parts.next(), parts.next(), parts.next())
+            else {
+                bail!("Expected `github:<owner>/<repo>/<path>`, found `{s}`");
+            };
+            let name = path.rsplit('/').next().unwrap_or_default();
+            let source = CustomSource::Github {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                path: path.to_string(),
+            };
+            return custom(name, source);
+        }
+
+        bail!(
+            "Unknown chain configuration `{s}`. Expected `local`, `testnet`, `devnet`, \
+            `ignition`, the name of a custom configuration used before, a directory, \
+            a tarball URL, or `github:<owner>/<repo>/<path>`"
+        );
+    }
+}
+
+/// Returns true if the file named `name` is kept by forc-node in a
+/// configuration folder, and is not a part of the configuration.
+fn is_metadata_file(name: &OsStr) -> bool {
+    name == LISTING_CACHE_FILE_NAME
+        || name == SOURCE_FILE_NAME
+        || name == PINNED_REF_FILE_NAME
+        || name == SHA_MANIFEST_FILE_NAME
+        || name == SOURCE_REPO_FILE_NAME
+        || name == UPDATE_INFO_FILE_NAME
+}
+
+/// Returns the number and the total size, in bytes, of the configuration
+/// files in `config_dir`, including the nested ones.
+fn config_files_size(config_dir: &Path) -> Result<(usize, u64)> {
+    let (mut count, mut bytes) = (0, 0);
+    let mut pending = vec![config_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.is_file() && !is_metadata_file(&entry.file_name()) {
+                count += 1;
+                bytes += entry.metadata()?.len();
+            }
+        }
+    }
+    Ok((count, bytes))
+}
+
+/// Reads the [UpdateInfo] of the configuration files in `config_dir`. Missing
+/// or corrupted files are ignored, e.g., for files downloaded by older versions
+/// of forc-node.
+fn read_update_info(config_dir: &Path) -> Option<UpdateInfo> {
+    let update_info = fs::read(config_dir.join(UPDATE_INFO_FILE_NAME)).ok()?;
+    serde_json::from_slice(&update_info).ok()
+}
+
+/// Formats the time elapsed from `then` until `now`, e.g., `3 days ago`.
+fn format_relative_time(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let (amount, unit) = match (now - then).num_seconds() {
+        seconds if seconds < 60 => return "just now".to_string(),
+        seconds if seconds < 60 * 60 => (seconds / 60, "minute"),
+        seconds if seconds < 24 * 60 * 60 => (seconds / (60 * 60), "hour"),
+        seconds => (seconds / (24 * 60 * 60), "day"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    format!("{amount} {unit}{plural} ago")
+}
+
+/// Copies the files of `source_dir` to `target_dir`.
+fn copy_config_files(source_dir: &Path, target_dir: &Path) -> Result<()> {
+    fs::create_dir_all(target_dir)?;
+    for entry in fs::read_dir(source_dir)? {
+        let entry = entry?;
+        if entry.path().is_file() && !is_metadata_file(&entry.file_name()) {
+            fs::copy(entry.path(), target_dir.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the JSON files in `config_dir`, including the ones in nested
+/// folders, can be parsed. The chain configuration file is also checked to
+/// have the fields that fuel-core requires.
+pub fn validate_config_files(config_dir: &Path) -> Result<()> {
+    let mut pending = vec![config_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if !path.is_file()
+                || is_metadata_file(&entry.file_name())
+                || path.extension() != Some(OsStr::new("json"))
+            {
+                continue;
+            }
+
+            let relative_path = path.strip_prefix(config_dir).unwrap_or(&path).display();
+            let value: serde_json::Value = serde_json::from_slice(&fs::read(&path)?)
+                .map_err(|err| anyhow!("Invalid configuration file {relative_path}: {err}"))?;
+            if entry.file_name() == CHAIN_CONFIG_FILE_NAME {
+                let Some(object) = value.as_object() else {
+                    bail!("Invalid configuration file {relative_path}: expected a JSON object");
+                };
+                if let Some(field) = CHAIN_CONFIG_REQUIRED_FIELDS
+                    .iter()
+                    .find(|field| !object.contains_key(**field))
+                {
+                    bail!("Invalid configuration file {relative_path}: missing field `{field}`");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decides what happens when the chain configuration is missing or outdated.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum ConfigUpdatePolicy {
+    /// Ask the user whether to download the configuration.
+    #[value(skip)]
+    Prompt,
+    /// Download the configuration without asking.
+    Accept,
+    /// Keep the existing configuration without asking. Fails if there is no
+    /// configuration to keep.
+    Skip,
+    /// Fail without changing the configuration, instead of asking.
+    Fail,
+}
+
+impl ConfigUpdatePolicy {
+    /// Returns `policy` if one is given explicitly. Otherwise, asks the user.
+    /// Questions that cannot be asked, because stdin is not a terminal, are
+    /// answered by their own default policy, see [ConfigUpdatePolicy::confirm].
+    pub fn resolve(policy: Option<ConfigUpdatePolicy>) -> Self {
+        policy.unwrap_or(ConfigUpdatePolicy::Prompt)
+    }
+
+    /// Returns true if the configuration should be downloaded, asking the
+    /// user the `question` only if the policy is [ConfigUpdatePolicy::Prompt].
+    ///
+    /// If stdin is not a terminal, nobody could answer the `question`, so it
+    /// is answered by the `unattended` policy instead, and the decision is
+    /// logged.
+    fn confirm(self, question: &str, unattended: ConfigUpdatePolicy) -> anyhow::Result<bool> {
+        self.confirm_with(question, unattended, stdin().is_terminal())
+    }
+
+    fn confirm_with(
+        self,
+        question: &str,
+        unattended: ConfigUpdatePolicy,
+        is_terminal: bool,
+    ) -> anyhow::Result<bool> {
+        let policy = match self {
+            ConfigUpdatePolicy::Prompt if is_terminal => return ask_user_yes_no_question(question),
+            ConfigUpdatePolicy::Prompt => {
+                let decision = match unattended {
+                    ConfigUpdatePolicy::Accept => "yes",
+                    ConfigUpdatePolicy::Skip => "no",
+                    ConfigUpdatePolicy::Prompt | ConfigUpdatePolicy::Fail => "none, failing",
+                };
+                println_warning(&format!(
+                    "stdin is not a terminal, answering \"{question}\" automatically: {decision}"
+                ));
+                unattended
+            }
+            policy => policy,
+        };
+        match policy {
+            ConfigUpdatePolicy::Accept => Ok(true),
+            ConfigUpdatePolicy::Skip => Ok(false),
+            ConfigUpdatePolicy::Prompt | ConfigUpdatePolicy::Fail => bail!(
+                "Could not answer \"{question}\" without asking. Use `--yes` or \
+                `--non-interactive accept` to download the network configuration, or \
+                `--non-interactive skip` to keep the existing one"
+            ),
+        }
+    }
+}
+
+/// Decides what happens when a downloaded chain configuration requires a
+/// fuel-core version other than the installed one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, ValueEnum)]
+pub enum FuelCoreCompatibility {
+    /// Install the configuration, warning about the incompatibility.
+    #[default]
+    Warn,
+    /// Keep the existing configuration and fail.
+    Refuse,
+    /// Do not check the fuel-core version.
+    Skip,
+}
+
+/// Parses the fuel-core version `requirement` of a chain configuration. A
+/// plain version is the minimum supported version.
+fn parse_fuel_core_requirement(requirement: &str) -> Result<VersionReq> {
+    let requirement = requirement.trim();
+    match Version::parse(requirement) {
+        Ok(version) => Ok(VersionReq::parse(&format!(">={version}"))?),
+        Err(_) => Ok(VersionReq::parse(requirement)?),
+    }
+}
+
+/// Checks the fuel-core version required by the configuration files of `conf`
+/// in `config_dir` against the `installed` one. Configurations without the
+/// [FUEL_CORE_VERSION_FILE_NAME] file are compatible with any version.
+fn check_fuel_core_compatibility(
+    conf: &ChainConfig,
+    config_dir: &Path,
+    check: FuelCoreCompatibility,
+    installed: impl FnOnce() -> Result<Version>,
+) -> Result<()> {
+    if check == FuelCoreCompatibility::Skip {
+        return Ok(());
+    }
+    let Ok(requirement) = fs::read_to_string(config_dir.join(FUEL_CORE_VERSION_FILE_NAME)) else {
+        return Ok(());
+    };
+    let incompatibility = match (parse_fuel_core_requirement(&requirement), installed()) {
+        (Ok(requirement), Ok(installed)) if requirement.matches(&installed) => return Ok(()),
+        (Ok(requirement), Ok(installed)) => format!(
+            "The {conf} network configuration requires fuel-core {requirement}, \
+            but fuel-core {installed} is installed"
+        ),
+        (Err(err), _) => format!(
+            "Invalid fuel-core version requirement `{}` in the {conf} network configuration: {err}",
+            requirement.trim()
+        ),
+        (_, Err(err)) => format!(
+            "Could not detect the installed fuel-core version, the {conf} network \
+            configuration requires fuel-core {}: {err}",
+            requirement.trim()
+        ),
+    };
+    match check {
+        FuelCoreCompatibility::Refuse => bail!(
+            "{incompatibility}. Keeping the existing configuration, \
+            use `--fuel-core-compatibility warn` to install it anyway"
+        ),
+        _ => {
+            println_warning(&incompatibility);
+            Ok(())
+        }
+    }
+}
+
+/// Settings for checking and updating the chain configuration on startup.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConfigUpdateOptions {
+    pub policy: ConfigUpdatePolicy,
+    /// Show which top-level keys of the modified JSON files changed.
+    pub verbose: bool,
+    /// Hash all the local files, instead of trusting the recorded hashes of
+    /// the files that did not change since the last update.
+    pub verify: bool,
+    /// Whether a configuration update that requires another fuel-core version
+    /// is installed.
+    pub fuel_core_compatibility: FuelCoreCompatibility,
+    /// The repository the built-in configurations are downloaded from.
+    pub config_repo: ConfigRepository,
+    /// Only check that the configuration exists, without any network access.
+    pub skip_check: bool,
+}
+
+/// The outcome of checking, and possibly updating, the chain configuration on
+/// startup. Serialized as JSON for tools wrapping forc-node, with the variant
+/// in the `status` field.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ConfigUpdateOutcome {
+    /// The local files match the remote ones.
+    UpToDate,
+    /// The local files were replaced by the remote ones.
+    Updated {
+        /// The added, removed and modified files, sorted.
+        files_changed: Vec<String>,
+    },
+    /// An update is available, but the existing files are kept.
+    SkippedByUser,
+    /// There were no local files, so they were downloaded.
+    MissingLocalCreated,
+    /// The local files exist, and were not checked for updates.
+    CheckSkipped,
+    /// Checking or updating the configuration failed.
+    Failed { reason: String },
+}
+
+/// The differences between the local files of a chain configuration and the
+/// remote ones. The file names are sorted.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ConfigChangeSet {
+    /// Files that are only available remotely.
+    pub added: Vec<String>,
+    /// Files that are only available locally.
+    pub removed: Vec<String>,
+    /// Files whose local contents differ from the remote ones.
+    pub modified: Vec<String>,
+    /// True if the configuration changed without known file changes, e.g.,
+    /// because it was never downloaded, or the tarball of a custom
+    /// configuration changed.
+    pub unknown_files: bool,
+    /// The remote versions of the modified files, by the file name.
+    remote_files: HashMap<String, RemoteFile>,
+}
+
+impl ConfigChangeSet {
+    /// Returns true if the local files are up-to-date.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && !self.unknown_files
+    }
+}
+
+/// Returns the sorted top-level keys whose values differ between the `local`
+/// and the `remote` JSON objects, including the keys missing from either.
+/// Returns no keys if either of the values is not an object.
+fn changed_top_level_keys(local: &serde_json::Value, remote: &serde_json::Value) -> Vec<String> {
+    let (Some(local), Some(remote)) = (local.as_object(), remote.as_object()) else {
+        return vec![];
+    };
+    local
+        .keys()
+        .chain(remote.keys())
+        .filter(|key| local.get(*key) != remote.get(*key))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .cloned()
+        .collect()
+}
+
+/// A file of a chain configuration in the vault.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct ConfigFileStatus {
+    /// The path of the file relative to the configuration folder.
+    pub name: String,
+    pub size: u64,
+    /// The git blob SHA1 of the contents of the file.
+    pub sha: String,
+}
+
+/// The state of a chain configuration in the vault.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct ConfigStatus {
+    /// The name of the configuration, e.g., `testnet`.
+    pub name: String,
+    /// The folder of the current version of the configuration in the vault.
+    pub path: PathBuf,
+    pub installed: bool,
+    /// The name of the current version, or `None` if the configuration is not
+    /// installed, or was installed before it was versioned.
+    pub version: Option<String>,
+    /// The files of the configuration, sorted by their name.
+    pub files: Vec<ConfigFileStatus>,
+    /// When the files were last downloaded, if they are installed.
+    pub last_updated: Option<DateTime<Utc>>,
+    /// When and where the files were downloaded from, if it was recorded.
+    pub update_info: Option<UpdateInfo>,
+    /// The git ref the configuration is pinned to, if any.
+    pub pinned_ref: Option<String>,
+    /// Whether the remote files differ from the installed ones, or `None` if
+    /// it was not checked, or the configuration is not updated remotely.
+    pub update_available: Option<bool>,
+}
+
+/// When and where the files of a version of a chain configuration were
+/// downloaded from. Recorded next to the files, see [UPDATE_INFO_FILE_NAME].
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub updated_at: DateTime<Utc>,
+    /// The `<owner>/<repo>` github repository, the URL of the tarball, or the
+    /// directory the files were downloaded from.
+    pub source: String,
+    /// The git ref of the files, or `None` for the default branch.
+    pub git_ref: Option<String>,
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
+impl UpdateInfo {
+    /// Returns the source of the files, with the git ref if it is known,
+    /// e.g., `FuelLabs/chain-configuration@v1.0.0`.
+    pub fn origin(&self) -> String {
+        match &self.git_ref {
+            Some(git_ref) => format!("{}@{git_ref}", self.source),
+            None => self.source.clone(),
+        }
+    }
+}
+
+/// A version of a chain configuration in the vault.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct ConfigVersion {
+    /// The name of the version, either the git ref it was downloaded at, or
+    /// the date and time it was downloaded.
+    pub name: String,
+    pub path: PathBuf,
+    /// Whether the configuration resolves to this version.
+    pub current: bool,
+    /// When the version was downloaded, if it is known.
+    pub installed_at: Option<DateTime<Utc>>,
+}
+
+/// Local configuration is validated based on its existence. Meaning that if
+/// the configuration exists in user's local it is validated. If it is missing
+/// the configuration embedded into forc-node is unpacked, without asking or
+/// any network access. Only if forc-node was built without it, the
+/// configuration files are fetched from remote, if the `policy` allows it.
+async fn validate_local_chainconfig(
+    fetcher: &ConfigFetcher,
+    policy: ConfigUpdatePolicy,
+) -> anyhow::Result<ConfigUpdateOutcome> {
+    let local_conf_dir = fetcher.config_vault.join(LOCAL_CONFIG_FOLDER_NAME);
+    if local_conf_dir.exists() {
+        return Ok(ConfigUpdateOutcome::UpToDate);
+    }
+    println_warning(&format!(
+        "Local node configuration files are missing at {}",
+        local_conf_dir.display()
+    ));
+    if unpack_embedded_local_config(&local_conf_dir)? {
+        println_action_green(
+            "Unpacked",
+            &format!(
+                "the default local network configuration to {}",
+                local_conf_dir.display()
+            ),
+        );
+        return Ok(ConfigUpdateOutcome::MissingLocalCreated);
+    }
+    // Ask user if they want to update the chain config. Nothing is
+    // overwritten, so the files are downloaded if nobody can answer.
+    let update = policy.confirm(
+        "Would you like to download network configuration?",
+        ConfigUpdatePolicy::Accept,
+    )?;
+    if !update {
+        bail!(
+            "Missing local network configuration, create one at {}",
+            local_conf_dir.display()
+        );
+    }
+    fetcher.download_config(&ChainConfig::Local).await?;
+    Ok(ConfigUpdateOutcome::MissingLocalCreated)
+}
+
+/// Testnet and mainnet chain configurations are validated against the remote
+/// versions from github. If local files exists for these configurations, hash
+/// values are collected from remote, and compared to find out if there any
+/// changes introduced in remote. If there is the chain configuration is
+/// fetched again to ensure, the bootstrapped node can sync with the rest of
+/// the network without any issues related to a different chain configuration.
+async fn validate_remote_chainconfig(
+    fetcher: &ConfigFetcher,
+    conf: &ChainConfig,
+    options: ConfigUpdateOptions,
+) -> anyhow::Result<ConfigUpdateOutcome> {
+    let policy = options.policy;
+    // For testnet and mainnet configs, we need to check online.
+    println_action_green("Checking", "for network configuration updates.");
+    if let Some(update_info) = read_update_info(&fetcher.config_dir(conf)) {
+        println_action_green(
+            &format!("{conf}"),
+            &format!(
+                "config last updated {} from {}",
+                format_relative_time(update_info.updated_at, Utc::now()),
+                update_info.origin()
+            ),
+        );
+    }
+
+    let changes = fetcher.check_changes(conf).await?;
+    if changes.is_empty() {
+        println_action_green(&format!("{conf}"), "is up-to-date.");
+        return Ok(ConfigUpdateOutcome::UpToDate);
+    }
+    println_warning(&format!(
+        "A network configuration update detected for {}, this might create problems while syncing with rest of the network",
+        conf
+    ));
+    print_changes(fetcher, conf, &changes, options.verbose).await;
+    // Ask user if they want to update the chain config. The current
+    // files are overwritten, so nobody answering is an error.
+    let update = policy.confirm(
+        "Would you like to update network configuration? A backup of the current files will be kept",
+        ConfigUpdatePolicy::Fail,
+    )?;
+    if !update {
+        if policy == ConfigUpdatePolicy::Skip {
+            println_warning(&format!(
+                "Skipping the network configuration update for {conf}, proceeding with the existing configuration"
+            ));
+        }
+        return Ok(ConfigUpdateOutcome::SkippedByUser);
+    }
+
+    let missing = !fetcher.config_dir(conf).exists();
+    println_action_green("Updating", &format!("configuration files for {conf}",));
+    fetcher.download_config(conf).await?;
+    println_action_green(
+        "Finished",
+        &format!("updating configuration files for {conf}",),
+    );
+    if missing {
+        return Ok(ConfigUpdateOutcome::MissingLocalCreated);
+    }
+    let files_changed = changes
+        .added
+        .into_iter()
+        .chain(changes.removed)
+        .chain(changes.modified)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    Ok(ConfigUpdateOutcome::Updated { files_changed })
+}
+
+/// Prints the files changed in `changes`. If `verbose`, the modified JSON
+/// files are downloaded to show which of their top-level keys changed.
+async fn print_changes(
+    fetcher: &ConfigFetcher,
+    conf: &ChainConfig,
+    changes: &ConfigChangeSet,
+    verbose: bool,
+) {
+    for name in &changes.added {
+        println_action_green("Added", name);
+    }
+    for name in &changes.removed {
+        println_action_red("Removed", name);
+    }
+    for name in &changes.modified {
+        if !verbose || !name.ends_with(".json") {
+            println_action_yellow("Modified", name);
+            continue;
+        }
+        match fetcher.changed_json_keys(conf, changes, name).await {
+            Ok(keys) if !keys.is_empty() => println_action_yellow(
+                "Modified",
+                &format!("{name} (changed keys: {})", keys.join(", ")),
+            ),
+            Ok(_) => println_action_yellow("Modified", name),
+            Err(err) => println_action_yellow(
+                "Modified",
+                &format!("{name} (failed to compare keys: {err})"),
+            ),
+        }
+    }
+}
+
+/// Checks only that the configuration of `conf` exists, without any network
+/// access or questions. A missing local configuration is still unpacked from
+/// the one embedded into forc-node.
+fn check_config_exists(fetcher: &ConfigFetcher, conf: &ChainConfig) -> Result<ConfigUpdateOutcome> {
+    let config_dir = fetcher.config_dir(conf);
+    if config_dir.exists() {
+        println_warning(&format!(
+            "Skipping the network configuration check for {conf}"
+        ));
+        return Ok(ConfigUpdateOutcome::CheckSkipped);
+    }
+    if matches!(conf, ChainConfig::Local)
+        && unpack_embedded_local_config(&fetcher.network_dir(conf))?
+    {
+        return Ok(ConfigUpdateOutcome::MissingLocalCreated);
+    }
+    bail!(
+        "Missing {conf} network configuration at {}, run without `--skip-config-check` to download it",
+        config_dir.display()
+    )
+}
+
+/// Check local state of the configuration file in the vault (if they exists)
+/// and compare them to the remote one in github. If a change is detected,
+/// decides according to the `options` whether to update, and does the update.
+///
+/// Errors are reported as [ConfigUpdateOutcome::Failed], so that the outcome
+/// can always be reported to the caller.
+pub async fn check_and_update_chain_config(
+    conf: ChainConfig,
+    options: ConfigUpdateOptions,
+) -> ConfigUpdateOutcome {
+    let fetcher = ConfigFetcher::default()
+        .with_config_repo(options.config_repo.clone())
+        .with_hash_verification(options.verify)
+        .with_fuel_core_compatibility(options.fuel_core_compatibility);
+    check_and_update_with(&fetcher, conf, options).await
+}
+
+/// Checks and updates the configuration of `conf` with the `fetcher`, see
+/// [check_and_update_chain_config].
+async fn check_and_update_with(
+    fetcher: &ConfigFetcher,
+    conf: ChainConfig,
+    options: ConfigUpdateOptions,
+) -> ConfigUpdateOutcome {
+    let outcome = match conf {
+        _ if options.skip_check => check_config_exists(fetcher, &conf),
+        ChainConfig::Local => validate_local_chainconfig(fetcher, options.policy).await,
+        // Files from a local directory are copied on every start, so there is
+        // nothing to update.
+        ChainConfig::Custom {
+            source: CustomSource::Directory(_),
+            ..
+        } => fetcher
+            .download_config(&conf)
+            .await
+            .map(|()| ConfigUpdateOutcome::UpToDate),
+        remote_config => validate_remote_chainconfig(fetcher, &remote_config, options).await,
+    };
+    outcome.unwrap_or_else(|err| ConfigUpdateOutcome::Failed {
+        reason: format!("{err:#}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_config::test_utils::{
+        create_github_response, mount_testnet_listing, CHAIN_CONFIG, NEW_CHAIN_CONFIG, STATE_CONFIG,
+    };
+    use std::time::Duration;
+    use tempfile::TempDir;
+    use wiremock::{
+        matchers::{any, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[cfg(feature = "embedded-local-config")]
+    #[tokio::test]
+    async fn test_missing_local_configuration_is_unpacked_offline() {
+        let test_dir = TempDir::new().unwrap();
+        // Nothing listens on port 0, so any request would fail.
+        let fetcher = ConfigFetcher::with_test_config(
+            "http://127.0.0.1:0".to_string(),
+            test_dir.path().to_path_buf(),
+        );
+
+        // The embedded configuration is unpacked without asking.
+        let outcome = validate_local_chainconfig(&fetcher, ConfigUpdatePolicy::Fail)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, ConfigUpdateOutcome::MissingLocalCreated);
+        let config_dir = fetcher.validate_config(&ChainConfig::Local).unwrap();
+        for name in ["chain_config.json", "metadata.json", "state_config.json"] {
+            assert!(config_dir.join(name).is_file(), "Missing {name}");
+        }
+        assert_eq!(fetcher.list_versions(&ChainConfig::Local).unwrap().len(), 1);
+
+        // The unpacked configuration is used from then on.
+        let outcome = validate_local_chainconfig(&fetcher, ConfigUpdatePolicy::Fail)
+            .await
+            .unwrap();
+        assert_eq!(outcome, ConfigUpdateOutcome::UpToDate);
+    }
+
+    #[cfg(not(feature = "embedded-local-config"))]
+    #[tokio::test]
+    async fn test_missing_local_configuration_fails_fast_when_skipping_updates() {
+        let test_dir = TempDir::new().unwrap();
+        let fetcher = ConfigFetcher::with_test_config(
+            "http://127.0.0.1:0".to_string(),
+            test_dir.path().to_path_buf(),
+        );
+
+        let err = validate_local_chainconfig(&fetcher, ConfigUpdatePolicy::Skip)
+            .await
+            .unwrap_err();
+
+        assert!(
+            err.to_string()
+                .starts_with("Missing local network configuration"),
+            "Unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outdated_remote_configuration_is_kept_when_skipping_updates() {
+        let mock_server = MockServer::start().await;
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("config.json"), "old config content").unwrap();
+
+        let github_response = create_github_response(&[("config.json", "new config content")]);
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+
+        let options = ConfigUpdateOptions {
+            policy: ConfigUpdatePolicy::Skip,
+            verbose: false,
+            verify: false,
+            fuel_core_compatibility: FuelCoreCompatibility::Skip,
+            config_repo: ConfigRepository::default(),
+            skip_check: false,
+        };
+        let outcome = validate_remote_chainconfig(&fetcher, &ChainConfig::Testnet, options)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, ConfigUpdateOutcome::SkippedByUser);
+        assert_eq!(
+            fs::read_to_string(test_folder.join("config.json")).unwrap(),
+            "old config content"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outdated_remote_configuration_is_replaced_when_accepting_updates() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[
+                ("chain_config.json", NEW_CHAIN_CONFIG),
+                ("state_config.json", STATE_CONFIG),
+            ],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        fs::write(test_folder.join("state_config.json"), STATE_CONFIG).unwrap();
+        for (name, content) in [
+            ("chain_config.json", NEW_CHAIN_CONFIG),
+            ("state_config.json", STATE_CONFIG),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/raw/{name}")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(content))
+                .mount(&mock_server)
+                .await;
+        }
+
+        // `--yes` and `--non-interactive accept` never ask, so the outdated
+        // configuration is replaced even though nobody could answer.
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        let options = ConfigUpdateOptions {
+            policy: ConfigUpdatePolicy::Accept,
+            verbose: false,
+            verify: false,
+            fuel_core_compatibility: FuelCoreCompatibility::Skip,
+            config_repo: ConfigRepository::default(),
+            skip_check: false,
+        };
+        let outcome = validate_remote_chainconfig(&fetcher, &ChainConfig::Testnet, options)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            ConfigUpdateOutcome::Updated {
+                files_changed: vec!["chain_config.json".to_string()]
+            }
+        );
+        assert_eq!(
+            fs::read_to_string(
+                fetcher
+                    .config_dir(&ChainConfig::Testnet)
+                    .join("chain_config.json")
+            )
+            .unwrap(),
+            NEW_CHAIN_CONFIG
+        );
+    }
+
+    #[tokio::test]
+    async fn test_accepted_update_reports_the_changed_files() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[
+                ("chain_config.json", NEW_CHAIN_CONFIG),
+                ("state_config.json", STATE_CONFIG),
+            ],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        fs::write(test_folder.join("removed.json"), "removed").unwrap();
+        for (name, content) in [
+            ("chain_config.json", NEW_CHAIN_CONFIG),
+            ("state_config.json", STATE_CONFIG),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/raw/{name}")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(content))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+        let options = ConfigUpdateOptions {
+            policy: ConfigUpdatePolicy::Accept,
+            verbose: false,
+            verify: false,
+            fuel_core_compatibility: FuelCoreCompatibility::Skip,
+            config_repo: ConfigRepository::default(),
+            skip_check: false,
+        };
+        let outcome = validate_remote_chainconfig(&fetcher, &ChainConfig::Testnet, options)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            ConfigUpdateOutcome::Updated {
+                files_changed: vec![
+                    "chain_config.json".to_string(),
+                    "removed.json".to_string(),
+                    "state_config.json".to_string(),
+                ]
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&outcome).unwrap(),
+            serde_json::json!({
+                "status": "updated",
+                "files_changed": ["chain_config.json", "removed.json", "state_config.json"],
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(ConfigUpdateOutcome::SkippedByUser).unwrap(),
+            serde_json::json!({ "status": "skipped_by_user" })
+        );
+    }
+
+    #[test]
+    fn test_explicit_update_policy_is_kept() {
+        for policy in [
+            ConfigUpdatePolicy::Accept,
+            ConfigUpdatePolicy::Skip,
+            ConfigUpdatePolicy::Fail,
+        ] {
+            assert_eq!(ConfigUpdatePolicy::resolve(Some(policy)), policy);
+        }
+        assert_eq!(
+            ConfigUpdatePolicy::resolve(None),
+            ConfigUpdatePolicy::Prompt
+        );
+    }
+
+    #[test]
+    fn test_unattended_questions_are_answered_by_their_default() {
+        let question = "Would you like to download network configuration?";
+        let prompt = ConfigUpdatePolicy::Prompt;
+        assert!(prompt
+            .confirm_with(question, ConfigUpdatePolicy::Accept, false)
+            .unwrap());
+        assert!(!prompt
+            .confirm_with(question, ConfigUpdatePolicy::Skip, false)
+            .unwrap());
+        let err = prompt
+            .confirm_with(question, ConfigUpdatePolicy::Fail, false)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains(question),
+            "Unexpected error: {err}"
+        );
+
+        // An explicit policy is used even if stdin is not a terminal.
+        assert!(!ConfigUpdatePolicy::Skip
+            .confirm_with(question, ConfigUpdatePolicy::Accept, false)
+            .unwrap());
+        assert!(ConfigUpdatePolicy::Fail
+            .confirm_with(question, ConfigUpdatePolicy::Accept, true)
+            .is_err());
+    }
+
+    #[test]
+    fn test_custom_chain_configs_are_parsed_from_their_sources() {
+        assert_eq!(
+            ChainConfig::from_str("github:acme/networks/configs/staging").unwrap(),
+            ChainConfig::Custom {
+                name: "staging".to_string(),
+                source: CustomSource::Github {
+                    owner: "acme".to_string(),
+                    repo: "networks".to_string(),
+                    path: "configs/staging".to_string(),
+                },
+            }
+        );
+        assert_eq!(
+            ChainConfig::from_str("https://example.com/staging.tar.gz").unwrap(),
+            ChainConfig::Custom {
+                name: "staging".to_string(),
+                source: CustomSource::Tarball("https://example.com/staging.tar.gz".to_string()),
+            }
+        );
+
+        let test_dir = TempDir::new().unwrap();
+        let config_dir = test_dir.path().join("staging");
+        fs::create_dir_all(&config_dir).unwrap();
+        assert_eq!(
+            ChainConfig::from_str(config_dir.to_str().unwrap()).unwrap(),
+            ChainConfig::Custom {
+                name: "staging".to_string(),
+                source: CustomSource::Directory(config_dir.canonicalize().unwrap()),
+            }
+        );
+
+        assert_eq!(
+            ChainConfig::from_str("testnet").unwrap(),
+            ChainConfig::Testnet
+        );
+        assert!(ChainConfig::from_str("github:acme/networks").is_err());
+    }
+
+    #[test]
+    fn test_custom_config_names_take_precedence_over_directories() {
+        // Tests run in the package root, which has a `src` directory.
+        assert!(Path::new("src").is_dir());
+        let vault_dir = TempDir::new().unwrap();
+        let source = CustomSource::Tarball("https://example.com/src.tar.gz".to_string());
+        let custom_dir = vault_dir.path().join(CUSTOM_CONFIG_FOLDER_NAME).join("src");
+        fs::create_dir_all(&custom_dir).unwrap();
+        fs::write(
+            custom_dir.join(SOURCE_FILE_NAME),
+            serde_json::to_vec(&StoredSource {
+                source: source.clone(),
+                etag: None,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            ChainConfig::parse_in_vault("src", vault_dir.path()).unwrap(),
+            ChainConfig::Custom {
+                name: "src".to_string(),
+                source,
+            }
+        );
+        assert_eq!(
+            ChainConfig::parse_in_vault("./src", vault_dir.path()).unwrap(),
+            ChainConfig::Custom {
+                name: "src".to_string(),
+                source: CustomSource::Directory(Path::new("src").canonicalize().unwrap()),
+            }
+        );
+        assert!(ChainConfig::parse_in_vault("unknown", vault_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_changed_top_level_keys_of_non_objects_are_empty() {
+        let object = serde_json::json!({ "a": 1 });
+        let array = serde_json::json!([1, 2]);
+        assert!(changed_top_level_keys(&object, &array).is_empty());
+        assert!(changed_top_level_keys(&array, &array).is_empty());
+        assert_eq!(
+            changed_top_level_keys(&object, &serde_json::json!({ "a": { "nested": 1 } })),
+            vec!["a"]
+        );
+    }
+
+    #[test]
+    fn test_configuration_on_disk_is_validated() {
+        let test_dir = TempDir::new().unwrap();
+        let fetcher = ConfigFetcher::with_test_config(
+            "http://127.0.0.1:0".to_string(),
+            test_dir.path().to_path_buf(),
+        );
+        let err = fetcher.validate_config(&ChainConfig::Testnet).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Missing testnet network configuration"));
+
+        let test_folder = test_dir.path().join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(test_folder.join("snapshots")).unwrap();
+        fs::write(
+            test_folder.join("chain_config.json"),
+            r#"{"chain_name": "chain"}"#,
+        )
+        .unwrap();
+        fs::write(test_folder.join("notes.txt"), "not json").unwrap();
+        let err = fetcher.validate_config(&ChainConfig::Testnet).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration file chain_config.json: missing field `consensus_parameters`"
+        );
+
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        fs::write(test_folder.join("snapshots").join("state_config.json"), "[").unwrap();
+        let err = fetcher.validate_config(&ChainConfig::Testnet).unwrap_err();
+        assert!(err.to_string().starts_with(&format!(
+            "Invalid configuration file {}:",
+            Path::new("snapshots").join("state_config.json").display()
+        )));
+
+        fs::write(
+            test_folder.join("snapshots").join("state_config.json"),
+            STATE_CONFIG,
+        )
+        .unwrap();
+        assert_eq!(
+            fetcher.validate_config(&ChainConfig::Testnet).unwrap(),
+            test_folder
+        );
+    }
+
+    #[test]
+    fn test_devnet_is_a_built_in_chain_config() {
+        let devnet = ChainConfig::from_str("devnet").unwrap();
+        assert_eq!(devnet, ChainConfig::Devnet);
+        assert_eq!(devnet.to_string(), "devnet");
+        assert_eq!(devnet.folder(), PathBuf::from(DEVNET_CONFIG_FOLDER_NAME));
+    }
+
+    #[test]
+    fn test_fuel_core_requirements_are_parsed() {
+        let minimum = parse_fuel_core_requirement("0.41.0\n").unwrap();
+        assert!(minimum.matches(&Version::new(0, 41, 0)));
+        assert!(minimum.matches(&Version::new(0, 43, 2)));
+        assert!(!minimum.matches(&Version::new(0, 40, 0)));
+
+        let range = parse_fuel_core_requirement(">=0.41, <0.43").unwrap();
+        assert!(range.matches(&Version::new(0, 42, 1)));
+        assert!(!range.matches(&Version::new(0, 43, 0)));
+
+        assert!(parse_fuel_core_requirement("latest").is_err());
+    }
+
+    #[test]
+    fn test_fuel_core_compatibility_is_checked_against_the_installed_version() {
+        let test_dir = TempDir::new().unwrap();
+        let installed = || Ok(Version::new(0, 40, 0));
+
+        // Configurations without a requirement work with any version.
+        check_fuel_core_compatibility(
+            &ChainConfig::Testnet,
+            test_dir.path(),
+            FuelCoreCompatibility::Refuse,
+            installed,
+        )
+        .unwrap();
+
+        fs::write(test_dir.path().join(FUEL_CORE_VERSION_FILE_NAME), "0.41.0").unwrap();
+        let err = check_fuel_core_compatibility(
+            &ChainConfig::Testnet,
+            test_dir.path(),
+            FuelCoreCompatibility::Refuse,
+            installed,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("requires fuel-core >=0.41.0, but fuel-core 0.40.0 is installed"),
+            "Unexpected error: {err}"
+        );
+        check_fuel_core_compatibility(
+            &ChainConfig::Testnet,
+            test_dir.path(),
+            FuelCoreCompatibility::Warn,
+            installed,
+        )
+        .unwrap();
+        check_fuel_core_compatibility(
+            &ChainConfig::Testnet,
+            test_dir.path(),
+            FuelCoreCompatibility::Skip,
+            || panic!("the installed version should not be detected"),
+        )
+        .unwrap();
+        check_fuel_core_compatibility(
+            &ChainConfig::Testnet,
+            test_dir.path(),
+            FuelCoreCompatibility::Refuse,
+            || Ok(Version::new(0, 41, 0)),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_skipped_config_check_does_not_send_requests() {
+        let mock_server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        let options = ConfigUpdateOptions {
+            policy: ConfigUpdatePolicy::Fail,
+            verbose: false,
+            verify: false,
+            fuel_core_compatibility: FuelCoreCompatibility::Skip,
+            config_repo: ConfigRepository::default(),
+            skip_check: true,
+        };
+
+        // A missing configuration is still caught.
+        let outcome = check_and_update_with(&fetcher, ChainConfig::Testnet, options.clone()).await;
+        let ConfigUpdateOutcome::Failed { reason } = outcome else {
+            panic!("Unexpected outcome: {outcome:?}");
+        };
+        assert!(
+            reason.starts_with("Missing testnet network configuration"),
+            "Unexpected reason: {reason}"
+        );
+
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), "outdated").unwrap();
+        let outcome = check_and_update_with(&fetcher, ChainConfig::Testnet, options).await;
+        assert_eq!(outcome, ConfigUpdateOutcome::CheckSkipped);
+        assert_eq!(
+            fs::read_to_string(test_folder.join("chain_config.json")).unwrap(),
+            "outdated"
+        );
+    }
+
+    #[test]
+    fn test_relative_time_is_formatted_in_the_largest_unit() {
+        let now = Utc::now();
+        for (elapsed, expected) in [
+            (chrono::Duration::seconds(-5), "just now"),
+            (chrono::Duration::seconds(59), "just now"),
+            (chrono::Duration::minutes(1), "1 minute ago"),
+            (chrono::Duration::minutes(59), "59 minutes ago"),
+            (chrono::Duration::hours(5), "5 hours ago"),
+            (chrono::Duration::days(1), "1 day ago"),
+            (chrono::Duration::days(40), "40 days ago"),
+        ] {
+            assert_eq!(format_relative_time(now - elapsed, now), expected);
+        }
+    }
+}
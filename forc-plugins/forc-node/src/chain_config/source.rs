@@ -0,0 +1,1264 @@
+use super::manifest::parse_checksums;
+use crate::consts::{
+    GITHUB_API_URL, GITHUB_CONTENTS_LIMIT, GITHUB_RAW_URL, GITHUB_TOKEN_ENV_VARS,
+    LISTING_CACHE_FILE_NAME, USER_AGENT_SUFFIX_ENV_VAR,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Returns the github api `url` for the contents at `git_ref`, or for the
+/// contents of the default branch if there is no `git_ref`.
+fn with_git_ref(url: &str, git_ref: Option<&str>) -> String {
+    match git_ref {
+        Some(git_ref) => format!("{url}?ref={git_ref}"),
+        None => url.to_string(),
+    }
+}
+
+/// A github api, content query response.
+/// Mainly used for fetching a download url and hash for configuration files.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct GithubContentDetails {
+    pub(crate) name: String,
+    pub(crate) sha: String,
+    /// Missing from the listings cached by older versions.
+    #[serde(default)]
+    pub(crate) size: u64,
+    pub(crate) download_url: Option<String>,
+    #[serde(rename = "type")]
+    pub(crate) content_type: String,
+}
+
+/// A github git trees api response.
+/// Used to list the folders that are too large for the content api.
+#[derive(Deserialize, Debug)]
+struct GithubTree {
+    tree: Vec<GithubTreeEntry>,
+    /// Whether the tree has more entries than github returns in a response.
+    truncated: bool,
+}
+
+/// An entry of a [GithubTree]. Its path is relative to the listed tree.
+#[derive(Deserialize, Debug)]
+struct GithubTreeEntry {
+    path: String,
+    sha: String,
+    /// Missing for the entries that are not blobs.
+    #[serde(default)]
+    size: u64,
+    #[serde(rename = "type")]
+    entry_type: String,
+}
+
+/// A github content listing together with the ETag of the response it came
+/// from. Persisted in the chain configuration folder, so that the listing is
+/// only downloaded again if it changed.
+#[derive(Serialize, Deserialize, Debug)]
+struct CachedListing {
+    etag: String,
+    /// The git ref the listing is for, or `None` for the default branch.
+    git_ref: Option<String>,
+    contents: Vec<GithubContentDetails>,
+}
+
+/// A folder of a git repository that holds the files of a chain configuration.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RemoteFolder {
+    pub owner: String,
+    pub repo: String,
+    /// The path of the folder within the repository.
+    pub path: String,
+    /// The git ref of the files, or `None` for the default branch.
+    pub git_ref: Option<String>,
+}
+
+/// A file of a [RemoteFolder].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RemoteFile {
+    /// The path of the file relative to the folder, e.g.,
+    /// `snapshots/state_config.json`.
+    pub name: String,
+    /// The git blob SHA1 of the contents of the file.
+    pub sha: String,
+    /// The size of the file in bytes, or 0 if it is not known.
+    pub size: u64,
+    pub download_url: String,
+}
+
+/// A file listed in the [CHECKSUMS_FILE_NAME] manifest of a [RemoteFolder].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ChecksummedFile {
+    /// The path of the file relative to the folder.
+    pub name: String,
+    /// The hex encoded SHA256 of the contents of the file.
+    pub sha256: String,
+    pub download_url: String,
+}
+
+/// Lists and downloads the files of the chain configurations hosted in git
+/// repositories. [GithubSource] is used by default, other sources can serve
+/// the files from a mirror.
+#[async_trait]
+pub trait ConfigSource: Send + Sync {
+    /// Lists the files of `folder`, including the files of nested folders.
+    ///
+    /// `config_dir` is the local folder of the configuration, which might not
+    /// exist yet. A source can use it to cache the listing.
+    async fn list_folder(
+        &self,
+        folder: &RemoteFolder,
+        config_dir: &Path,
+    ) -> Result<Vec<RemoteFile>>;
+
+    /// Downloads `file`, writing its contents to `writer`.
+    async fn download_file(&self, file: &RemoteFile, writer: &mut (dyn Write + Send))
+        -> Result<()>;
+
+    /// Downloads the [CHECKSUMS_FILE_NAME] manifest of `folder`, so that the
+    /// folder can be checked for updates without listing it. Returns `None`
+    /// if the folder has no manifest, or the source does not support them.
+    async fn fetch_checksums(
+        &self,
+        _folder: &RemoteFolder,
+    ) -> Result<Option<Vec<ChecksummedFile>>> {
+        Ok(None)
+    }
+}
+
+/// How requests that fail with a connection error or a server error are
+/// retried. Requests that fail with a client error are never retried.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RetryPolicy {
+    /// The number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled for each further retry.
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Returns the user agent of the http requests, e.g.,
+/// `forc-node/0.66.0 (x86_64-unknown-linux-gnu)`, followed by `suffix`.
+fn user_agent_with(suffix: Option<&str>) -> String {
+    let user_agent = format!(
+        "forc-node/{} ({})",
+        env!("CARGO_PKG_VERSION"),
+        env!("TARGET")
+    );
+    match suffix.map(str::trim).filter(|suffix| !suffix.is_empty()) {
+        Some(suffix) => format!("{user_agent} {suffix}"),
+        None => user_agent,
+    }
+}
+
+/// Returns the user agent of the http requests, followed by the suffix in
+/// [USER_AGENT_SUFFIX_ENV_VAR], if it is set.
+pub(crate) fn user_agent() -> String {
+    user_agent_with(std::env::var(USER_AGENT_SUFFIX_ENV_VAR).ok().as_deref())
+}
+
+/// A http client that retries the requests according to its [RetryPolicy].
+/// All the requests are sent with the forc-node [user_agent].
+#[derive(Clone, Debug)]
+pub(crate) struct HttpClient {
+    client: reqwest::Client,
+    pub(crate) retry_policy: RetryPolicy,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .user_agent(user_agent())
+                .build()
+                .expect("failed to create the http client"),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
+
+impl HttpClient {
+    pub(crate) fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub(crate) fn head(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.head(url)
+    }
+
+    /// Sends the idempotent `request`, retrying it according to the
+    /// [RetryPolicy] if it fails with a connection error or a server error.
+    /// If all the attempts fail, the error includes the number of attempts.
+    pub(crate) async fn send_with_retry(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut backoff = self.retry_policy.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let Some(attempt_request) = request.try_clone() else {
+                bail!("failed to create a retryable request");
+            };
+            let error = match attempt_request.send().await {
+                Ok(response) if !response.status().is_server_error() => return Ok(response),
+                Ok(response) => anyhow!("{} responded with {}", response.url(), response.status()),
+                Err(err) if err.is_connect() || err.is_timeout() => err.into(),
+                Err(err) => return Err(err.into()),
+            };
+            if attempt >= self.retry_policy.max_attempts {
+                return Err(error.context(format!("request failed after {attempt} attempt(s)")));
+            }
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+            attempt += 1;
+        }
+    }
+}
+
+/// Returns the url of the next page of a paginated github api `response`,
+/// advertised in its `Link` header, if there is one.
+fn next_page_url(response: &reqwest::Response) -> Option<String> {
+    let link = response
+        .headers()
+        .get(reqwest::header::LINK)?
+        .to_str()
+        .ok()?;
+    link.split(',').find_map(|link| {
+        let (url, params) = link.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+/// Returns true if the github content listing `contents` of a folder is
+/// possibly missing some of its entries.
+fn is_truncated(contents: &[GithubContentDetails]) -> bool {
+    contents.len() >= GITHUB_CONTENTS_LIMIT
+}
+
+/// Returns the first non-empty github token found in the [GITHUB_TOKEN_ENV_VARS].
+fn github_token_from_env() -> Option<String> {
+    GITHUB_TOKEN_ENV_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|token| !token.trim().is_empty())
+}
+
+/// Creates the error for an unsuccessful github api `response`. If the
+/// response is caused by github's rate limiting, the error tells the user when
+/// to retry and how to avoid the limit. Otherwise, `message` is used.
+fn github_error(response: &reqwest::Response, message: &str) -> anyhow::Error {
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    let status = response.status();
+    let is_rate_limited = (status == reqwest::StatusCode::FORBIDDEN
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS)
+        && header("x-ratelimit-remaining") == Some("0");
+    if !is_rate_limited {
+        return anyhow!("{message}");
+    }
+
+    let retry = match header("x-ratelimit-reset").and_then(|reset| reset.parse::<u64>().ok()) {
+        Some(reset) => {
+            let reset = UNIX_EPOCH + Duration::from_secs(reset);
+            let wait = reset
+                .duration_since(SystemTime::now())
+                .unwrap_or_default()
+                .as_secs();
+            format!("retry in {} minute(s)", wait.div_ceil(60))
+        }
+        None => "retry later".to_string(),
+    };
+    anyhow!(
+        "github api rate limit exceeded, {retry} or set {} to a github token to make authorized requests",
+        GITHUB_TOKEN_ENV_VARS.join(" or ")
+    )
+}
+
+/// A [ConfigSource] that lists the files with the github content api.
+///
+/// If one of the [GITHUB_TOKEN_ENV_VARS] is set, its value is sent as a
+/// bearer token with the github api requests.
+#[derive(Clone)]
+pub struct GithubSource {
+    http: HttpClient,
+    base_url: String,
+    raw_url: String,
+    github_token: Option<String>,
+}
+
+impl Default for GithubSource {
+    fn default() -> Self {
+        Self {
+            http: HttpClient::default(),
+            base_url: GITHUB_API_URL.to_string(),
+            raw_url: GITHUB_RAW_URL.to_string(),
+            github_token: github_token_from_env(),
+        }
+    }
+}
+
+impl GithubSource {
+    /// Creates a source that uses the github api at [GITHUB_API_URL].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses the github api at `base_url`, e.g., a mirror of it.
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..self
+        }
+    }
+
+    /// Downloads the files of the folders that are too large for the github
+    /// content api from `raw_url`, instead of [GITHUB_RAW_URL].
+    pub fn with_raw_url(self, raw_url: impl Into<String>) -> Self {
+        Self {
+            raw_url: raw_url.into(),
+            ..self
+        }
+    }
+
+    /// Authorizes the github api requests with `github_token`, instead of the
+    /// token found in the [GITHUB_TOKEN_ENV_VARS].
+    pub fn with_github_token(self, github_token: Option<String>) -> Self {
+        Self {
+            github_token,
+            ..self
+        }
+    }
+
+    /// Retries the github api requests and the downloads according to
+    /// `retry_policy`.
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            http: HttpClient {
+                retry_policy,
+                ..self.http
+            },
+            ..self
+        }
+    }
+
+    /// Creates a GET request to the github api, authorized with the github
+    /// token, if there is one.
+    fn api_get(&self, url: &str) -> reqwest::RequestBuilder {
+        let request = self.http.get(url);
+        match &self.github_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Returns the github api endpoint that lists the files of `folder`.
+    fn api_endpoint(&self, folder: &RemoteFolder) -> String {
+        format!(
+            "{}/repos/{}/{}/contents/{}",
+            self.base_url, folder.owner, folder.repo, folder.path
+        )
+    }
+
+    /// Helper function to fetch folder contents from github.
+    async fn fetch_folder_contents(&self, url: &str) -> anyhow::Result<Vec<GithubContentDetails>> {
+        let response = self.http.send_with_retry(self.api_get(url)).await?;
+
+        if !response.status().is_success() {
+            return Err(github_error(
+                &response,
+                "failed to fetch contents from github",
+            ));
+        }
+
+        self.read_listing_pages(response).await
+    }
+
+    /// Reads the github listing in `response`, followed by the rest of its
+    /// pages, if the listing is paginated.
+    async fn read_listing_pages(
+        &self,
+        response: reqwest::Response,
+    ) -> anyhow::Result<Vec<GithubContentDetails>> {
+        let mut next_page = next_page_url(&response);
+        let mut contents: Vec<GithubContentDetails> = response.json().await?;
+        while let Some(url) = next_page {
+            let response = self.http.send_with_retry(self.api_get(&url)).await?;
+            if !response.status().is_success() {
+                return Err(github_error(
+                    &response,
+                    "failed to fetch contents from github",
+                ));
+            }
+            next_page = next_page_url(&response);
+            contents.extend(response.json::<Vec<GithubContentDetails>>().await?);
+        }
+        Ok(contents)
+    }
+
+    /// Replaces the folders in the github listing `contents` of `api_endpoint`
+    /// at `git_ref` with the files within them, recursively. The files are named
+    /// by their path relative to `api_endpoint`, e.g., `snapshots/state_config.json`.
+    ///
+    /// Returns `None` if the listing of one of the folders is truncated.
+    async fn expand_github_dirs(
+        &self,
+        api_endpoint: &str,
+        git_ref: Option<&str>,
+        contents: Vec<GithubContentDetails>,
+    ) -> anyhow::Result<Option<Vec<GithubContentDetails>>> {
+        let mut expanded = vec![];
+        let mut pending = vec![(String::new(), contents)];
+        while let Some((prefix, contents)) = pending.pop() {
+            if is_truncated(&contents) {
+                return Ok(None);
+            }
+            for mut item in contents {
+                let path = format!("{prefix}{}", item.name);
+                if item.content_type == "dir" {
+                    let dir_contents = self
+                        .fetch_folder_contents(&with_git_ref(
+                            &format!("{api_endpoint}/{path}"),
+                            git_ref,
+                        ))
+                        .await?;
+                    pending.push((format!("{path}/"), dir_contents));
+                } else {
+                    item.name = path;
+                    expanded.push(item);
+                }
+            }
+        }
+        Ok(Some(expanded))
+    }
+
+    /// Lists the files of `folder`, including the ones in nested folders,
+    /// with the git trees api, which is not limited to
+    /// [GITHUB_CONTENTS_LIMIT] entries per folder.
+    async fn fetch_tree_listing(
+        &self,
+        folder: &RemoteFolder,
+    ) -> anyhow::Result<Vec<GithubContentDetails>> {
+        let git_ref = folder.git_ref.as_deref().unwrap_or("HEAD");
+        let url = format!(
+            "{}/repos/{}/{}/git/trees/{git_ref}:{}?recursive=1",
+            self.base_url, folder.owner, folder.repo, folder.path
+        );
+        let response = self.http.send_with_retry(self.api_get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(github_error(
+                &response,
+                "failed to fetch the file tree from github",
+            ));
+        }
+
+        let tree: GithubTree = response.json().await?;
+        // Comparing against a partial listing would report the missing
+        // files as removed upstream.
+        if tree.truncated {
+            bail!(
+                "{} has too many files to be listed with the github api",
+                folder.path
+            );
+        }
+        Ok(tree
+            .tree
+            .into_iter()
+            .filter(|entry| entry.entry_type == "blob")
+            .map(|entry| GithubContentDetails {
+                download_url: Some(format!(
+                    "{}/{}/{}/{git_ref}/{}/{}",
+                    self.raw_url, folder.owner, folder.repo, folder.path, entry.path
+                )),
+                name: entry.path,
+                sha: entry.sha,
+                size: entry.size,
+                content_type: "file".to_string(),
+            })
+            .collect())
+    }
+
+    /// Fetches the github listing of `folder`, expanding the nested folders.
+    /// All the pages of paginated listings are fetched, and folders with
+    /// truncated listings are listed with the git trees api instead.
+    ///
+    /// The listing is cached with its ETag in `config_dir`. If github reports
+    /// that the listing did not change, the cached one is used.
+    async fn fetch_listing(
+        &self,
+        folder: &RemoteFolder,
+        config_dir: &Path,
+    ) -> anyhow::Result<Vec<GithubContentDetails>> {
+        let api_endpoint = self.api_endpoint(folder);
+        let cache_path = config_dir.join(LISTING_CACHE_FILE_NAME);
+        let git_ref = folder.git_ref.clone();
+        // A missing or invalid cache is ignored, and the listing is fetched again.
+        // So is a cache with unexpanded folders, written by an older version,
+        // and a cache for another git ref.
+        let cached_listing = fs::read(&cache_path)
+            .ok()
+            .and_then(|cache| serde_json::from_slice::<CachedListing>(&cache).ok())
+            .filter(|cache| cache.contents.iter().all(|item| item.content_type != "dir"))
+            .filter(|cache| cache.git_ref == git_ref);
+
+        let mut request = self.api_get(&with_git_ref(&api_endpoint, git_ref.as_deref()));
+        if let Some(cached_listing) = &cached_listing {
+            request = request.header(reqwest::header::IF_NONE_MATCH, &cached_listing.etag);
+        }
+        let response = self.http.send_with_retry(request).await?;
+
+        if let (reqwest::StatusCode::NOT_MODIFIED, Some(cached_listing)) =
+            (response.status(), cached_listing)
+        {
+            return Ok(cached_listing.contents);
+        }
+
+        if !response.status().is_success() {
+            return Err(github_error(
+                &response,
+                "failed to fetch updates from github",
+            ));
+        }
+
+        // The ETag only covers the first page of a paginated listing, so
+        // paginated listings are not cached.
+        let mut etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(str::to_string)
+            .filter(|_| next_page_url(&response).is_none());
+        let contents = self.read_listing_pages(response).await?;
+        let contents = match self
+            .expand_github_dirs(&api_endpoint, git_ref.as_deref(), contents)
+            .await?
+        {
+            Some(contents) => contents,
+            None => {
+                etag = None;
+                self.fetch_tree_listing(folder).await?
+            }
+        };
+        if let Some(etag) = etag {
+            let cached_listing = CachedListing {
+                etag,
+                git_ref,
+                contents,
+            };
+            // Failing to cache the listing only means that it gets fetched again next time.
+            if let Ok(cache) = serde_json::to_vec(&cached_listing) {
+                let _ = fs::write(&cache_path, cache);
+            }
+            return Ok(cached_listing.contents);
+        }
+        Ok(contents)
+    }
+}
+
+#[async_trait]
+impl ConfigSource for GithubSource {
+    async fn list_folder(
+        &self,
+        folder: &RemoteFolder,
+        config_dir: &Path,
+    ) -> Result<Vec<RemoteFile>> {
+        let contents = self.fetch_listing(folder, config_dir).await?;
+        contents
+            .into_iter()
+            .filter(|item| item.content_type == "file")
+            .map(|item| {
+                let Some(download_url) = item.download_url else {
+                    bail!("{} cannot be downloaded from github", item.name);
+                };
+                Ok(RemoteFile {
+                    name: item.name,
+                    sha: item.sha,
+                    size: item.size,
+                    download_url,
+                })
+            })
+            .collect()
+    }
+
+    async fn download_file(
+        &self,
+        file: &RemoteFile,
+        writer: &mut (dyn Write + Send),
+    ) -> Result<()> {
+        let mut response = self
+            .http
+            .send_with_retry(self.http.get(&file.download_url))
+            .await?;
+        if !response.status().is_success() {
+            bail!("the server responded with {}", response.status());
+        }
+        while let Some(chunk) = response.chunk().await? {
+            writer.write_all(&chunk)?;
+        }
+        Ok(())
+    }
+
+    async fn fetch_checksums(&self, folder: &RemoteFolder) -> Result<Option<Vec<ChecksummedFile>>> {
+        let git_ref = folder.git_ref.as_deref().unwrap_or("HEAD");
+        let folder_url = format!(
+            "{}/{}/{}/{git_ref}/{}",
+            self.raw_url, folder.owner, folder.repo, folder.path
+        );
+        let response = self
+            .http
+            .send_with_retry(
+                self.http
+                    .get(&format!("{folder_url}/{CHECKSUMS_FILE_NAME}")),
+            )
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            bail!(
+                "failed to download {CHECKSUMS_FILE_NAME} of {}: the server responded with {}",
+                folder.path,
+                response.status()
+            );
+        }
+        let checksums = parse_checksums(&response.text().await?)?;
+        Ok(Some(
+            checksums
+                .into_iter()
+                .map(|(name, sha256)| ChecksummedFile {
+                    download_url: format!("{folder_url}/{name}"),
+                    name,
+                    sha256,
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chain_config::{
+            manifest::git_blob_sha,
+            test_utils::{
+                create_github_response, mount_testnet_listing, CHAIN_CONFIG, NEW_CHAIN_CONFIG,
+                STATE_CONFIG,
+            },
+            ChainConfig, ConfigFetcher,
+        },
+        consts::{CHAIN_CONFIG_REPO_NAME, MAX_CONCURRENT_DOWNLOADS, TESTNET_CONFIG_FOLDER_NAME},
+    };
+    use tempfile::TempDir;
+    use wiremock::{
+        matchers::{header, method, path, query_param, query_param_is_missing},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[tokio::test]
+    async fn test_github_token_is_sent_as_bearer_token() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+
+        let github_response = create_github_response(&[("config.json", "test config content")]);
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .and(header("Authorization", "Bearer test-token"))
+            .and(header("User-Agent", user_agent().as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let source = GithubSource::new()
+            .with_base_url(mock_server.uri())
+            .with_github_token(Some("test-token".to_string()));
+        let fetcher =
+            ConfigFetcher::with_source(source).with_config_vault(test_dir.path().to_path_buf());
+
+        let files = fetcher
+            .check_remote_files(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        assert_eq!(files.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_reports_when_to_retry() {
+        let mock_server = MockServer::start().await;
+
+        let test_dir = TempDir::new().unwrap();
+        let test_folder = test_dir.path().join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("config.json"), "test config content").unwrap();
+
+        let reset = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 30 * 60;
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("x-ratelimit-remaining", "0")
+                    .insert_header("x-ratelimit-reset", reset.to_string().as_str()),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let fetcher =
+            ConfigFetcher::with_test_config(mock_server.uri(), test_dir.path().to_path_buf());
+
+        let err = fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            err.contains("rate limit exceeded"),
+            "Unexpected error: {err}"
+        );
+        assert!(
+            err.contains("retry in 30 minute(s)"),
+            "Unexpected error: {err}"
+        );
+        assert!(err.contains("FORC_GITHUB_TOKEN"), "Unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn test_cached_listing_is_used_when_not_modified() {
+        let mock_server = MockServer::start().await;
+        let test_files = [("config.json", "test config content")];
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        for (name, content) in &test_files {
+            fs::write(test_folder.join(name), content).unwrap();
+        }
+        let cached_listing = CachedListing {
+            etag: "\"listing-etag\"".to_string(),
+            git_ref: None,
+            contents: create_github_response(&test_files),
+        };
+        fs::write(
+            test_folder.join(LISTING_CACHE_FILE_NAME),
+            serde_json::to_vec(&cached_listing).unwrap(),
+        )
+        .unwrap();
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .and(header("If-None-Match", "\"listing-etag\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+
+        let needs_fetch = fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        assert!(
+            !needs_fetch,
+            "Fetch should not be required when the cached listing matches the files"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_listing_cache_is_replaced() {
+        let mock_server = MockServer::start().await;
+        let test_files = [("config.json", "test config content")];
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        for (name, content) in &test_files {
+            fs::write(test_folder.join(name), content).unwrap();
+        }
+        let cache_path = test_folder.join(LISTING_CACHE_FILE_NAME);
+        fs::write(&cache_path, "not a listing").unwrap();
+
+        let github_response = create_github_response(&test_files);
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"new-etag\"")
+                    .set_body_json(&github_response),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+
+        let needs_fetch = fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+
+        assert!(
+            !needs_fetch,
+            "Fetch should not be required when files match"
+        );
+        let cached_listing: CachedListing =
+            serde_json::from_slice(&fs::read(&cache_path).unwrap()).unwrap();
+        assert_eq!(cached_listing.etag, "\"new-etag\"");
+        assert_eq!(cached_listing.contents.len(), test_files.len());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_downloads_report_the_failed_file() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let names = (0..2 * MAX_CONCURRENT_DOWNLOADS)
+            .map(|i| format!("state_{i}.json"))
+            .collect::<Vec<_>>();
+        let files = names
+            .iter()
+            .map(|name| (name.as_str(), STATE_CONFIG))
+            .collect::<Vec<_>>();
+        mount_testnet_listing(&mock_server, &files, &config_path).await;
+
+        for name in &names[1..] {
+            Mock::given(method("GET"))
+                .and(path(format!("/raw/{name}")))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(STATE_CONFIG)
+                        .set_delay(Duration::from_millis(100)),
+                )
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/raw/state_0.json"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        let err = fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Failed to download file: state_0.json");
+        assert!(!config_path.join(TESTNET_CONFIG_FOLDER_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let listing_path = format!(
+            "/repos/FuelLabs/{}/contents/{}",
+            CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+        );
+        Mock::given(method("GET"))
+            .and(path(listing_path.clone()))
+            .respond_with(ResponseTemplate::new(502))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CHAIN_CONFIG))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(
+                config_path
+                    .join(TESTNET_CONFIG_FOLDER_NAME)
+                    .join("chain_config.json")
+            )
+            .unwrap(),
+            CHAIN_CONFIG
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let test_dir = TempDir::new().unwrap();
+        let fetcher =
+            ConfigFetcher::with_test_config(mock_server.uri(), test_dir.path().to_path_buf());
+        assert!(fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_persistent_failures_report_the_attempts() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let test_dir = TempDir::new().unwrap();
+        let test_folder = test_dir.path().join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        let source = GithubSource::new()
+            .with_base_url(mock_server.uri())
+            .with_raw_url(format!("{}/raw", mock_server.uri()))
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 2,
+                initial_backoff: Duration::from_millis(10),
+            });
+        let fetcher =
+            ConfigFetcher::with_source(source).with_config_vault(test_dir.path().to_path_buf());
+        let err = fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").starts_with("request failed after 2 attempt(s): "));
+        assert!(format!("{err:#}").ends_with("responded with 500 Internal Server Error"));
+    }
+
+    #[tokio::test]
+    async fn test_download_not_matching_the_listed_hash_is_rejected() {
+        let mock_server = MockServer::start().await;
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().join("configs");
+        let test_folder = mount_testnet_listing(
+            &mock_server,
+            &[("chain_config.json", NEW_CHAIN_CONFIG)],
+            &config_path,
+        )
+        .await;
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+
+        // A valid configuration file, but not the one in the listing.
+        let served = r#"{"chain_name": "other chain", "consensus_parameters": {}}"#;
+        Mock::given(method("GET"))
+            .and(path("/raw/chain_config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(served))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path.clone());
+        let err = fetcher
+            .download_config(&ChainConfig::Testnet)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to download file: chain_config.json"
+        );
+        assert_eq!(
+            format!("{:#}", err),
+            format!(
+                "Failed to download file: chain_config.json: the hash of the downloaded file is {}, expected {}",
+                git_blob_sha(served.as_bytes()),
+                git_blob_sha(NEW_CHAIN_CONFIG.as_bytes())
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(test_folder.join("chain_config.json")).unwrap(),
+            CHAIN_CONFIG
+        );
+        assert_eq!(fs::read_dir(&config_path).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_all_pages_of_a_paginated_listing_are_checked() {
+        let mock_server = MockServer::start().await;
+        let listing_path = format!(
+            "/repos/FuelLabs/{}/contents/{}",
+            CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+        );
+        let with_mock_download_urls = |files: Vec<GithubContentDetails>| {
+            files
+                .into_iter()
+                .map(|item| GithubContentDetails {
+                    download_url: Some(format!("{}/raw/{}", mock_server.uri(), item.name)),
+                    ..item
+                })
+                .collect::<Vec<_>>()
+        };
+        let first_page = with_mock_download_urls(create_github_response(&[(
+            "chain_config.json",
+            CHAIN_CONFIG,
+        )]));
+        let second_page = with_mock_download_urls(create_github_response(&[(
+            "state_config.json",
+            STATE_CONFIG,
+        )]));
+        let next_page = format!("{}{listing_path}?page=2", mock_server.uri());
+        Mock::given(method("GET"))
+            .and(path(listing_path.clone()))
+            .and(query_param_is_missing("page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!("<{next_page}>; rel=\"next\", <{next_page}>; rel=\"last\"",)
+                            .as_str(),
+                    )
+                    .insert_header("ETag", "\"first-page-etag\"")
+                    .set_body_json(&first_page),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(listing_path))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&second_page))
+            .mount(&mock_server)
+            .await;
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("chain_config.json"), CHAIN_CONFIG).unwrap();
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+
+        let changes = fetcher.check_changes(&ChainConfig::Testnet).await.unwrap();
+        assert_eq!(changes.added, vec!["state_config.json"]);
+        assert!(changes.removed.is_empty());
+        assert!(changes.modified.is_empty());
+        // The ETag of the first page does not cover the second one.
+        assert!(!test_folder.join(LISTING_CACHE_FILE_NAME).exists());
+
+        fs::write(test_folder.join("state_config.json"), STATE_CONFIG).unwrap();
+        assert!(!fetcher
+            .check_fetch_required(&ChainConfig::Testnet)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_truncated_listing_falls_back_to_the_git_tree() {
+        let mock_server = MockServer::start().await;
+        let files = (0..GITHUB_CONTENTS_LIMIT + 1)
+            .map(|index| (format!("snapshot_{index:04}.json"), index.to_string()))
+            .collect::<Vec<_>>();
+        let files = files
+            .iter()
+            .map(|(name, content)| (name.as_str(), content.as_str()))
+            .collect::<Vec<_>>();
+
+        // The content api lists only the first files of the folder.
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(create_github_response(&files[..GITHUB_CONTENTS_LIMIT])),
+            )
+            .mount(&mock_server)
+            .await;
+        let tree = create_github_response(&files)
+            .into_iter()
+            .map(|item| {
+                serde_json::json!({
+                    "path": item.name,
+                    "sha": item.sha,
+                    "size": item.size,
+                    "type": "blob",
+                })
+            })
+            .collect::<Vec<_>>();
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/git/trees/HEAD:{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .and(query_param("recursive", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "tree": tree, "truncated": false })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        for (name, content) in &files[..GITHUB_CONTENTS_LIMIT] {
+            fs::write(test_folder.join(name), content).unwrap();
+        }
+        let source = GithubSource::new()
+            .with_base_url(mock_server.uri())
+            .with_raw_url(format!("{}/raw", mock_server.uri()))
+            .with_github_token(None);
+        let fetcher = ConfigFetcher::with_source(source).with_config_vault(config_path);
+
+        let changes = fetcher.check_changes(&ChainConfig::Testnet).await.unwrap();
+        assert_eq!(changes.added, vec!["snapshot_1000.json"]);
+        assert!(changes.removed.is_empty());
+        assert!(changes.modified.is_empty());
+
+        let remote_files = fetcher
+            .check_remote_files(&ChainConfig::Testnet)
+            .await
+            .unwrap();
+        let added = remote_files
+            .iter()
+            .find(|file| file.name == "snapshot_1000.json")
+            .unwrap();
+        assert_eq!(
+            added.download_url,
+            format!(
+                "{}/raw/FuelLabs/{}/HEAD/{}/snapshot_1000.json",
+                mock_server.uri(),
+                CHAIN_CONFIG_REPO_NAME,
+                TESTNET_CONFIG_FOLDER_NAME
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_truncated_git_tree_is_an_error() {
+        let mock_server = MockServer::start().await;
+        let files = (0..GITHUB_CONTENTS_LIMIT)
+            .map(|index| (format!("snapshot_{index:04}.json"), index.to_string()))
+            .collect::<Vec<_>>();
+        let files = files
+            .iter()
+            .map(|(name, content)| (name.as_str(), content.as_str()))
+            .collect::<Vec<_>>();
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/contents/{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(create_github_response(&files)))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/repos/FuelLabs/{}/git/trees/HEAD:{}",
+                CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+            )))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "tree": [], "truncated": true })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let test_dir = TempDir::new().unwrap();
+        let config_path = test_dir.path().to_path_buf();
+        let test_folder = config_path.join(TESTNET_CONFIG_FOLDER_NAME);
+        fs::create_dir_all(&test_folder).unwrap();
+        fs::write(test_folder.join("snapshot_0000.json"), "0").unwrap();
+        let fetcher = ConfigFetcher::with_test_config(mock_server.uri(), config_path);
+
+        let err = fetcher
+            .check_changes(&ChainConfig::Testnet)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("too many files"), "Unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_user_agent_includes_the_version_platform_and_suffix() {
+        let user_agent = user_agent_with(None);
+        assert_eq!(
+            user_agent,
+            format!(
+                "forc-node/{} ({})",
+                env!("CARGO_PKG_VERSION"),
+                env!("TARGET")
+            )
+        );
+        assert_eq!(user_agent_with(Some(" ")), user_agent);
+        assert_eq!(
+            user_agent_with(Some("ci-fleet/7")),
+            format!("{user_agent} ci-fleet/7")
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    chain_config::source::{user_agent, GithubContentDetails},
+    consts::{CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME},
+};
+use sha1::{Digest, Sha1};
+use std::path::{Path, PathBuf};
+use wiremock::{
+    matchers::{header, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+// Contents of configuration files that pass the validation of downloads.
+pub(crate) const CHAIN_CONFIG: &str = r#"{"chain_name": "chain", "consensus_parameters": {}}"#;
+pub(crate) const NEW_CHAIN_CONFIG: &str =
+    r#"{"chain_name": "new chain", "consensus_parameters": {}}"#;
+pub(crate) const STATE_CONFIG: &str = r#"{"coins": []}"#;
+
+/// Creates a dummy github listing of the `files`.
+pub(crate) fn create_github_response(files: &[(&str, &str)]) -> Vec<GithubContentDetails> {
+    files
+        .iter()
+        .map(|(name, content)| {
+            let mut hasher = Sha1::new();
+            hasher.update(b"blob ");
+            hasher.update(content.len().to_string().as_bytes());
+            hasher.update([0]);
+            hasher.update(content.as_bytes());
+            let sha = format!("{:x}", hasher.finalize());
+
+            GithubContentDetails {
+                name: name.to_string(),
+                sha,
+                size: content.len() as u64,
+                download_url: Some(format!("https://raw.githubusercontent.com/test/{}", name)),
+                content_type: "file".to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Mounts the listing of the testnet configuration, with files downloaded
+/// from the mock server, and returns the folder the files are stored in.
+pub(crate) async fn mount_testnet_listing(
+    mock_server: &MockServer,
+    files: &[(&str, &str)],
+    config_path: &Path,
+) -> PathBuf {
+    let github_response = create_github_response(files)
+        .into_iter()
+        .map(|item| GithubContentDetails {
+            download_url: Some(format!("{}/raw/{}", mock_server.uri(), item.name)),
+            ..item
+        })
+        .collect::<Vec<_>>();
+    Mock::given(method("GET"))
+        .and(path(format!(
+            "/repos/FuelLabs/{}/contents/{}",
+            CHAIN_CONFIG_REPO_NAME, TESTNET_CONFIG_FOLDER_NAME
+        )))
+        .and(header("User-Agent", user_agent().as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&github_response))
+        .mount(mock_server)
+        .await;
+    config_path.join(TESTNET_CONFIG_FOLDER_NAME)
+}
@@ -0,0 +1,11 @@
+use crate::chain_config::ChainConfig;
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CheckConfigCmd {
+    /// The chain configuration to check. Either the name of a configuration,
+    /// or the source of a custom one: a directory, a URL of a gzipped
+    /// tarball, or `github:<owner>/<repo>/<path>`.
+    #[clap(default_value = "local")]
+    pub chain_config: ChainConfig,
+}
@@ -0,0 +1,30 @@
+use super::cmd::CheckConfigCmd;
+use crate::{
+    chain_config::{verify_checksums, ConfigFetcher, ConfigRepository},
+    consts::CHECKSUMS_FILE_NAME,
+};
+use forc_tracing::println_action_green;
+
+/// Checks that the files of the chain configuration currently in the vault
+/// can be parsed, and match their checksum manifest if they have one, without
+/// downloading anything.
+pub(crate) fn run(cmd: CheckConfigCmd, config_repo: ConfigRepository) -> anyhow::Result<()> {
+    let config_dir = ConfigFetcher::default()
+        .with_config_repo(config_repo)
+        .validate_config(&cmd.chain_config)?;
+    if let Some(count) = verify_checksums(&config_dir)? {
+        println_action_green(
+            "Verified",
+            &format!("{count} files against {CHECKSUMS_FILE_NAME}"),
+        );
+    }
+    println_action_green(
+        "Checked",
+        &format!(
+            "{} network configuration at {}",
+            cmd.chain_config,
+            config_dir.display()
+        ),
+    );
+    Ok(())
+}
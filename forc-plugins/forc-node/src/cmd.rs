@@ -1,9 +1,17 @@
 use std::net::IpAddr;
 
 use crate::{
-    consts::{DEFAULT_PEERING_PORT, DEFAULT_PORT},
+    chain_config::{
+        ChainConfig, ConfigRepository, ConfigUpdateOptions, ConfigUpdatePolicy,
+        FuelCoreCompatibility,
+    },
+    check_config::cmd::CheckConfigCmd,
+    config::cmd::ConfigCmd,
+    consts::{CHAIN_CONFIG_REPO_NAME, CHAIN_CONFIG_REPO_OWNER, DEFAULT_PEERING_PORT, DEFAULT_PORT},
+    devnet::cmd::DevnetCmd,
     ignition::cmd::IgnitionCmd,
     local::cmd::LocalCmd,
+    pin_config::cmd::PinConfigCmd,
     testnet::cmd::TestnetCmd,
 };
 use clap::{Parser, Subcommand};
@@ -11,11 +19,51 @@ use clap::{Parser, Subcommand};
 #[derive(Debug, Parser)]
 #[clap(name = "forc node", version)]
 /// Forc node is a wrapper around fuel-core with sensible defaults to provide
-/// easy way of bootstrapping a node for local development, testnet or mainnet.
+/// easy way of bootstrapping a node for local development, testnet, devnet or
+/// mainnet.
 pub struct ForcNodeCmd {
     /// Print the fuel-core command without running it.
     #[arg(long)]
     pub dry_run: bool,
+    /// Download missing or outdated network configuration without asking.
+    /// Same as `--non-interactive accept`.
+    #[arg(short = 'y', long, conflicts_with = "non_interactive")]
+    pub yes: bool,
+    /// Never ask whether to download missing or outdated network configuration.
+    /// `accept` downloads it, `skip` proceeds with the existing configuration,
+    /// `fail` stops without changing it. If stdin is not a terminal, missing
+    /// configuration is downloaded, and outdated configuration is an error.
+    #[arg(long, value_enum, env = "FORC_NODE_NON_INTERACTIVE")]
+    pub non_interactive: Option<ConfigUpdatePolicy>,
+    /// Show which top-level keys of the modified JSON files changed when a
+    /// network configuration update is detected.
+    #[arg(short, long)]
+    pub verbose: bool,
+    /// Hash all the local network configuration files when checking for
+    /// updates, instead of trusting the recorded hashes of unchanged files.
+    #[arg(long)]
+    pub verify: bool,
+    /// What to do when a network configuration update requires a fuel-core
+    /// version other than the installed one: `warn` installs it anyway,
+    /// `refuse` keeps the existing configuration, `skip` does not check.
+    #[arg(long, value_enum, default_value_t = FuelCoreCompatibility::Warn)]
+    pub fuel_core_compatibility: FuelCoreCompatibility,
+    /// Owner of the github repository to download the built-in network
+    /// configurations from, e.g., for a fork with its own network parameters.
+    #[arg(long, env = "FORC_NODE_CONFIG_REPO_ORG", default_value = CHAIN_CONFIG_REPO_OWNER)]
+    pub config_repo_org: String,
+    /// Name of the github repository to download the built-in network
+    /// configurations from.
+    #[arg(long, env = "FORC_NODE_CONFIG_REPO", default_value = CHAIN_CONFIG_REPO_NAME)]
+    pub config_repo: String,
+    /// Do not check the network configuration for updates, only that it
+    /// exists, so that the node starts without any network access.
+    #[arg(long, env = "FORC_NODE_SKIP_CONFIG_CHECK")]
+    pub skip_config_check: bool,
+    /// Print the outcome of the network configuration check as JSON on
+    /// stdout, and everything else on stderr.
+    #[arg(long)]
+    pub json: bool,
     #[command(subcommand)]
     pub mode: Mode,
 }
@@ -26,8 +74,62 @@ pub enum Mode {
     Local(LocalCmd),
     /// Starts a node that will connect to latest testnet.
     Testnet(TestnetCmd),
+    /// Starts a node that will connect to devnet.
+    Devnet(DevnetCmd),
     /// Starts a node that will connect to ignition network.
     Ignition(IgnitionCmd),
+    /// Checks that the network configuration files on disk can be parsed.
+    CheckConfig(CheckConfigCmd),
+    /// Pins the network configuration to a git ref of its repository.
+    PinConfig(PinConfigCmd),
+    /// Shows the installed network configurations.
+    Config(ConfigCmd),
+}
+
+impl Mode {
+    /// Returns the network configuration the node runs with, or `None` if
+    /// the mode does not start a node.
+    pub fn chain_config(&self) -> Option<ChainConfig> {
+        match self {
+            Mode::Local(local) => Some(local.chain_config.clone().unwrap_or(ChainConfig::Local)),
+            Mode::Testnet(_) | Mode::Ignition(_) => Some(ChainConfig::Testnet),
+            Mode::Devnet(_) => Some(ChainConfig::Devnet),
+            Mode::CheckConfig(_) | Mode::PinConfig(_) | Mode::Config(_) => None,
+        }
+    }
+}
+
+impl ForcNodeCmd {
+    /// Returns the policy for updating the network configuration, based on
+    /// the `--yes` and `--non-interactive` flags.
+    pub fn config_update_policy(&self) -> ConfigUpdatePolicy {
+        if self.yes {
+            return ConfigUpdatePolicy::Accept;
+        }
+        ConfigUpdatePolicy::resolve(self.non_interactive)
+    }
+
+    /// Returns the repository of the built-in network configurations, based
+    /// on the `--config-repo-org` and `--config-repo` flags.
+    pub fn config_repo(&self) -> ConfigRepository {
+        ConfigRepository {
+            owner: self.config_repo_org.clone(),
+            repo: self.config_repo.clone(),
+        }
+    }
+
+    /// Returns the settings for checking and updating the network
+    /// configuration on startup.
+    pub fn config_update_options(&self) -> ConfigUpdateOptions {
+        ConfigUpdateOptions {
+            policy: self.config_update_policy(),
+            verbose: self.verbose,
+            verify: self.verify,
+            fuel_core_compatibility: self.fuel_core_compatibility,
+            config_repo: self.config_repo(),
+            skip_check: self.skip_config_check,
+        }
+    }
 }
 
 /// Set of shared node settings, specifically related to connections.
@@ -0,0 +1,57 @@
+use crate::chain_config::ChainConfig;
+use clap::{Parser, Subcommand};
+
+#[derive(Parser, Debug, Clone)]
+pub struct ConfigCmd {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommand {
+    /// Lists the built-in network configurations and the custom ones used
+    /// before, without checking for updates.
+    List {
+        /// Print the configurations as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Shows the files of a network configuration, and whether an update is
+    /// available.
+    Status {
+        /// The chain configuration to show, e.g., `testnet`, or the name of a
+        /// custom configuration used before.
+        chain_config: ChainConfig,
+        /// Do not check whether an update is available.
+        #[clap(long)]
+        offline: bool,
+        /// Print the status as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Lists the downloaded versions of a network configuration.
+    Versions {
+        /// The chain configuration to list the versions of.
+        chain_config: ChainConfig,
+        /// Print the versions as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Switches a network configuration to one of its downloaded versions,
+    /// without downloading anything.
+    Switch {
+        /// The chain configuration to switch.
+        chain_config: ChainConfig,
+        /// The name of the version to switch to, as listed by `versions`.
+        version: String,
+    },
+    /// Removes the oldest downloaded versions of a network configuration. The
+    /// version in use is never removed.
+    Prune {
+        /// The chain configuration to remove the versions of.
+        chain_config: ChainConfig,
+        /// The number of versions to keep, including the one in use.
+        #[clap(long, default_value_t = 1)]
+        keep: usize,
+    },
+}
@@ -0,0 +1,159 @@
+use super::cmd::{ConfigCmd, ConfigCommand};
+use crate::chain_config::{
+    format_bytes, ConfigFetcher, ConfigRepository, ConfigStatus, ConfigVersion, UpdateInfo,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use forc_tracing::println_action_green;
+use term_table::{row::Row, table_cell::TableCell};
+
+/// Shows the chain configurations in the vault, or switches between their
+/// versions. Nothing is downloaded.
+pub(crate) async fn run(cmd: ConfigCmd, config_repo: ConfigRepository) -> anyhow::Result<()> {
+    let fetcher = ConfigFetcher::default().with_config_repo(config_repo);
+    match cmd.command {
+        ConfigCommand::List { json } => {
+            let statuses = fetcher
+                .known_configs()
+                .iter()
+                .map(|conf| fetcher.config_status(conf))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&statuses)?);
+            } else {
+                print_config_list(&statuses);
+            }
+        }
+        ConfigCommand::Status {
+            chain_config,
+            offline,
+            json,
+        } => {
+            let status = if offline {
+                fetcher.config_status(&chain_config)?
+            } else {
+                fetcher.check_config_status(&chain_config).await?
+            };
+            if json {
+                println!("{}", serde_json::to_string_pretty(&status)?);
+            } else {
+                print_config_status(&status);
+            }
+        }
+        ConfigCommand::Versions { chain_config, json } => {
+            let versions = fetcher.list_versions(&chain_config)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&versions)?);
+            } else {
+                print_config_versions(&versions);
+            }
+        }
+        ConfigCommand::Switch {
+            chain_config,
+            version,
+        } => {
+            fetcher.switch_version(&chain_config, &version)?;
+            println_action_green("Switched", &format!("{chain_config} to {version}"));
+        }
+        ConfigCommand::Prune { chain_config, keep } => {
+            for version in fetcher.prune_versions(&chain_config, keep)? {
+                println_action_green("Removed", &format!("{chain_config} version {version}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn new_table(header: &[&str]) -> term_table::Table {
+    let mut table = term_table::Table::new();
+    table.separate_rows = false;
+    table.style = term_table::TableStyle::empty();
+    table.add_row(Row::new(
+        header
+            .iter()
+            .map(|cell| TableCell::new(*cell))
+            .collect::<Vec<_>>(),
+    ));
+    table
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn print_config_list(statuses: &[ConfigStatus]) {
+    let mut table = new_table(&["name", "installed", "files", "last updated", "path"]);
+    for status in statuses {
+        table.add_row(Row::new(vec![
+            TableCell::new(&status.name),
+            TableCell::new(if status.installed { "yes" } else { "no" }),
+            TableCell::new(status.files.len()),
+            TableCell::new(format_time(status.last_updated)),
+            TableCell::new(status.path.display()),
+        ]));
+    }
+    println!("{}", table.render());
+}
+
+fn print_config_status(status: &ConfigStatus) {
+    let update_available = match status.update_available {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "not checked",
+    };
+    let mut summary = new_table(&["name", &status.name]);
+    for (key, value) in [
+        ("path", status.path.display().to_string()),
+        (
+            "version",
+            status.version.clone().unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "installed",
+            if status.installed { "yes" } else { "no" }.to_string(),
+        ),
+        ("last updated", format_time(status.last_updated)),
+        (
+            "updated from",
+            status
+                .update_info
+                .as_ref()
+                .map(UpdateInfo::origin)
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "pinned ref",
+            status.pinned_ref.clone().unwrap_or_else(|| "-".to_string()),
+        ),
+        ("update available", update_available.to_string()),
+    ] {
+        summary.add_row(Row::new(vec![TableCell::new(key), TableCell::new(value)]));
+    }
+    println!("{}", summary.render());
+
+    if status.files.is_empty() {
+        return;
+    }
+    let mut files = new_table(&["file", "size", "sha"]);
+    for file in &status.files {
+        files.add_row(Row::new(vec![
+            TableCell::new(&file.name),
+            TableCell::new(format_bytes(file.size)),
+            TableCell::new(&file.sha),
+        ]));
+    }
+    println!("{}", files.render());
+}
+
+fn print_config_versions(versions: &[ConfigVersion]) {
+    let mut table = new_table(&["version", "current", "downloaded", "path"]);
+    for version in versions {
+        table.add_row(Row::new(vec![
+            TableCell::new(&version.name),
+            TableCell::new(if version.current { "yes" } else { "no" }),
+            TableCell::new(format_time(version.installed_at)),
+            TableCell::new(version.path.display()),
+        ]));
+    }
+    println!("{}", table.render());
+}
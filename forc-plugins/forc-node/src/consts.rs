@@ -9,6 +9,14 @@ pub const TESTNET_RELAYER_LOG_PAGE_SIZE: u32 = 500;
 pub const TESTNET_SYNC_BLOCK_STREAM_BUFFER_SIZE: u32 = 30;
 pub const TESTNET_BOOTSTRAP_NODE: &str = "/dnsaddr/testnet.fuel.network.";
 
+pub const DEVNET_SERVICE_NAME: &str = "fuel-devnet-node";
+pub const DEVNET_SYNC_HEADER_BATCH_SIZE: u32 = 100;
+pub const DEVNET_RELAYER_LISTENING_CONTRACT: &str = "0x768f9459E3339A1F7d59CcF24C80Eb4A711a01FB";
+pub const DEVNET_RELAYER_DA_DEPLOY_HEIGHT: u32 = 5791365;
+pub const DEVNET_RELAYER_LOG_PAGE_SIZE: u32 = 500;
+pub const DEVNET_SYNC_BLOCK_STREAM_BUFFER_SIZE: u32 = 30;
+pub const DEVNET_BOOTSTRAP_NODE: &str = "/dnsaddr/devnet.fuel.network.";
+
 pub const MAINNET_BOOTSTRAP_NODE: &str = "/dnsaddr/mainnet.fuel.network.";
 pub const MAINNET_SERVICE_NAME: &str = "fuel-mainnet-node";
 pub const MAINNET_SYNC_HEADER_BATCH_SIZE: u32 = 30;
@@ -20,6 +28,10 @@ pub const MAINNET_RELAYER_LOG_PAGE_SIZE: u32 = 100;
 /// https://github.com/fuelLabs/chain-configuration/
 /// And name of the db path if persistent db is used.
 pub const TESTNET_CONFIG_FOLDER_NAME: &str = "ignition-test";
+/// Name of the folder for devnet at the configuration repo:
+/// https://github.com/fuelLabs/chain-configuration/
+/// And name of the db path if persistent db is used.
+pub const DEVNET_CONFIG_FOLDER_NAME: &str = "ignition-dev";
 /// Name of the folder for ignition mainnet at the configuration repo:
 /// https://github.com/fuelLabs/chain-configuration/
 /// And name of the db path if persistent db is used.
@@ -27,11 +39,78 @@ pub const IGNITION_CONFIG_FOLDER_NAME: &str = "ignition";
 /// Name of the folder for local configuration repo:
 /// And name of the db path if persistent db is used.
 pub const LOCAL_CONFIG_FOLDER_NAME: &str = "local";
+/// Name of the folder, within the configuration vault, that holds the custom
+/// chain configurations, each in a folder named after the configuration.
+pub const CUSTOM_CONFIG_FOLDER_NAME: &str = "custom";
+/// Owner of the github repository that hosts chain-configurations.
+pub const CHAIN_CONFIG_REPO_OWNER: &str = "FuelLabs";
 /// Name of the github repository that hosts chain-configurations.
 pub const CHAIN_CONFIG_REPO_NAME: &str = "chain-configuration";
+/// The github api used to list and download the chain configurations.
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+/// The host that serves the raw files of github repositories.
+pub const GITHUB_RAW_URL: &str = "https://raw.githubusercontent.com";
+/// Maximum number of entries the github content api lists for a folder.
+/// Larger folders are listed with the git trees api instead.
+pub const GITHUB_CONTENTS_LIMIT: usize = 1000;
+/// Environment variables that can hold a github token, in the order of
+/// precedence. If set, the token is used to authorize github api requests,
+/// which have a much higher rate limit than anonymous ones.
+pub const GITHUB_TOKEN_ENV_VARS: [&str; 2] = ["FORC_GITHUB_TOKEN", "GITHUB_TOKEN"];
+/// Environment variable that holds a suffix appended to the user agent of the
+/// http requests, e.g., to tell the requests of a CI fleet apart.
+pub const USER_AGENT_SUFFIX_ENV_VAR: &str = "FORC_NODE_USER_AGENT_SUFFIX";
+
+/// Exit code when the node ran with an outdated network configuration, because
+/// the update was declined.
+pub const CONFIG_UPDATE_DECLINED_EXIT_CODE: u8 = 2;
 
 pub const DEFAULT_PORT: u16 = 4000;
 pub const DEFAULT_PEERING_PORT: u16 = 30333;
 
 pub const CONFIG_FOLDER: &str = "chainspecs";
+/// Maximum number of chain configuration files downloaded at the same time.
+pub const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+/// Name of the file, within the folder of a chain configuration, that caches
+/// the last github content listing of the folder together with its ETag.
+pub const LISTING_CACHE_FILE_NAME: &str = ".listing-cache.json";
+/// Name of the file, within the folder of a custom chain configuration, that
+/// records where the configuration files come from.
+pub const SOURCE_FILE_NAME: &str = ".source.json";
+/// Name of the file, within the folder of a chain configuration, that records
+/// the hash, size and modification time of each configuration file.
+pub const SHA_MANIFEST_FILE_NAME: &str = ".sha-manifest.json";
+/// Name of the optional file of a chain configuration that holds the
+/// fuel-core versions the configuration works with, either as a semver
+/// requirement, e.g., `>=0.41, <0.43`, or as the minimum version, e.g., `0.41.0`.
+pub const FUEL_CORE_VERSION_FILE_NAME: &str = "fuel-core-version";
+/// Name of the file, within the folder of a chain configuration hosted on
+/// github, that holds the git ref the configuration is pinned to.
+pub const PINNED_REF_FILE_NAME: &str = ".pinned-ref";
+/// Name of the file, within the folder of a built-in chain configuration, that
+/// records the `<owner>/<repo>` github repository its files were downloaded from.
+pub const SOURCE_REPO_FILE_NAME: &str = ".source-repo";
+/// Name of the file, within a version of a chain configuration, that records
+/// when and where its files were downloaded from.
+pub const UPDATE_INFO_FILE_NAME: &str = "update-info.json";
+/// Name of the pointer, within the folder of a chain configuration, to the
+/// version of the configuration in use. A symlink to the folder of the
+/// version, or a file holding its name on platforms without symlinks.
+pub const CURRENT_VERSION_POINTER: &str = "current";
+/// Name of the optional manifest, within the remote folder of a chain
+/// configuration, that lists the SHA256 checksums of its files in the format
+/// of `sha256sum`.
+pub const CHECKSUMS_FILE_NAME: &str = "CHECKSUMS";
+/// Name of the chain configuration file that fuel-core reads the chain
+/// parameters from.
+pub const CHAIN_CONFIG_FILE_NAME: &str = "chain_config.json";
+/// Fields of the chain configuration file that fuel-core requires.
+pub const CHAIN_CONFIG_REQUIRED_FIELDS: [&str; 2] = ["chain_name", "consensus_parameters"];
+/// Prefix of the timestamp in the names of the backups of a chain
+/// configuration folder, which older versions of forc-node kept next to it as
+/// `<folder>.backup-<RFC3339 timestamp>`.
+pub const BACKUP_FOLDER_PREFIX: &str = "backup-";
+/// Number of versions kept for each chain configuration, including the one in
+/// use. The oldest versions are removed once a new one is downloaded.
+pub const MAX_CONFIG_VERSIONS: usize = 4;
 pub const DB_FOLDER: &str = "db";
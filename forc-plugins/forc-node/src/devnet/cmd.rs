@@ -0,0 +1,17 @@
+use crate::{cmd::ConnectionSettings, consts::DEVNET_BOOTSTRAP_NODE, util::DbConfig};
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug, Clone)]
+pub struct DevnetCmd {
+    #[clap(flatten)]
+    pub connection_settings: ConnectionSettings,
+    #[clap(long, default_value = default_devnet_db_path().into_os_string())]
+    pub db_path: PathBuf,
+    #[clap(long, default_value_t = DEVNET_BOOTSTRAP_NODE.to_string())]
+    pub bootstrap_node: String,
+}
+
+fn default_devnet_db_path() -> PathBuf {
+    DbConfig::Devnet.into()
+}
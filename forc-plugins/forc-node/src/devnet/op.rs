@@ -0,0 +1,106 @@
+use crate::{
+    chain_config::ChainConfig,
+    consts::{
+        DEVNET_RELAYER_DA_DEPLOY_HEIGHT, DEVNET_RELAYER_LISTENING_CONTRACT,
+        DEVNET_RELAYER_LOG_PAGE_SIZE, DEVNET_SERVICE_NAME, DEVNET_SYNC_BLOCK_STREAM_BUFFER_SIZE,
+        DEVNET_SYNC_HEADER_BATCH_SIZE,
+    },
+    devnet::cmd::DevnetCmd,
+    run_opts::{DbType, RunOpts},
+    util::{ask_user_keypair, ask_user_string, HumanReadableCommand, KeyPair},
+};
+use anyhow::Context;
+use forc_tracing::println_green;
+use std::{
+    net::IpAddr,
+    path::PathBuf,
+    process::{Child, Command},
+};
+
+/// Configures the node with devnet configuration to connect the node to devnet.
+/// Returns `None` if this is a dry_run and no child process created for fuel-core.
+pub(crate) async fn run(cmd: DevnetCmd, dry_run: bool) -> anyhow::Result<Option<Child>> {
+    let keypair = if let (Some(peer_id), Some(secret)) = (
+        &cmd.connection_settings.peer_id,
+        &cmd.connection_settings.secret,
+    ) {
+        KeyPair {
+            peer_id: peer_id.clone(),
+            secret: secret.clone(),
+        }
+    } else {
+        ask_user_keypair()?
+    };
+
+    let relayer = cmd.connection_settings.relayer.unwrap_or_else(|| {
+        ask_user_string("Ethereum RPC (Sepolia) Endpoint:").expect("Failed to get RPC endpoint")
+    });
+
+    let opts = DevnetOpts {
+        keypair,
+        relayer,
+        ip: cmd.connection_settings.ip,
+        port: cmd.connection_settings.port,
+        peering_port: cmd.connection_settings.peering_port,
+        db_path: cmd.db_path,
+        bootstrap_node: cmd.bootstrap_node,
+    };
+    let run_opts = RunOpts::from(opts);
+    let params = run_opts.generate_params();
+    let mut fuel_core_command = Command::new("fuel-core");
+    fuel_core_command.arg("run");
+    fuel_core_command.args(params.as_slice());
+
+    println_green(&format!(
+        "{}",
+        HumanReadableCommand::from(&fuel_core_command)
+    ));
+
+    if dry_run {
+        return Ok(None);
+    }
+
+    // Spawn the process with proper error handling
+    let handle = fuel_core_command
+        .spawn()
+        .with_context(|| "Failed to spawn fuel-core process:".to_string())?;
+    Ok(Some(handle))
+}
+
+#[derive(Debug)]
+pub struct DevnetOpts {
+    keypair: KeyPair,
+    relayer: String,
+    ip: IpAddr,
+    port: u16,
+    peering_port: u16,
+    db_path: PathBuf,
+    bootstrap_node: String,
+}
+
+impl From<DevnetOpts> for RunOpts {
+    fn from(value: DevnetOpts) -> Self {
+        Self {
+            service_name: Some(DEVNET_SERVICE_NAME.to_string()),
+            db_type: DbType::RocksDb,
+            debug: false,
+            snapshot: ChainConfig::Devnet.into(),
+            keypair: Some(value.keypair.secret),
+            relayer: Some(value.relayer),
+            ip: Some(value.ip),
+            port: Some(value.port),
+            peering_port: Some(value.peering_port),
+            db_path: Some(value.db_path),
+            bootstrap_nodes: Some(value.bootstrap_node),
+            utxo_validation: true,
+            poa_instant: false,
+            enable_p2p: true,
+            sync_header_batch_size: Some(DEVNET_SYNC_HEADER_BATCH_SIZE),
+            enable_relayer: true,
+            relayer_listener: Some(DEVNET_RELAYER_LISTENING_CONTRACT.to_string()),
+            relayer_da_deploy_height: Some(DEVNET_RELAYER_DA_DEPLOY_HEIGHT),
+            relayer_log_page_size: Some(DEVNET_RELAYER_LOG_PAGE_SIZE),
+            sync_block_stream_buffer_size: Some(DEVNET_SYNC_BLOCK_STREAM_BUFFER_SIZE),
+        }
+    }
+}
@@ -1,9 +1,13 @@
 pub mod chain_config;
+pub mod check_config;
 pub mod cmd;
+pub mod config;
 pub mod consts;
+pub mod devnet;
 pub mod ignition;
 pub mod local;
 pub mod op;
+pub mod pin_config;
 pub mod run_opts;
 pub mod testnet;
 pub mod util;
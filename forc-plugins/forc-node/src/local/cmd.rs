@@ -1,10 +1,14 @@
+use crate::chain_config::ChainConfig;
 use clap::Parser;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug, Clone)]
 pub struct LocalCmd {
+    /// The chain configuration to use, `local` by default. Either the name of
+    /// a configuration, or the source of a custom one: a directory, a URL of
+    /// a gzipped tarball, or `github:<owner>/<repo>/<path>`.
     #[clap(long)]
-    pub chain_config: Option<PathBuf>,
+    pub chain_config: Option<ChainConfig>,
     #[clap(long)]
     pub port: Option<u16>,
     #[clap(long)]
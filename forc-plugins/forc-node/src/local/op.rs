@@ -1,6 +1,6 @@
 use super::cmd::LocalCmd;
 use crate::{
-    chain_config::{check_and_update_chain_config, ChainConfig},
+    chain_config::ChainConfig,
     run_opts::{DbType, RunOpts},
     util::HumanReadableCommand,
 };
@@ -12,8 +12,6 @@ use std::process::{Child, Command};
 /// By default, the node is in `debug` mode and the db used is `in-memory`.
 /// Returns `None` if this is a dry_run and no child process created for fuel-core.
 pub(crate) async fn run(cmd: LocalCmd, dry_run: bool) -> anyhow::Result<Option<Child>> {
-    check_and_update_chain_config(ChainConfig::Local).await?;
-
     let run_opts = RunOpts::from(cmd);
     let params = run_opts.generate_params();
 
@@ -40,9 +38,7 @@ pub(crate) async fn run(cmd: LocalCmd, dry_run: bool) -> anyhow::Result<Option<C
 
 impl From<LocalCmd> for RunOpts {
     fn from(value: LocalCmd) -> Self {
-        let path = value
-            .chain_config
-            .unwrap_or_else(|| ChainConfig::Local.into());
+        let path = value.chain_config.unwrap_or(ChainConfig::Local).into();
         let db_type = value
             .db_path
             .as_ref()
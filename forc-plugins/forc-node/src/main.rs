@@ -3,14 +3,19 @@
 use anyhow::anyhow;
 use clap::Parser;
 use forc_node::{
+    chain_config::ConfigUpdateOutcome,
     cmd::{self, ForcNodeCmd},
-    op,
+    consts::CONFIG_UPDATE_DECLINED_EXIT_CODE,
+    op::{self, RunOutcome},
 };
-use forc_tracing::init_tracing_subscriber;
-use forc_util::{ForcCliResult, ForcResult};
+use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions, TracingWriterMode};
+use forc_util::{ForcCliResult, ForcError, ForcResult};
 
 async fn run(cmd: ForcNodeCmd) -> ForcResult<()> {
-    let mut handle = op::run(cmd).await?;
+    let RunOutcome {
+        mut handle,
+        config_update,
+    } = op::run(cmd).await?;
 
     // if this is not a dry run we should wait for the kill signal and kill
     // fuel-core upon receiving it.
@@ -23,13 +28,26 @@ async fn run(cmd: ForcNodeCmd) -> ForcResult<()> {
 
         handle.kill()?;
     }
+
+    // Tools wrapping forc-node can tell a declined update apart from success.
+    if config_update == Some(ConfigUpdateOutcome::SkippedByUser) {
+        return Err(ForcError::new(
+            anyhow!("The network configuration update was declined, the node ran with the outdated configuration"),
+            CONFIG_UPDATE_DECLINED_EXIT_CODE,
+        ));
+    }
     ForcResult::Ok(())
 }
 
 #[tokio::main]
 async fn main() -> ForcCliResult<()> {
-    init_tracing_subscriber(Default::default());
-
     let command = cmd::ForcNodeCmd::parse();
+    // Keep stdout for the JSON output.
+    let writer_mode = command.json.then_some(TracingWriterMode::Stderr);
+    init_tracing_subscriber(TracingSubscriberOptions {
+        writer_mode,
+        ..Default::default()
+    });
+
     run(command).await.into()
 }
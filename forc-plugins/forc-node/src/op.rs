@@ -1,16 +1,52 @@
 use crate::{
+    chain_config::{check_and_update_chain_config, ConfigUpdateOutcome},
     cmd::{ForcNodeCmd, Mode},
     consts::MIN_FUEL_CORE_VERSION,
     util::get_fuel_core_version,
 };
+use anyhow::bail;
 use forc_util::forc_result_bail;
 use semver::Version;
 use std::process::Child;
 
+/// The result of running a forc-node command.
+#[derive(Debug, Default)]
+pub struct RunOutcome {
+    /// The fuel-core process, or `None` if this is a dry run, or the command
+    /// does not start a node.
+    pub handle: Option<Child>,
+    /// The outcome of checking the network configuration before starting the
+    /// node, or `None` if the command does not start a node.
+    pub config_update: Option<ConfigUpdateOutcome>,
+}
+
 /// First checks locally installed `forc-node` version and compares it with
 /// `consts::MIN_FUEL_CORE_VERSION`. If local version is acceptable, proceeding
 /// with the correct mode of operation.
-pub async fn run(cmd: ForcNodeCmd) -> anyhow::Result<Option<Child>> {
+///
+/// Managing the network configuration does not start fuel-core, so it
+/// does not require fuel-core to be installed.
+///
+/// The network configuration is checked before starting the node. With
+/// `--json`, the outcome of the check is printed as JSON, also if it failed.
+pub async fn run(cmd: ForcNodeCmd) -> anyhow::Result<RunOutcome> {
+    let config_repo = cmd.config_repo();
+    match cmd.mode {
+        Mode::CheckConfig(check_config) => {
+            crate::check_config::op::run(check_config, config_repo)?;
+            return Ok(RunOutcome::default());
+        }
+        Mode::PinConfig(pin_config) => {
+            crate::pin_config::op::run(pin_config, config_repo)?;
+            return Ok(RunOutcome::default());
+        }
+        Mode::Config(config) => {
+            crate::config::op::run(config, config_repo).await?;
+            return Ok(RunOutcome::default());
+        }
+        _ => {}
+    }
+
     let current_version = get_fuel_core_version()?;
     let supported_min_version = Version::parse(MIN_FUEL_CORE_VERSION)?;
     if current_version < supported_min_version {
@@ -19,10 +55,29 @@ pub async fn run(cmd: ForcNodeCmd) -> anyhow::Result<Option<Child>> {
             current_version
         ));
     }
-    let forc_node_handle = match cmd.mode {
+    let config_update = match cmd.mode.chain_config() {
+        Some(chain_config) => {
+            let outcome =
+                check_and_update_chain_config(chain_config, cmd.config_update_options()).await;
+            if cmd.json {
+                println!("{}", serde_json::to_string(&outcome)?);
+            }
+            if let ConfigUpdateOutcome::Failed { reason } = &outcome {
+                bail!("{reason}");
+            }
+            Some(outcome)
+        }
+        None => None,
+    };
+    let handle = match cmd.mode {
         Mode::Local(local) => crate::local::op::run(local, cmd.dry_run).await?,
         Mode::Testnet(testnet) => crate::testnet::op::run(testnet, cmd.dry_run).await?,
+        Mode::Devnet(devnet) => crate::devnet::op::run(devnet, cmd.dry_run).await?,
         Mode::Ignition(ignition) => crate::ignition::op::run(ignition, cmd.dry_run).await?,
+        Mode::CheckConfig(_) | Mode::PinConfig(_) | Mode::Config(_) => None,
     };
-    Ok(forc_node_handle)
+    Ok(RunOutcome {
+        handle,
+        config_update,
+    })
 }
@@ -0,0 +1,15 @@
+use crate::chain_config::ChainConfig;
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+pub struct PinConfigCmd {
+    /// The chain configuration to pin, e.g., `testnet`, `devnet`, `ignition`, or a custom
+    /// configuration hosted on github.
+    pub chain_config: ChainConfig,
+    /// The git ref to pin the configuration to: a tag, a branch, or a commit
+    /// sha. Shows the current pin if omitted.
+    pub git_ref: Option<String>,
+    /// Follow the default branch of the configuration repository again.
+    #[clap(long, conflicts_with = "git_ref")]
+    pub unpin: bool,
+}
@@ -0,0 +1,35 @@
+use super::cmd::PinConfigCmd;
+use crate::chain_config::{ConfigFetcher, ConfigRepository};
+use forc_tracing::println_action_green;
+
+/// Pins the chain configuration to a git ref, unpins it, or shows its pin.
+/// The files are updated to the ones at the new ref on the next start.
+pub(crate) fn run(cmd: PinConfigCmd, config_repo: ConfigRepository) -> anyhow::Result<()> {
+    let fetcher = ConfigFetcher::default().with_config_repo(config_repo);
+    let conf = &cmd.chain_config;
+    if cmd.unpin {
+        fetcher.pin_ref(conf, None)?;
+        println_action_green(
+            "Unpinned",
+            &format!("{conf} network configuration, it follows the default branch"),
+        );
+    } else if let Some(git_ref) = &cmd.git_ref {
+        fetcher.pin_ref(conf, Some(git_ref))?;
+        println_action_green(
+            "Pinned",
+            &format!("{conf} network configuration to {git_ref}"),
+        );
+    } else {
+        match fetcher.pinned_ref(conf) {
+            Some(git_ref) => println_action_green(
+                "Pinned",
+                &format!("{conf} network configuration to {git_ref}"),
+            ),
+            None => println_action_green(
+                "Unpinned",
+                &format!("{conf} network configuration follows the default branch"),
+            ),
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::consts::{
-    DB_FOLDER, IGNITION_CONFIG_FOLDER_NAME, LOCAL_CONFIG_FOLDER_NAME, TESTNET_CONFIG_FOLDER_NAME,
+    DB_FOLDER, DEVNET_CONFIG_FOLDER_NAME, IGNITION_CONFIG_FOLDER_NAME, LOCAL_CONFIG_FOLDER_NAME,
+    TESTNET_CONFIG_FOLDER_NAME,
 };
 use anyhow::{anyhow, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Password};
@@ -24,6 +25,7 @@ use std::{
 pub enum DbConfig {
     Local,
     Testnet,
+    Devnet,
     Ignition,
 }
 
@@ -54,6 +56,7 @@ impl From<DbConfig> for PathBuf {
         match value {
             DbConfig::Local => user_db_dir.join(LOCAL_CONFIG_FOLDER_NAME),
             DbConfig::Testnet => user_db_dir.join(TESTNET_CONFIG_FOLDER_NAME),
+            DbConfig::Devnet => user_db_dir.join(DEVNET_CONFIG_FOLDER_NAME),
             DbConfig::Ignition => user_db_dir.join(IGNITION_CONFIG_FOLDER_NAME),
         }
     }
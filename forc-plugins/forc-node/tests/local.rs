@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use forc_node::{
+    chain_config::FuelCoreCompatibility,
     cmd::{ForcNodeCmd, Mode},
     local::cmd::LocalCmd,
     op,
@@ -21,11 +22,20 @@ async fn start_local_node_check_health() {
 
     let cmd = ForcNodeCmd {
         dry_run: false,
+        yes: true,
+        non_interactive: None,
+        verbose: false,
+        verify: false,
+        fuel_core_compatibility: FuelCoreCompatibility::Warn,
+        config_repo_org: "FuelLabs".to_string(),
+        config_repo: "chain-configuration".to_string(),
+        skip_config_check: false,
+        json: false,
         mode: Mode::Local(local_cmd),
     };
 
     #[allow(clippy::zombie_processes)]
-    let mut handle = op::run(cmd).await.unwrap().unwrap();
+    let mut handle = op::run(cmd).await.unwrap().handle.unwrap();
     // Wait for node to start grapqhl service
     sleep(Duration::from_secs(2)).await;
 
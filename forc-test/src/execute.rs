@@ -1,7 +1,7 @@
 use crate::maxed_consensus_params;
 use crate::setup::TestSetup;
+use crate::ExecutionContext;
 use crate::TestResult;
-use crate::TEST_METADATA_SEED;
 use forc_pkg::PkgTestEntry;
 use fuel_tx::{self as tx, output::contract::Contract, Chargeable, Finalizable};
 use fuel_vm::error::InterpreterError;
@@ -50,6 +50,26 @@ impl TestExecutor {
         test_setup: TestSetup,
         test_entry: &PkgTestEntry,
         name: String,
+    ) -> anyhow::Result<Self> {
+        Self::build_with_context(
+            bytecode,
+            test_instruction_index,
+            test_setup,
+            test_entry,
+            name,
+            &ExecutionContext::default(),
+        )
+    }
+
+    /// Builds the executor of a test like [TestExecutor::build], with a transaction made in the given
+    /// [ExecutionContext]. The context should be the one the `test_setup` was made in.
+    pub fn build_with_context(
+        bytecode: &[u8],
+        test_instruction_index: u32,
+        test_setup: TestSetup,
+        test_entry: &PkgTestEntry,
+        name: String,
+        context: &ExecutionContext,
     ) -> anyhow::Result<Self> {
         let storage = test_setup.storage().clone();
 
@@ -59,19 +79,19 @@ impl TestExecutor {
 
         // Create a transaction to execute the test function.
         let script_input_data = vec![];
-        let rng = &mut rand::rngs::StdRng::seed_from_u64(TEST_METADATA_SEED);
+        let rng = &mut rand::rngs::StdRng::seed_from_u64(context.seed());
 
         // Prepare the transaction metadata.
         let secret_key = SecretKey::random(rng);
         let utxo_id = rng.gen();
-        let amount = 1;
+        let amount = context.coin_amount();
         let maturity = 1.into();
         // NOTE: fuel-core is using dynamic asset id and interacting with the fuel-core, using static
         // asset id is not correct. But since forc-test maintains its own interpreter instance, correct
         // base asset id is indeed the static `tx::AssetId::BASE`.
         let asset_id = tx::AssetId::BASE;
         let tx_pointer = rng.gen();
-        let block_height = (u32::MAX >> 1).into();
+        let block_height = context.block_height().into();
         let gas_price = 0;
 
         let mut tx_builder = tx::TransactionBuilder::script(bytecode.to_vec(), script_input_data);
@@ -55,6 +55,42 @@ pub struct TestFilter<'a> {
     pub exact_match: bool,
 }
 
+/// The chain context tests are executed in, which can be set to reproduce a failure. Each value that is
+/// not set keeps the one `forc test` uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionContext {
+    /// The seed of the random metadata of the transactions, such as the key signing their coin input.
+    pub seed: Option<u64>,
+    /// The height of the block the transactions are executed in.
+    pub block_height: Option<u32>,
+    /// The amount of the base asset coin spent by the transactions.
+    pub coin_amount: Option<u64>,
+}
+
+impl ExecutionContext {
+    pub(crate) fn seed(&self) -> u64 {
+        self.seed.unwrap_or(TEST_METADATA_SEED)
+    }
+
+    pub(crate) fn block_height(&self) -> u32 {
+        self.block_height.unwrap_or(u32::MAX >> 1)
+    }
+
+    pub(crate) fn coin_amount(&self) -> u64 {
+        self.coin_amount.unwrap_or(1)
+    }
+
+    /// Returns the storage the tests start with, before any contracts are deployed.
+    pub(crate) fn storage(&self) -> vm::storage::MemoryStorage {
+        match self.block_height {
+            Some(block_height) => {
+                vm::storage::MemoryStorage::new(block_height.into(), Default::default())
+            }
+            None => vm::storage::MemoryStorage::default(),
+        }
+    }
+}
+
 /// The result of executing a single test within a single package.
 #[derive(Debug, Clone)]
 pub struct TestResult {
@@ -203,11 +239,11 @@ impl PackageWithDeploymentToTest {
     ///
     /// For scripts deploys all contract dependencies.
     /// For contract deploys all contract dependencies and the root contract itself.
-    fn deploy(&self) -> anyhow::Result<TestSetup> {
+    fn deploy(&self, context: &ExecutionContext) -> anyhow::Result<TestSetup> {
         // Setup the interpreter for deployment.
         let gas_price = 0;
         let params = maxed_consensus_params();
-        let storage = vm::storage::MemoryStorage::default();
+        let storage = context.storage();
         let interpreter_params = InterpreterParams::new(gas_price, params.clone());
         let mut interpreter: vm::prelude::Interpreter<_, _, _, vm::interpreter::NotSupportedEcal> =
             vm::interpreter::Interpreter::with_storage(
@@ -218,9 +254,9 @@ impl PackageWithDeploymentToTest {
 
         // Iterate and create deployment transactions for contract dependencies of the root
         // contract.
-        let contract_dependency_setups = self
-            .contract_dependencies()
-            .map(|built_pkg| deployment_transaction(built_pkg, &built_pkg.bytecode, &params));
+        let contract_dependency_setups = self.contract_dependencies().map(|built_pkg| {
+            deployment_transaction(built_pkg, &built_pkg.bytecode, &params, context)
+        });
 
         // Deploy contract dependencies of the root contract and collect their ids.
         let contract_dependency_ids = contract_dependency_setups
@@ -242,6 +278,7 @@ impl PackageWithDeploymentToTest {
                 &contract_to_test.pkg,
                 &contract_to_test.without_tests_bytecode,
                 &params,
+                context,
             );
             let root_contract_tx = root_contract_tx
                 .into_ready(gas_price, params.gas_costs(), params.fee_params(), None)
@@ -423,18 +460,23 @@ impl<'a> PackageTests {
     /// For testing contracts, storage returned from this function contains the deployed contract.
     /// For other types, default storage is returned.
     pub fn setup(&self) -> anyhow::Result<TestSetup> {
+        self.setup_with_context(&ExecutionContext::default())
+    }
+
+    /// Setup the storage for a test like [PackageTests::setup], in the given [ExecutionContext].
+    pub fn setup_with_context(&self, context: &ExecutionContext) -> anyhow::Result<TestSetup> {
         match self {
             PackageTests::Contract(contract_to_test) => {
-                let test_setup = contract_to_test.deploy()?;
+                let test_setup = contract_to_test.deploy(context)?;
                 Ok(test_setup)
             }
             PackageTests::Script(script_to_test) => {
-                let test_setup = script_to_test.deploy()?;
+                let test_setup = script_to_test.deploy(context)?;
                 Ok(test_setup)
             }
-            PackageTests::Predicate(_) | PackageTests::Library(_) => Ok(
-                TestSetup::WithoutDeployment(vm::storage::MemoryStorage::default()),
-            ),
+            PackageTests::Predicate(_) | PackageTests::Library(_) => {
+                Ok(TestSetup::WithoutDeployment(context.storage()))
+            }
         }
     }
 }
@@ -635,6 +677,7 @@ fn deployment_transaction(
     built_pkg: &pkg::BuiltPackage,
     without_tests_bytecode: &pkg::BuiltPackageBytecode,
     params: &tx::ConsensusParameters,
+    context: &ExecutionContext,
 ) -> ContractDeploymentSetup {
     // Obtain the contract id for deployment.
     let mut storage_slots = built_pkg.storage_slots.clone();
@@ -647,19 +690,19 @@ fn deployment_transaction(
     let contract_id = contract.id(&salt, &root, &state_root);
 
     // Create the deployment transaction.
-    let rng = &mut rand::rngs::StdRng::seed_from_u64(TEST_METADATA_SEED);
+    let rng = &mut rand::rngs::StdRng::seed_from_u64(context.seed());
 
     // Prepare the transaction metadata.
     let secret_key = SecretKey::random(rng);
     let utxo_id = rng.gen();
-    let amount = 1;
+    let amount = context.coin_amount();
     let maturity = 1u32.into();
     // NOTE: fuel-core is using dynamic asset id and interacting with the fuel-core, using static
     // asset id is not correct. But since forc-test maintains its own interpreter instance, correct
     // base asset id is indeed the static `tx::AssetId::BASE`.
     let asset_id = tx::AssetId::BASE;
     let tx_pointer = rng.gen();
-    let block_height = (u32::MAX >> 1).into();
+    let block_height = context.block_height().into();
 
     let tx = tx::TransactionBuilder::create(bytecode.as_slice().into(), salt, storage_slots)
         .with_params(params.clone())
@@ -239,6 +239,7 @@ fn opts_from_cmd(cmd: Command) -> forc_test::TestOpts {
             ast: cmd.build.print.ast,
             dca_graph: cmd.build.print.dca_graph.clone(),
             dca_graph_url_format: cmd.build.print.dca_graph_url_format.clone(),
+            trait_map: cmd.build.print.trait_map.clone(),
             asm: cmd.build.print.asm(),
             bytecode: cmd.build.print.bytecode,
             bytecode_spans: false,
@@ -71,6 +71,12 @@ pub struct Print {
     ///   "vscode://file/{path}:{line}:{col}"
     #[clap(long, verbatim_doc_comment)]
     pub dca_graph_url_format: Option<String>,
+    /// Print the trait map of the module with the specified path.
+    ///
+    /// The trait map is printed after the symbol collection and after the type checking.
+    /// The module path must be absolute, e.g., "my_lib::my_module".
+    #[clap(long, verbatim_doc_comment, value_name = "MODULE_PATH")]
+    pub trait_map: Option<String>,
     /// Print the generated ASM (assembler).
     ///
     /// Values that can be combined:
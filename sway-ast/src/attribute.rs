@@ -1,3 +1,4 @@
+use crate::literal::LitString;
 use crate::priv_prelude::*;
 
 #[derive(Clone, Debug, Serialize)]
@@ -28,6 +29,8 @@ pub const FALLBACK_ATTRIBUTE_NAME: &str = "fallback";
 // They are exclusively created by the compiler to denote
 // doc comments, `///` and `//!`.
 pub const DOC_COMMENT_ATTRIBUTE_NAME: &str = "doc-comment";
+// The documentation line is stored as the string value of this argument.
+pub const DOC_COMMENT_TEXT_ARG_NAME: &str = "text";
 
 // In-language unit testing.
 pub const TEST_ATTRIBUTE_NAME: &str = "test";
@@ -37,11 +40,15 @@ pub const TEST_SHOULD_REVERT_ARG_NAME: &str = "should_revert";
 pub const ALLOW_ATTRIBUTE_NAME: &str = "allow";
 pub const ALLOW_DEAD_CODE_ARG_NAME: &str = "dead_code";
 pub const ALLOW_DEPRECATED_ARG_NAME: &str = "deprecated";
+pub const ALLOW_ORPHAN_IMPL_ARG_NAME: &str = "orphan_impl";
 
 // Conditional compilation.
 pub const CFG_ATTRIBUTE_NAME: &str = "cfg";
 pub const CFG_TARGET_ARG_NAME: &str = "target";
 pub const CFG_PROGRAM_TYPE_ARG_NAME: &str = "program_type";
+pub const CFG_ALL_ARG_NAME: &str = "all";
+pub const CFG_ANY_ARG_NAME: &str = "any";
+pub const CFG_NOT_ARG_NAME: &str = "not";
 
 // Deprecation.
 pub const DEPRECATED_ATTRIBUTE_NAME: &str = "deprecated";
@@ -62,7 +69,7 @@ pub const KNOWN_ATTRIBUTE_NAMES: &[&str] = &[
 /// An attribute declaration. Attribute declaration
 /// can potentially have an arbitrary number of [Attribute]s.
 /// [Attribute]s can potentially have any number of [AttributeArg]s.
-/// Each [AttributeArg] can have a value assigned.
+/// Each [AttributeArg] can have a value assigned, or nested [AttributeArg]s.
 ///
 /// E.g.:
 ///
@@ -74,6 +81,8 @@ pub const KNOWN_ATTRIBUTE_NAMES: &[&str] = &[
 /// #[attribute(arg_1, arg_2)]
 /// #[attribute(arg_1 = "value", arg_2 = true)]
 /// #[attribute_1, attribute_2(arg_1), attribute_3(arg_1, arg_2 = true)]
+/// #[attribute(arg_1(arg_2 = "value", arg_3(arg_4)))]
+/// #[namespace::attribute(arg)]
 /// ```
 ///
 /// [AttributeDecl]s can be _inner_ or _outer_, as explained in [AttributeHashKind].
@@ -116,19 +125,28 @@ impl AttributeDecl {
     }
 
     fn new_doc_comment(span: Span, content_span: Span, hash_kind: AttributeHashKind) -> Self {
-        // TODO: Store the comment line in an argument value as
-        //       discussed in https://github.com/FuelLabs/sway/issues/6938.
-        let name = Ident::new_no_trim(content_span.clone());
+        let text = Literal::String(LitString {
+            span: content_span.clone(),
+            parsed: content_span.as_str().to_string(),
+        });
         AttributeDecl {
             hash_kind,
             attribute: SquareBrackets::new(
                 Punctuated::single(Attribute {
+                    namespace: vec![],
                     name: Ident::new_with_override(
                         DOC_COMMENT_ATTRIBUTE_NAME.to_string(),
                         span.clone(),
                     ),
                     args: Some(Parens::new(
-                        Punctuated::single(AttributeArg { name, value: None }),
+                        Punctuated::single(AttributeArg {
+                            name: Ident::new_with_override(
+                                DOC_COMMENT_TEXT_ARG_NAME.to_string(),
+                                content_span.clone(),
+                            ),
+                            value: Some(text),
+                            args: None,
+                        }),
                         content_span,
                     )),
                 }),
@@ -207,12 +225,17 @@ impl Spanned for AttributeHashKind {
 pub struct AttributeArg {
     pub name: Ident,
     pub value: Option<Literal>,
+    /// Nested arguments, e.g., `target = "fuel"` and `not(program_type = "script")`
+    /// in `all(target = "fuel", not(program_type = "script"))`.
+    pub args: Option<Parens<Punctuated<AttributeArg, CommaToken>>>,
 }
 
 impl Spanned for AttributeArg {
     fn span(&self) -> Span {
         if let Some(value) = &self.value {
             Span::join(self.name.span(), &value.span())
+        } else if let Some(args) = &self.args {
+            Span::join(self.name.span(), &args.span())
         } else {
             self.name.span()
         }
@@ -221,16 +244,56 @@ impl Spanned for AttributeArg {
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Attribute {
+    /// The namespace of a namespaced attribute, e.g., `fuels::` in `#[fuels::test]`.
+    /// Empty if the attribute is not namespaced, which is the case for all the built-in attributes.
+    pub namespace: Vec<(Ident, DoubleColonToken)>,
     pub name: Ident,
     pub args: Option<Parens<Punctuated<AttributeArg, CommaToken>>>,
 }
 
 impl Attribute {
     pub fn is_doc_comment(&self) -> bool {
-        self.name.as_str() == DOC_COMMENT_ATTRIBUTE_NAME
+        !self.is_namespaced() && self.name.as_str() == DOC_COMMENT_ATTRIBUTE_NAME
+    }
+    /// Returns the documentation line of a doc-comment attribute,
+    /// or `None` if `self` is not a doc comment.
+    pub fn doc_comment_text(&self) -> Option<&str> {
+        if !self.is_doc_comment() {
+            return None;
+        }
+        self.args
+            .as_ref()
+            .and_then(|args| args.inner.final_value_opt.as_ref())
+            .and_then(|arg| match &arg.value {
+                Some(Literal::String(lit_string)) => Some(lit_string.parsed.as_str()),
+                _ => None,
+            })
     }
     pub fn is_cfg(&self) -> bool {
-        self.name.as_str() == CFG_ATTRIBUTE_NAME
+        !self.is_namespaced() && self.name.as_str() == CFG_ATTRIBUTE_NAME
+    }
+    pub fn is_namespaced(&self) -> bool {
+        !self.namespace.is_empty()
+    }
+
+    /// Returns the full path of the attribute, e.g., `fuels::test` for `#[fuels::test]`,
+    /// or just the name, if the attribute is not namespaced.
+    pub fn path_str(&self) -> String {
+        self.namespace
+            .iter()
+            .map(|(segment, _)| segment.as_str())
+            .chain(std::iter::once(self.name.as_str()))
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    /// Returns the [Span] of the full path of the attribute, e.g., `fuels::test` for `#[fuels::test]`,
+    /// or just the span of the name, if the attribute is not namespaced.
+    pub fn path_span(&self) -> Span {
+        match self.namespace.first() {
+            Some((first_segment, _)) => Span::join(first_segment.span(), &self.name.span()),
+            None => self.name.span(),
+        }
     }
 }
 
@@ -238,7 +301,7 @@ impl Spanned for Attribute {
     fn span(&self) -> Span {
         self.args
             .as_ref()
-            .map(|args| Span::join(self.name.span(), &args.span()))
-            .unwrap_or_else(|| self.name.span())
+            .map(|args| Span::join(self.path_span(), &args.span()))
+            .unwrap_or_else(|| self.path_span())
     }
 }
@@ -14,6 +14,10 @@ pub struct GenericParams {
 #[derive(Clone, Debug, Serialize)]
 pub struct GenericArgs {
     pub parameters: AngleBrackets<Punctuated<Ty, CommaToken>>,
+    /// Associated type bindings, e.g., `Item = u64` in `Iterator<Item = u64>`.
+    /// Bindings are written inside of the angle brackets, after all of the
+    /// positional type arguments in `parameters`.
+    pub associated_type_bindings: Punctuated<AssociatedTypeBinding, CommaToken>,
 }
 
 impl Spanned for GenericArgs {
@@ -21,3 +25,17 @@ impl Spanned for GenericArgs {
         self.parameters.span()
     }
 }
+
+/// An associated type binding in generic arguments, e.g., `Item = u64`.
+#[derive(Clone, Debug, Serialize)]
+pub struct AssociatedTypeBinding {
+    pub name: Ident,
+    pub eq_token: EqToken,
+    pub ty: Ty,
+}
+
+impl Spanned for AssociatedTypeBinding {
+    fn span(&self) -> Span {
+        Span::join(self.name.span(), &self.ty.span())
+    }
+}
@@ -28,7 +28,7 @@ pub use crate::{
         AbiCastArgs, CodeBlockContents, Expr, ExprArrayDescriptor, ExprStructField,
         ExprTupleDescriptor, IfCondition, IfExpr, MatchBranch, MatchBranchKind,
     },
-    generics::{GenericArgs, GenericParams},
+    generics::{AssociatedTypeBinding, GenericArgs, GenericParams},
     intrinsics::*,
     item::{
         item_abi::ItemAbi,
@@ -7,6 +7,11 @@ pub struct Module {
     pub kind: ModuleKind,
     pub semicolon_token: SemicolonToken,
     pub items: Vec<Item>,
+    /// The span of the whole module file, including the leading inner doc comments
+    /// and attributes that precede the module kind.
+    /// Unlike the spans of the items, it also covers an empty module and the trailing
+    /// comments and whitespace.
+    pub span: Span,
 }
 
 impl Module {
@@ -27,13 +32,7 @@ impl Module {
 
 impl Spanned for Module {
     fn span(&self) -> Span {
-        let start = self.kind.span();
-        let end = if let Some(item) = self.items.last() {
-            item.span()
-        } else {
-            self.semicolon_token.span()
-        };
-        Span::join(start, &end)
+        self.span.clone()
     }
 }
 
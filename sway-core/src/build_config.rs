@@ -185,6 +185,7 @@ pub struct BuildConfig {
     pub(crate) canonical_root_module: Arc<PathBuf>,
     pub(crate) print_dca_graph: Option<String>,
     pub(crate) print_dca_graph_url_format: Option<String>,
+    pub(crate) print_trait_map: Option<String>,
     pub(crate) print_asm: PrintAsm,
     pub(crate) print_bytecode: bool,
     pub(crate) print_bytecode_spans: bool,
@@ -233,6 +234,7 @@ impl BuildConfig {
             canonical_root_module: Arc::new(canonical_root_module),
             print_dca_graph: None,
             print_dca_graph_url_format: None,
+            print_trait_map: None,
             print_asm: PrintAsm::default(),
             print_bytecode: false,
             print_bytecode_spans: false,
@@ -260,6 +262,15 @@ impl BuildConfig {
         }
     }
 
+    /// Print the trait map of the module with the given absolute path,
+    /// e.g., `my_lib::my_module`, after the symbol collection and after the type checking.
+    pub fn with_print_trait_map(self, a: Option<String>) -> Self {
+        Self {
+            print_trait_map: a,
+            ..self
+        }
+    }
+
     pub fn with_print_asm(self, print_asm: PrintAsm) -> Self {
         Self { print_asm, ..self }
     }
@@ -7,6 +7,7 @@ use crate::{
         DebugWithEngines, EqWithEngines, PartialEqWithEngines, PartialEqWithEnginesContext,
     },
     language::CallPath,
+    transform,
     type_system::TypeArgument,
     Engines, TypeParameter,
 };
@@ -75,6 +76,7 @@ pub struct ImplSelfOrTrait {
     pub trait_decl_ref: Option<ParsedInterfaceDeclId>,
     pub implementing_for: TypeArgument,
     pub items: Vec<ImplItem>,
+    pub attributes: transform::Attributes,
     /// The [Span] of the whole impl trait and block.
     pub(crate) block_span: Span,
 }
@@ -24,6 +24,9 @@ pub struct TyConstantDecl {
     pub return_type: TypeId,
     pub type_ascription: TypeArgument,
     pub span: Span,
+    /// True for constants declared in a trait or an ABI. Their values, if any,
+    /// are defaults that implementations can override.
+    pub is_trait_const_default: bool,
 }
 
 impl TyDeclParsedType for TyConstantDecl {
@@ -63,6 +66,7 @@ impl HashWithEngines for TyConstantDecl {
             // reliable source of obj v. obj distinction
             attributes: _,
             span: _,
+            is_trait_const_default: _,
         } = self;
         call_path.hash(state);
         value.hash(state, engines);
@@ -333,6 +333,12 @@ pub(crate) fn attr_decls_to_attributes(
                     }
                 }
 
+                // The `cfg` predicates never have values, and they are checked
+                // together with their nested arguments during the cfg-evaluation.
+                if attribute.kind == AttributeKind::Cfg && arg.is_cfg_predicate() {
+                    continue;
+                }
+
                 if (is_value_expected && arg.value.is_none())
                     || (!is_value_expected && arg.value.is_some())
                 {
@@ -355,6 +361,27 @@ pub(crate) fn attr_decls_to_attributes(
         }
     }
 
+    // Check for nested arguments.
+    // Only the `cfg` predicates can have nested arguments, which are checked
+    // during the cfg-evaluation. Unknown attributes can have any arguments.
+    for attribute in attributes.all().filter(|attr| {
+        should_be_checked(attr) && attr.can_have_arguments() && attr.kind != AttributeKind::Unknown
+    }) {
+        for arg in attribute.args.iter().filter(|arg| !arg.args.is_empty()) {
+            if attribute.kind == AttributeKind::Cfg && arg.is_cfg_predicate() {
+                continue;
+            }
+            handler.emit_err(
+                ConvertParseTreeError::InvalidAttributeArgNestedArgs {
+                    span: arg.span(),
+                    attribute: attribute.name.clone(),
+                    arg: (&arg.name).into(),
+                }
+                .into(),
+            );
+        }
+    }
+
     (handler, attributes)
 }
 
@@ -372,7 +399,8 @@ fn parse_in_memory(
 
     let (attributes_handler, attributes) = attr_decls_to_attributes(
         &module.attributes,
-        |attr| attr.can_annotate_module_kind(),
+        // Outer doc comments before the module kind are already reported by the parser.
+        |attr| attr.can_annotate_module_kind() || (attr.is_doc_comment() && attr.is_outer()),
         module.value.kind.friendly_name(),
     );
     let attributes_error_emitted = handler.append(attributes_handler);
@@ -539,7 +567,8 @@ fn parse_module_tree(
 
     let (attributes_handler, attributes) = attr_decls_to_attributes(
         &module.attributes,
-        |attr| attr.can_annotate_module_kind(),
+        // Outer doc comments before the module kind are already reported by the parser.
+        |attr| attr.can_annotate_module_kind() || (attr.is_doc_comment() && attr.is_outer()),
         module.value.kind.friendly_name(),
     );
     let attributes_error_emitted = handler.append(attributes_handler);
@@ -731,6 +760,44 @@ pub fn build_module_dep_graph(
 
 pub struct CompiledAsm(pub FinalizedAsm);
 
+/// Prints the trait map of the module requested in [BuildConfig::with_print_trait_map],
+/// if the module is a part of the `package`.
+fn print_trait_map(
+    engines: &Engines,
+    build_config: Option<&BuildConfig>,
+    package: &namespace::Package,
+    phase: &str,
+) {
+    let Some(mod_path) = build_config.and_then(|config| config.print_trait_map.as_deref()) else {
+        return;
+    };
+
+    if let Some(dump) = trait_map_dump(engines, package, mod_path, phase) {
+        tracing::info!("{dump}");
+    }
+}
+
+/// Returns the rendering of the trait map of the module with the absolute `mod_path`,
+/// e.g., `my_lib::my_module`, or `None` if the module is not a part of the `package`.
+fn trait_map_dump(
+    engines: &Engines,
+    package: &namespace::Package,
+    mod_path: &str,
+    phase: &str,
+) -> Option<String> {
+    let mod_path = mod_path
+        .split("::")
+        .map(|name| Ident::new_no_span(name.to_string()))
+        .collect::<Vec<_>>();
+    package.module_from_absolute_path(&mod_path).map(|module| {
+        format!(
+            "// Trait map of module \"{}\" after {phase}:\n{:?}",
+            mod_path.iter().map(|name| name.as_str()).join("::"),
+            engines.help_out(&module.root_items().implemented_traits)
+        )
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn parsed_to_ast(
     handler: &Handler,
@@ -767,6 +834,12 @@ pub fn parsed_to_ast(
                 error,
             },
         )?;
+    print_trait_map(
+        engines,
+        build_config,
+        collection_ctx.namespace().current_package_ref(),
+        "symbol collection",
+    );
 
     let typecheck_namespace =
         Namespace::new(handler, engines, initial_namespace, true).map_err(|error| {
@@ -789,6 +862,12 @@ pub fn parsed_to_ast(
     );
 
     let mut typed_program = typed_program_opt?;
+    print_trait_map(
+        engines,
+        build_config,
+        typed_program.namespace.current_package_ref(),
+        "type checking",
+    );
 
     check_should_abort(handler, retrigger_compilation.clone()).map_err(|error| {
         TypeCheckFailed {
@@ -1013,6 +1092,15 @@ pub fn compile_to_ast(
 
     check_should_abort(handler, retrigger_compilation.clone())?;
 
+    if build_config.is_some_and(|config| config.profile) {
+        let package = match &program {
+            Ok(typed_program) => typed_program.namespace.current_package_ref(),
+            Err(type_check_failed) => &type_check_failed.namespace,
+        };
+        let trait_map_profile = namespace::TraitMapProfile::from_root_module(package.root_module());
+        println!("{}", trait_map_profile.to_profile_line(package_name));
+    }
+
     handler.dedup();
 
     let programs = Programs::new(lexed_program, parsed_program, program, metrics);
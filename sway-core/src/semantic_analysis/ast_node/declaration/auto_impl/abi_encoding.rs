@@ -536,13 +536,15 @@ where
     fn check_impl_is_missing(handler: &Handler, gen_handler: &Handler) {
         let constraint_not_satisfied = gen_handler.find_error(|x| {
             matches!(x, CompileError::TraitConstraintNotSatisfied { trait_name, .. }
+                | CompileError::ImplTraitConstraintNotSatisfied { trait_name, .. }
                 if trait_name == "AbiEncode" || trait_name == "AbiDecode" && {
                 true
             })
         });
         if let Some(constraint_not_satisfied) = constraint_not_satisfied {
             let ty = match constraint_not_satisfied {
-                CompileError::TraitConstraintNotSatisfied { ty, .. } => ty,
+                CompileError::TraitConstraintNotSatisfied { ty, .. }
+                | CompileError::ImplTraitConstraintNotSatisfied { ty, .. } => ty,
                 _ => unreachable!("unexpected error"),
             };
             handler.emit_err(CompileError::CouldNotGenerateEntryMissingImpl {
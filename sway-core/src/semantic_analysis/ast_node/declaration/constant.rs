@@ -109,6 +109,7 @@ impl ty::TyConstantDecl {
             span,
             value,
             visibility,
+            is_trait_const_default: false,
         };
         Ok(decl)
     }
@@ -133,6 +134,7 @@ impl ty::TyConstantDecl {
             type_ascription,
             value: None,
             visibility,
+            is_trait_const_default: false,
         }
     }
 }
@@ -119,6 +119,11 @@ impl TyImplSelfOrTrait {
             }
         });
 
+        // Impls generated by the compiler and impls explicitly marked with
+        // `#[allow(orphan_impl)]` are not checked for coherence.
+        let check_orphan_impl = !(impl_trait.attributes.has_allow_orphan_impl()
+            || impl_trait.is_autogenerated(ctx.engines.se()));
+
         let ImplSelfOrTrait {
             impl_type_parameters,
             impl_const_generics_parameters,
@@ -248,6 +253,16 @@ impl TyImplSelfOrTrait {
                     Some(ty::TyDecl::TraitDecl(ty::TraitDecl { decl_id, .. })) => {
                         let mut trait_decl = (*decl_engine.get_trait(&decl_id)).clone();
 
+                        if check_orphan_impl {
+                            let _ = check_for_orphan_impl(
+                                handler,
+                                &ctx,
+                                &trait_decl,
+                                &trait_name,
+                                &implementing_for,
+                            );
+                        }
+
                         // the following essentially is needed to map `Self` to `implementing_for`
                         // during trait decl monomorphization
                         trait_decl
@@ -782,6 +797,18 @@ fn type_check_trait_implementation(
 
     // Check to see if the type that we are implementing for implements the
     // supertraits of this trait.
+    // The supertraits are matched by the full path of their declarations, if known,
+    // so that a different trait with the same name does not satisfy the constraint.
+    let supertrait_constraints = trait_supertraits
+        .iter()
+        .map(|supertrait| {
+            let mut constraint = TraitConstraint::from(supertrait);
+            if let Some(decl_ref) = &supertrait.decl_ref {
+                constraint.trait_name = decl_engine.get_trait(decl_ref.id()).call_path.clone();
+            }
+            constraint
+        })
+        .collect::<Vec<_>>();
     ctx.namespace_mut()
         .current_module_mut()
         .write(engines, |m| {
@@ -789,10 +816,7 @@ fn type_check_trait_implementation(
                 handler,
                 m,
                 implementing_for,
-                &trait_supertraits
-                    .iter()
-                    .map(|x| x.into())
-                    .collect::<Vec<_>>(),
+                &supertrait_constraints,
                 block_span,
                 engines,
             )
@@ -991,6 +1015,7 @@ fn type_check_trait_implementation(
                     ctx.by_ref().with_type_subst(&trait_type_mapping),
                     &const_decl,
                     trait_name,
+                    implementing_for,
                     is_contract,
                     &impld_item_refs,
                     &constant_checklist,
@@ -1503,6 +1528,7 @@ fn type_check_const_decl(
     mut ctx: TypeCheckContext,
     const_decl: &ConstantDeclaration,
     trait_name: &CallPath,
+    self_type: TypeId,
     is_contract: bool,
     impld_constant_ids: &ItemMap,
     constant_checklist: &BTreeMap<Ident, Arc<ty::TyConstantDecl>>,
@@ -1510,10 +1536,6 @@ fn type_check_const_decl(
     let type_engine = ctx.engines.te();
     let engines = ctx.engines();
 
-    // Create a new type parameter for the "self type".
-    let self_type_param = TypeParameter::new_self_type(engines, const_decl.span.clone());
-    let self_type_id = self_type_param.type_id;
-
     let mut ctx = ctx
         .by_ref()
         .with_help_text("")
@@ -1548,7 +1570,7 @@ fn type_check_const_decl(
     }
 
     // Ensure that there aren't multiple definitions of this constant
-    if impld_constant_ids.contains_key(&(const_name.clone(), self_type_id)) {
+    if impld_constant_ids.contains_key(&(const_name.clone(), self_type)) {
         return Err(
             handler.emit_err(CompileError::MultipleDefinitionsOfConstant {
                 name: const_name.clone(),
@@ -1731,6 +1753,51 @@ fn check_for_unconstrained_type_parameters(
     })
 }
 
+/// Checks that either the trait or the type that it is implemented for is
+/// declared in the current package.
+///
+/// Otherwise, two dependencies could implement a trait from a third package
+/// for a type from that same package, and the conflicting impls would collide
+/// only in the packages that depend on both of them.
+///
+/// Only structs and enums, and references to them, are declared in a package.
+/// Impls for other types, like built-in types or generic type parameters, are
+/// not checked.
+fn check_for_orphan_impl(
+    handler: &Handler,
+    ctx: &TypeCheckContext,
+    trait_decl: &ty::TyTraitDecl,
+    trait_name: &CallPath,
+    implementing_for: &TypeArgument,
+) -> Result<(), ErrorEmitted> {
+    let engines = ctx.engines();
+    let package_name = ctx.namespace().current_package_name();
+    let is_local = |call_path: &CallPath| call_path.prefixes.first() == Some(package_name);
+
+    let mut type_id = implementing_for.type_id;
+    let type_call_path = loop {
+        match &*engines.te().get_unaliased(type_id) {
+            TypeInfo::Struct(decl_id) => break engines.de().get_struct(decl_id).call_path.clone(),
+            TypeInfo::Enum(decl_id) => break engines.de().get_enum(decl_id).call_path.clone(),
+            TypeInfo::Ref {
+                referenced_type, ..
+            } => type_id = referenced_type.type_id,
+            _ => return Ok(()),
+        }
+    };
+
+    if is_local(&trait_decl.call_path) || is_local(&type_call_path) {
+        return Ok(());
+    }
+
+    Err(handler.emit_err(CompileError::OrphanTraitImpl {
+        trait_name: trait_decl.call_path.to_string(),
+        type_name: engines.help_out(implementing_for.type_id).to_string(),
+        package_name: package_name.to_string(),
+        span: Span::join(trait_name.span(), &implementing_for.span),
+    }))
+}
+
 fn handle_supertraits(
     handler: &Handler,
     mut ctx: TypeCheckContext,
@@ -17,6 +17,26 @@ pub enum SupertraitOf {
     Trait,
 }
 
+/// Returns the declaration of the `supertrait`.
+///
+/// The declaration resolved when type checking the subtrait is preferred, because
+/// the supertrait does not have to be in scope where the subtrait is used, e.g.,
+/// when only the subtrait is imported into a module.
+pub(crate) fn resolve_supertrait_decl(
+    ctx: &TypeCheckContext,
+    supertrait: &parsed::Supertrait,
+) -> Option<ty::TyDecl> {
+    match &supertrait.decl_ref {
+        Some(decl_ref) => Some(ty::TyDecl::TraitDecl(ty::TraitDecl {
+            decl_id: *decl_ref.id(),
+        })),
+        None => ctx
+            // Use the default Handler to avoid emitting the redundant SymbolNotFound error.
+            .resolve_call_path(&Handler::default(), &supertrait.name)
+            .ok(),
+    }
+}
+
 /// Recursively insert the interface surfaces and methods from supertraits to
 /// the given namespace.
 pub(crate) fn insert_supertraits_into_namespace(
@@ -54,10 +74,7 @@ pub(crate) fn insert_supertraits_into_namespace(
                 continue;
             }
 
-            let decl = ctx
-                // Use the default Handler to avoid emitting the redundant SymbolNotFound error.
-                .resolve_call_path(&Handler::default(), &supertrait.name)
-                .ok();
+            let decl = resolve_supertrait_decl(&ctx, supertrait);
 
             match (decl.clone(), supertraits_of) {
                 // a trait can be a supertrait of either a trait or a an ABI
@@ -195,8 +195,9 @@ impl TyTraitDecl {
                         }
                         TraitItem::Constant(decl_id) => {
                             let const_decl = engines.pe().get_constant(&decl_id).as_ref().clone();
-                            let const_decl =
+                            let mut const_decl =
                                 ty::TyConstantDecl::type_check(handler, ctx.by_ref(), const_decl)?;
+                            const_decl.is_trait_const_default = true;
                             let decl_ref =
                                 ctx.engines.de().insert(const_decl.clone(), Some(&decl_id));
                             new_interface_surface
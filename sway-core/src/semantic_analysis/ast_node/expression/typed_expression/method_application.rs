@@ -3,6 +3,7 @@ use crate::{
         engine::{DeclEngineGet, DeclEngineGetParsedDeclId, DeclEngineReplace},
         DeclEngineInsert, DeclRefFunction, ReplaceDecls, UpdateConstantExpression,
     },
+    engine_threading::Engines,
     language::{
         parsed::*,
         ty::{self, TyDecl, TyExpression, TyFunctionSig},
@@ -747,8 +748,9 @@ pub(crate) fn type_check_method_application(
             // Handle the trait constraints. This includes checking to see if the trait
             // constraints are satisfied and replacing old decl ids based on the
             // constraint with new decl ids based on the new type.
+            let trait_constraints_handler = Handler::default();
             let decl_mapping = TypeParameter::gather_decl_mapping_from_trait_constraints(
-                handler,
+                &trait_constraints_handler,
                 ctx.by_ref(),
                 &method.type_parameters,
                 method.name.as_str(),
@@ -756,6 +758,16 @@ pub(crate) fn type_check_method_application(
             )
             .ok();
 
+            let (errors, warnings) = trait_constraints_handler.consume();
+            for warning in warnings {
+                handler.emit_warn(warning);
+            }
+            for error in errors {
+                handler.emit_err(explain_unsatisfied_impl_trait_constraint(
+                    engines, &method, error,
+                ));
+            }
+
             if let Some(decl_mapping) = decl_mapping {
                 method.replace_decls(&decl_mapping, handler, &mut ctx)?;
             }
@@ -1012,3 +1024,63 @@ pub(crate) fn monomorphize_method(
 
     Ok(decl_ref)
 }
+
+/// Replaces a [CompileError::TraitConstraintNotSatisfied] caused by a type parameter of the
+/// impl that the `method` belongs to with a [CompileError::ImplTraitConstraintNotSatisfied],
+/// which points to that impl.
+///
+/// E.g., for `impl<T: Hash> Store for Vec<T>` and a method call on `Vec<MyType>`, the error
+/// explains that `MyType` must implement `Hash` because of the impl of `Store` for `Vec<T>`.
+fn explain_unsatisfied_impl_trait_constraint(
+    engines: &Engines,
+    method: &ty::TyFunctionDecl,
+    error: CompileError,
+) -> CompileError {
+    let Some(TyDecl::ImplSelfOrTrait(impl_self_or_trait)) = &method.implementing_type else {
+        return error;
+    };
+
+    match error {
+        CompileError::TraitConstraintNotSatisfied {
+            type_id,
+            ty,
+            trait_name,
+            span,
+        } => {
+            let impl_type_parameter = method.type_parameters.iter().find(|type_parameter| {
+                type_parameter.is_from_parent
+                    && engines
+                        .te()
+                        .get_unaliased_type_id(type_parameter.type_id)
+                        .index()
+                        == type_id
+            });
+            match impl_type_parameter {
+                Some(impl_type_parameter) => {
+                    let impl_decl = engines
+                        .de()
+                        .get_impl_self_or_trait(&impl_self_or_trait.decl_id);
+                    CompileError::ImplTraitConstraintNotSatisfied {
+                        ty,
+                        trait_name,
+                        type_parameter: impl_type_parameter.name.to_string(),
+                        impl_trait_name: impl_decl
+                            .trait_decl_ref
+                            .as_ref()
+                            .map(|_| impl_decl.trait_name.suffix.to_string()),
+                        implementing_for: impl_decl.implementing_for.span.as_str().to_string(),
+                        impl_span: impl_decl.span.clone(),
+                        span,
+                    }
+                }
+                None => CompileError::TraitConstraintNotSatisfied {
+                    type_id,
+                    ty,
+                    trait_name,
+                    span,
+                },
+            }
+        }
+        error => error,
+    }
+}
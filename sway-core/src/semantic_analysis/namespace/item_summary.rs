@@ -0,0 +1,317 @@
+use sway_types::{Ident, Span, Spanned};
+
+use crate::{
+    language::{
+        ty::{self, VariableMutability},
+        CallPath,
+    },
+    Engines, TypeId,
+};
+
+use super::{trait_map::IsImplSelf, Module, ResolvedTraitImplItem, TraitMap};
+
+/// The kind of an item available on a type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ItemSummaryKind {
+    Method,
+    Constant,
+    AssociatedType,
+}
+
+/// A summary of an item that is available on a type, meant to be consumed
+/// by tooling, e.g., for completions in the language server.
+///
+/// New fields can be added in the future, so the summaries can only be
+/// obtained from [items_for_type_at].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ItemSummary {
+    /// The name of the item. Its span points to the name in the item declaration.
+    pub name: Ident,
+    pub kind: ItemSummaryKind,
+    /// The path of the trait that declares the item,
+    /// or `None` if the item comes from an `impl Type` block.
+    pub trait_path: Option<CallPath>,
+    /// The mutability of the `self` parameter of a method,
+    /// or `None` if the item is not a method that takes `self`.
+    pub self_mutability: Option<VariableMutability>,
+    /// The rendered return type of a method, the type of a constant,
+    /// or the type an associated type is set to, if any.
+    ///
+    /// The types are rendered as declared in the impl, e.g.,
+    /// a method of `impl<T> Wrapper<T>` that returns `T` has the type `T`.
+    pub ty: Option<String>,
+    /// The span of the whole item declaration.
+    pub span: Span,
+    /// The span of the impl block that provides the item.
+    pub impl_span: Span,
+}
+
+/// Returns the summaries of all the items available on `type_id` at a
+/// position within the `module`. The items are looked up in the current
+/// lexical scope of the `module` and all of its parent scopes.
+///
+/// Items visible in several scopes are returned only once, and the
+/// placeholders of trait methods that are not callable on the type are
+/// omitted. The summaries are sorted by the item name and the trait path.
+///
+/// Example:
+///
+/// ```rust,ignore
+/// // struct Point { x: u64 }
+/// // impl Point { fn x(self) -> u64 { self.x } }
+/// let items = namespace::items_for_type_at(module, engines, point_type_id);
+/// assert_eq!(items[0].name.as_str(), "x");
+/// assert_eq!(items[0].kind, ItemSummaryKind::Method);
+/// assert_eq!(items[0].self_mutability, Some(VariableMutability::Immutable));
+/// assert_eq!(items[0].ty.as_deref(), Some("u64"));
+/// ```
+pub fn items_for_type_at(module: &Module, engines: &Engines, type_id: TypeId) -> Vec<ItemSummary> {
+    let mut summaries = TraitMap::get_items_and_trait_key_for_type(module, engines, type_id)
+        .into_iter()
+        .filter_map(|(item, trait_key, origin, impl_span)| {
+            let ResolvedTraitImplItem::Typed(item) = item else {
+                return None;
+            };
+            let (kind, self_mutability, ty, span) = match &item {
+                ty::TyTraitItem::Fn(decl_ref) => {
+                    let decl = engines.de().get_function(decl_ref);
+                    let self_mutability = decl
+                        .parameters
+                        .first()
+                        .filter(|param| param.is_self())
+                        .map(|param| {
+                            VariableMutability::new_from_ref_mut(
+                                param.is_reference,
+                                param.is_mutable,
+                            )
+                        });
+                    (
+                        ItemSummaryKind::Method,
+                        self_mutability,
+                        Some(engines.help_out(decl.return_type.type_id).to_string()),
+                        decl.span.clone(),
+                    )
+                }
+                ty::TyTraitItem::Constant(decl_ref) => {
+                    let decl = engines.de().get_constant(decl_ref);
+                    (
+                        ItemSummaryKind::Constant,
+                        None,
+                        Some(engines.help_out(decl.return_type).to_string()),
+                        decl.span.clone(),
+                    )
+                }
+                ty::TyTraitItem::Type(decl_ref) => {
+                    let decl = engines.de().get_type(decl_ref);
+                    (
+                        ItemSummaryKind::AssociatedType,
+                        None,
+                        decl.ty
+                            .as_ref()
+                            .map(|ty| engines.help_out(ty.type_id).to_string()),
+                        decl.span.clone(),
+                    )
+                }
+            };
+            let trait_path = match origin.is_impl_self {
+                IsImplSelf::Yes => None,
+                IsImplSelf::No => Some(CallPath {
+                    prefixes: trait_key.name.prefixes.clone(),
+                    suffix: trait_key.name.suffix.name.clone(),
+                    callpath_type: trait_key.name.callpath_type,
+                }),
+            };
+
+            Some(ItemSummary {
+                name: ResolvedTraitImplItem::Typed(item).name(engines),
+                kind,
+                trait_path,
+                self_mutability,
+                ty,
+                span,
+                impl_span,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    summaries.sort_by_cached_key(|summary| {
+        (
+            summary.name.as_str().to_string(),
+            summary.trait_path.as_ref().map(ToString::to_string),
+            summary.span.start(),
+        )
+    });
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        language::ty::{TyDecl, TyProgram},
+        namespace::Package,
+        ExperimentalFeatures,
+    };
+    use sway_error::handler::Handler;
+    use sway_types::ProgramId;
+
+    fn compile(src: &str) -> (Engines, TyProgram) {
+        let engines = Engines::default();
+        let handler = Handler::default();
+        let package = Package::new(
+            Ident::new_no_span("items_for_type_at_test".to_string()),
+            None,
+            ProgramId::new(0),
+            false,
+        );
+        let programs = crate::compile_to_ast(
+            &handler,
+            &engines,
+            src.into(),
+            package,
+            None,
+            "test",
+            None,
+            ExperimentalFeatures::default(),
+        );
+        let (errors, _warnings) = handler.consume();
+        if !errors.is_empty() {
+            panic!("{:#?}", errors);
+        }
+        let program = programs.unwrap().typed.unwrap();
+        (engines, program)
+    }
+
+    fn return_type_of(engines: &Engines, program: &TyProgram, fn_name: &str) -> TypeId {
+        program
+            .declarations
+            .iter()
+            .find_map(|decl| match decl {
+                TyDecl::FunctionDecl(decl) => {
+                    let decl = engines.de().get_function(&decl.decl_id);
+                    (decl.name.as_str() == fn_name).then_some(decl.return_type.type_id)
+                }
+                _ => None,
+            })
+            .expect("the function is declared")
+    }
+
+    #[test]
+    fn items_of_struct_with_inherent_and_trait_items_are_summarized() {
+        let src = r#"
+            library;
+
+            pub struct Wrapper<T> {
+                value: T,
+            }
+
+            impl<T> Wrapper<T> {
+                const ID: u64 = 42;
+
+                fn new(value: T) -> Self {
+                    Self { value }
+                }
+
+                fn get(self) -> T {
+                    self.value
+                }
+
+                fn set(ref mut self, value: T) {
+                    self.value = value;
+                }
+            }
+
+            trait Describe {
+                type Output;
+                fn describe(self) -> u64;
+            }
+
+            impl Describe for Wrapper<u64> {
+                type Output = bool;
+                fn describe(self) -> u64 {
+                    self.value
+                }
+            }
+
+            impl Describe for Wrapper<bool> {
+                type Output = u64;
+                fn describe(self) -> u64 {
+                    0
+                }
+            }
+
+            fn wrapper_of_u64() -> Wrapper<u64> {
+                Wrapper::new(0)
+            }
+        "#;
+        let (engines, program) = compile(src);
+        let module = program.namespace.current_module();
+        let type_id = return_type_of(&engines, &program, "wrapper_of_u64");
+
+        let summaries = items_for_type_at(module, &engines, type_id)
+            .into_iter()
+            .map(|summary| {
+                assert_eq!(summary.name.span().as_str(), summary.name.as_str());
+                assert!(summary.span.as_str().contains(summary.name.as_str()));
+                assert!(summary.impl_span.as_str().starts_with("impl"));
+                (
+                    summary.name.to_string(),
+                    summary.kind,
+                    summary.trait_path.map(|path| path.suffix.to_string()),
+                    summary.self_mutability,
+                    summary.ty,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summaries,
+            vec![
+                (
+                    "ID".to_string(),
+                    ItemSummaryKind::Constant,
+                    None,
+                    None,
+                    Some("u64".to_string())
+                ),
+                (
+                    "Output".to_string(),
+                    ItemSummaryKind::AssociatedType,
+                    Some("Describe".to_string()),
+                    None,
+                    Some("bool".to_string())
+                ),
+                (
+                    "describe".to_string(),
+                    ItemSummaryKind::Method,
+                    Some("Describe".to_string()),
+                    Some(VariableMutability::Immutable),
+                    Some("u64".to_string())
+                ),
+                (
+                    "get".to_string(),
+                    ItemSummaryKind::Method,
+                    None,
+                    Some(VariableMutability::Immutable),
+                    Some("T".to_string())
+                ),
+                (
+                    "new".to_string(),
+                    ItemSummaryKind::Method,
+                    None,
+                    None,
+                    Some("Wrapper<T>".to_string())
+                ),
+                (
+                    "set".to_string(),
+                    ItemSummaryKind::Method,
+                    None,
+                    Some(VariableMutability::RefMutable),
+                    Some("()".to_string())
+                ),
+            ]
+        );
+    }
+}
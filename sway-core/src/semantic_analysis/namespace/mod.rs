@@ -1,4 +1,5 @@
 mod contract_helpers;
+mod item_summary;
 mod lexical_scope;
 mod module;
 #[allow(clippy::module_inception)]
@@ -8,6 +9,7 @@ mod resolved_declaration;
 mod trait_map;
 
 pub use contract_helpers::*;
+pub use item_summary::{items_for_type_at, ItemSummary, ItemSummaryKind};
 pub use lexical_scope::{Items, LexicalScope, LexicalScopeId, LexicalScopePath};
 pub use module::module_not_found;
 pub use module::Module;
@@ -16,8 +18,12 @@ pub use package::Package;
 pub use resolved_declaration::ResolvedDeclaration;
 pub(crate) use trait_map::IsExtendingExistingImpl;
 pub(crate) use trait_map::IsImplSelf;
-pub(super) use trait_map::ResolvedTraitImplItem;
+pub use trait_map::ModuleTraitMapStats;
+pub use trait_map::ResolvedTraitImplItem;
+pub use trait_map::SatisfiedTraitConstraint;
 pub use trait_map::TraitMap;
+pub use trait_map::TraitMapProfile;
+pub use trait_map::TraitMapStats;
 pub use trait_map::TryInsertingTraitImplOnFailure;
 
 use sway_types::Ident;
@@ -3,28 +3,37 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt,
     hash::{DefaultHasher, Hash, Hasher},
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicU64},
+        Arc,
+    },
 };
 
+use parking_lot::Mutex;
+use rayon_cond::CondIterator;
+use serde::{Deserialize, Serialize};
 use sway_error::{
-    error::CompileError,
+    error::{ApplicableItemCandidate, CompileError, ImplForOtherRefMutability},
+    formatting::did_you_mean,
     handler::{ErrorEmitted, Handler},
+    warning::{CompileWarning, Warning},
 };
-use sway_types::{integer_bits::IntegerBits, BaseIdent, Ident, Span, Spanned};
+use sway_types::{integer_bits::IntegerBits, Ident, Span, Spanned};
 
 use crate::{
     decl_engine::{
-        parsed_id::ParsedDeclId, DeclEngineGet, DeclEngineGetParsedDeclId, DeclEngineInsert,
+        parsed_id::ParsedDeclId, DeclEngineGet, DeclEngineGetParsedDeclId, DeclEngineInsert, DeclId,
     },
     engine_threading::*,
     language::{
         parsed::{EnumDeclaration, ImplItem, StructDeclaration},
         ty::{self, TyDecl, TyImplItem, TyTraitItem},
-        CallPath,
+        CallPath, CallPathType,
     },
+    transform::{self, AttributeKind},
     type_system::{SubstTypes, TypeId},
-    IncludeSelf, SubstTypesContext, TraitConstraint, TypeArgument, TypeEngine, TypeInfo,
-    TypeSubstMap, UnifyCheck,
+    IncludeSelf, SubstTypesContext, TraitConstraint, TreatNumericAs, TypeArgument, TypeEngine,
+    TypeInfo, TypeSubstMap, UnifyCheck,
 };
 
 use super::Module;
@@ -145,6 +154,93 @@ impl ResolvedTraitImplItem {
             ResolvedTraitImplItem::Typed(item) => item.span(),
         }
     }
+
+    /// Returns a key identifying the declaration of the item, made of whether
+    /// the item is typed, the item kind, and the declaration id.
+    /// Items referring to the same declaration have equal keys.
+    fn decl_key(&self) -> (bool, u8, usize) {
+        match self {
+            ResolvedTraitImplItem::Parsed(item) => match item {
+                ImplItem::Fn(id) => (false, 0, id.inner()),
+                ImplItem::Constant(id) => (false, 1, id.inner()),
+                ImplItem::Type(id) => (false, 2, id.inner()),
+            },
+            ResolvedTraitImplItem::Typed(item) => match item {
+                TyTraitItem::Fn(decl_ref) => (true, 0, decl_ref.id().inner()),
+                TyTraitItem::Constant(decl_ref) => (true, 1, decl_ref.id().inner()),
+                TyTraitItem::Type(decl_ref) => (true, 2, decl_ref.id().inner()),
+            },
+        }
+    }
+
+    /// Returns the name of the item.
+    pub fn name(&self, engines: &Engines) -> Ident {
+        match self {
+            ResolvedTraitImplItem::Parsed(item) => match item {
+                ImplItem::Fn(id) => engines.pe().get_function(id).name.clone(),
+                ImplItem::Constant(id) => engines.pe().get_constant(id).name.clone(),
+                ImplItem::Type(id) => engines.pe().get_trait_type(id).name.clone(),
+            },
+            ResolvedTraitImplItem::Typed(item) => match item {
+                TyTraitItem::Fn(decl_ref) => decl_ref.name().clone(),
+                TyTraitItem::Constant(decl_ref) => decl_ref.name().clone(),
+                TyTraitItem::Type(decl_ref) => decl_ref.name().clone(),
+            },
+        }
+    }
+
+    /// Returns the span of the item's name.
+    pub fn name_span(&self, engines: &Engines) -> Span {
+        self.name(engines).span()
+    }
+
+    /// Returns true if the item is a placeholder for a trait method that is
+    /// not implemented, but only declared in the trait.
+    pub fn is_trait_method_dummy(&self, engines: &Engines) -> bool {
+        match self {
+            ResolvedTraitImplItem::Parsed(_) => false,
+            ResolvedTraitImplItem::Typed(TyTraitItem::Fn(decl_ref)) => {
+                engines
+                    .de()
+                    .get_function(decl_ref.id())
+                    .is_trait_method_dummy
+            }
+            ResolvedTraitImplItem::Typed(_) => false,
+        }
+    }
+
+    /// Returns the attributes of the item, including its doc comments.
+    pub fn attributes(&self, engines: &Engines) -> transform::Attributes {
+        match self {
+            ResolvedTraitImplItem::Parsed(item) => match item {
+                ImplItem::Fn(id) => engines.pe().get_function(id).attributes.clone(),
+                ImplItem::Constant(id) => engines.pe().get_constant(id).attributes.clone(),
+                ImplItem::Type(id) => engines.pe().get_trait_type(id).attributes.clone(),
+            },
+            ResolvedTraitImplItem::Typed(item) => match item {
+                TyTraitItem::Fn(decl_ref) => {
+                    engines.de().get_function(decl_ref.id()).attributes.clone()
+                }
+                TyTraitItem::Constant(decl_ref) => {
+                    engines.de().get_constant(decl_ref.id()).attributes.clone()
+                }
+                TyTraitItem::Type(decl_ref) => {
+                    engines.de().get_type(decl_ref.id()).attributes.clone()
+                }
+            },
+        }
+    }
+
+    /// Returns the text of the item's doc comments, one line per doc comment,
+    /// or `None` if the item is not documented.
+    pub fn doc_comment(&self, engines: &Engines) -> Option<String> {
+        let attributes = self.attributes(engines);
+        let lines = attributes
+            .of_kind(AttributeKind::DocComment)
+            .filter_map(|attribute| attribute.doc_comment_text())
+            .collect::<Vec<_>>();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
 }
 
 /// Map of name to [ResolvedTraitImplItem](ResolvedTraitImplItem)
@@ -155,6 +251,37 @@ pub(crate) struct TraitValue {
     pub(crate) trait_items: TraitItems,
     /// The span of the entire impl block.
     pub(crate) impl_span: Span,
+    /// The kind of the impl block the items come from.
+    pub(crate) origin: ImplOrigin,
+    /// The trait constraints on the type parameters of the impl block.
+    pub(crate) where_constraints: Vec<ImplTypeParameterConstraints>,
+}
+
+/// An impl that matches a type, but cannot be used for it, because a trait
+/// constraint from its `where` clause is not satisfied.
+struct RejectedImpl {
+    trait_name: CallPath,
+    type_parameter: Ident,
+    type_argument: TypeId,
+    trait_constraint: TraitConstraint,
+    impl_span: Span,
+}
+
+/// The trait constraints of a single type parameter of an impl block,
+/// e.g., `T: Bar` in `impl<T> Foo for Wrapper<T> where T: Bar`.
+#[derive(Clone, Debug)]
+pub(crate) struct ImplTypeParameterConstraints {
+    pub(crate) name: Ident,
+    pub(crate) type_id: TypeId,
+    pub(crate) trait_constraints: Vec<TraitConstraint>,
+}
+
+/// Records whether the items of a [TraitEntry] come from an `impl self` block
+/// or from a trait impl, and whether an existing impl was extended with them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ImplOrigin {
+    pub(crate) is_impl_self: IsImplSelf,
+    pub(crate) is_extending_existing_impl: IsExtendingExistingImpl,
 }
 
 #[derive(Clone, Debug)]
@@ -163,6 +290,48 @@ pub(crate) struct TraitEntry {
     pub(crate) value: TraitValue,
 }
 
+impl DebugWithEngines for TraitEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, engines: &Engines) -> fmt::Result {
+        // "impl self" blocks do not have a trait declaration.
+        if self.key.trait_decl_span.is_some() {
+            write!(
+                f,
+                "impl {} for {}",
+                engines.help_out(&*self.key.name),
+                engines.help_out(self.key.type_id)
+            )?;
+        } else {
+            write!(f, "impl {}", engines.help_out(self.key.type_id))?;
+        }
+
+        if self.value.trait_items.is_empty() {
+            write!(f, " {{}}")?;
+        } else {
+            write!(
+                f,
+                " {{ {} }}",
+                self.value
+                    .trait_items
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+
+        if let Some(source_id) = self.value.impl_span.source_id() {
+            write!(
+                f,
+                " ({}:{})",
+                engines.se().get_path(source_id).display(),
+                self.value.impl_span.start_pos().line_col().line
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Map of string of type entry id and vec of [TraitEntry].
 /// We are using the HashMap as a wrapper to the vec so the TraitMap algorithms
 /// don't need to traverse every TraitEntry.
@@ -185,11 +354,18 @@ pub(crate) enum TypeRootFilter {
     B256,
     Contract,
     ErrorRecovery,
-    Tuple(usize),
+    /// Tuples whose elements all have known root filters.
+    Tuple(Vec<TypeRootFilter>),
+    /// Tuples of the given length that have at least one element whose root
+    /// filter is not known, e.g., an unknown or a generic type.
+    TupleWildcard(usize),
     Enum(ParsedDeclId<EnumDeclaration>),
     Struct(ParsedDeclId<StructDeclaration>),
     ContractCaller(String),
-    Array,
+    /// Arrays whose element has a known root filter.
+    Array(Box<TypeRootFilter>),
+    /// Arrays whose element root filter is not known.
+    ArrayWildcard,
     RawUntypedPtr,
     RawUntypedSlice,
     Ptr,
@@ -197,6 +373,216 @@ pub(crate) enum TypeRootFilter {
     TraitType(String),
 }
 
+impl TypeRootFilter {
+    /// Returns `true` if types with this root filter can unify with types that
+    /// have other root filters, e.g., an unknown or a generic type.
+    fn is_wildcard(&self) -> bool {
+        matches!(
+            self,
+            TypeRootFilter::Unknown
+                | TypeRootFilter::Never
+                | TypeRootFilter::Placeholder
+                | TypeRootFilter::ErrorRecovery
+                | TypeRootFilter::Custom(_)
+                | TypeRootFilter::TraitType(_)
+                | TypeRootFilter::TupleWildcard(_)
+                | TypeRootFilter::ArrayWildcard
+        )
+    }
+}
+
+impl DebugWithEngines for TypeRootFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, engines: &Engines) -> fmt::Result {
+        // Declaration ids are not stable between compilations, so we render
+        // the declaration names instead.
+        match self {
+            TypeRootFilter::Enum(decl_id) => {
+                write!(f, "Enum({})", engines.pe().get_enum(decl_id).name)
+            }
+            TypeRootFilter::Struct(decl_id) => {
+                write!(f, "Struct({})", engines.pe().get_struct(decl_id).name)
+            }
+            TypeRootFilter::Tuple(elements) => write!(
+                f,
+                "Tuple([{}])",
+                elements
+                    .iter()
+                    .map(|element| format!("{:?}", engines.help_out(element)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            TypeRootFilter::Array(element) => {
+                write!(f, "Array({:?})", engines.help_out(element.as_ref()))
+            }
+            _ => write!(f, "{self:?}"),
+        }
+    }
+}
+
+/// Maximum number of [TypeInfo::Alias]es and [TypeInfo::Ref]erences that are
+/// followed when looking for the underlying type of a type.
+///
+/// Well-formed types always terminate long before reaching this limit. Malformed
+/// types, e.g., produced during error recovery, can alias or reference themselves
+/// and would otherwise send the trait map into an endless loop.
+const MAX_TYPE_INDIRECTION_DEPTH: usize = 1024;
+
+/// Maximum nesting depth of tuples and arrays whose elements are taken into account
+/// by their [TypeRootFilter]. Deeper nested tuples and arrays are bucketed as wildcards.
+const MAX_TYPE_ROOT_FILTER_DEPTH: usize = 2;
+
+/// Minimum number of trait constraints for which the constraints are checked in parallel.
+/// Below this number the overhead of parallelization outweighs its benefits.
+const PARALLEL_CONSTRAINTS_THRESHOLD: usize = 8;
+
+/// `left` can unify into `right`. Additionally we need to check subset condition in case of
+/// [TypeInfo::Ref] types.  Although `&mut <type>` can unify with `&<type>`
+/// when it comes to trait and self impls, we considered them to be different types.
+/// E.g., we can have `impl Foo for &T` and at the same time `impl Foo for &mut T`.
+/// Or in general, `impl Foo for & &mut .. &T` is different type then, e.g., `impl Foo for &mut & .. &mut T`.
+///
+/// Returns `None` if the references do not terminate within [MAX_TYPE_INDIRECTION_DEPTH],
+/// or if one of the types is a self-referential alias.
+fn is_unified_type_subset(
+    type_engine: &TypeEngine,
+    mut left: TypeId,
+    mut right: TypeId,
+) -> Option<bool> {
+    for _ in 0..MAX_TYPE_INDIRECTION_DEPTH {
+        let left_ty_info = &*type_engine.try_get_unaliased(left)?;
+        let right_ty_info = &*type_engine.try_get_unaliased(right)?;
+        match (left_ty_info, right_ty_info) {
+            (
+                TypeInfo::Ref {
+                    to_mutable_value: l_to_mut,
+                    ..
+                },
+                TypeInfo::Ref {
+                    to_mutable_value: r_to_mut,
+                    ..
+                },
+            ) if *l_to_mut != *r_to_mut => return Some(false), // Different mutability means not subset.
+            (
+                TypeInfo::Ref {
+                    referenced_type: l_ty,
+                    ..
+                },
+                TypeInfo::Ref {
+                    referenced_type: r_ty,
+                    ..
+                },
+            ) => {
+                left = l_ty.type_id;
+                right = r_ty.type_id;
+            }
+            _ => return Some(true),
+        }
+    }
+    None
+}
+
+/// Returns true if `left` and `right` are references of the same depth that
+/// differ only in the mutability of at least one of their [TypeInfo::Ref]erences.
+/// E.g., `&T` and `&mut T`, or `&mut & T` and `&mut &mut T`.
+fn differs_only_in_ref_mutability(engines: &Engines, mut left: TypeId, mut right: TypeId) -> bool {
+    let type_engine = engines.te();
+    let mut mutability_differs = false;
+    for _ in 0..MAX_TYPE_INDIRECTION_DEPTH {
+        let (Some(left_ty_info), Some(right_ty_info)) = (
+            type_engine.try_get_unaliased(left),
+            type_engine.try_get_unaliased(right),
+        ) else {
+            return false;
+        };
+        match (&*left_ty_info, &*right_ty_info) {
+            (
+                TypeInfo::Ref {
+                    to_mutable_value: l_to_mut,
+                    referenced_type: l_ty,
+                },
+                TypeInfo::Ref {
+                    to_mutable_value: r_to_mut,
+                    referenced_type: r_ty,
+                },
+            ) => {
+                mutability_differs |= *l_to_mut != *r_to_mut;
+                left = l_ty.type_id;
+                right = r_ty.type_id;
+            }
+            (TypeInfo::Ref { .. }, _) | (_, TypeInfo::Ref { .. }) => return false,
+            _ => {
+                return mutability_differs
+                    && UnifyCheck::constraint_subset(engines).check(left, right)
+            }
+        }
+    }
+    false
+}
+
+/// How the call path of an implemented trait matches the call path of a required trait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TraitPathMatch {
+    /// Both paths are fully qualified and point to the same trait.
+    Full,
+    /// At least one of the paths is not fully qualified, so only the trait names could be compared.
+    SuffixOnly,
+    NoMatch,
+}
+
+/// Matches the canonical call path of an `implemented` trait against the canonical
+/// call path of a `required` trait.
+///
+/// Trait names alone are not enough to identify a trait, because traits with the same
+/// name can be declared in different modules. The prefixes of the paths are compared
+/// only if both paths are canonical, i.e., full paths to the trait declarations, see
+/// [CallPath::to_canonical_path]. Relative paths, like `b::Convert`, can point to the
+/// same trait as a full path with different prefixes, like `my_pkg::b::Convert`.
+/// Comparison of names only is kept for paths that could not be resolved to their full form.
+fn match_trait_paths(implemented: &CallPath, required: &CallPath) -> TraitPathMatch {
+    let is_canonical =
+        |path: &CallPath| path.callpath_type == CallPathType::Full && !path.prefixes.is_empty();
+    if implemented.suffix != required.suffix {
+        TraitPathMatch::NoMatch
+    } else if !is_canonical(implemented) || !is_canonical(required) {
+        TraitPathMatch::SuffixOnly
+    } else if implemented.prefixes == required.prefixes {
+        TraitPathMatch::Full
+    } else {
+        TraitPathMatch::NoMatch
+    }
+}
+
+/// A trait constraint satisfied by an impl found in the [TraitMap].
+#[derive(Clone, Debug)]
+pub struct SatisfiedTraitConstraint {
+    /// The satisfied constraint.
+    pub constraint: TraitConstraint,
+    /// The type the satisfying impl is implemented for.
+    pub implementing_type_id: TypeId,
+    /// The type arguments of the implemented trait.
+    pub trait_type_arguments: Vec<TypeArgument>,
+    /// The span of the entire impl block.
+    pub impl_span: Span,
+    /// The span of the implemented trait's declaration.
+    pub trait_decl_span: Option<Span>,
+}
+
+impl SatisfiedTraitConstraint {
+    /// Returns the name of the implemented trait together with its
+    /// type arguments, e.g., `From<u64>`.
+    pub fn trait_name_with_type_arguments(&self, engines: &Engines) -> String {
+        let trait_name = self.constraint.trait_name.suffix.as_str();
+        if self.trait_type_arguments.is_empty() {
+            trait_name.to_string()
+        } else {
+            format!(
+                "{trait_name}<{}>",
+                engines.help_out(self.trait_type_arguments.clone())
+            )
+        }
+    }
+}
+
 /// Map holding trait implementations for types.
 ///
 /// Note: "impl self" blocks are considered traits and are stored in the
@@ -204,16 +590,224 @@ pub(crate) enum TypeRootFilter {
 #[derive(Clone, Debug, Default)]
 pub struct TraitMap {
     pub(crate) trait_impls: TraitImpls,
-    satisfied_cache: HashSet<u64>,
+    satisfied_cache: SatisfiedCache,
+    item_import_cache: ItemImportCache,
+    counters: TraitMapCounters,
+}
+
+/// Counters of the operations performed on a [TraitMap], reported in
+/// [TraitMapStats] when compiler profiling is requested.
+///
+/// The counters never influence the behavior of the [TraitMap]. They are
+/// relaxed atomics, so that counting is cheap and possible in lookups that
+/// only have a shared reference to the [TraitMap].
+#[derive(Debug, Default)]
+struct TraitMapCounters {
+    inserts: AtomicU64,
+    extends: AtomicU64,
+    lookups: AtomicU64,
+    satisfied_cache_hits: AtomicU64,
+    satisfied_cache_misses: AtomicU64,
+}
+
+impl TraitMapCounters {
+    fn bump(counter: &AtomicU64) {
+        counter.fetch_add(1, atomic::Ordering::Relaxed);
+    }
+
+    fn get(counter: &AtomicU64) -> u64 {
+        counter.load(atomic::Ordering::Relaxed)
+    }
+}
+
+impl Clone for TraitMapCounters {
+    fn clone(&self) -> Self {
+        let copy = |counter: &AtomicU64| AtomicU64::new(Self::get(counter));
+        Self {
+            inserts: copy(&self.inserts),
+            extends: copy(&self.extends),
+            lookups: copy(&self.lookups),
+            satisfied_cache_hits: copy(&self.satisfied_cache_hits),
+            satisfied_cache_misses: copy(&self.satisfied_cache_misses),
+        }
+    }
+}
+
+/// Statistics of a single [TraitMap], or the aggregated statistics of several of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraitMapStats {
+    /// The number of non-empty [TypeRootFilter] buckets.
+    pub buckets: usize,
+    /// The number of impls stored in all the buckets.
+    pub entries: usize,
+    /// The number of impls stored in the largest bucket.
+    pub max_bucket_entries: usize,
+    /// The number of items of all the stored impls.
+    pub items: usize,
+    pub inserts: u64,
+    pub extends: u64,
+    pub lookups: u64,
+    pub satisfied_cache_hits: u64,
+    pub satisfied_cache_misses: u64,
+}
+
+impl TraitMapStats {
+    /// Adds the statistics of `other` to `self`.
+    pub fn merge(&mut self, other: &TraitMapStats) {
+        self.buckets += other.buckets;
+        self.entries += other.entries;
+        self.max_bucket_entries = self.max_bucket_entries.max(other.max_bucket_entries);
+        self.items += other.items;
+        self.inserts += other.inserts;
+        self.extends += other.extends;
+        self.lookups += other.lookups;
+        self.satisfied_cache_hits += other.satisfied_cache_hits;
+        self.satisfied_cache_misses += other.satisfied_cache_misses;
+    }
+}
+
+/// [TraitMapStats] of a single module, summed over all of its lexical scopes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleTraitMapStats {
+    /// The path of the module, e.g., `my_package::my_module`.
+    pub module: String,
+    pub stats: TraitMapStats,
+}
+
+/// Trait map statistics of all the modules of a package, printed when
+/// compiler profiling is requested.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraitMapProfile {
+    /// The statistics of every module, sorted by the module path.
+    pub modules: Vec<ModuleTraitMapStats>,
+    /// The statistics of all the modules together.
+    pub total: TraitMapStats,
+}
+
+impl TraitMapProfile {
+    /// The prefix of the line that [TraitMapProfile::to_profile_line] renders.
+    pub const PROFILE_LINE_PREFIX: &'static str = "/trait_map_stats";
+
+    /// Collects the statistics of the `root_module` and all of its submodules.
+    pub fn from_root_module(root_module: &Module) -> Self {
+        fn collect(module: &Module, modules: &mut Vec<ModuleTraitMapStats>) {
+            let mut stats = TraitMapStats::default();
+            for lexical_scope in module.lexical_scopes.iter() {
+                stats.merge(&lexical_scope.items.implemented_traits.stats());
+            }
+            modules.push(ModuleTraitMapStats {
+                module: module
+                    .mod_path()
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join("::"),
+                stats,
+            });
+
+            // The order of the submodules does not matter, the modules are sorted at the end.
+            module
+                .submodules()
+                .values()
+                .for_each(|submodule| collect(submodule, modules));
+        }
+
+        let mut modules = vec![];
+        collect(root_module, &mut modules);
+        modules.sort_by(|a, b| a.module.cmp(&b.module));
+
+        let mut total = TraitMapStats::default();
+        for module in modules.iter() {
+            total.merge(&module.stats);
+        }
+
+        Self { modules, total }
+    }
+
+    /// Renders the profile as a single line, consisting of the
+    /// [TraitMapProfile::PROFILE_LINE_PREFIX], the `package_name`, and the
+    /// profile serialized to JSON.
+    pub fn to_profile_line(&self, package_name: &str) -> String {
+        format!(
+            "{} {} {}",
+            Self::PROFILE_LINE_PREFIX,
+            package_name,
+            serde_json::to_string(self).expect("trait map profile is always serializable")
+        )
+    }
+}
+
+/// Hashes of the types and trait constraints that are known to be satisfied,
+/// filled by [TraitMap::check_if_trait_constraints_are_satisfied_for_type].
+///
+/// The cache is also filled when checking the `where` clauses of impls during
+/// lookups, which only have a shared reference to the [TraitMap].
+#[derive(Debug, Default)]
+struct SatisfiedCache(Mutex<HashSet<u64>>);
+
+impl Clone for SatisfiedCache {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.0.lock().clone()))
+    }
+}
+
+/// Results of [TraitMap::filter_by_type_item_import], keyed by the unaliased
+/// [TypeId] of the imported type.
+///
+/// The same types are imported from the same modules over and over again,
+/// e.g., on every method call on a struct or an enum. Only the results for
+/// types that cannot change are cached, and the cache is cleared whenever the
+/// [TraitMap] it belongs to is extended.
+#[derive(Debug, Default)]
+struct ItemImportCache(Mutex<HashMap<TypeId, TraitMap>>);
+
+impl Clone for ItemImportCache {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.0.lock().clone()))
+    }
+}
+
+impl DebugWithEngines for TraitMap {
+    /// Renders the impls in the [TraitMap] grouped by their [TypeRootFilter].
+    /// Both the groups and the impls within a group are sorted, so that the
+    /// output can be diffed between compilations and compiler versions.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, engines: &Engines) -> fmt::Result {
+        let mut groups = self
+            .trait_impls
+            .iter()
+            .map(|(type_root_filter, entries)| {
+                let mut impls = entries
+                    .iter()
+                    .map(|entry| format!("{:?}", engines.help_out(entry)))
+                    .collect::<Vec<_>>();
+                impls.sort();
+                (format!("{:?}", engines.help_out(type_root_filter)), impls)
+            })
+            .collect::<Vec<_>>();
+        groups.sort();
+
+        for (type_root_filter, impls) in groups {
+            writeln!(f, "{type_root_filter}:")?;
+            for impl_string in impls {
+                writeln!(f, "    {impl_string}")?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub(crate) enum IsImplSelf {
     Yes,
+    #[default]
     No,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub(crate) enum IsExtendingExistingImpl {
     Yes,
+    #[default]
     No,
 }
 
@@ -240,6 +834,17 @@ impl TraitMap {
         is_extending_existing_impl: IsExtendingExistingImpl,
         engines: &Engines,
     ) -> Result<(), ErrorEmitted> {
+        TraitMapCounters::bump(&self.counters.inserts);
+
+        // Self-referential types can only be produced by error recovery and
+        // have no type root filter to store the impl under.
+        if Self::get_root_type_id(engines, type_id).is_none() {
+            return Err(handler.emit_err(CompileError::Internal(
+                "Self-referential type found while inserting into the trait map.",
+                impl_span.clone(),
+            )));
+        }
+
         let unaliased_type_id = engines.te().get_unaliased_type_id(type_id);
 
         handler.scope(|handler| {
@@ -261,7 +866,33 @@ impl TraitMap {
                             }
                         }
                         TyImplItem::Constant(decl_ref) => {
-                            trait_items.insert(decl_ref.name().to_string(), item.clone());
+                            let name = decl_ref.name().to_string();
+                            let is_trait_const_default = |item: &ResolvedTraitImplItem| {
+                                matches!(
+                                    item,
+                                    ResolvedTraitImplItem::Typed(TyImplItem::Constant(const_ref))
+                                        if engines.de().get_constant(const_ref).is_trait_const_default
+                                )
+                            };
+                            match trait_items.get(&name) {
+                                // The constant overrides the default provided by the trait,
+                                // or a trait provided default replaces the previous one.
+                                Some(existing_item) if is_trait_const_default(existing_item) => {
+                                    trait_items.insert(name, item.clone());
+                                }
+                                // A trait provided default never replaces an implemented constant.
+                                Some(_) if is_trait_const_default(item) => {}
+                                // duplicate constant name
+                                Some(_) => {
+                                    handler.emit_err(CompileError::MultipleDefinitionsOfName {
+                                        name: decl_ref.name().clone(),
+                                        span: decl_ref.span(),
+                                    });
+                                }
+                                None => {
+                                    trait_items.insert(name, item.clone());
+                                }
+                            }
                         }
                         TyImplItem::Type(decl_ref) => {
                             trait_items.insert(decl_ref.name().to_string(), item.clone());
@@ -270,7 +901,8 @@ impl TraitMap {
                 }
             }
 
-            let trait_impls = self.get_impls_mut(engines, unaliased_type_id);
+            let type_root_filter = Self::get_type_root_filter(engines, unaliased_type_id);
+            let trait_impls = self.get_impls_buckets(&type_root_filter, false);
 
             // check to see if adding this trait will produce a conflicting definition
             for TraitEntry {
@@ -285,8 +917,10 @@ impl TraitMap {
                     TraitValue {
                         trait_items: map_trait_items,
                         impl_span: existing_impl_span,
+                        origin: _,
+                        where_constraints: _,
                     },
-            } in trait_impls.iter()
+            } in trait_impls.flatten()
             {
                 let CallPath {
                     suffix:
@@ -295,60 +929,10 @@ impl TraitMap {
                             args: map_trait_type_args,
                         },
                     ..
-                } = &*map_trait_name.clone();
+                } = &**map_trait_name;
 
                 let unify_checker = UnifyCheck::non_generic_constraint_subset(engines);
 
-                // Types are subset if the `unaliased_type_id` that we want to insert can unify with the
-                // existing `map_type_id`. In addition we need to additionally check for the case of
-                // `&mut <type>` and `&<type>`.
-                let types_are_subset = unify_checker.check(unaliased_type_id, *map_type_id)
-                    && is_unified_type_subset(engines.te(), unaliased_type_id, *map_type_id);
-
-                /// `left` can unify into `right`. Additionally we need to check subset condition in case of
-                /// [TypeInfo::Ref] types.  Although `&mut <type>` can unify with `&<type>`
-                /// when it comes to trait and self impls, we considered them to be different types.
-                /// E.g., we can have `impl Foo for &T` and at the same time `impl Foo for &mut T`.
-                /// Or in general, `impl Foo for & &mut .. &T` is different type then, e.g., `impl Foo for &mut & .. &mut T`.
-                fn is_unified_type_subset(
-                    type_engine: &TypeEngine,
-                    mut left: TypeId,
-                    mut right: TypeId,
-                ) -> bool {
-                    // The loop cannot be endless, because at the end we must hit a referenced type which is not
-                    // a reference.
-                    loop {
-                        let left_ty_info = &*type_engine.get_unaliased(left);
-                        let right_ty_info = &*type_engine.get_unaliased(right);
-                        match (left_ty_info, right_ty_info) {
-                            (
-                                TypeInfo::Ref {
-                                    to_mutable_value: l_to_mut,
-                                    ..
-                                },
-                                TypeInfo::Ref {
-                                    to_mutable_value: r_to_mut,
-                                    ..
-                                },
-                            ) if *l_to_mut != *r_to_mut => return false, // Different mutability means not subset.
-                            (
-                                TypeInfo::Ref {
-                                    referenced_type: l_ty,
-                                    ..
-                                },
-                                TypeInfo::Ref {
-                                    referenced_type: r_ty,
-                                    ..
-                                },
-                            ) => {
-                                left = l_ty.type_id;
-                                right = r_ty.type_id;
-                            }
-                            _ => return true,
-                        }
-                    }
-                }
-
                 let mut traits_are_subset = true;
                 if *map_trait_name_suffix != trait_name.suffix
                     || map_trait_type_args.len() != trait_type_args.len()
@@ -364,11 +948,46 @@ impl TraitMap {
                     }
                 }
 
-                if matches!(is_extending_existing_impl, IsExtendingExistingImpl::No)
-                    && types_are_subset
-                    && traits_are_subset
-                    && matches!(is_impl_self, IsImplSelf::No)
-                {
+                // The impls can only conflict if they implement the same trait, and their
+                // items can only clash if both of them have items. E.g., impls of marker
+                // traits never have items, so their items are never compared.
+                let impls_may_conflict =
+                    matches!(is_extending_existing_impl, IsExtendingExistingImpl::No)
+                        && traits_are_subset
+                        && matches!(is_impl_self, IsImplSelf::No);
+                let items_may_clash = (traits_are_subset
+                    || matches!(is_impl_self, IsImplSelf::Yes))
+                    && !trait_items.is_empty()
+                    && !map_trait_items.is_empty();
+                if !impls_may_conflict && !items_may_clash {
+                    continue;
+                }
+
+                // Types are subset if the `unaliased_type_id` that we want to insert can unify with the
+                // existing `map_type_id`. In addition we need to additionally check for the case of
+                // `&mut <type>` and `&<type>`.
+                let types_are_subset = unify_checker.check(unaliased_type_id, *map_type_id)
+                    && match is_unified_type_subset(engines.te(), unaliased_type_id, *map_type_id)
+                    {
+                        Some(is_subset) => is_subset,
+                        None => {
+                            handler.emit_err(CompileError::Internal(
+                                "Self-referential reference type found while inserting into the trait map.",
+                                impl_span.clone(),
+                            ));
+                            false
+                        }
+                    };
+                // When extending an existing impl, the items are added to the very same impl,
+                // so they collide even if the types differ only in the mutability of references,
+                // e.g., `&T` and `&mut T`. Otherwise, the later item would silently shadow the
+                // existing one.
+                let items_collide = types_are_subset
+                    || (matches!(is_extending_existing_impl, IsExtendingExistingImpl::Yes)
+                        && traits_are_subset
+                        && differs_only_in_ref_mutability(engines, unaliased_type_id, *map_type_id));
+
+                if impls_may_conflict && types_are_subset {
                     handler.emit_err(CompileError::ConflictingImplsForTraitAndType {
                         trait_name: trait_name.to_string_with_args(engines, &trait_type_args),
                         type_implementing_for: engines.help_out(type_id).to_string(),
@@ -378,9 +997,7 @@ impl TraitMap {
                         existing_impl_span: existing_impl_span.clone(),
                         second_impl_span: impl_span.clone(),
                     });
-                } else if types_are_subset
-                    && (traits_are_subset || matches!(is_impl_self, IsImplSelf::Yes))
-                {
+                } else if items_may_clash && items_collide {
                     for name in trait_items.keys() {
                         let item = &trait_items[name];
                         match item {
@@ -398,10 +1015,9 @@ impl TraitMap {
                                                 type_implementing_for_unaliased: engines
                                                     .help_out(unaliased_type_id)
                                                     .to_string(),
-                                                existing_impl_span: existing_item
-                                                    .span(engines)
-                                                    .clone(),
-                                                second_impl_span: decl_ref.name().span(),
+                                                existing_decl_span: existing_item
+                                                    .name_span(engines),
+                                                second_decl_span: decl_ref.name().span(),
                                             },
                                         );
                                     }
@@ -418,10 +1034,9 @@ impl TraitMap {
                                                 type_implementing_for_unaliased: engines
                                                     .help_out(unaliased_type_id)
                                                     .to_string(),
-                                                existing_impl_span: existing_item
-                                                    .span(engines)
-                                                    .clone(),
-                                                second_impl_span: decl_ref.name().span(),
+                                                existing_decl_span: existing_item
+                                                    .name_span(engines),
+                                                second_decl_span: decl_ref.name().span(),
                                             },
                                         );
                                     }
@@ -438,10 +1053,9 @@ impl TraitMap {
                                                 type_implementing_for_unaliased: engines
                                                     .help_out(unaliased_type_id)
                                                     .to_string(),
-                                                existing_impl_span: existing_item
-                                                    .span(engines)
-                                                    .clone(),
-                                                second_impl_span: decl_ref.name().span(),
+                                                existing_decl_span: existing_item
+                                                    .name_span(engines),
+                                                second_decl_span: decl_ref.name().span(),
                                             },
                                         );
                                     }
@@ -460,6 +1074,8 @@ impl TraitMap {
                 callpath_type: trait_name.callpath_type,
             });
 
+            let where_constraints = Self::get_where_constraints(engines, &impl_type_parameters);
+
             // even if there is a conflicting definition, add the trait anyway
             self.insert_inner(
                 trait_name,
@@ -468,6 +1084,11 @@ impl TraitMap {
                 unaliased_type_id,
                 impl_type_parameters,
                 trait_items,
+                ImplOrigin {
+                    is_impl_self,
+                    is_extending_existing_impl,
+                },
+                where_constraints,
                 engines,
             );
 
@@ -484,6 +1105,8 @@ impl TraitMap {
         type_id: TypeId,
         impl_type_parameters: Vec<TypeId>,
         trait_methods: TraitItems,
+        origin: ImplOrigin,
+        where_constraints: Vec<ImplTypeParameterConstraints>,
         engines: &Engines,
     ) {
         let key = TraitKey {
@@ -495,6 +1118,8 @@ impl TraitMap {
         let value = TraitValue {
             trait_items: trait_methods,
             impl_span,
+            origin,
+            where_constraints,
         };
         let entry = TraitEntry { key, value };
         let mut trait_impls: TraitImpls = BTreeMap::<TypeRootFilter, Vec<TraitEntry>>::new();
@@ -504,15 +1129,44 @@ impl TraitMap {
 
         let trait_map = TraitMap {
             trait_impls,
-            satisfied_cache: HashSet::default(),
+            ..Default::default()
         };
 
         self.extend(trait_map, engines);
     }
 
+    /// Returns the trait constraints of the `impl_type_parameters` that
+    /// have at least one constraint.
+    fn get_where_constraints(
+        engines: &Engines,
+        impl_type_parameters: &[TypeId],
+    ) -> Vec<ImplTypeParameterConstraints> {
+        impl_type_parameters
+            .iter()
+            .filter_map(|type_id| match &*engines.te().get(*type_id) {
+                TypeInfo::TypeParam(type_parameter)
+                    if !type_parameter.trait_constraints.is_empty() =>
+                {
+                    Some(ImplTypeParameterConstraints {
+                        name: type_parameter.name.clone(),
+                        type_id: type_parameter.type_id,
+                        trait_constraints: type_parameter.trait_constraints.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Given [TraitMap]s `self` and `other`, extend `self` with `other`,
     /// extending existing entries when possible.
     pub(crate) fn extend(&mut self, other: TraitMap, engines: &Engines) {
+        TraitMapCounters::bump(&self.counters.extends);
+
+        if !other.trait_impls.is_empty() {
+            self.item_import_cache.0.get_mut().clear();
+        }
+
         for impls_key in other.trait_impls.keys() {
             let oe_vec = &other.trait_impls[impls_key];
             let self_vec = if let Some(self_vec) = self.trait_impls.get_mut(impls_key) {
@@ -529,10 +1183,16 @@ impl TraitMap {
                 });
 
                 match pos {
-                    Ok(pos) => self_vec[pos]
-                        .value
-                        .trait_items
-                        .extend(oe.value.trait_items.clone()),
+                    Ok(pos) => {
+                        let se_value = &mut self_vec[pos].value;
+                        se_value.trait_items.extend(oe.value.trait_items.clone());
+                        if oe.value.origin.is_extending_existing_impl
+                            == IsExtendingExistingImpl::Yes
+                        {
+                            se_value.origin.is_extending_existing_impl =
+                                IsExtendingExistingImpl::Yes;
+                        }
+                    }
                     Err(pos) => self_vec.insert(pos, oe.clone()),
                 }
             }
@@ -627,8 +1287,27 @@ impl TraitMap {
         type_id: TypeId,
         engines: &Engines,
     ) -> TraitMap {
-        let unify_checker = UnifyCheck::constraint_subset(engines);
-        let unify_checker_for_item_import = UnifyCheck::non_generic_constraint_subset(engines);
+        let type_engine = engines.te();
+        let type_id = type_engine.get_unaliased_type_id(type_id);
+        if type_engine.is_type_changeable(engines, &type_engine.get(type_id)) {
+            return self.filter_by_type_item_import_uncached(type_id, engines);
+        }
+
+        if let Some(trait_map) = self.item_import_cache.0.lock().get(&type_id) {
+            return trait_map.clone();
+        }
+        let trait_map = self.filter_by_type_item_import_uncached(type_id, engines);
+        self.item_import_cache
+            .0
+            .lock()
+            .insert(type_id, trait_map.clone());
+        trait_map
+    }
+
+    fn filter_by_type_item_import_uncached(&self, type_id: TypeId, engines: &Engines) -> TraitMap {
+        let unify_checker = UnifyCheck::constraint_subset(engines).with_memo();
+        let unify_checker_for_item_import =
+            UnifyCheck::non_generic_constraint_subset(engines).with_memo();
 
         // a curried version of the decider protocol to use in the helper functions
         let decider = |left: TypeId, right: TypeId| {
@@ -675,6 +1354,8 @@ impl TraitMap {
                          TraitValue {
                              trait_items: map_trait_items,
                              impl_span,
+                             origin,
+                             where_constraints,
                          },
                  }| {
                     if !type_engine.is_type_changeable(engines, &type_info)
@@ -687,6 +1368,8 @@ impl TraitMap {
                             *type_id,
                             map_impl_type_parameters.clone(),
                             map_trait_items.clone(),
+                            *origin,
+                            where_constraints.clone(),
                             engines,
                         );
                     } else if decider(*type_id, *map_type_id) {
@@ -702,6 +1385,8 @@ impl TraitMap {
                                 *map_type_id,
                                 engines,
                             ),
+                            *origin,
+                            where_constraints.clone(),
                             engines,
                         );
                     }
@@ -819,7 +1504,7 @@ impl TraitMap {
     /// - this method does not translate types from the found entries to the
     ///     `type_id` (like in `filter_by_type()`). This is because the only
     ///     entries that qualify as hits are equivalents of `type_id`
-    pub(crate) fn get_items_for_type(
+    pub fn get_items_for_type(
         module: &Module,
         engines: &Engines,
         type_id: TypeId,
@@ -830,13 +1515,18 @@ impl TraitMap {
             .collect::<Vec<_>>()
     }
 
-    fn get_items_and_trait_key_for_type(
+    /// Like [TraitMap::get_items_for_type], but also returns the [TraitKey] of
+    /// the impl each item comes from, together with the [ImplOrigin] and the
+    /// span of the impl.
+    ///
+    /// Items of impls whose `where` clause is not satisfied for `type_id` are not returned.
+    pub(crate) fn get_items_and_trait_key_for_type(
         module: &Module,
         engines: &Engines,
         type_id: TypeId,
-    ) -> Vec<(ResolvedTraitImplItem, TraitKey)> {
+    ) -> Vec<(ResolvedTraitImplItem, TraitKey, ImplOrigin, Span)> {
         let type_engine = engines.te();
-        let unify_check = UnifyCheck::constraint_subset(engines);
+        let unify_check = UnifyCheck::constraint_subset(engines).with_memo();
 
         let type_id = engines.te().get_unaliased_type_id(type_id);
 
@@ -846,13 +1536,20 @@ impl TraitMap {
             return items;
         }
 
+        // The same impl can be visible in several lexical scopes of the chain.
+        // The walk starts at the innermost scope, so we keep the first instance
+        // of an item and skip the ones found in the outer scopes.
+        let mut seen_items = HashSet::new();
         let _ = module.walk_scope_chain_early_return(|lexical_scope| {
             lexical_scope.items.implemented_traits.for_each_impls(
                 engines,
                 type_id,
                 true,
                 |entry| {
-                    if unify_check.check(type_id, entry.key.type_id) {
+                    if unify_check.check(type_id, entry.key.type_id)
+                        && Self::find_unsatisfied_where_constraint(module, engines, entry, type_id)
+                            .is_none()
+                    {
                         let trait_items = Self::filter_dummy_methods(
                             &entry.value.trait_items,
                             type_id,
@@ -860,8 +1557,16 @@ impl TraitMap {
                             engines,
                         )
                         .values()
+                        .filter(|i| seen_items.insert((i.decl_key(), entry.key.type_id)))
                         .cloned()
-                        .map(|i| (i, entry.key.clone()))
+                        .map(|i| {
+                            (
+                                i,
+                                entry.key.clone(),
+                                entry.value.origin,
+                                entry.value.impl_span.clone(),
+                            )
+                        })
                         .collect::<Vec<_>>();
 
                         items.extend(trait_items);
@@ -874,22 +1579,84 @@ impl TraitMap {
         items
     }
 
-    /// Find the spans of all impls for the given type.
+    /// Find an impl providing an item named `symbol` for a type that differs
+    /// from `type_id` only in the mutability of its references, e.g., for
+    /// `&mut T` when `type_id` is `&T`.
     ///
-    /// Notes:
-    /// - equivalency is defined (1) based on whether the types contains types
-    ///     that are dynamic and can change and (2) whether the types hold
-    ///     equivalency after (1) is fulfilled
-    /// - this method does not translate types from the found entries to the
-    ///     `type_id` (like in `filter_by_type()`). This is because the only
-    ///     entries that qualify as hits are equivalents of `type_id`
+    /// Used to give a targeted hint when looking up `symbol` for `type_id` fails.
+    pub(crate) fn get_impl_for_other_ref_mutability(
+        module: &Module,
+        engines: &Engines,
+        symbol: &Ident,
+        type_id: TypeId,
+    ) -> Option<ImplForOtherRefMutability> {
+        let type_id = engines.te().get_unaliased_type_id(type_id);
+        let TypeInfo::Ref {
+            to_mutable_value, ..
+        } = *engines.te().get(type_id)
+        else {
+            return None;
+        };
+
+        module
+            .walk_scope_chain_early_return(|lexical_scope| {
+                let mut found = None;
+                lexical_scope.items.implemented_traits.for_each_impls(
+                    engines,
+                    type_id,
+                    false,
+                    |entry| {
+                        if found.is_none()
+                            && entry.value.trait_items.contains_key(symbol.as_str())
+                            && differs_only_in_ref_mutability(engines, type_id, entry.key.type_id)
+                        {
+                            found = Some(ImplForOtherRefMutability {
+                                item_name: symbol.as_str().to_string(),
+                                trait_name: entry
+                                    .key
+                                    .trait_decl_span
+                                    .as_ref()
+                                    .map(|_| engines.help_out(&*entry.key.name).to_string()),
+                                type_name: engines.help_out(entry.key.type_id).to_string(),
+                                outermost_ref_mutability: match &*engines
+                                    .te()
+                                    .get_unaliased(entry.key.type_id)
+                                {
+                                    TypeInfo::Ref {
+                                        to_mutable_value: other_to_mutable_value,
+                                        ..
+                                    } if *other_to_mutable_value != to_mutable_value => {
+                                        Some(*other_to_mutable_value)
+                                    }
+                                    _ => None,
+                                },
+                                impl_span: entry.value.impl_span.clone(),
+                            });
+                        }
+                    },
+                );
+                Ok(found)
+            })
+            .ok()
+            .flatten()
+    }
+
+    /// Find the spans of all impls for the given type.
+    ///
+    /// Notes:
+    /// - equivalency is defined (1) based on whether the types contains types
+    ///     that are dynamic and can change and (2) whether the types hold
+    ///     equivalency after (1) is fulfilled
+    /// - this method does not translate types from the found entries to the
+    ///     `type_id` (like in `filter_by_type()`). This is because the only
+    ///     entries that qualify as hits are equivalents of `type_id`
     pub fn get_impl_spans_for_type(
         module: &Module,
         engines: &Engines,
         type_id: &TypeId,
     ) -> Vec<Span> {
         let type_engine = engines.te();
-        let unify_check = UnifyCheck::constraint_subset(engines);
+        let unify_check = UnifyCheck::constraint_subset(engines).with_memo();
 
         let type_id = &engines.te().get_unaliased_type_id(*type_id);
 
@@ -986,7 +1753,7 @@ impl TraitMap {
         let type_id = engines.te().get_unaliased_type_id(type_id);
 
         let type_engine = engines.te();
-        let unify_check = UnifyCheck::constraint_subset(engines);
+        let unify_check = UnifyCheck::constraint_subset(engines).with_memo();
         let mut items = vec![];
         // small performance gain in bad case
         if matches!(&*type_engine.get(type_id), TypeInfo::ErrorRecovery(_)) {
@@ -1009,6 +1776,8 @@ impl TraitMap {
                             .iter()
                             .zip(e.key.name.suffix.args.iter())
                             .all(|(t1, t2)| unify_check.check(t1.type_id, t2.type_id))
+                        && Self::find_unsatisfied_where_constraint(module, engines, e, type_id)
+                            .is_none()
                     {
                         let type_mapping =
                             TypeSubstMap::from_superset_and_subset(engines, e.key.type_id, type_id);
@@ -1069,6 +1838,52 @@ impl TraitMap {
         .collect::<Vec<_>>()
     }
 
+    /// Checks if `type_id` implements the trait `trait_name` with the trait
+    /// type arguments `trait_type_args`.
+    ///
+    /// The impls are matched exactly like in
+    /// [TraitMap::get_items_for_type_and_trait_name_and_trait_type_arguments],
+    /// but the search stops at the first matching impl and the items of the
+    /// impl are never looked at. Use this method when only the existence of
+    /// the impl is of interest.
+    pub fn has_impl_for(
+        module: &Module,
+        engines: &Engines,
+        type_id: TypeId,
+        trait_name: &CallPath,
+        trait_type_args: &[TypeArgument],
+    ) -> bool {
+        let type_id = engines.te().get_unaliased_type_id(type_id);
+
+        // small performance gain in bad case
+        if matches!(&*engines.te().get(type_id), TypeInfo::ErrorRecovery(_)) {
+            return false;
+        }
+
+        let unify_check = UnifyCheck::constraint_subset(engines).with_memo();
+        let found = module.walk_scope_chain_early_return(|lexical_scope| {
+            let found =
+                lexical_scope
+                    .items
+                    .implemented_traits
+                    .any_impls(engines, type_id, false, |e| {
+                        e.key.name.suffix.name == trait_name.suffix
+                            && e.key.name.prefixes == trait_name.prefixes
+                            && e.key.name.callpath_type == trait_name.callpath_type
+                            && unify_check.check(type_id, e.key.type_id)
+                            && trait_type_args.len() == e.key.name.suffix.args.len()
+                            && trait_type_args
+                                .iter()
+                                .zip(e.key.name.suffix.args.iter())
+                                .all(|(t1, t2)| unify_check.check(t1.type_id, t2.type_id))
+                            && Self::find_unsatisfied_where_constraint(module, engines, e, type_id)
+                                .is_none()
+                    });
+            Ok(found.then_some(()))
+        });
+        matches!(found, Ok(Some(())))
+    }
+
     pub(crate) fn get_trait_names_and_type_arguments_for_type(
         module: &Module,
         engines: &Engines,
@@ -1077,12 +1892,15 @@ impl TraitMap {
         let type_id = engines.te().get_unaliased_type_id(type_id);
 
         let type_engine = engines.te();
-        let unify_check = UnifyCheck::constraint_subset(engines);
+        let unify_check = UnifyCheck::constraint_subset(engines).with_memo();
         let mut trait_names = vec![];
         // small performance gain in bad case
         if matches!(&*type_engine.get(type_id), TypeInfo::ErrorRecovery(_)) {
             return trait_names;
         }
+        // Like in `get_items_and_trait_key_for_type`, impls visible in several
+        // lexical scopes are reported only once, for the innermost scope.
+        let mut seen_impls = HashSet::new();
         let _ = module.walk_scope_chain_early_return(|lexical_scope| {
             lexical_scope.items.implemented_traits.for_each_impls(
                 engines,
@@ -1095,7 +1913,13 @@ impl TraitMap {
                             suffix: entry.key.name.suffix.name.clone(),
                             callpath_type: entry.key.name.callpath_type,
                         };
-                        trait_names.push((trait_call_path, entry.key.name.suffix.args.clone()));
+                        if seen_impls.insert((
+                            trait_call_path.clone(),
+                            entry.key.type_id,
+                            entry.value.impl_span.clone(),
+                        )) {
+                            trait_names.push((trait_call_path, entry.key.name.suffix.args.clone()));
+                        }
                     }
                 },
             );
@@ -1110,98 +1934,47 @@ impl TraitMap {
         engines: &Engines,
         symbol: &Ident,
         type_id: TypeId,
-        as_trait: Option<CallPath>,
+        as_trait: Option<DeclId<ty::TyTraitDecl>>,
     ) -> Result<ResolvedTraitImplItem, ErrorEmitted> {
         let type_id = engines.te().get_unaliased_type_id(type_id);
 
-        let mut candidates = HashMap::<String, ResolvedTraitImplItem>::new();
-        for (trait_item, trait_key) in
-            TraitMap::get_items_and_trait_key_for_type(module, engines, type_id)
-        {
-            match trait_item {
-                ResolvedTraitImplItem::Parsed(impl_item) => match impl_item {
-                    ImplItem::Fn(fn_ref) => {
-                        let decl = engines.pe().get_function(&fn_ref);
-                        let trait_call_path_string = engines.help_out(&*trait_key.name).to_string();
-                        if decl.name.as_str() == symbol.as_str()
-                            && (as_trait.is_none()
-                                || as_trait.clone().unwrap().to_string() == trait_call_path_string)
-                        {
-                            candidates.insert(
-                                trait_call_path_string,
-                                ResolvedTraitImplItem::Parsed(ImplItem::Fn(fn_ref)),
-                            );
-                        }
-                    }
-                    ImplItem::Constant(const_ref) => {
-                        let decl = engines.pe().get_constant(&const_ref);
-                        let trait_call_path_string = engines.help_out(&*trait_key.name).to_string();
-                        if decl.name.as_str() == symbol.as_str()
-                            && (as_trait.is_none()
-                                || as_trait.clone().unwrap().to_string() == trait_call_path_string)
-                        {
-                            candidates.insert(
-                                trait_call_path_string,
-                                ResolvedTraitImplItem::Parsed(ImplItem::Constant(const_ref)),
-                            );
-                        }
-                    }
-                    ImplItem::Type(type_ref) => {
-                        let decl = engines.pe().get_trait_type(&type_ref);
-                        let trait_call_path_string = engines.help_out(&*trait_key.name).to_string();
-                        if decl.name.as_str() == symbol.as_str()
-                            && (as_trait.is_none()
-                                || as_trait.clone().unwrap().to_string() == trait_call_path_string)
-                        {
-                            candidates.insert(
-                                trait_call_path_string,
-                                ResolvedTraitImplItem::Parsed(ImplItem::Type(type_ref)),
-                            );
-                        }
-                    }
-                },
-                ResolvedTraitImplItem::Typed(ty_impl_item) => match ty_impl_item {
-                    ty::TyTraitItem::Fn(fn_ref) => {
-                        let decl = engines.de().get_function(&fn_ref);
-                        let trait_call_path_string = engines.help_out(&*trait_key.name).to_string();
-                        if decl.name.as_str() == symbol.as_str()
-                            && (as_trait.is_none()
-                                || as_trait.clone().unwrap().to_string() == trait_call_path_string)
-                        {
-                            candidates.insert(
-                                trait_call_path_string,
-                                ResolvedTraitImplItem::Typed(TyTraitItem::Fn(fn_ref)),
-                            );
-                        }
-                    }
-                    ty::TyTraitItem::Constant(const_ref) => {
-                        let decl = engines.de().get_constant(&const_ref);
-                        let trait_call_path_string = engines.help_out(&*trait_key.name).to_string();
-                        if decl.call_path.suffix.as_str() == symbol.as_str()
-                            && (as_trait.is_none()
-                                || as_trait.clone().unwrap().to_string() == trait_call_path_string)
-                        {
-                            candidates.insert(
-                                trait_call_path_string,
-                                ResolvedTraitImplItem::Typed(TyTraitItem::Constant(const_ref)),
-                            );
-                        }
-                    }
-                    ty::TyTraitItem::Type(type_ref) => {
-                        let decl = engines.de().get_type(&type_ref);
-                        let trait_call_path_string = engines.help_out(&*trait_key.name).to_string();
-                        if decl.name.as_str() == symbol.as_str()
-                            && (as_trait.is_none()
-                                || as_trait.clone().unwrap().to_string() == trait_call_path_string)
-                        {
-                            candidates.insert(
-                                trait_call_path_string,
-                                ResolvedTraitImplItem::Typed(TyTraitItem::Type(type_ref)),
-                            );
-                        }
-                    }
-                },
+        // The trait map identifies trait declarations by their spans.
+        let as_trait_decl_span = as_trait.map(|decl_id| engines.de().get_trait(&decl_id).span());
+        let collect_candidates = || {
+            // Candidates are keyed by the full path of the trait providing them, which
+            // also makes the order in which they are reported deterministic.
+            let mut candidates = BTreeMap::<String, (ResolvedTraitImplItem, Span)>::new();
+            // Names of all the items that could be accessed for the type, used to
+            // suggest similar names if `symbol` is not found.
+            let mut available_item_names = BTreeSet::<String>::new();
+            for (trait_item, trait_key, _, impl_span) in
+                TraitMap::get_items_and_trait_key_for_type(module, engines, type_id)
+            {
+                if as_trait_decl_span.is_some() && trait_key.trait_decl_span != as_trait_decl_span {
+                    continue;
+                }
+                let item_name = trait_item.name(engines);
+                if item_name.as_str() == symbol.as_str() {
+                    let trait_call_path_string = engines.help_out(&*trait_key.name).to_string();
+                    candidates.insert(trait_call_path_string, (trait_item, impl_span));
+                }
+                if !trait_item.is_trait_method_dummy(engines) {
+                    available_item_names.insert(item_name.to_string());
+                }
             }
+            (candidates, available_item_names)
+        };
+
+        let (mut candidates, mut available_item_names) = collect_candidates();
+
+        // An unresolved numeric type is defaulted to u64, like when checking trait
+        // constraints, but only if the item cannot be resolved for it otherwise.
+        // Doing it upfront would override numerics that annotations resolve later.
+        if candidates.len() != 1 && engines.te().contains_numeric(engines, type_id) {
+            engines
+                .te()
+                .decay_numeric(handler, engines, type_id, &symbol.span())?;
+            (candidates, available_item_names) = collect_candidates();
         }
 
         match candidates.len().cmp(&1) {
@@ -1209,36 +1982,58 @@ impl TraitMap {
                 CompileError::MultipleApplicableItemsInScope {
                     item_name: symbol.as_str().to_string(),
                     item_kind: "item".to_string(),
-                    as_traits: candidates
-                        .keys()
-                        .map(|k| {
-                            (
-                                k.clone()
-                                    .split("::")
-                                    .collect::<Vec<_>>()
-                                    .last()
-                                    .unwrap()
-                                    .to_string(),
-                                engines.help_out(type_id).to_string(),
-                            )
+                    candidates: candidates
+                        .iter()
+                        .map(|(trait_path, (_, impl_span))| ApplicableItemCandidate {
+                            type_name: engines.help_out(type_id).to_string(),
+                            trait_path: Self::trait_path_to_display(module, trait_path),
+                            impl_span: impl_span.clone(),
                         })
-                        .collect::<Vec<_>>(),
+                        .collect(),
                     span: symbol.span(),
                 },
             )),
-            Ordering::Less => Err(handler.emit_err(CompileError::SymbolNotFound {
-                name: symbol.clone(),
-                span: symbol.span(),
-            })),
-            Ordering::Equal => Ok(candidates.values().next().unwrap().clone()),
+            Ordering::Less => {
+                let impl_for_other_ref_mutability =
+                    Self::get_impl_for_other_ref_mutability(module, engines, symbol, type_id);
+                let similar_item_names =
+                    did_you_mean(symbol.as_str(), available_item_names.iter(), 3);
+                if !similar_item_names.is_empty() || impl_for_other_ref_mutability.is_some() {
+                    return Err(handler.emit_err(CompileError::SymbolNotFoundForType {
+                        name: symbol.clone(),
+                        type_name: engines.help_out(type_id).to_string(),
+                        similar_item_names,
+                        impl_for_other_ref_mutability,
+                        span: symbol.span(),
+                    }));
+                }
+                Err(handler.emit_err(CompileError::SymbolNotFound {
+                    name: symbol.clone(),
+                    span: symbol.span(),
+                }))
+            }
+            Ordering::Equal => Ok(candidates.into_values().next().unwrap().0),
         }
     }
 
+    /// Returns the `trait_path` without the name of the package of the `module`,
+    /// because traits from the current package can be referred to without it.
+    fn trait_path_to_display(module: &Module, trait_path: &str) -> String {
+        module
+            .mod_path()
+            .first()
+            .and_then(|package_name| {
+                trait_path.strip_prefix(&format!("{}::", package_name.as_str()))
+            })
+            .unwrap_or(trait_path)
+            .to_string()
+    }
+
     /// Checks to see if the trait constraints are satisfied for a given type.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn check_if_trait_constraints_are_satisfied_for_type(
         handler: &Handler,
-        module: &mut Module,
+        module: &Module,
         type_id: TypeId,
         constraints: &[TraitConstraint],
         access_span: &Span,
@@ -1264,63 +2059,204 @@ impl TraitMap {
         let hash = hasher.finish();
 
         {
-            let trait_map = &mut module.current_lexical_scope_mut().items.implemented_traits;
-            if trait_map.satisfied_cache.contains(&hash) {
+            let trait_map = &module.current_lexical_scope().items.implemented_traits;
+            if trait_map.satisfied_cache.0.lock().contains(&hash) {
+                TraitMapCounters::bump(&trait_map.counters.satisfied_cache_hits);
                 return Ok(());
             }
+            TraitMapCounters::bump(&trait_map.counters.satisfied_cache_misses);
         }
 
-        let all_impld_traits: BTreeSet<(Ident, TypeId)> =
-            Self::get_all_implemented_traits(module, type_id, engines);
+        // Constraints without type arguments and associated type bindings, like
+        // the ones on marker traits, are satisfied as soon as a single impl of
+        // each trait is found. Only if that fails, all the traits implemented
+        // for the type are collected, to report the unsatisfied constraints.
+        let satisfied_by_single_impls = constraints.iter().all(|constraint| {
+            constraint.type_arguments.is_empty()
+                && constraint.associated_type_bindings.is_empty()
+                && Self::has_impl_for(module, engines, type_id, &constraint.trait_name, &[])
+        });
 
         // Call the real implementation and cache when true
-        match Self::check_if_trait_constraints_are_satisfied_for_type_inner(
+        let result = if satisfied_by_single_impls {
+            Ok(())
+        } else {
+            Self::check_if_trait_constraints_are_satisfied_for_type_uncached(
+                handler,
+                module,
+                type_id,
+                constraints,
+                access_span,
+                engines,
+            )
+        };
+        match result {
+            Ok(()) => {
+                let trait_map = &module.current_lexical_scope().items.implemented_traits;
+                trait_map.satisfied_cache.0.lock().insert(hash);
+                Ok(())
+            }
+            r => r,
+        }
+    }
+
+    /// Like [TraitMap::check_if_trait_constraints_are_satisfied_for_type], but
+    /// neither decays numeric types nor uses the cache of satisfied constraints.
+    fn check_if_trait_constraints_are_satisfied_for_type_uncached(
+        handler: &Handler,
+        module: &Module,
+        type_id: TypeId,
+        constraints: &[TraitConstraint],
+        access_span: &Span,
+        engines: &Engines,
+    ) -> Result<(), ErrorEmitted> {
+        // The same pairs of types are checked for every lexical scope and every
+        // constraint, so the results are shared for the whole check.
+        let unify_check = UnifyCheck::constraint_subset(engines).with_memo();
+
+        let mut rejected_impls = vec![];
+        let all_impld_traits: BTreeSet<(CallPath, TypeId)> = Self::get_all_implemented_traits(
+            module,
+            type_id,
+            engines,
+            &unify_check,
+            &mut rejected_impls,
+        );
+
+        Self::check_if_trait_constraints_are_satisfied_for_type_inner(
             handler,
             type_id,
             constraints,
             access_span,
             engines,
+            &unify_check,
             all_impld_traits,
-        ) {
-            Ok(()) => {
-                let trait_map = &mut module.current_lexical_scope_mut().items.implemented_traits;
-                trait_map.satisfied_cache.insert(hash);
-                Ok(())
-            }
-            r => r,
+            &rejected_impls,
+        )
+        .and_then(|_| {
+            Self::check_associated_type_bindings_for_type(
+                handler,
+                module,
+                type_id,
+                constraints,
+                access_span,
+                engines,
+            )
+        })
+    }
+
+    /// Returns the first trait constraint from the `where` clause of the impl
+    /// `entry` that is not satisfied when the impl is used for `type_id`,
+    /// together with the constrained type parameter and its type argument.
+    ///
+    /// Only concrete type arguments are checked. Blanket impls, like
+    /// `impl<T> Foo for T where T: Bar`, are never rejected, because checking
+    /// their constraints would mean checking `type_id` itself again.
+    fn find_unsatisfied_where_constraint<'a>(
+        module: &Module,
+        engines: &Engines,
+        entry: &'a TraitEntry,
+        type_id: TypeId,
+    ) -> Option<(&'a ImplTypeParameterConstraints, TypeId, TraitConstraint)> {
+        if entry.value.where_constraints.is_empty()
+            || matches!(
+                &*engines.te().get(entry.key.type_id),
+                TypeInfo::UnknownGeneric { .. } | TypeInfo::Placeholder(_)
+            )
+        {
+            return None;
         }
+
+        let type_mapping =
+            TypeSubstMap::from_superset_and_subset(engines, entry.key.type_id, type_id);
+        entry
+            .value
+            .where_constraints
+            .iter()
+            .find_map(|where_constraint| {
+                let type_argument = type_mapping.find_match(where_constraint.type_id, engines)?;
+                if !type_argument.is_concrete(engines, TreatNumericAs::Abstract) {
+                    return None;
+                }
+
+                where_constraint
+                    .trait_constraints
+                    .iter()
+                    .map(|constraint| {
+                        let mut constraint = constraint.clone();
+                        constraint.subst(&SubstTypesContext::new(engines, &type_mapping, false));
+                        constraint
+                    })
+                    .find(|constraint| {
+                        Self::check_if_trait_constraints_are_satisfied_for_type(
+                            &Handler::default(),
+                            module,
+                            type_argument,
+                            std::slice::from_ref(constraint),
+                            &Span::dummy(),
+                            engines,
+                        )
+                        .is_err()
+                    })
+                    .map(|constraint| (where_constraint, type_argument, constraint))
+            })
     }
 
     fn get_all_implemented_traits(
         module: &Module,
         type_id: TypeId,
         engines: &Engines,
-    ) -> BTreeSet<(Ident, TypeId)> {
-        let mut all_impld_traits: BTreeSet<(Ident, TypeId)> = Default::default();
+        unify_check: &UnifyCheck,
+        rejected_impls: &mut Vec<RejectedImpl>,
+    ) -> BTreeSet<(CallPath, TypeId)> {
+        let mut all_impld_traits: BTreeSet<(CallPath, TypeId)> = Default::default();
         let _ = module.walk_scope_chain_early_return(|lexical_scope| {
             all_impld_traits.extend(
                 lexical_scope
                     .items
                     .implemented_traits
-                    .get_implemented_traits(type_id, engines),
+                    .get_implemented_traits(module, type_id, engines, unify_check, rejected_impls),
             );
             Ok(None::<()>)
         });
         all_impld_traits
     }
 
+    /// Returns the traits implemented for `type_id` in `self`.
+    ///
+    /// Impls that match `type_id` but whose `where` clause is not satisfied
+    /// are not returned, but added to the `rejected_impls` instead.
     fn get_implemented_traits(
         &self,
+        module: &Module,
         type_id: TypeId,
         engines: &Engines,
-    ) -> BTreeSet<(Ident, TypeId)> {
+        unify_check: &UnifyCheck,
+        rejected_impls: &mut Vec<RejectedImpl>,
+    ) -> BTreeSet<(CallPath, TypeId)> {
         let type_engine = engines.te();
-        let unify_check = UnifyCheck::constraint_subset(engines);
-        let mut all_impld_traits = BTreeSet::<(Ident, TypeId)>::new();
+        let mut all_impld_traits = BTreeSet::<(CallPath, TypeId)>::new();
         self.for_each_impls(engines, type_id, true, |e| {
             let key = &e.key;
             let suffix = &key.name.suffix;
             if unify_check.check(type_id, key.type_id) {
+                if let Some((where_constraint, type_argument, trait_constraint)) =
+                    Self::find_unsatisfied_where_constraint(module, engines, e, type_id)
+                {
+                    rejected_impls.push(RejectedImpl {
+                        trait_name: CallPath {
+                            prefixes: key.name.prefixes.clone(),
+                            suffix: suffix.name.clone(),
+                            callpath_type: key.name.callpath_type,
+                        },
+                        type_parameter: where_constraint.name.clone(),
+                        type_argument,
+                        trait_constraint,
+                        impl_span: e.value.impl_span.clone(),
+                    });
+                    return;
+                }
+
                 let map_trait_type_id = type_engine.new_custom(
                     engines,
                     suffix.name.clone().into(),
@@ -1330,7 +2266,12 @@ impl TraitMap {
                         Some(suffix.args.to_vec())
                     },
                 );
-                all_impld_traits.insert((suffix.name.clone(), map_trait_type_id));
+                let trait_path = CallPath {
+                    prefixes: key.name.prefixes.clone(),
+                    suffix: suffix.name.clone(),
+                    callpath_type: key.name.callpath_type,
+                };
+                all_impld_traits.insert((trait_path, map_trait_type_id));
             }
         });
         all_impld_traits
@@ -1343,17 +2284,19 @@ impl TraitMap {
         constraints: &[TraitConstraint],
         access_span: &Span,
         engines: &Engines,
-        all_impld_traits: BTreeSet<(Ident, TypeId)>,
+        unify_check: &UnifyCheck,
+        all_impld_traits: BTreeSet<(CallPath, TypeId)>,
+        rejected_impls: &[RejectedImpl],
     ) -> Result<(), ErrorEmitted> {
         let type_engine = engines.te();
-        let unify_check = UnifyCheck::constraint_subset(engines);
 
-        let required_traits: BTreeSet<(Ident, TypeId)> = constraints
+        let required_traits: BTreeSet<(CallPath, TypeId)> = constraints
             .iter()
             .map(|c| {
                 let TraitConstraint {
                     trait_name: constraint_trait_name,
                     type_arguments: constraint_type_arguments,
+                    associated_type_bindings: _,
                 } = c;
                 let constraint_type_id = type_engine.new_custom(
                     engines,
@@ -1364,24 +2307,78 @@ impl TraitMap {
                         Some(constraint_type_arguments.clone())
                     },
                 );
-                (c.trait_name.suffix.clone(), constraint_type_id)
+                (constraint_trait_name.clone(), constraint_type_id)
             })
             .collect();
 
-        let traits_not_found: BTreeSet<(BaseIdent, TypeId)> = required_traits
-            .into_iter()
-            .filter(|(required_trait_name, required_trait_type_id)| {
-                !all_impld_traits
-                    .iter()
-                    .any(|(trait_name, constraint_type_id)| {
-                        trait_name == required_trait_name
-                            && unify_check.check(*constraint_type_id, *required_trait_type_id)
-                    })
-            })
-            .collect();
+        // Constraints are checked independently of each other, so long lists of
+        // constraints are checked in parallel. `required_traits` is ordered and the
+        // order is preserved when collecting, which keeps the emitted errors deterministic.
+        let use_parallel = required_traits.len() >= PARALLEL_CONSTRAINTS_THRESHOLD;
+        let unsatisfied_or_legacy: Vec<(CallPath, TypeId, Option<CallPath>)> = CondIterator::new(
+            required_traits.into_iter().collect::<Vec<_>>(),
+            use_parallel,
+        )
+        .filter_map(|(required_trait_name, required_trait_type_id)| {
+            let mut suffix_only_match = None;
+            for (trait_name, constraint_type_id) in all_impld_traits.iter() {
+                let paths_match = match_trait_paths(trait_name, &required_trait_name);
+                if paths_match == TraitPathMatch::NoMatch
+                    || !unify_check.check(*constraint_type_id, required_trait_type_id)
+                {
+                    continue;
+                }
+                if paths_match == TraitPathMatch::Full {
+                    return None;
+                }
+                suffix_only_match.get_or_insert_with(|| trait_name.clone());
+            }
+            Some((
+                required_trait_name,
+                required_trait_type_id,
+                suffix_only_match,
+            ))
+        })
+        .collect();
 
         handler.scope(|handler| {
-            for (trait_name, constraint_type_id) in traits_not_found.iter() {
+            for (trait_name, constraint_type_id, suffix_only_match) in unsatisfied_or_legacy.iter()
+            {
+                // Legacy case: at least one of the trait paths is not fully qualified,
+                // so the traits could only be matched by their names.
+                if let Some(implemented_trait_name) = suffix_only_match {
+                    handler.emit_warn(CompileWarning {
+                        span: access_span.clone(),
+                        warning_content: Warning::TraitConstraintMatchedBySuffixOnly {
+                            required_trait_name: trait_name.to_string(),
+                            implemented_trait_name: implemented_trait_name.to_string(),
+                        },
+                    });
+                    continue;
+                }
+
+                // The trait is implemented for the type, but the `where` clause of the impl
+                // is not satisfied, so we point to the constraint that rules the impl out.
+                if let Some(rejected_impl) = rejected_impls.iter().find(|rejected_impl| {
+                    match_trait_paths(&rejected_impl.trait_name, trait_name) == TraitPathMatch::Full
+                }) {
+                    handler.emit_err(CompileError::ImplWhereClauseNotSatisfied {
+                        ty: engines.help_out(type_id).to_string(),
+                        trait_name: trait_name.suffix.to_string(),
+                        type_parameter: rejected_impl.type_parameter.to_string(),
+                        type_argument: engines.help_out(rejected_impl.type_argument).to_string(),
+                        required_trait_name: rejected_impl
+                            .trait_constraint
+                            .trait_name
+                            .suffix
+                            .to_string(),
+                        impl_span: rejected_impl.impl_span.clone(),
+                        span: access_span.clone(),
+                    });
+                    continue;
+                }
+
+                let trait_name = &trait_name.suffix;
                 let mut type_arguments_string = "".to_string();
                 if let TypeInfo::Custom {
                     qualified_call_path: _,
@@ -1404,26 +2401,94 @@ impl TraitMap {
         })
     }
 
+    /// Checks that the associated types implemented for `type_id` match the
+    /// associated type bindings of the `constraints`.
+    /// E.g., for `T: Iterator<Item = u64>` checks that `Item` is `u64` in the
+    /// implementation of `Iterator` for `type_id`.
+    fn check_associated_type_bindings_for_type(
+        handler: &Handler,
+        module: &Module,
+        type_id: TypeId,
+        constraints: &[TraitConstraint],
+        access_span: &Span,
+        engines: &Engines,
+    ) -> Result<(), ErrorEmitted> {
+        let decl_engine = engines.de();
+        let unify_check = UnifyCheck::non_dynamic_equality(engines);
+
+        handler.scope(|handler| {
+            for constraint in constraints
+                .iter()
+                .filter(|c| !c.associated_type_bindings.is_empty())
+            {
+                let trait_items =
+                    Self::get_items_for_type_and_trait_name_and_trait_type_arguments_typed(
+                        module,
+                        engines,
+                        type_id,
+                        &constraint.trait_name,
+                        &constraint.type_arguments,
+                    );
+
+                for binding in constraint.associated_type_bindings.iter() {
+                    let trait_type = trait_items.iter().find_map(|item| match item {
+                        TyTraitItem::Type(type_ref) => {
+                            let trait_type = decl_engine.get_type(type_ref);
+                            (trait_type.name == binding.name).then_some(trait_type)
+                        }
+                        _ => None,
+                    });
+
+                    let Some(trait_type) = trait_type else {
+                        handler.emit_err(CompileError::SymbolNotFound {
+                            name: binding.name.clone(),
+                            span: binding.name.span(),
+                        });
+                        continue;
+                    };
+
+                    let Some(actual) = &trait_type.ty else {
+                        continue;
+                    };
+
+                    if !unify_check.check(actual.type_id, binding.type_argument.type_id) {
+                        handler.emit_err(CompileError::TraitConstraintAssociatedTypeMismatch {
+                            ty: engines.help_out(type_id).to_string(),
+                            trait_name: constraint.trait_name.suffix.to_string(),
+                            associated_type_name: binding.name.to_string(),
+                            expected: engines.help_out(binding.type_argument.type_id).to_string(),
+                            actual: engines.help_out(actual.type_id).to_string(),
+                            span: access_span.clone(),
+                            associated_type_span: trait_type.span.clone(),
+                        });
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+
     pub fn get_trait_constraints_are_satisfied_for_types(
         module: &Module,
         _handler: &Handler,
         type_id: TypeId,
         constraints: &[TraitConstraint],
         engines: &Engines,
-    ) -> Result<Vec<(TypeId, String)>, ErrorEmitted> {
+    ) -> Result<Vec<SatisfiedTraitConstraint>, ErrorEmitted> {
         let type_id = engines.te().get_unaliased_type_id(type_id);
 
         let _decl_engine = engines.de();
         let unify_check = UnifyCheck::coercion(engines);
         let unify_check_equality = UnifyCheck::constraint_subset(engines);
 
-        let mut impld_traits_type_ids: Vec<Vec<(TypeId, String)>> = vec![];
+        let mut impld_traits_type_ids: Vec<Vec<SatisfiedTraitConstraint>> = vec![];
         let _ = module.walk_scope_chain_early_return(|lexical_scope| {
             lexical_scope
                 .items
                 .implemented_traits
                 .for_each_impls(engines, type_id, true, |e| {
-                    let mut traits: Vec<(TypeId, String)> = vec![];
+                    let mut traits: Vec<SatisfiedTraitConstraint> = vec![];
 
                     let key = &e.key;
                     for constraint in constraints {
@@ -1437,14 +2502,13 @@ impl TraitMap {
                                 .all(|(a1, a2)| unify_check_equality.check(a1.type_id, a2.type_id))
                             && unify_check.check(type_id, key.type_id)
                         {
-                            let name_type_args = if !key.name.suffix.args.is_empty() {
-                                format!("<{}>", engines.help_out(key.name.suffix.args.clone()))
-                            } else {
-                                "".to_string()
-                            };
-                            let name =
-                                format!("{}{}", key.name.suffix.name.as_str(), name_type_args);
-                            traits.push((key.type_id, name));
+                            traits.push(SatisfiedTraitConstraint {
+                                constraint: constraint.clone(),
+                                implementing_type_id: key.type_id,
+                                trait_type_arguments: key.name.suffix.args.clone(),
+                                impl_span: e.value.impl_span.clone(),
+                                trait_decl_span: key.trait_decl_span.clone(),
+                            });
                             break;
                         }
                     }
@@ -1456,14 +2520,84 @@ impl TraitMap {
         Ok(impld_traits_type_ids.concat())
     }
 
-    fn get_impls_mut(&mut self, engines: &Engines, type_id: TypeId) -> &mut Vec<TraitEntry> {
-        let type_root_filter = Self::get_type_root_filter(engines, type_id);
-        if !self.trait_impls.contains_key(&type_root_filter) {
-            self.trait_impls
-                .insert(type_root_filter.clone(), Vec::new());
+    /// Returns the current [TraitMapStats] of `self`.
+    pub fn stats(&self) -> TraitMapStats {
+        let counters = &self.counters;
+        TraitMapStats {
+            buckets: self
+                .trait_impls
+                .values()
+                .filter(|entries| !entries.is_empty())
+                .count(),
+            entries: self.trait_impls.values().map(Vec::len).sum(),
+            max_bucket_entries: self.trait_impls.values().map(Vec::len).max().unwrap_or(0),
+            items: self
+                .trait_impls
+                .values()
+                .flatten()
+                .map(|entry| entry.value.trait_items.len())
+                .sum(),
+            inserts: TraitMapCounters::get(&counters.inserts),
+            extends: TraitMapCounters::get(&counters.extends),
+            lookups: TraitMapCounters::get(&counters.lookups),
+            satisfied_cache_hits: TraitMapCounters::get(&counters.satisfied_cache_hits),
+            satisfied_cache_misses: TraitMapCounters::get(&counters.satisfied_cache_misses),
         }
+    }
+
+    /// Returns the buckets of `self` that can contain impls for the types with
+    /// the `type_root_filter`.
+    ///
+    /// The buckets are borrowed from `self` and chained lazily, so looking up
+    /// impls neither clones entries nor allocates.
+    fn get_impls_buckets<'a>(
+        &'a self,
+        type_root_filter: &'a TypeRootFilter,
+        include_placeholder: bool,
+    ) -> impl Iterator<Item = &'a Vec<TraitEntry>> + 'a {
+        // Impls for tuples and arrays with unknown or generic elements can be
+        // impls for any tuple and array of the same shape.
+        let wildcard_bucket = match type_root_filter {
+            TypeRootFilter::Tuple(elements) => self
+                .trait_impls
+                .get(&TypeRootFilter::TupleWildcard(elements.len())),
+            TypeRootFilter::Array(_) => self.trait_impls.get(&TypeRootFilter::ArrayWildcard),
+            _ => None,
+        };
+
+        // And the other way around, tuples and arrays with unknown or generic
+        // elements can have the impls of any tuple and array of the same shape.
+        let same_shape_buckets = matches!(
+            type_root_filter,
+            TypeRootFilter::TupleWildcard(_) | TypeRootFilter::ArrayWildcard
+        )
+        .then(|| {
+            self.trait_impls
+                .iter()
+                .filter(move |(filter, _)| match type_root_filter {
+                    TypeRootFilter::TupleWildcard(len) => {
+                        matches!(filter, TypeRootFilter::Tuple(elements) if elements.len() == *len)
+                    }
+                    _ => matches!(filter, TypeRootFilter::Array(_)),
+                })
+                .map(|(_, bucket)| bucket)
+        })
+        .into_iter()
+        .flatten();
 
-        self.trait_impls.get_mut(&type_root_filter).unwrap()
+        let placeholder_bucket =
+            if include_placeholder && *type_root_filter != TypeRootFilter::Placeholder {
+                self.trait_impls.get(&TypeRootFilter::Placeholder)
+            } else {
+                None
+            };
+
+        self.trait_impls
+            .get(type_root_filter)
+            .into_iter()
+            .chain(wildcard_bucket)
+            .chain(same_shape_buckets)
+            .chain(placeholder_bucket)
     }
 
     pub(crate) fn for_each_impls<F>(
@@ -1475,23 +2609,67 @@ impl TraitMap {
     ) where
         F: FnMut(&TraitEntry),
     {
+        TraitMapCounters::bump(&self.counters.lookups);
         let type_root_filter = Self::get_type_root_filter(engines, type_id);
-        self.trait_impls
-            .get(&type_root_filter)
-            .iter()
-            .for_each(|vec| vec.iter().for_each(&mut callback));
-        if include_placeholder && type_root_filter != TypeRootFilter::Placeholder {
-            self.trait_impls
-                .get(&TypeRootFilter::Placeholder)
-                .iter()
-                .for_each(|vec| vec.iter().for_each(&mut callback));
+        self.get_impls_buckets(&type_root_filter, include_placeholder)
+            .flatten()
+            .for_each(&mut callback);
+    }
+
+    /// Like [TraitMap::for_each_impls], but stops at the first impl for which
+    /// `predicate` returns `true`.
+    fn any_impls<F>(
+        &self,
+        engines: &Engines,
+        type_id: TypeId,
+        include_placeholder: bool,
+        mut predicate: F,
+    ) -> bool
+    where
+        F: FnMut(&TraitEntry) -> bool,
+    {
+        TraitMapCounters::bump(&self.counters.lookups);
+        let type_root_filter = Self::get_type_root_filter(engines, type_id);
+        self.get_impls_buckets(&type_root_filter, include_placeholder)
+            .flatten()
+            .any(&mut predicate)
+    }
+
+    /// Follows [TypeInfo::Alias]es and [TypeInfo::Ref]erences of `type_id` down to
+    /// the underlying type.
+    ///
+    /// Returns `None` if the chain of aliases and references does not terminate
+    /// within [MAX_TYPE_INDIRECTION_DEPTH], which can happen for self-referential
+    /// types produced during error recovery.
+    fn get_root_type_id(engines: &Engines, mut type_id: TypeId) -> Option<TypeId> {
+        for _ in 0..MAX_TYPE_INDIRECTION_DEPTH {
+            match &*engines.te().get(type_id) {
+                TypeInfo::Alias { ty, .. } => type_id = ty.type_id,
+                TypeInfo::Ref {
+                    referenced_type, ..
+                } => type_id = referenced_type.type_id,
+                _ => return Some(type_id),
+            }
         }
+        None
     }
 
     // Return a string representing only the base type.
     // This is used by the trait map to filter the entries into a HashMap with the return type string as key.
+    // Self-referential types are reported when inserted, see [TraitMap::insert], and are looked up as unknown.
     fn get_type_root_filter(engines: &Engines, type_id: TypeId) -> TypeRootFilter {
+        Self::get_type_root_filter_at_depth(engines, type_id, 0)
+    }
+
+    fn get_type_root_filter_at_depth(
+        engines: &Engines,
+        type_id: TypeId,
+        depth: usize,
+    ) -> TypeRootFilter {
         use TypeInfo::*;
+        let Some(type_id) = Self::get_root_type_id(engines, type_id) else {
+            return TypeRootFilter::Unknown;
+        };
         match &*engines.te().get(type_id) {
             Unknown => TypeRootFilter::Unknown,
             Never => TypeRootFilter::Never,
@@ -1515,7 +2693,26 @@ impl TraitMap {
             Numeric => TypeRootFilter::U64, // u64 is the default
             Contract => TypeRootFilter::Contract,
             ErrorRecovery(_) => TypeRootFilter::ErrorRecovery,
-            Tuple(fields) => TypeRootFilter::Tuple(fields.len()),
+            Tuple(fields) => {
+                let elements = (depth < MAX_TYPE_ROOT_FILTER_DEPTH)
+                    .then(|| {
+                        fields
+                            .iter()
+                            .map(|field| {
+                                Self::get_type_root_filter_at_depth(
+                                    engines,
+                                    field.type_id,
+                                    depth + 1,
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .filter(|elements| elements.iter().all(|element| !element.is_wildcard()));
+                match elements {
+                    Some(elements) => TypeRootFilter::Tuple(elements),
+                    None => TypeRootFilter::TupleWildcard(fields.len()),
+                }
+            }
             UntypedEnum(decl_id) => TypeRootFilter::Enum(*decl_id),
             UntypedStruct(decl_id) => TypeRootFilter::Struct(*decl_id),
             Enum(decl_id) => {
@@ -1527,16 +2724,1084 @@ impl TraitMap {
                 TypeRootFilter::Struct(engines.de().get_parsed_decl_id(decl_id).unwrap())
             }
             ContractCaller { abi_name, .. } => TypeRootFilter::ContractCaller(abi_name.to_string()),
-            Array(_, _) => TypeRootFilter::Array,
+            Array(elem_ty, _) => {
+                let element = (depth < MAX_TYPE_ROOT_FILTER_DEPTH)
+                    .then(|| {
+                        Self::get_type_root_filter_at_depth(engines, elem_ty.type_id, depth + 1)
+                    })
+                    .filter(|element| !element.is_wildcard());
+                match element {
+                    Some(element) => TypeRootFilter::Array(Box::new(element)),
+                    None => TypeRootFilter::ArrayWildcard,
+                }
+            }
             RawUntypedPtr => TypeRootFilter::RawUntypedPtr,
             RawUntypedSlice => TypeRootFilter::RawUntypedSlice,
             Ptr(_) => TypeRootFilter::Ptr,
             Slice(_) => TypeRootFilter::Slice,
-            Alias { ty, .. } => Self::get_type_root_filter(engines, ty.type_id),
             TraitType { name, .. } => TypeRootFilter::TraitType(name.to_string()),
-            Ref {
-                referenced_type, ..
-            } => Self::get_type_root_filter(engines, referenced_type.type_id),
+            Alias { .. } | Ref { .. } => {
+                unreachable!("aliases and references are resolved by `get_root_type_id`")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        decl_engine::ParsedDeclEngineInsert,
+        language::{
+            parsed::{CodeBlock, FunctionDeclaration, FunctionDeclarationKind},
+            Visibility,
+        },
+        namespace::{ModulePathBuf, Package},
+        ExperimentalFeatures, VecSet,
+    };
+    use sway_types::ProgramId;
+
+    fn trait_name(name: &str) -> TraitName {
+        Arc::new(CallPath {
+            prefixes: vec![],
+            suffix: TraitSuffix {
+                name: Ident::new_no_span(name.into()),
+                args: vec![],
+            },
+            callpath_type: CallPathType::Ambiguous,
+        })
+    }
+
+    /// Creates a malformed type that aliases or references itself.
+    fn new_self_referential_type(
+        engines: &Engines,
+        make_type_info: impl Fn(TypeArgument) -> TypeInfo,
+    ) -> TypeId {
+        let type_id = engines.te().new_unknown();
+        let type_argument = TypeArgument {
+            type_id,
+            initial_type_id: type_id,
+            span: Span::dummy(),
+            call_path_tree: None,
+        };
+        engines
+            .te()
+            .replace(engines, type_id, make_type_info(type_argument));
+        type_id
+    }
+
+    #[test]
+    fn type_root_filter_of_self_referential_types_terminates() {
+        let engines = Engines::default();
+        let alias = new_self_referential_type(&engines, |ty| TypeInfo::Alias {
+            name: Ident::new_no_span("SelfAlias".into()),
+            ty,
+        });
+        let reference = new_self_referential_type(&engines, |referenced_type| TypeInfo::Ref {
+            to_mutable_value: false,
+            referenced_type,
+        });
+
+        assert_eq!(
+            TraitMap::get_type_root_filter(&engines, alias),
+            TypeRootFilter::Unknown
+        );
+        assert_eq!(
+            TraitMap::get_type_root_filter(&engines, reference),
+            TypeRootFilter::Unknown
+        );
+    }
+
+    #[test]
+    fn unaliasing_self_referential_alias_terminates() {
+        let engines = Engines::default();
+        let alias = new_self_referential_type(&engines, |ty| TypeInfo::Alias {
+            name: Ident::new_no_span("SelfAlias".into()),
+            ty,
+        });
+        let u64_alias = engines.te().insert(
+            &engines,
+            TypeInfo::Alias {
+                name: Ident::new_no_span("U64Alias".into()),
+                ty: TypeArgument {
+                    type_id: engines.te().id_of_u64(),
+                    initial_type_id: engines.te().id_of_u64(),
+                    span: Span::dummy(),
+                    call_path_tree: None,
+                },
+            },
+            None,
+        );
+
+        assert_eq!(engines.te().try_get_unaliased_type_id(alias), None);
+        assert_eq!(engines.te().get_unaliased_type_id(alias), alias);
+        assert_eq!(
+            engines.te().try_get_unaliased_type_id(u64_alias),
+            Some(engines.te().id_of_u64())
+        );
+
+        assert_eq!(is_unified_type_subset(engines.te(), alias, alias), None);
+        assert!(!differs_only_in_ref_mutability(&engines, alias, alias));
+    }
+
+    #[test]
+    fn inserting_impl_for_self_referential_alias_emits_internal_error() {
+        let engines = Engines::default();
+        let alias = new_self_referential_type(&engines, |ty| TypeInfo::Alias {
+            name: Ident::new_no_span("SelfAlias".into()),
+            ty,
+        });
+
+        let handler = Handler::default();
+        let mut trait_map = TraitMap::default();
+        let result = trait_map.insert(
+            &handler,
+            CallPath {
+                prefixes: vec![],
+                suffix: Ident::new_no_span("MyTrait".into()),
+                callpath_type: CallPathType::Ambiguous,
+            },
+            vec![],
+            alias,
+            vec![],
+            &[],
+            &Span::dummy(),
+            Some(Span::dummy()),
+            IsImplSelf::No,
+            IsExtendingExistingImpl::No,
+            &engines,
+        );
+
+        assert!(result.is_err());
+        assert!(trait_map.trait_impls.is_empty());
+        let (errors, _) = handler.consume();
+        assert!(matches!(
+            errors.as_slice(),
+            [CompileError::Internal(
+                "Self-referential type found while inserting into the trait map.",
+                _
+            )]
+        ));
+    }
+
+    #[test]
+    fn trait_map_dump_lists_impls_grouped_by_type_root_filter() {
+        let engines = Engines::default();
+        let handler = Handler::default();
+        let package = Package::new(
+            Ident::new_no_span("trait_map_dump_test".to_string()),
+            None,
+            ProgramId::new(0),
+            false,
+        );
+        let programs = crate::compile_to_ast(
+            &handler,
+            &engines,
+            r#"
+            library;
+
+            struct S {}
+
+            trait MyTrait {
+                fn my_fn(self);
+            }
+
+            trait AnotherTrait {
+                fn another_fn(self) -> u64;
+            }
+
+            impl MyTrait for u64 {
+                fn my_fn(self) {}
+            }
+
+            impl AnotherTrait for u64 {
+                fn another_fn(self) -> u64 {
+                    self
+                }
+            }
+
+            impl MyTrait for bool {
+                fn my_fn(self) {}
+            }
+
+            impl MyTrait for S {
+                fn my_fn(self) {}
+            }
+
+            impl S {
+                fn new() -> Self {
+                    Self {}
+                }
+            }
+            "#
+            .into(),
+            package,
+            None,
+            "test",
+            None,
+            ExperimentalFeatures::default(),
+        );
+        let (errors, _warnings) = handler.consume();
+        assert!(errors.is_empty(), "{errors:#?}");
+        let program = programs.unwrap().typed.unwrap();
+
+        let dump = crate::trait_map_dump(
+            &engines,
+            program.namespace.current_package_ref(),
+            "trait_map_dump_test",
+            "type checking",
+        )
+        .expect("the root module is a part of the package");
+
+        assert!(dump
+            .starts_with("// Trait map of module \"trait_map_dump_test\" after type checking:\n"));
+
+        // Groups are sorted by their type root filters, and impls within a group
+        // by their rendering.
+        let lines = dump.lines().skip(1).collect::<Vec<_>>();
+        let position = |expected: &str| {
+            lines
+                .iter()
+                .position(|line| line.starts_with(expected))
+                .unwrap_or_else(|| panic!("\"{expected}\" is not in the trait map dump:\n{dump}"))
+        };
+        let bool_group = position("Bool:");
+        let bool_my_trait = position("    impl trait_map_dump_test::MyTrait for bool { my_fn }");
+        let struct_group = position("Struct(S):");
+        let struct_self = position("    impl S { new }");
+        let struct_my_trait = position("    impl trait_map_dump_test::MyTrait for S { my_fn }");
+        let u64_group = position("U64:");
+        let u64_another_trait =
+            position("    impl trait_map_dump_test::AnotherTrait for u64 { another_fn }");
+        let u64_my_trait = position("    impl trait_map_dump_test::MyTrait for u64 { my_fn }");
+
+        assert!(bool_group < bool_my_trait && bool_my_trait < struct_group);
+        assert!(struct_group < struct_self && struct_self < struct_my_trait);
+        assert!(struct_my_trait < u64_group);
+        assert!(u64_group < u64_another_trait && u64_another_trait < u64_my_trait);
+
+        assert_eq!(
+            crate::trait_map_dump(
+                &engines,
+                program.namespace.current_package_ref(),
+                "trait_map_dump_test::not_a_module",
+                "type checking",
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn impls_visible_in_several_scopes_are_returned_once() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let type_ref = engines.de().insert(
+            ty::TyTraitType {
+                name: Ident::new_no_span("Item".into()),
+                attributes: Default::default(),
+                ty: None,
+                implementing_type: u64_type_id,
+                span: Span::dummy(),
+            },
+            None,
+        );
+        let entry = TraitEntry {
+            key: TraitKey {
+                name: trait_name("MyTrait"),
+                type_id: u64_type_id,
+                impl_type_parameters: vec![],
+                trait_decl_span: Some(Span::dummy()),
+            },
+            value: TraitValue {
+                trait_items: BTreeMap::from([(
+                    "Item".to_string(),
+                    ResolvedTraitImplItem::Typed(TyTraitItem::Type(type_ref)),
+                )]),
+                impl_span: Span::dummy(),
+                origin: ImplOrigin::default(),
+                where_constraints: vec![],
+            },
+        };
+
+        // The same impl is visible in the root scope, e.g., imported via the prelude,
+        // and in a nested scope, e.g., imported directly.
+        let mut module = Module::new(
+            Ident::new_no_span("lib".into()),
+            Visibility::Public,
+            None,
+            &ModulePathBuf::new(),
+        );
+        module
+            .current_items_mut()
+            .implemented_traits
+            .trait_impls
+            .insert(TypeRootFilter::U64, vec![entry.clone()]);
+        module.push_new_lexical_scope(Span::dummy(), None);
+        module
+            .current_items_mut()
+            .implemented_traits
+            .trait_impls
+            .insert(TypeRootFilter::U64, vec![entry]);
+
+        assert_eq!(
+            TraitMap::get_items_for_type(&module, &engines, u64_type_id).len(),
+            1
+        );
+        assert_eq!(
+            TraitMap::get_trait_names_and_type_arguments_for_type(&module, &engines, u64_type_id)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn docs_of_trait_impl_items_are_returned_for_implementing_type() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+
+        let src = "library;\n/// Converts the value.\n/// Never fails.\nfn convert() {}\n";
+        let module = sway_parse::parse_file(&Handler::default(), Arc::from(src), None).unwrap();
+        let attributes = transform::Attributes::new(&module.value.items[0].attributes);
+
+        let parsed_fn = FunctionDeclaration {
+            purity: Default::default(),
+            attributes: attributes.clone(),
+            name: Ident::new_no_span("convert".into()),
+            visibility: Visibility::Public,
+            body: CodeBlock {
+                contents: vec![],
+                whole_block_span: Span::dummy(),
+            },
+            parameters: vec![],
+            span: Span::dummy(),
+            return_type: TypeArgument {
+                type_id: u64_type_id,
+                initial_type_id: u64_type_id,
+                span: Span::dummy(),
+                call_path_tree: None,
+            },
+            type_parameters: vec![],
+            const_generic_parameters: vec![],
+            where_clause: vec![],
+            kind: FunctionDeclarationKind::Default,
+            implementing_type: None,
+        };
+        let mut typed_fn = ty::TyFunctionDecl::error(&parsed_fn);
+        typed_fn.attributes = attributes;
+        let typed_fn_ref = engines.de().insert(typed_fn, None);
+        let parsed_fn_id = engines.pe().insert(parsed_fn);
+
+        let entry = TraitEntry {
+            key: TraitKey {
+                name: trait_name("Convert"),
+                type_id: u64_type_id,
+                impl_type_parameters: vec![],
+                trait_decl_span: Some(Span::dummy()),
+            },
+            value: TraitValue {
+                trait_items: BTreeMap::from([(
+                    "convert".to_string(),
+                    ResolvedTraitImplItem::Typed(TyTraitItem::Fn(typed_fn_ref)),
+                )]),
+                impl_span: Span::dummy(),
+                origin: ImplOrigin::default(),
+                where_constraints: vec![],
+            },
+        };
+        let mut module = Module::new(
+            Ident::new_no_span("lib".into()),
+            Visibility::Public,
+            None,
+            &ModulePathBuf::new(),
+        );
+        module
+            .current_items_mut()
+            .implemented_traits
+            .trait_impls
+            .insert(TypeRootFilter::U64, vec![entry]);
+
+        let typed_item = TraitMap::get_items_for_type(&module, &engines, u64_type_id)
+            .pop()
+            .unwrap();
+        let parsed_item = ResolvedTraitImplItem::Parsed(ImplItem::Fn(parsed_fn_id));
+
+        for item in [typed_item, parsed_item] {
+            assert_eq!(item.name(&engines).as_str(), "convert");
+            assert_eq!(
+                item.doc_comment(&engines).as_deref(),
+                Some(" Converts the value.\n Never fails.")
+            );
+            assert_eq!(item.attributes(&engines).all().count(), 2);
+        }
+    }
+
+    #[test]
+    fn has_impl_for_agrees_with_items_lookup() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let bool_type_id = engines.te().id_of_bool();
+        let type_argument = |type_id| TypeArgument {
+            type_id,
+            initial_type_id: type_id,
+            span: Span::dummy(),
+            call_path_tree: None,
+        };
+        let type_ref = engines.de().insert(
+            ty::TyTraitType {
+                name: Ident::new_no_span("Item".into()),
+                attributes: Default::default(),
+                ty: None,
+                implementing_type: u64_type_id,
+                span: Span::dummy(),
+            },
+            None,
+        );
+
+        // impl MyTrait<u64> for u64 { type Item; }
+        let mut my_trait_of_u64 = (*trait_name("MyTrait")).clone();
+        my_trait_of_u64.suffix.args = vec![type_argument(u64_type_id)];
+        let entry = TraitEntry {
+            key: TraitKey {
+                name: Arc::new(my_trait_of_u64),
+                type_id: u64_type_id,
+                impl_type_parameters: vec![],
+                trait_decl_span: Some(Span::dummy()),
+            },
+            value: TraitValue {
+                trait_items: BTreeMap::from([(
+                    "Item".to_string(),
+                    ResolvedTraitImplItem::Typed(TyTraitItem::Type(type_ref)),
+                )]),
+                impl_span: Span::dummy(),
+                origin: ImplOrigin::default(),
+                where_constraints: vec![],
+            },
+        };
+        let mut module = Module::new(
+            Ident::new_no_span("lib".into()),
+            Visibility::Public,
+            None,
+            &ModulePathBuf::new(),
+        );
+        module
+            .current_items_mut()
+            .implemented_traits
+            .trait_impls
+            .insert(TypeRootFilter::U64, vec![entry]);
+        module.push_new_lexical_scope(Span::dummy(), None);
+
+        let call_path = |name: &str| CallPath {
+            prefixes: vec![],
+            suffix: Ident::new_no_span(name.into()),
+            callpath_type: CallPathType::Ambiguous,
+        };
+        let queries = [
+            (u64_type_id, call_path("MyTrait"), vec![u64_type_id], true),
+            (u64_type_id, call_path("MyTrait"), vec![bool_type_id], false),
+            (u64_type_id, call_path("MyTrait"), vec![], false),
+            (
+                u64_type_id,
+                call_path("OtherTrait"),
+                vec![u64_type_id],
+                false,
+            ),
+            (bool_type_id, call_path("MyTrait"), vec![u64_type_id], false),
+        ];
+        for (type_id, trait_name, trait_type_args, expected) in queries {
+            let trait_type_args = trait_type_args
+                .into_iter()
+                .map(type_argument)
+                .collect::<Vec<_>>();
+            let has_items = !TraitMap::get_items_for_type_and_trait_name_and_trait_type_arguments(
+                &module,
+                &engines,
+                type_id,
+                &trait_name,
+                &trait_type_args,
+            )
+            .is_empty();
+            let has_impl =
+                TraitMap::has_impl_for(&module, &engines, type_id, &trait_name, &trait_type_args);
+            assert_eq!(has_impl, has_items);
+            assert_eq!(has_impl, expected);
+        }
+    }
+
+    #[test]
+    fn impl_origin_is_kept_for_stored_entries() {
+        let engines = Engines::default();
+        let handler = Handler::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let trait_decl_span = Span::from_string("trait MyTrait {}".into());
+        let call_path = |name: &str| CallPath {
+            prefixes: vec![],
+            suffix: Ident::new_no_span(name.into()),
+            callpath_type: CallPathType::Ambiguous,
+        };
+        let trait_type = |name: &str| {
+            ResolvedTraitImplItem::Typed(TyImplItem::Type(engines.de().insert(
+                ty::TyTraitType {
+                    name: Ident::new_no_span(name.into()),
+                    attributes: Default::default(),
+                    ty: None,
+                    implementing_type: u64_type_id,
+                    span: Span::dummy(),
+                },
+                None,
+            )))
+        };
+        let impl_self = ImplOrigin {
+            is_impl_self: IsImplSelf::Yes,
+            is_extending_existing_impl: IsExtendingExistingImpl::No,
+        };
+        let extended_trait_impl = ImplOrigin {
+            is_impl_self: IsImplSelf::No,
+            is_extending_existing_impl: IsExtendingExistingImpl::Yes,
+        };
+
+        let mut trait_map = TraitMap::default();
+        // impl u64 { type Inherent; }
+        trait_map
+            .insert(
+                &handler,
+                call_path("u64"),
+                vec![],
+                u64_type_id,
+                vec![],
+                &[trait_type("Inherent")],
+                &Span::dummy(),
+                None,
+                IsImplSelf::Yes,
+                IsExtendingExistingImpl::No,
+                &engines,
+            )
+            .unwrap();
+        // impl MyTrait for u64 { type Item; }
+        trait_map
+            .insert(
+                &handler,
+                call_path("MyTrait"),
+                vec![],
+                u64_type_id,
+                vec![],
+                &[trait_type("Item")],
+                &Span::dummy(),
+                Some(trait_decl_span.clone()),
+                IsImplSelf::No,
+                IsExtendingExistingImpl::No,
+                &engines,
+            )
+            .unwrap();
+        assert_eq!(
+            trait_map.trait_impls[&TypeRootFilter::U64]
+                .iter()
+                .map(|entry| entry.value.origin)
+                .collect::<Vec<_>>(),
+            vec![ImplOrigin::default(), impl_self]
+        );
+
+        // Extending the existing `MyTrait` impl marks the whole entry as extended.
+        trait_map
+            .insert(
+                &handler,
+                call_path("MyTrait"),
+                vec![],
+                u64_type_id,
+                vec![],
+                &[trait_type("Extension")],
+                &Span::dummy(),
+                Some(trait_decl_span.clone()),
+                IsImplSelf::No,
+                IsExtendingExistingImpl::Yes,
+                &engines,
+            )
+            .unwrap();
+        assert!(!handler.has_errors());
+
+        let mut module = Module::new(
+            Ident::new_no_span("lib".into()),
+            Visibility::Public,
+            None,
+            &ModulePathBuf::new(),
+        );
+        module
+            .current_items_mut()
+            .implemented_traits
+            .extend(trait_map, &engines);
+
+        let origins = TraitMap::get_items_and_trait_key_for_type(&module, &engines, u64_type_id)
+            .into_iter()
+            .map(|(item, _, origin, _)| (item.name(&engines).to_string(), origin))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(
+            origins,
+            BTreeMap::from([
+                ("Extension".to_string(), extended_trait_impl),
+                ("Inherent".to_string(), impl_self),
+                ("Item".to_string(), extended_trait_impl),
+            ])
+        );
+
+        let implemented_traits = &module.current_items().implemented_traits;
+        for filtered in [
+            implemented_traits.filter_by_trait_decl_span(trait_decl_span),
+            implemented_traits.filter_by_type_item_import(u64_type_id, &engines),
+        ] {
+            for entry in &filtered.trait_impls[&TypeRootFilter::U64] {
+                let expected = if entry.key.trait_decl_span.is_some() {
+                    extended_trait_impl
+                } else {
+                    impl_self
+                };
+                assert_eq!(entry.value.origin, expected);
+            }
         }
     }
+
+    #[test]
+    fn item_import_results_are_invalidated_when_trait_map_is_extended() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let trait_map_with_impl_of = |name: &str| {
+            let mut trait_map = TraitMap::default();
+            trait_map.insert_inner(
+                trait_name(name),
+                Span::dummy(),
+                Some(Span::dummy()),
+                u64_type_id,
+                vec![],
+                BTreeMap::new(),
+                ImplOrigin::default(),
+                vec![],
+                &engines,
+            );
+            trait_map
+        };
+        let imported_trait_names = |trait_map: &TraitMap| {
+            trait_map
+                .filter_by_type_item_import(u64_type_id, &engines)
+                .trait_impls
+                .values()
+                .flatten()
+                .map(|entry| entry.key.name.suffix.name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let mut trait_map = trait_map_with_impl_of("FirstTrait");
+        assert_eq!(imported_trait_names(&trait_map), ["FirstTrait"]);
+        // The second import of the same type is served from the cache.
+        assert_eq!(imported_trait_names(&trait_map), ["FirstTrait"]);
+
+        trait_map.extend(trait_map_with_impl_of("SecondTrait"), &engines);
+        assert_eq!(
+            imported_trait_names(&trait_map),
+            ["FirstTrait", "SecondTrait"]
+        );
+    }
+
+    #[test]
+    fn unified_type_subset_of_self_referential_references_terminates() {
+        let engines = Engines::default();
+        let reference = new_self_referential_type(&engines, |referenced_type| TypeInfo::Ref {
+            to_mutable_value: true,
+            referenced_type,
+        });
+
+        assert_eq!(
+            is_unified_type_subset(engines.te(), reference, reference),
+            None
+        );
+        assert_eq!(
+            is_unified_type_subset(engines.te(), reference, engines.te().id_of_u64()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn tuple_impls_are_bucketed_by_element_kinds() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let bool_type_id = engines.te().id_of_bool();
+        let generic = |name: &str| {
+            engines.te().new_unknown_generic(
+                Ident::new_no_span(name.into()),
+                VecSet(vec![]),
+                None,
+                true,
+            )
+        };
+        let u64_bool = engines
+            .te()
+            .insert_tuple_without_annotations(&engines, vec![u64_type_id, bool_type_id]);
+        let u64_u64 = engines
+            .te()
+            .insert_tuple_without_annotations(&engines, vec![u64_type_id, u64_type_id]);
+        let generic_tuple = engines
+            .te()
+            .insert_tuple_without_annotations(&engines, vec![generic("A"), generic("B")]);
+
+        assert_eq!(
+            TraitMap::get_type_root_filter(&engines, u64_bool),
+            TypeRootFilter::Tuple(vec![TypeRootFilter::U64, TypeRootFilter::Bool])
+        );
+        assert_eq!(
+            TraitMap::get_type_root_filter(&engines, generic_tuple),
+            TypeRootFilter::TupleWildcard(2)
+        );
+
+        // impl U64BoolTrait for (u64, bool) {}
+        // impl U64U64Trait for (u64, u64) {}
+        // impl<A, B> GenericTrait for (A, B) {}
+        let mut module = Module::new(
+            Ident::new_no_span("lib".into()),
+            Visibility::Public,
+            None,
+            &ModulePathBuf::new(),
+        );
+        for (name, type_id) in [
+            ("U64BoolTrait", u64_bool),
+            ("U64U64Trait", u64_u64),
+            ("GenericTrait", generic_tuple),
+        ] {
+            module
+                .current_items_mut()
+                .implemented_traits
+                .trait_impls
+                .entry(TraitMap::get_type_root_filter(&engines, type_id))
+                .or_default()
+                .push(TraitEntry {
+                    key: TraitKey {
+                        name: trait_name(name),
+                        type_id,
+                        impl_type_parameters: vec![],
+                        trait_decl_span: Some(Span::dummy()),
+                    },
+                    value: TraitValue {
+                        trait_items: BTreeMap::new(),
+                        impl_span: Span::dummy(),
+                        origin: ImplOrigin::default(),
+                        where_constraints: vec![],
+                    },
+                });
+        }
+
+        let trait_names_for = |type_id| {
+            let mut trait_names =
+                TraitMap::get_trait_names_and_type_arguments_for_type(&module, &engines, type_id)
+                    .into_iter()
+                    .map(|(call_path, _)| call_path.suffix.to_string())
+                    .collect::<Vec<_>>();
+            trait_names.sort();
+            trait_names
+        };
+        assert_eq!(trait_names_for(u64_bool), ["GenericTrait", "U64BoolTrait"]);
+        assert_eq!(trait_names_for(u64_u64), ["GenericTrait", "U64U64Trait"]);
+
+        // Tuples of a different length never see the impls above.
+        let triple = engines.te().insert_tuple_without_annotations(
+            &engines,
+            vec![u64_type_id, bool_type_id, u64_type_id],
+        );
+        assert!(trait_names_for(triple).is_empty());
+    }
+
+    #[test]
+    fn trait_map_profile_line_is_emitted_and_parsed_back() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let mut module = Module::new(
+            Ident::new_no_span("lib".into()),
+            Visibility::Public,
+            None,
+            &ModulePathBuf::new(),
+        );
+        module
+            .current_items_mut()
+            .implemented_traits
+            .insert(
+                &Handler::default(),
+                CallPath {
+                    prefixes: vec![],
+                    suffix: Ident::new_no_span("MyTrait".into()),
+                    callpath_type: CallPathType::Full,
+                },
+                vec![],
+                u64_type_id,
+                vec![],
+                &[],
+                &Span::dummy(),
+                Some(Span::dummy()),
+                IsImplSelf::No,
+                IsExtendingExistingImpl::No,
+                &engines,
+            )
+            .unwrap();
+        assert!(TraitMap::get_items_for_type(&module, &engines, u64_type_id).is_empty());
+
+        let line = TraitMapProfile::from_root_module(&module).to_profile_line("my_package");
+        let json = line
+            .strip_prefix(TraitMapProfile::PROFILE_LINE_PREFIX)
+            .and_then(|rest| rest.strip_prefix(" my_package "))
+            .expect("the profile line starts with the prefix and the package name");
+        let profile: TraitMapProfile = serde_json::from_str(json).unwrap();
+
+        assert_eq!(profile.modules.len(), 1);
+        assert_eq!(profile.modules[0].module, "lib");
+        assert_eq!(profile.modules[0].stats, profile.total);
+        assert_eq!(profile.total.buckets, 1);
+        assert_eq!(profile.total.entries, 1);
+        assert_eq!(profile.total.max_bucket_entries, 1);
+        assert_eq!(profile.total.inserts, 1);
+        assert!(profile.total.lookups > 0);
+    }
+
+    #[test]
+    fn trait_constraints_without_type_arguments_are_checked_against_single_impls() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let bool_type_id = engines.te().id_of_bool();
+        let marker_trait_name = CallPath {
+            prefixes: vec![Ident::new_no_span("lib".into())],
+            suffix: Ident::new_no_span("Marker".into()),
+            callpath_type: CallPathType::Full,
+        };
+        let mut module = Module::new(
+            Ident::new_no_span("lib".into()),
+            Visibility::Public,
+            None,
+            &ModulePathBuf::new(),
+        );
+        module
+            .current_items_mut()
+            .implemented_traits
+            .insert(
+                &Handler::default(),
+                marker_trait_name.clone(),
+                vec![],
+                u64_type_id,
+                vec![],
+                &[],
+                &Span::dummy(),
+                Some(Span::dummy()),
+                IsImplSelf::No,
+                IsExtendingExistingImpl::No,
+                &engines,
+            )
+            .unwrap();
+
+        let constraints = [TraitConstraint {
+            trait_name: marker_trait_name,
+            type_arguments: vec![],
+            associated_type_bindings: vec![],
+        }];
+        let mut check = |type_id| {
+            let handler = Handler::default();
+            let result = TraitMap::check_if_trait_constraints_are_satisfied_for_type(
+                &handler,
+                &module,
+                type_id,
+                &constraints,
+                &Span::dummy(),
+                &engines,
+            );
+            (result.is_ok(), handler.consume().0.len())
+        };
+
+        assert_eq!(check(u64_type_id), (true, 0));
+        // The satisfied constraints are cached, so the second check is a cache hit.
+        assert_eq!(check(u64_type_id), (true, 0));
+        assert_eq!(check(bool_type_id), (false, 1));
+
+        let stats = module.current_items().implemented_traits.stats();
+        assert_eq!(stats.satisfied_cache_hits, 1);
+        assert_eq!(stats.satisfied_cache_misses, 2);
+    }
+
+    #[test]
+    fn trait_constraint_not_fully_qualified_is_matched_by_suffix_with_warning() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let u64_type_argument = TypeArgument {
+            type_id: u64_type_id,
+            initial_type_id: u64_type_id,
+            span: Span::dummy(),
+            call_path_tree: None,
+        };
+        let mut module = Module::new(
+            Ident::new_no_span("lib".into()),
+            Visibility::Public,
+            None,
+            &ModulePathBuf::new(),
+        );
+        module
+            .current_items_mut()
+            .implemented_traits
+            .insert(
+                &Handler::default(),
+                CallPath {
+                    prefixes: vec![Ident::new_no_span("lib".into())],
+                    suffix: Ident::new_no_span("Convert".into()),
+                    callpath_type: CallPathType::Full,
+                },
+                vec![u64_type_argument.clone()],
+                u64_type_id,
+                vec![],
+                &[],
+                &Span::dummy(),
+                Some(Span::dummy()),
+                IsImplSelf::No,
+                IsExtendingExistingImpl::No,
+                &engines,
+            )
+            .unwrap();
+
+        let check = |trait_name: CallPath| {
+            let handler = Handler::default();
+            let result = TraitMap::check_if_trait_constraints_are_satisfied_for_type(
+                &handler,
+                &module,
+                u64_type_id,
+                &[TraitConstraint {
+                    trait_name,
+                    type_arguments: vec![u64_type_argument.clone()],
+                    associated_type_bindings: vec![],
+                }],
+                &Span::dummy(),
+                &engines,
+            );
+            let (errors, warnings) = handler.consume();
+            (result.is_ok(), errors.len(), warnings)
+        };
+
+        // The canonical path of the implemented trait matches without a warning.
+        let (is_ok, errors, warnings) = check(CallPath {
+            prefixes: vec![Ident::new_no_span("lib".into())],
+            suffix: Ident::new_no_span("Convert".into()),
+            callpath_type: CallPathType::Full,
+        });
+        assert!(is_ok);
+        assert_eq!(errors, 0);
+        assert!(warnings.is_empty());
+
+        // A trait with the same name, declared in a different module, is not matched.
+        let (is_ok, errors, _) = check(CallPath {
+            prefixes: vec![
+                Ident::new_no_span("lib".into()),
+                Ident::new_no_span("other".into()),
+            ],
+            suffix: Ident::new_no_span("Convert".into()),
+            callpath_type: CallPathType::Full,
+        });
+        assert!(!is_ok);
+        assert!(errors > 0);
+
+        // A path that is not fully qualified can only be matched by the trait name.
+        let (is_ok, errors, warnings) = check(CallPath {
+            prefixes: vec![],
+            suffix: Ident::new_no_span("Convert".into()),
+            callpath_type: CallPathType::Ambiguous,
+        });
+        assert!(is_ok);
+        assert_eq!(errors, 0);
+        assert!(matches!(
+            warnings.as_slice(),
+            [CompileWarning {
+                warning_content: Warning::TraitConstraintMatchedBySuffixOnly {
+                    required_trait_name,
+                    implemented_trait_name,
+                },
+                ..
+            }] if required_trait_name == "Convert" && implemented_trait_name == "lib::Convert"
+        ));
+    }
+
+    #[test]
+    fn extending_impl_with_existing_item_for_other_ref_mutability_is_duplicate() {
+        let engines = Engines::default();
+        let u64_type_id = engines.te().id_of_u64();
+        let ref_u64 = engines
+            .te()
+            .insert_ref_without_annotations(&engines, false, u64_type_id);
+        let ref_mut_u64 = engines
+            .te()
+            .insert_ref_without_annotations(&engines, true, u64_type_id);
+        let method = |name: &str| {
+            let parsed_fn = FunctionDeclaration {
+                purity: Default::default(),
+                attributes: Default::default(),
+                name: Ident::new_no_span(name.into()),
+                visibility: Visibility::Public,
+                body: CodeBlock {
+                    contents: vec![],
+                    whole_block_span: Span::dummy(),
+                },
+                parameters: vec![],
+                span: Span::dummy(),
+                return_type: u64_type_id.into(),
+                type_parameters: vec![],
+                const_generic_parameters: vec![],
+                where_clause: vec![],
+                kind: FunctionDeclarationKind::Default,
+                implementing_type: None,
+            };
+            ResolvedTraitImplItem::Typed(TyImplItem::Fn(
+                engines
+                    .de()
+                    .insert(ty::TyFunctionDecl::error(&parsed_fn), None),
+            ))
+        };
+        let duplicate_methods = |existing_type_id, extending_type_id, is_extending| {
+            let handler = Handler::default();
+            let mut trait_map = TraitMap::default();
+            for (type_id, is_extending_existing_impl) in [
+                (existing_type_id, IsExtendingExistingImpl::No),
+                (extending_type_id, is_extending),
+            ] {
+                let _ = trait_map.insert(
+                    &handler,
+                    CallPath {
+                        prefixes: vec![],
+                        suffix: Ident::new_no_span("MyTrait".into()),
+                        callpath_type: CallPathType::Ambiguous,
+                    },
+                    vec![],
+                    type_id,
+                    vec![],
+                    &[method("foo")],
+                    &Span::dummy(),
+                    Some(Span::dummy()),
+                    IsImplSelf::No,
+                    is_extending_existing_impl,
+                    &engines,
+                );
+            }
+            handler
+                .consume()
+                .0
+                .into_iter()
+                .map(|error| match error {
+                    CompileError::DuplicateDeclDefinedForType { decl_name, .. } => decl_name,
+                    error => panic!("unexpected error: {error:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Extending an impl with an already existing item is always an error,
+        // regardless of the mutability of the references.
+        for (existing, extending) in [
+            (ref_u64, ref_u64),
+            (ref_u64, ref_mut_u64),
+            (ref_mut_u64, ref_u64),
+            (ref_mut_u64, ref_mut_u64),
+        ] {
+            assert_eq!(
+                duplicate_methods(existing, extending, IsExtendingExistingImpl::Yes),
+                vec!["foo".to_string()]
+            );
+        }
+
+        // Separate impls for `&T` and `&mut T` can provide items with the same name.
+        assert!(duplicate_methods(ref_u64, ref_mut_u64, IsExtendingExistingImpl::No).is_empty());
+        assert!(duplicate_methods(ref_mut_u64, ref_u64, IsExtendingExistingImpl::No).is_empty());
+    }
 }
@@ -414,6 +414,9 @@ impl ResolveSymbols for TraitConstraint {
         self.type_arguments
             .iter_mut()
             .for_each(|tc| tc.resolve_symbols(handler, ctx.by_ref()));
+        self.associated_type_bindings
+            .iter_mut()
+            .for_each(|binding| binding.type_argument.resolve_symbols(handler, ctx.by_ref()));
     }
 }
 
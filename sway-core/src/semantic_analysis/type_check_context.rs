@@ -1,5 +1,5 @@
 #![allow(clippy::mutable_key_type)]
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
 use crate::{
     decl_engine::{DeclEngineGet, DeclRefFunction, MaterializeConstGenerics},
@@ -7,7 +7,7 @@ use crate::{
     language::{
         parsed::TreeType,
         ty::{self, TyDecl, TyExpression},
-        CallPath, QualifiedCallPath, Visibility,
+        CallPath, CallPathDisplayType, QualifiedCallPath, Visibility,
     },
     monomorphization::{monomorphize_with_modpath, MonomorphizeHelper},
     namespace::{
@@ -23,7 +23,8 @@ use crate::{
     UnifyCheck,
 };
 use sway_error::{
-    error::CompileError,
+    error::{ApplicableItemCandidate, CompileError, ImplForOtherRefMutability},
+    formatting::did_you_mean,
     handler::{ErrorEmitted, Handler},
 };
 use sway_features::ExperimentalFeatures;
@@ -787,6 +788,53 @@ impl<'a> TypeCheckContext<'a> {
         Ok(matching_item_decl_refs)
     }
 
+    /// Given a name and a type, find an impl providing the item for a type that
+    /// differs from `type_id` only in the mutability of its references.
+    /// Looks into the same modules as [Self::find_items_for_type].
+    fn find_impl_for_other_ref_mutability(
+        &self,
+        type_id: TypeId,
+        item_prefix: &ModulePath,
+        item_name: &Ident,
+    ) -> Option<ImplForOtherRefMutability> {
+        let namespace = self.namespace();
+        [
+            namespace.module_from_absolute_path(&namespace.current_mod_path),
+            namespace.module_from_absolute_path(&item_prefix.to_vec()),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|module| {
+            TraitMap::get_impl_for_other_ref_mutability(module, self.engines, item_name, type_id)
+        })
+    }
+
+    /// Given a method name and a type, returns the names of the methods implemented
+    /// for the type that are similar to the method name, most similar first.
+    /// Looks into the same modules as [Self::find_items_for_type].
+    fn find_similar_method_names(
+        &self,
+        type_id: TypeId,
+        method_prefix: &ModulePath,
+        method_name: &Ident,
+    ) -> Vec<String> {
+        let namespace = self.namespace();
+        let method_names = [
+            namespace.module_from_absolute_path(&namespace.current_mod_path),
+            namespace.module_from_absolute_path(&method_prefix.to_vec()),
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|module| module.get_items_for_type(self.engines, type_id))
+        .filter(|item| {
+            matches!(item, ResolvedTraitImplItem::Typed(ty::TyTraitItem::Fn(_)))
+                && !item.is_trait_method_dummy(self.engines)
+        })
+        .map(|item| item.name(self.engines).to_string())
+        .collect::<BTreeSet<_>>();
+        did_you_mean(method_name.as_str(), method_names.iter(), 3)
+    }
+
     /// Given a `method_name` and a `type_id`, find that method on that type in the namespace.
     /// `annotation_type` is the expected method return type. Requires `argument_types` because:
     /// - standard operations like +, <=, etc. are called like "std::ops::<operation>" and the
@@ -820,6 +868,8 @@ impl<'a> TypeCheckContext<'a> {
                     method: method_name.clone().as_str().to_string(),
                     type_name: self.engines.help_out(type_id).to_string(),
                     matching_method_strings: vec![],
+                    similar_method_names: vec![],
+                    impl_for_other_ref_mutability: None,
                     span: method_name.span(),
                 }));
             }
@@ -1040,48 +1090,62 @@ impl<'a> TypeCheckContext<'a> {
                         if exact_matching_methods.len() == 1 {
                             exact_matching_methods.into_iter().next().cloned()
                         } else {
-                            fn to_string(
-                                trait_name: CallPath,
-                                trait_type_args: Vec<WithEngines<TypeArgument>>,
-                            ) -> String {
-                                format!(
-                                    "{}{}",
-                                    trait_name.suffix,
-                                    if trait_type_args.is_empty() {
-                                        String::new()
-                                    } else {
-                                        format!(
-                                            "<{}>",
-                                            trait_type_args
-                                                .iter()
-                                                .map(|type_arg| type_arg.to_string())
-                                                .collect::<Vec<_>>()
-                                                .join(", ")
-                                        )
+                            let mut candidates = trait_methods
+                                .iter()
+                                .map(
+                                    |(
+                                        (trait_name, trait_type_args, implementing_for),
+                                        method_ref,
+                                    )| {
+                                        let trait_path = trait_name.to_display_path(
+                                            CallPathDisplayType::StripPackagePrefix,
+                                            self.namespace(),
+                                        );
+                                        let impl_span = match &decl_engine
+                                            .get_function(method_ref)
+                                            .implementing_type
+                                        {
+                                            Some(ty::TyDecl::ImplSelfOrTrait(impl_trait)) => {
+                                                decl_engine
+                                                    .get_impl_self_or_trait(&impl_trait.decl_id)
+                                                    .span
+                                                    .clone()
+                                            }
+                                            _ => method_ref.span(),
+                                        };
+                                        ApplicableItemCandidate {
+                                            type_name: implementing_for
+                                                .as_ref()
+                                                .map(|t| t.to_string())
+                                                .unwrap_or_else(|| {
+                                                    self.engines().help_out(type_id).to_string()
+                                                }),
+                                            trait_path: if trait_type_args.is_empty() {
+                                                trait_path.to_string()
+                                            } else {
+                                                format!(
+                                                    "{trait_path}<{}>",
+                                                    trait_type_args
+                                                        .iter()
+                                                        .map(|type_arg| type_arg.to_string())
+                                                        .collect::<Vec<_>>()
+                                                        .join(", ")
+                                                )
+                                            },
+                                            impl_span,
+                                        }
                                     },
                                 )
-                            }
-                            let mut trait_strings = trait_methods
-                                .keys()
-                                .map(|t| {
-                                    (
-                                        to_string(t.0.clone(), t.1.clone()),
-                                        t.2.clone()
-                                            .map(|t| t.to_string())
-                                            .or_else(|| {
-                                                Some(self.engines().help_out(type_id).to_string())
-                                            })
-                                            .unwrap(),
-                                    )
-                                })
-                                .collect::<Vec<(String, String)>>();
+                                .collect::<Vec<_>>();
                             // Sort so the output of the error is always the same.
-                            trait_strings.sort();
+                            candidates.sort_by(|a, b| {
+                                (&a.trait_path, &a.type_name).cmp(&(&b.trait_path, &b.type_name))
+                            });
                             return Err(handler.emit_err(
                                 CompileError::MultipleApplicableItemsInScope {
                                     item_name: method_name.as_str().to_string(),
                                     item_kind: "function".to_string(),
-                                    as_traits: trait_strings,
+                                    candidates,
                                     span: method_name.span(),
                                 },
                             ));
@@ -1139,6 +1203,20 @@ impl<'a> TypeCheckContext<'a> {
                 self.engines.help_out(type_id).to_string()
             };
 
+            // Only suggest other names if there is no method with the given name at all.
+            let similar_method_names = if matching_method_strings.is_empty() {
+                self.find_similar_method_names(type_id, method_prefix, method_name)
+            } else {
+                vec![]
+            };
+
+            let impl_for_other_ref_mutability = qualified_call_path
+                .is_none()
+                .then(|| {
+                    self.find_impl_for_other_ref_mutability(type_id, method_prefix, method_name)
+                })
+                .flatten();
+
             Err(handler.emit_err(CompileError::MethodNotFound {
                 method: format!(
                     "{}({}){}",
@@ -1162,6 +1240,8 @@ impl<'a> TypeCheckContext<'a> {
                     .iter()
                     .cloned()
                     .collect::<Vec<_>>(),
+                similar_method_names,
+                impl_for_other_ref_mutability,
                 span: method_name.span(),
             }))
         }
@@ -1301,11 +1381,15 @@ impl<'a> TypeCheckContext<'a> {
     ) -> Result<(), ErrorEmitted> {
         let engines = self.engines;
 
-        // Use trait name with full path, improves consistency between
-        // this inserting and getting in `get_methods_for_type_and_trait_name`.
+        // Use trait names with the canonical paths of their declarations, like the
+        // trait map keys. This improves consistency between this inserting and getting
+        // in `get_methods_for_type_and_trait_name`, and allows matching the `where`
+        // constraints of the impl against the implemented traits by their full paths.
         impl_type_parameters.iter_mut().for_each(|tp| {
             tp.trait_constraints.iter_mut().for_each(|tc| {
-                tc.trait_name = tc.trait_name.to_fullpath(self.engines(), self.namespace())
+                tc.trait_name = tc
+                    .trait_name
+                    .to_canonical_path(self.engines(), self.namespace())
             })
         });
 
@@ -1370,21 +1454,163 @@ impl<'a> TypeCheckContext<'a> {
         )
     }
 
+    /// Checks if `type_id` implements the trait `trait_name` with the trait
+    /// type arguments `trait_type_args`.
+    ///
+    /// The impls are matched like in
+    /// [Self::get_items_for_type_and_trait_name_and_trait_type_arguments],
+    /// but without materializing their items.
+    pub(crate) fn type_implements_trait(
+        &self,
+        type_id: TypeId,
+        trait_name: &CallPath,
+        trait_type_args: &[TypeArgument],
+    ) -> bool {
+        // CallPath::to_fullpath gives a resolvable path, but is not guaranteed to provide the path
+        // to the actual trait declaration. Since the path of the trait declaration is used as a key
+        // in the trait map, we need to find the actual declaration path.
+        let canonical_trait_path = trait_name.to_canonical_path(self.engines(), self.namespace());
+
+        TraitMap::has_impl_for(
+            self.namespace().current_module(),
+            self.engines,
+            type_id,
+            &canonical_trait_path,
+            trait_type_args,
+        )
+    }
+
     pub fn check_type_impls_traits(
         &mut self,
         type_id: TypeId,
         constraints: &[TraitConstraint],
     ) -> bool {
+        // Constraints without associated type bindings only need an impl of
+        // their trait. Only the bindings require looking into the impl items.
+        let (constraints_with_bindings, constraints_without_bindings): (Vec<_>, Vec<_>) =
+            constraints
+                .iter()
+                .cloned()
+                .partition(|constraint| !constraint.associated_type_bindings.is_empty());
+
+        if !constraints_without_bindings.iter().all(|constraint| {
+            self.type_implements_trait(type_id, &constraint.trait_name, &constraint.type_arguments)
+        }) {
+            return false;
+        }
+
         let handler = Handler::default();
         let engines = self.engines;
         TraitMap::check_if_trait_constraints_are_satisfied_for_type(
             &handler,
             self.namespace_mut().current_module_mut(),
             type_id,
-            constraints,
+            &constraints_with_bindings,
             &Span::dummy(),
             engines,
         )
         .is_ok()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decl_engine::DeclEngineInsert, language::CallPathType, namespace::Package};
+    use sway_types::ProgramId;
+
+    #[test]
+    fn type_implements_trait_agrees_with_items_lookup() {
+        let handler = Handler::default();
+        let engines = Engines::default();
+        let package = Package::new(
+            Ident::new_no_span("lib".into()),
+            None,
+            ProgramId::new(0),
+            false,
+        );
+        let mut collection_ctx = SymbolCollectionContext::new(
+            Namespace::new(&handler, &engines, package.clone(), false).unwrap(),
+        );
+        let mut namespace = Namespace::new(&handler, &engines, package, false).unwrap();
+        let mut ctx = TypeCheckContext::from_root(
+            &mut namespace,
+            &mut collection_ctx,
+            &engines,
+            ExperimentalFeatures::default(),
+        );
+
+        let u64_type_id = engines.te().id_of_u64();
+        let bool_type_id = engines.te().id_of_bool();
+        let type_argument = |type_id| TypeArgument {
+            type_id,
+            initial_type_id: type_id,
+            span: Span::dummy(),
+            call_path_tree: None,
+        };
+        let call_path = |name: &str| CallPath {
+            prefixes: vec![],
+            suffix: Ident::new_no_span(name.into()),
+            callpath_type: CallPathType::Ambiguous,
+        };
+
+        // impl MyTrait<u64> for u64 { type Item; }
+        let type_ref = engines.de().insert(
+            ty::TyTraitType {
+                name: Ident::new_no_span("Item".into()),
+                attributes: Default::default(),
+                ty: None,
+                implementing_type: u64_type_id,
+                span: Span::dummy(),
+            },
+            None,
+        );
+        ctx.insert_trait_implementation(
+            &handler,
+            call_path("MyTrait"),
+            vec![type_argument(u64_type_id)],
+            u64_type_id,
+            vec![],
+            &[ty::TyTraitItem::Type(type_ref)],
+            &Span::dummy(),
+            Some(Span::dummy()),
+            IsImplSelf::No,
+            IsExtendingExistingImpl::No,
+        )
+        .unwrap();
+
+        let queries = [
+            (u64_type_id, "MyTrait", vec![u64_type_id], true),
+            (u64_type_id, "MyTrait", vec![bool_type_id], false),
+            (u64_type_id, "MyTrait", vec![], false),
+            (u64_type_id, "OtherTrait", vec![u64_type_id], false),
+            (bool_type_id, "MyTrait", vec![u64_type_id], false),
+        ];
+        for (type_id, trait_name, trait_type_args, expected) in queries {
+            let trait_name = call_path(trait_name);
+            let trait_type_args = trait_type_args
+                .into_iter()
+                .map(type_argument)
+                .collect::<Vec<_>>();
+            let has_items = !ctx
+                .get_items_for_type_and_trait_name_and_trait_type_arguments(
+                    type_id,
+                    &trait_name,
+                    &trait_type_args,
+                )
+                .is_empty();
+            let implements = ctx.type_implements_trait(type_id, &trait_name, &trait_type_args);
+            let impls_traits = ctx.check_type_impls_traits(
+                type_id,
+                &[TraitConstraint {
+                    trait_name,
+                    type_arguments: trait_type_args,
+                    associated_type_bindings: vec![],
+                }],
+            );
+            assert_eq!(implements, has_items);
+            assert_eq!(impls_traits, has_items);
+            assert_eq!(implements, expected);
+        }
+    }
+}
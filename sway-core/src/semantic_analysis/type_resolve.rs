@@ -5,7 +5,9 @@ use sway_error::{
 use sway_types::{Ident, Span, Spanned};
 
 use crate::{
+    decl_engine::DeclId,
     language::{
+        parsed::Declaration,
         ty::{self, TyTraitItem},
         CallPath, QualifiedCallPath,
     },
@@ -235,12 +237,42 @@ pub fn resolve_qualified_call_path(
             TypeInfo::Custom {
                 qualified_call_path: call_path,
                 ..
-            } => Some(
-                call_path
-                    .clone()
-                    .to_call_path(handler)?
-                    .to_canonical_path(engines, namespace),
-            ),
+            } => {
+                let call_path = call_path.clone().to_call_path(handler)?;
+                match resolve_call_path(
+                    handler,
+                    engines,
+                    namespace,
+                    mod_path,
+                    &call_path,
+                    self_type,
+                    check_visibility,
+                )? {
+                    // The trait in `<T as Trait>` is matched by its typed
+                    // declaration, which a parsed one does not have yet.
+                    ResolvedDeclaration::Parsed(Declaration::TraitDeclaration(_)) => {
+                        return Err(handler.emit_err(CompileError::Internal(
+                            "Trait in a qualified path is not type checked",
+                            call_path.span(),
+                        )))
+                    }
+                    ResolvedDeclaration::Parsed(decl) => {
+                        return Err(handler.emit_err(CompileError::DeclIsNotATrait {
+                            actually: decl.friendly_type_name().to_string(),
+                            span: call_path.span(),
+                        }))
+                    }
+                    ResolvedDeclaration::Typed(ty::TyDecl::TraitDecl(ty::TraitDecl {
+                        decl_id,
+                    })) => Some(decl_id),
+                    ResolvedDeclaration::Typed(decl) => {
+                        return Err(handler.emit_err(CompileError::DeclIsNotATrait {
+                            actually: decl.friendly_type_name().to_string(),
+                            span: call_path.span(),
+                        }))
+                    }
+                }
+            }
             _ => None,
         };
 
@@ -472,7 +504,7 @@ fn resolve_associated_item_from_type_id(
     module: &Module,
     symbol: &Ident,
     type_id: TypeId,
-    as_trait: Option<CallPath>,
+    as_trait: Option<DeclId<ty::TyTraitDecl>>,
     self_type: Option<TypeId>,
 ) -> Result<ResolvedDeclaration, ErrorEmitted> {
     let type_id = if engines.te().get(type_id).is_self_type() {
@@ -506,7 +538,7 @@ fn resolve_associated_type_or_item(
     module: &Module,
     symbol: &Ident,
     decl: ResolvedDeclaration,
-    as_trait: Option<CallPath>,
+    as_trait: Option<DeclId<ty::TyTraitDecl>>,
     self_type: Option<TypeId>,
 ) -> Result<ResolvedDeclaration, ErrorEmitted> {
     let type_info = decl_to_type_info(handler, engines, symbol, decl)?;
@@ -525,7 +557,7 @@ fn resolve_call_path_and_root_type_id(
     engines: &Engines,
     module: &Module,
     root_type_id: TypeId,
-    mut as_trait: Option<CallPath>,
+    mut as_trait: Option<DeclId<ty::TyTraitDecl>>,
     call_path: &CallPath,
     self_type: Option<TypeId>,
 ) -> Result<ResolvedDeclaration, ErrorEmitted> {
@@ -536,24 +568,12 @@ fn resolve_call_path_and_root_type_id(
         if let Some(type_id) = type_id_opt {
             type_id_opt = None;
             decl_opt = Some(resolve_associated_item_from_type_id(
-                handler,
-                engines,
-                module,
-                ident,
-                type_id,
-                as_trait.clone(),
-                self_type,
+                handler, engines, module, ident, type_id, as_trait, self_type,
             )?);
             as_trait = None;
         } else if let Some(decl) = decl_opt {
             decl_opt = Some(resolve_associated_type_or_item(
-                handler,
-                engines,
-                module,
-                ident,
-                decl,
-                as_trait.clone(),
-                self_type,
+                handler, engines, module, ident, decl, as_trait, self_type,
             )?);
             as_trait = None;
         }
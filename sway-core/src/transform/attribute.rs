@@ -27,10 +27,10 @@
 //! #[foo(bar, bar)]
 //! ```
 //!
-//! Attribute args can have values:
+//! Attribute args can have string, integer, or bool literal values:
 //!
 //! ```ignore
-//! #[foo(bar = "some value", baz = true)]
+//! #[foo(bar = "some value", baz = true, qux = 42)]
 //! ```
 //!
 //! All attributes have the following common properties:
@@ -65,6 +65,8 @@ use crate::language::{Inline, Purity};
 pub struct AttributeArg {
     pub name: Ident,
     pub value: Option<Literal>,
+    /// Nested arguments, e.g., `target = "fuel"` in `#[cfg(not(target = "fuel"))]`.
+    pub args: Vec<AttributeArg>,
     pub span: Span,
 }
 
@@ -159,6 +161,10 @@ impl AttributeArg {
         self.name.as_str() == ALLOW_DEPRECATED_ARG_NAME
     }
 
+    pub fn is_allow_orphan_impl(&self) -> bool {
+        self.name.as_str() == ALLOW_ORPHAN_IMPL_ARG_NAME
+    }
+
     pub fn is_cfg_target(&self) -> bool {
         self.name.as_str() == CFG_TARGET_ARG_NAME
     }
@@ -171,6 +177,24 @@ impl AttributeArg {
         Feature::CFG.contains(&self.name.as_str())
     }
 
+    pub fn is_cfg_all(&self) -> bool {
+        self.name.as_str() == CFG_ALL_ARG_NAME
+    }
+
+    pub fn is_cfg_any(&self) -> bool {
+        self.name.as_str() == CFG_ANY_ARG_NAME
+    }
+
+    pub fn is_cfg_not(&self) -> bool {
+        self.name.as_str() == CFG_NOT_ARG_NAME
+    }
+
+    /// Returns true if `self` is one of the `all`, `any`, or `not` `cfg`
+    /// predicates, that combine their nested arguments.
+    pub fn is_cfg_predicate(&self) -> bool {
+        self.is_cfg_all() || self.is_cfg_any() || self.is_cfg_not()
+    }
+
     pub fn is_deprecated_note(&self) -> bool {
         self.name.as_str() == DEPRECATED_NOTE_ARG_NAME
     }
@@ -186,6 +210,21 @@ impl Spanned for AttributeArg {
     }
 }
 
+impl From<&sway_ast::attribute::AttributeArg> for AttributeArg {
+    fn from(arg: &sway_ast::attribute::AttributeArg) -> Self {
+        AttributeArg {
+            name: arg.name.clone(),
+            value: arg.value.clone(),
+            args: arg
+                .args
+                .as_ref()
+                .map(|parens| parens.get().into_iter().map(AttributeArg::from).collect())
+                .unwrap_or_default(),
+            span: arg.span(),
+        }
+    }
+}
+
 // TODO: Currently we do not support arbitrary inner attributes.
 //       Only compiler-generated `doc-comment` attributes for `//!`
 //       can currently be inner attributes.
@@ -411,6 +450,18 @@ impl Attribute {
         self.kind == AttributeKind::DocComment
     }
 
+    /// Returns the documentation line of a doc-comment attribute,
+    /// or `None` if `self` is not a doc comment.
+    pub fn doc_comment_text(&self) -> Option<&str> {
+        if !self.is_doc_comment() {
+            return None;
+        }
+        self.args.first().and_then(|arg| match &arg.value {
+            Some(Literal::String(lit_string)) => Some(lit_string.parsed.as_str()),
+            _ => None,
+        })
+    }
+
     pub fn is_inner(&self) -> bool {
         self.direction == AttributeDirection::Inner
     }
@@ -424,8 +475,8 @@ impl Attribute {
         use AttributeKind::*;
         match self.kind {
             Unknown => Multiplicity::arbitrary(),
-            // Each `doc-comment` attribute contains exactly one argument
-            // whose name is the actual documentation text and whose value is `None`.
+            // Each `doc-comment` attribute contains exactly one argument,
+            // `text`, whose value is the actual documentation text.
             // Thus, we expect exactly one argument.
             DocComment => Multiplicity::exactly(1),
             // `storage(read, write)`.
@@ -476,15 +527,22 @@ impl Attribute {
         use ExpectedArgs::*;
         match self.kind {
             Unknown => Any,
-            DocComment => Any,
+            DocComment => MustBeIn(vec![DOC_COMMENT_TEXT_ARG_NAME]),
             Storage => MustBeIn(vec![STORAGE_READ_ARG_NAME, STORAGE_WRITE_ARG_NAME]),
             Inline => MustBeIn(vec![INLINE_ALWAYS_ARG_NAME, INLINE_NEVER_ARG_NAME]),
             Test => MustBeIn(vec![TEST_SHOULD_REVERT_ARG_NAME]),
             Payable => None,
-            Allow => ShouldBeIn(vec![ALLOW_DEAD_CODE_ARG_NAME, ALLOW_DEPRECATED_ARG_NAME]),
+            Allow => ShouldBeIn(vec![
+                ALLOW_DEAD_CODE_ARG_NAME,
+                ALLOW_DEPRECATED_ARG_NAME,
+                ALLOW_ORPHAN_IMPL_ARG_NAME,
+            ]),
             Cfg => {
                 let mut args = vec![
                     // Arguments, ordered alphabetically.
+                    CFG_ALL_ARG_NAME,
+                    CFG_ANY_ARG_NAME,
+                    CFG_NOT_ARG_NAME,
                     CFG_PROGRAM_TYPE_ARG_NAME,
                     CFG_TARGET_ARG_NAME,
                 ];
@@ -501,8 +559,8 @@ impl Attribute {
         use AttributeKind::*;
         match self.kind {
             Unknown => Maybe,
-            // The actual documentation line is in the name of the attribute.
-            DocComment => No,
+            // The actual documentation line is in the value of the `text` argument.
+            DocComment => Yes,
             Storage => No,
             Inline => No,
             // `test(should_revert)`, `test(should_revert = "18446744073709486084")`.
@@ -794,30 +852,32 @@ impl Attributes {
         for attr_decl in attribute_decls {
             let attrs = attr_decl.attribute.get().into_iter();
             for attr in attrs {
-                let name = attr.name.as_str();
+                // Namespaced attributes, e.g., `#[fuels::test]`, are meant for third-party tools
+                // and are never built-in, even if their last segment is a built-in attribute name.
+                // Their name is the full path, so that diagnostics and tools can refer to it.
+                let (name, kind) = if attr.is_namespaced() {
+                    (
+                        Ident::new_with_override(attr.path_str(), attr.path_span()),
+                        AttributeKind::Unknown,
+                    )
+                } else {
+                    (
+                        attr.name.clone(),
+                        AttributeKind::from_attribute_name(attr.name.as_str()),
+                    )
+                };
                 let args = attr
                     .args
                     .as_ref()
-                    .map(|parens| {
-                        parens
-                            .get()
-                            .into_iter()
-                            .cloned()
-                            .map(|arg| AttributeArg {
-                                name: arg.name.clone(),
-                                value: arg.value.clone(),
-                                span: arg.span(),
-                            })
-                            .collect()
-                    })
+                    .map(|parens| parens.get().into_iter().map(AttributeArg::from).collect())
                     .unwrap_or_default();
 
                 let attribute = Attribute {
                     direction: (&attr_decl.hash_kind).into(),
-                    name: attr.name.clone(),
+                    name,
                     args,
                     span: attr_decl.span(),
-                    kind: AttributeKind::from_attribute_name(name),
+                    kind,
                 };
 
                 attributes.push(attribute);
@@ -886,6 +946,10 @@ impl Attributes {
         self.has_allow(|arg| arg.is_allow_deprecated())
     }
 
+    pub fn has_allow_orphan_impl(&self) -> bool {
+        self.has_allow(|arg| arg.is_allow_orphan_impl())
+    }
+
     fn has_allow(&self, arg_filter: impl Fn(&AttributeArg) -> bool) -> bool {
         self.of_kind(AttributeKind::Allow)
             .flat_map(|attribute| &attribute.args)
@@ -172,7 +172,8 @@ pub fn item_to_ast_nodes(
             decl(trait_decl)
         }
         ItemKind::Impl(item_impl) => {
-            let impl_decl = item_impl_to_declaration(context, handler, engines, item_impl)?;
+            let impl_decl =
+                item_impl_to_declaration(context, handler, engines, item_impl, attributes)?;
             context.implementing_type = Some(impl_decl.clone());
             decl(impl_decl)
         }
@@ -730,6 +731,7 @@ pub fn item_impl_to_declaration(
     handler: &Handler,
     engines: &Engines,
     item_impl: ItemImpl,
+    attributes: Attributes,
 ) -> Result<Declaration, ErrorEmitted> {
     let block_span = item_impl.span();
     let implementing_for = ty_to_type_argument(context, handler, engines, item_impl.ty)?;
@@ -815,6 +817,7 @@ pub fn item_impl_to_declaration(
                 trait_decl_ref: None,
                 implementing_for,
                 items,
+                attributes,
                 block_span,
             };
             let impl_trait = engines.pe().insert(impl_trait);
@@ -837,6 +840,7 @@ pub fn item_impl_to_declaration(
                     impl_type_parameters,
                     impl_const_generics_parameters,
                     items,
+                    attributes,
                     block_span,
                 };
                 let impl_self = engines.pe().insert(impl_self);
@@ -1745,22 +1749,34 @@ fn traits_to_trait_constraints(
     engines: &Engines,
     traits: Traits,
 ) -> Result<Vec<TraitConstraint>, ErrorEmitted> {
-    let mut parsed_traits = vec![path_type_to_call_path_and_type_arguments(
-        context,
-        handler,
-        engines,
-        traits.prefix,
-    )?];
-    for (_add_token, suffix) in traits.suffixes {
-        let supertrait =
-            path_type_to_call_path_and_type_arguments(context, handler, engines, suffix)?;
-        parsed_traits.push(supertrait);
-    }
     let mut trait_constraints = vec![];
-    for (trait_name, type_arguments) in parsed_traits {
+    for mut path_type in iter::once(traits.prefix).chain(
+        traits
+            .suffixes
+            .into_iter()
+            .map(|(_add_token, suffix)| suffix),
+    ) {
+        // Associated type bindings, e.g., `Item = u64` in `T: Iterator<Item = u64>`,
+        // are only allowed on the last segment of the trait path.
+        let mut associated_type_bindings = vec![];
+        if let Some((_, generic_args)) = path_type.last_segment_mut().generics_opt.as_mut() {
+            let bindings = std::mem::replace(
+                &mut generic_args.associated_type_bindings,
+                Punctuated::empty(),
+            );
+            for binding in bindings {
+                associated_type_bindings.push(AssociatedTypeBinding {
+                    name: binding.name,
+                    type_argument: ty_to_type_argument(context, handler, engines, binding.ty)?,
+                });
+            }
+        }
+        let (trait_name, type_arguments) =
+            path_type_to_call_path_and_type_arguments(context, handler, engines, path_type)?;
         trait_constraints.push(TraitConstraint {
             trait_name: trait_name.to_call_path(handler)?,
             type_arguments,
+            associated_type_bindings,
         })
     }
     Ok(trait_constraints)
@@ -4695,6 +4711,13 @@ fn generic_args_to_type_arguments(
     engines: &Engines,
     generic_args: GenericArgs,
 ) -> Result<Vec<TypeArgument>, ErrorEmitted> {
+    if let Some(binding) = generic_args.associated_type_bindings.iter().next() {
+        let error = ConvertParseTreeError::AssociatedTypeBindingsNotSupportedHere {
+            span: binding.span(),
+        };
+        return Err(handler.emit_err(error.into()));
+    }
+
     generic_args
         .parameters
         .into_inner()
@@ -4885,70 +4908,131 @@ pub fn cfg_eval(
             "`#[cfg]` attribute must have argument multiplicity of exactly one"
         );
 
-        let arg = &cfg_attr.args[0];
-        if arg.is_cfg_target() {
-            let cfg_target_val = arg.get_string(handler, cfg_attr)?;
-            if let Ok(cfg_target) = BuildTarget::from_str(cfg_target_val) {
-                if cfg_target != context.build_target() {
-                    return Ok(false);
+        if !cfg_arg_eval(context, handler, cfg_attr, &cfg_attr.args[0], experimental)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Evaluates a single `cfg` argument, e.g., `target = "fuel"`, or a predicate
+/// combining nested arguments, e.g., `all(target = "fuel", not(program_type = "script"))`.
+///
+/// `all()` with no nested arguments evaluates to `true`, and `any()` to `false`.
+fn cfg_arg_eval(
+    context: &Context,
+    handler: &Handler,
+    cfg_attr: &Attribute,
+    arg: &AttributeArg,
+    experimental: ExperimentalFeatures,
+) -> Result<bool, ErrorEmitted> {
+    if arg.is_cfg_predicate() {
+        if let Some(value) = &arg.value {
+            return Err(handler.emit_err(
+                ConvertParseTreeError::InvalidCfgPredicateValue {
+                    predicate: (&arg.name).into(),
+                    value_span: value.span(),
                 }
-            } else {
-                return Err(handler.emit_err(
-                    (ConvertParseTreeError::InvalidAttributeArgValue {
-                        span: arg
-                            .value
-                            .as_ref()
-                            .expect("`cfg_target` is the value of `arg`")
-                            .span(),
-                        arg: arg.name.clone(),
-                        expected_values: BuildTarget::CFG.to_vec(),
-                    })
-                    .into(),
-                ));
+                .into(),
+            ));
+        }
+
+        // Evaluate all the nested arguments, to report errors in any of them.
+        let mut nested_values = Vec::with_capacity(arg.args.len());
+        let mut nested_error = None;
+        for nested_arg in arg.args.iter() {
+            match cfg_arg_eval(context, handler, cfg_attr, nested_arg, experimental) {
+                Ok(value) => nested_values.push(value),
+                Err(err) => nested_error = Some(err),
             }
-        } else if arg.is_cfg_program_type() {
-            let cfg_program_type_val = arg.get_string(handler, cfg_attr)?;
-            if let Ok(cfg_program_type) = TreeType::from_str(cfg_program_type_val) {
-                if cfg_program_type
-                    != context
-                        .program_type()
-                        .expect("at this compilation stage the `program_type` is defined")
-                {
-                    return Ok(false);
-                }
-            } else {
-                return Err(handler.emit_err(
-                    (ConvertParseTreeError::InvalidAttributeArgValue {
-                        span: arg
-                            .value
-                            .as_ref()
-                            .expect("`cfg_target` is the value of `arg`")
-                            .span(),
-                        arg: arg.name.clone(),
-                        expected_values: TreeType::CFG.to_vec(),
-                    })
+        }
+        if let Some(err) = nested_error {
+            return Err(err);
+        }
+
+        return if arg.is_cfg_all() {
+            Ok(nested_values.into_iter().all(|value| value))
+        } else if arg.is_cfg_any() {
+            Ok(nested_values.into_iter().any(|value| value))
+        } else {
+            match nested_values[..] {
+                [value] => Ok(!value),
+                _ => Err(handler.emit_err(
+                    ConvertParseTreeError::InvalidCfgNotPredicateArgsMultiplicity {
+                        span: arg.span(),
+                        num_of_args: nested_values.len(),
+                    }
                     .into(),
-                ));
+                )),
             }
-        } else if arg.is_cfg_experimental() {
-            let cfg_experimental_val = arg.get_bool(handler, cfg_attr)?;
-            let experimental_enabled = experimental
-                .is_enabled_for_cfg(arg.name.as_str())
-                .expect("`arg` is a known `cfg` experimental argument");
-            if cfg_experimental_val != experimental_enabled {
-                return Ok(false);
+        };
+    }
+
+    if !arg.args.is_empty() {
+        return Err(handler.emit_err(
+            ConvertParseTreeError::InvalidAttributeArgNestedArgs {
+                span: arg.span(),
+                attribute: cfg_attr.name.clone(),
+                arg: (&arg.name).into(),
             }
+            .into(),
+        ));
+    }
+
+    if arg.is_cfg_target() {
+        let cfg_target_val = arg.get_string(handler, cfg_attr)?;
+        if let Ok(cfg_target) = BuildTarget::from_str(cfg_target_val) {
+            Ok(cfg_target == context.build_target())
         } else {
-            return Err(handler.emit_err(
-                ConvertParseTreeError::InvalidAttributeArg {
-                    attribute: cfg_attr.name.clone(),
-                    arg: (&arg.name).into(),
-                    expected_args: cfg_attr.expected_args().args_names(),
-                }
+            Err(handler.emit_err(
+                (ConvertParseTreeError::InvalidAttributeArgValue {
+                    span: arg
+                        .value
+                        .as_ref()
+                        .expect("`cfg_target` is the value of `arg`")
+                        .span(),
+                    arg: arg.name.clone(),
+                    expected_values: BuildTarget::CFG.to_vec(),
+                })
                 .into(),
-            ));
+            ))
         }
+    } else if arg.is_cfg_program_type() {
+        let cfg_program_type_val = arg.get_string(handler, cfg_attr)?;
+        if let Ok(cfg_program_type) = TreeType::from_str(cfg_program_type_val) {
+            Ok(cfg_program_type
+                == context
+                    .program_type()
+                    .expect("at this compilation stage the `program_type` is defined"))
+        } else {
+            Err(handler.emit_err(
+                (ConvertParseTreeError::InvalidAttributeArgValue {
+                    span: arg
+                        .value
+                        .as_ref()
+                        .expect("`cfg_target` is the value of `arg`")
+                        .span(),
+                    arg: arg.name.clone(),
+                    expected_values: TreeType::CFG.to_vec(),
+                })
+                .into(),
+            ))
+        }
+    } else if arg.is_cfg_experimental() {
+        let cfg_experimental_val = arg.get_bool(handler, cfg_attr)?;
+        let experimental_enabled = experimental
+            .is_enabled_for_cfg(arg.name.as_str())
+            .expect("`arg` is a known `cfg` experimental argument");
+        Ok(cfg_experimental_val == experimental_enabled)
+    } else {
+        Err(handler.emit_err(
+            ConvertParseTreeError::InvalidAttributeArg {
+                attribute: cfg_attr.name.clone(),
+                arg: (&arg.name).into(),
+                expected_args: cfg_attr.expected_args().args_names(),
+            }
+            .into(),
+        ))
     }
-
-    Ok(true)
 }
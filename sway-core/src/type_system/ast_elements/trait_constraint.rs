@@ -20,18 +20,63 @@ use sway_error::{
     error::CompileError,
     handler::{ErrorEmitted, Handler},
 };
-use sway_types::Spanned;
+use sway_types::{Ident, Spanned};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitConstraint {
     pub trait_name: CallPath,
     pub type_arguments: Vec<TypeArgument>,
+    /// Associated type bindings of the constraint, e.g., `Item = u64`
+    /// in `T: Iterator<Item = u64>`.
+    pub associated_type_bindings: Vec<AssociatedTypeBinding>,
+}
+
+/// An associated type binding in a [TraitConstraint], e.g., `Item = u64`
+/// in `T: Iterator<Item = u64>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssociatedTypeBinding {
+    pub name: Ident,
+    pub type_argument: TypeArgument,
+}
+
+impl HashWithEngines for AssociatedTypeBinding {
+    fn hash<H: Hasher>(&self, state: &mut H, engines: &Engines) {
+        self.name.hash(state);
+        self.type_argument.hash(state, engines);
+    }
+}
+
+impl EqWithEngines for AssociatedTypeBinding {}
+impl PartialEqWithEngines for AssociatedTypeBinding {
+    fn eq(&self, other: &Self, ctx: &PartialEqWithEnginesContext) -> bool {
+        self.name == other.name && self.type_argument.eq(&other.type_argument, ctx)
+    }
+}
+
+impl OrdWithEngines for AssociatedTypeBinding {
+    fn cmp(&self, other: &Self, ctx: &OrdWithEnginesContext) -> Ordering {
+        self.name
+            .cmp(&other.name)
+            .then_with(|| self.type_argument.cmp(&other.type_argument, ctx))
+    }
+}
+
+impl DebugWithEngines for AssociatedTypeBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, engines: &Engines) -> fmt::Result {
+        write!(
+            f,
+            "{} = {:?}",
+            self.name,
+            engines.help_out(&self.type_argument)
+        )
+    }
 }
 
 impl HashWithEngines for TraitConstraint {
     fn hash<H: Hasher>(&self, state: &mut H, engines: &Engines) {
         self.trait_name.hash(state);
         self.type_arguments.hash(state, engines);
+        self.associated_type_bindings.hash(state, engines);
     }
 }
 
@@ -42,10 +87,13 @@ impl PartialEqWithEngines for TraitConstraint {
             // Check if eq is already inside of a trait constraint, if it is we don't compare type arguments.
             // This breaks the recursion when we use a where clause such as `T:MyTrait<T>`.
             && (ctx.is_inside_trait_constraint()
-                || self.type_arguments.eq(
+                || (self.type_arguments.eq(
                     &other.type_arguments,
                     &(ctx.with_is_inside_trait_constraint()),
-                ))
+                ) && self.associated_type_bindings.eq(
+                    &other.associated_type_bindings,
+                    &(ctx.with_is_inside_trait_constraint()),
+                )))
     }
 }
 
@@ -54,17 +102,21 @@ impl OrdWithEngines for TraitConstraint {
         let TraitConstraint {
             trait_name: ltn,
             type_arguments: lta,
+            associated_type_bindings: lab,
         } = self;
         let TraitConstraint {
             trait_name: rtn,
             type_arguments: rta,
+            associated_type_bindings: rab,
         } = other;
         let mut res = ltn.cmp(rtn);
 
         // Check if cmp is already inside of a trait constraint, if it is we don't compare type arguments.
         // This breaks the recursion when we use a where clause such as `T:MyTrait<T>`.
         if !ctx.is_inside_trait_constraint() {
-            res = res.then_with(|| lta.cmp(rta, &ctx.with_is_inside_trait_constraint()));
+            res = res
+                .then_with(|| lta.cmp(rta, &ctx.with_is_inside_trait_constraint()))
+                .then_with(|| lab.cmp(rab, &ctx.with_is_inside_trait_constraint()));
         }
 
         res
@@ -80,11 +132,14 @@ impl DisplayWithEngines for TraitConstraint {
 impl DebugWithEngines for TraitConstraint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>, engines: &Engines) -> fmt::Result {
         let mut res = write!(f, "{}", self.trait_name);
-        if !self.type_arguments.is_empty() {
+        if !self.type_arguments.is_empty() || !self.associated_type_bindings.is_empty() {
             write!(f, "<")?;
             for ty_arg in self.type_arguments.clone() {
                 write!(f, "{:?}", engines.help_out(ty_arg))?;
             }
+            for binding in self.associated_type_bindings.iter() {
+                write!(f, "{:?}", engines.help_out(binding))?;
+            }
             res = write!(f, ">");
         }
         res
@@ -99,7 +154,11 @@ impl Spanned for TraitConstraint {
 
 impl SubstTypes for TraitConstraint {
     fn subst_inner(&mut self, ctx: &SubstTypesContext) -> HasChanges {
-        self.type_arguments.subst(ctx)
+        let mut has_changes = self.type_arguments.subst(ctx);
+        for binding in self.associated_type_bindings.iter_mut() {
+            has_changes |= binding.type_argument.subst(ctx);
+        }
+        has_changes
     }
 }
 
@@ -108,6 +167,7 @@ impl From<&Supertrait> for TraitConstraint {
         TraitConstraint {
             trait_name: supertrait.name.clone(),
             type_arguments: vec![],
+            associated_type_bindings: vec![],
         }
     }
 }
@@ -120,7 +180,11 @@ impl CollectTypesMetadata for TraitConstraint {
     ) -> Result<Vec<TypeMetadata>, ErrorEmitted> {
         let mut res = vec![];
         handler.scope(|handler| {
-            for type_arg in &self.type_arguments {
+            for type_arg in self.type_arguments.iter().chain(
+                self.associated_type_bindings
+                    .iter()
+                    .map(|binding| &binding.type_argument),
+            ) {
                 res.extend(
                     match type_arg.type_id.collect_types_metadata(handler, ctx) {
                         Ok(res) => res,
@@ -177,6 +241,19 @@ impl TraitConstraint {
                 .unwrap_or_else(|err| ctx.engines.te().id_of_error_recovery(err));
         }
 
+        // Type check the associated type bindings.
+        for binding in &mut self.associated_type_bindings {
+            binding.type_argument.type_id = ctx
+                .resolve_type(
+                    handler,
+                    binding.type_argument.type_id,
+                    &binding.type_argument.span,
+                    EnforceTypeArguments::Yes,
+                    None,
+                )
+                .unwrap_or_else(|err| ctx.engines.te().id_of_error_recovery(err));
+        }
+
         Ok(())
     }
 
@@ -192,6 +269,7 @@ impl TraitConstraint {
         let TraitConstraint {
             trait_name,
             type_arguments,
+            associated_type_bindings: _,
         } = trait_constraint;
 
         let mut type_arguments = type_arguments.clone();
@@ -1,16 +1,18 @@
 use crate::{
-    decl_engine::{DeclMapping, InterfaceItemMap, ItemMap},
+    decl_engine::{DeclId, DeclMapping, InterfaceItemMap, ItemMap},
     engine_threading::*,
     has_changes,
     language::{ty, CallPath},
-    namespace::TraitMap,
-    semantic_analysis::{GenericShadowingMode, TypeCheckContext},
+    namespace::{SatisfiedTraitConstraint, TraitMap},
+    semantic_analysis::{
+        declaration::resolve_supertrait_decl, GenericShadowingMode, TypeCheckContext,
+    },
     type_system::priv_prelude::*,
 };
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
-    collections::BTreeMap,
+    collections::{BTreeMap, HashSet},
     fmt,
     hash::{Hash, Hasher},
 };
@@ -263,38 +265,79 @@ impl TypeParameter {
         })
     }
 
-    // Expands a trait constraint to include all its supertraits.
+    // Expands trait constraints to include all their supertraits.
     // Another way to incorporate this info would be at the level of unification,
     // we would check that two generic type parameters should unify when
     // the left one is a supertrait of the right one (at least in the NonDynamicEquality mode)
+    //
+    // Supertraits are taken from the trait declarations, so they do not need to be
+    // in scope where the constraints are used. Every supertrait is added only once,
+    // even if it is reachable through several subtraits, e.g., in diamond hierarchies.
     fn expand_trait_constraints(
         handler: &Handler,
         ctx: &TypeCheckContext,
-        tc: &TraitConstraint,
+        trait_constraints: &[TraitConstraint],
     ) -> Vec<TraitConstraint> {
-        match ctx.resolve_call_path(handler, &tc.trait_name).ok() {
-            Some(ty::TyDecl::TraitDecl(ty::TraitDecl { decl_id, .. })) => {
-                let trait_decl = ctx.engines.de().get_trait(&decl_id);
-                let mut result = vec![tc.clone()];
-                result.extend(
-                    trait_decl
-                        .supertraits
-                        .iter()
-                        .flat_map(|supertrait| {
-                            TypeParameter::expand_trait_constraints(
-                                handler,
-                                ctx,
-                                &TraitConstraint {
-                                    trait_name: supertrait.name.clone(),
-                                    type_arguments: tc.type_arguments.clone(),
-                                },
-                            )
-                        })
-                        .collect::<Vec<TraitConstraint>>(),
+        let mut visited = HashSet::new();
+        let mut result = vec![];
+        for tc in trait_constraints {
+            result.push(tc.clone());
+            if let Some(ty::TyDecl::TraitDecl(ty::TraitDecl { decl_id, .. })) =
+                ctx.resolve_call_path(handler, &tc.trait_name).ok()
+            {
+                visited.insert(decl_id);
+                TypeParameter::collect_supertrait_constraints(
+                    ctx,
+                    &decl_id,
+                    tc,
+                    &mut visited,
+                    &mut result,
                 );
-                result
             }
-            _ => vec![tc.clone()],
+        }
+        result
+    }
+
+    fn collect_supertrait_constraints(
+        ctx: &TypeCheckContext,
+        decl_id: &DeclId<ty::TyTraitDecl>,
+        tc: &TraitConstraint,
+        visited: &mut HashSet<DeclId<ty::TyTraitDecl>>,
+        result: &mut Vec<TraitConstraint>,
+    ) {
+        let trait_decl = ctx.engines.de().get_trait(decl_id);
+        for supertrait in trait_decl.supertraits.iter() {
+            match resolve_supertrait_decl(ctx, supertrait) {
+                Some(ty::TyDecl::TraitDecl(ty::TraitDecl {
+                    decl_id: supertrait_decl_id,
+                })) => {
+                    if !visited.insert(supertrait_decl_id) {
+                        continue;
+                    }
+                    result.push(TraitConstraint {
+                        trait_name: ctx
+                            .engines
+                            .de()
+                            .get_trait(&supertrait_decl_id)
+                            .call_path
+                            .clone(),
+                        type_arguments: tc.type_arguments.clone(),
+                        associated_type_bindings: vec![],
+                    });
+                    TypeParameter::collect_supertrait_constraints(
+                        ctx,
+                        &supertrait_decl_id,
+                        tc,
+                        visited,
+                        result,
+                    );
+                }
+                _ => result.push(TraitConstraint {
+                    trait_name: supertrait.name.clone(),
+                    type_arguments: tc.type_arguments.clone(),
+                    associated_type_bindings: vec![],
+                }),
+            }
         }
     }
 
@@ -316,10 +359,8 @@ impl TypeParameter {
             type_id,
         } = type_parameter;
 
-        let trait_constraints_with_supertraits: Vec<TraitConstraint> = trait_constraints
-            .iter()
-            .flat_map(|tc| TypeParameter::expand_trait_constraints(handler, &ctx, tc))
-            .collect();
+        let trait_constraints_with_supertraits =
+            TypeParameter::expand_trait_constraints(handler, &ctx, &trait_constraints);
 
         let parent = if let TypeInfo::UnknownGeneric {
             name: _,
@@ -366,17 +407,17 @@ impl TypeParameter {
     ) -> Result<(), ErrorEmitted> {
         let type_engine = ctx.engines.te();
 
-        let mut trait_constraints_with_supertraits: Vec<TraitConstraint> = type_parameter
-            .trait_constraints
-            .iter()
-            .flat_map(|tc| TypeParameter::expand_trait_constraints(handler, &ctx, tc))
-            .collect();
-
         // Type check the trait constraints.
-        for trait_constraint in &mut trait_constraints_with_supertraits {
+        let mut trait_constraints = type_parameter.trait_constraints.clone();
+        for trait_constraint in &mut trait_constraints {
             trait_constraint.type_check(handler, ctx.by_ref())?;
         }
 
+        // Supertrait constraints are expanded from the type checked constraints and
+        // get the full paths of the supertraits, so they are already type checked.
+        let trait_constraints_with_supertraits =
+            TypeParameter::expand_trait_constraints(handler, &ctx, &trait_constraints);
+
         // TODO: add check here to see if the type parameter has a valid name and does not have type parameters
 
         let parent = if let TypeInfo::UnknownGeneric {
@@ -527,7 +568,7 @@ impl TypeParameter {
                     // If more than one implementation exists we throw an error.
                     // We only try to do the type inference from trait with a single trait constraint.
                     if !type_id.is_concrete(engines, TreatNumericAs::Concrete) && trait_constraints.len() == 1 {
-                        let concrete_trait_type_ids : Vec<(TypeId, String)>=
+                        let concrete_trait_type_ids : Vec<SatisfiedTraitConstraint>=
                             TraitMap::get_trait_constraints_are_satisfied_for_types(
                                 ctx
                             .namespace()
@@ -535,7 +576,7 @@ impl TypeParameter {
                             )?
                             .into_iter()
                             .filter_map(|t| {
-                                if t.0.is_concrete(engines, TreatNumericAs::Concrete) {
+                                if t.implementing_type_id.is_concrete(engines, TreatNumericAs::Concrete) {
                                     Some(t)
                                 } else {
                                     None
@@ -548,7 +589,7 @@ impl TypeParameter {
                                     handler,
                                     engines,
                                     *type_id,
-                                    concrete_trait_type_ids.first().unwrap().0,
+                                    concrete_trait_type_ids.first().unwrap().implementing_type_id,
                                     access_span,
                                     "Type parameter type does not match up with matched trait implementing type.",
                                     None,
@@ -559,7 +600,7 @@ impl TypeParameter {
                                     span:access_span.clone(),
                                     type_annotation: engines.help_out(type_id).to_string(),
                                     trait_names: trait_constraints.iter().map(|t| t.to_display_name(engines, ctx.namespace())).collect(),
-                                    trait_types_and_names: concrete_trait_type_ids.iter().map(|t| (engines.help_out(t.0).to_string(), t.1.clone())).collect::<Vec<_>>()
+                                    trait_types_and_names: concrete_trait_type_ids.iter().map(|t| (engines.help_out(t.implementing_type_id).to_string(), t.trait_name_with_type_arguments(engines))).collect::<Vec<_>>()
                                 }));
                             }
                             Ordering::Less => {
@@ -588,6 +629,7 @@ impl TypeParameter {
                     let TraitConstraint {
                         trait_name,
                         type_arguments: trait_type_arguments,
+                        associated_type_bindings: _,
                     } = trait_constraint;
 
                     let (trait_interface_item_refs, trait_item_refs, trait_impld_item_refs) =
@@ -26,6 +26,10 @@ use sway_types::{integer_bits::IntegerBits, span::Span, Ident, ProgramId, Source
 
 use super::{ast_elements::length::NumericLength, unify::unifier::UnifyKind};
 
+/// Maximum number of [TypeInfo::Alias]es followed when unaliasing a type.
+/// Longer chains of aliases are considered to be self-referential.
+const MAX_ALIAS_DEPTH: usize = 1024;
+
 /// To be able to garbage-collect [TypeInfo]s from the [TypeEngine]
 /// we need to track which types need to be GCed when a particular
 /// module, represented by its source id, is GCed. [TypeSourceInfo]
@@ -1980,26 +1984,42 @@ impl TypeEngine {
 
     /// Performs a lookup of `id` into the [TypeEngine] recursing when finding a
     /// [TypeInfo::Alias].
+    ///
+    /// A self-referential alias is returned as is. Use [TypeEngine::try_get_unaliased]
+    /// to detect it.
     pub fn get_unaliased(&self, id: TypeId) -> Arc<TypeInfo> {
-        // A slight infinite loop concern if we somehow have self-referential aliases, but that
-        // shouldn't be possible.
-        let tsi = self.slab.get(id.index());
-        match &*tsi.type_info {
-            TypeInfo::Alias { ty, .. } => self.get_unaliased(ty.type_id),
-            _ => tsi.type_info.clone(),
-        }
+        self.get(self.get_unaliased_type_id(id))
     }
 
     /// Performs a lookup of `id` into the [TypeEngine] recursing when finding a
     /// [TypeInfo::Alias].
+    ///
+    /// A self-referential alias is returned as is. Use [TypeEngine::try_get_unaliased_type_id]
+    /// to detect it.
     pub fn get_unaliased_type_id(&self, id: TypeId) -> TypeId {
-        // A slight infinite loop concern if we somehow have self-referential aliases, but that
-        // shouldn't be possible.
-        let tsi = self.slab.get(id.index());
-        match &*tsi.type_info {
-            TypeInfo::Alias { ty, .. } => self.get_unaliased_type_id(ty.type_id),
-            _ => id,
+        self.try_get_unaliased_type_id(id).unwrap_or(id)
+    }
+
+    /// Like [TypeEngine::get_unaliased], but returns `None` if `id` is a
+    /// self-referential alias.
+    pub(crate) fn try_get_unaliased(&self, id: TypeId) -> Option<Arc<TypeInfo>> {
+        self.try_get_unaliased_type_id(id).map(|id| self.get(id))
+    }
+
+    /// Like [TypeEngine::get_unaliased_type_id], but returns `None` if `id` is a
+    /// self-referential alias.
+    ///
+    /// Aliases cannot refer to themselves in well-formed programs, but error recovery
+    /// can produce such types. To avoid looping endlessly on them, at most
+    /// [MAX_ALIAS_DEPTH] aliases are followed.
+    pub(crate) fn try_get_unaliased_type_id(&self, mut id: TypeId) -> Option<TypeId> {
+        for _ in 0..=MAX_ALIAS_DEPTH {
+            match &*self.get(id) {
+                TypeInfo::Alias { ty, .. } => id = ty.type_id,
+                _ => return Some(id),
+            }
         }
+        None
     }
 
     /// Make the types of `received` and `expected` equivalent (or produce an
@@ -927,47 +927,35 @@ impl TypeId {
     ) -> bool {
         let engines = ctx.engines();
 
-        let unify_check = UnifyCheck::constraint_subset(engines);
         let mut found_error = false;
-        let generic_trait_constraints_trait_names_and_args =
-            TraitMap::get_trait_names_and_type_arguments_for_type(
-                ctx.namespace().current_module(),
-                engines,
-                *structure_type_id,
-            );
         for structure_trait_constraint in structure_trait_constraints {
             let structure_trait_constraint_trait_name = &structure_trait_constraint
                 .trait_name
                 .to_canonical_path(ctx.engines(), ctx.namespace());
-
-            if !generic_trait_constraints_trait_names_and_args.iter().any(
-                |(trait_name, trait_args)| {
-                    trait_name == structure_trait_constraint_trait_name
-                        && trait_args.len() == structure_trait_constraint.type_arguments.len()
-                        && trait_args
-                            .iter()
-                            .zip(structure_trait_constraint.type_arguments.iter())
-                            .all(|(t1, t2)| {
-                                unify_check.check(
-                                    ctx.resolve_type(
-                                        handler,
-                                        t1.type_id,
-                                        &t1.span,
-                                        EnforceTypeArguments::No,
-                                        None,
-                                    )
-                                    .unwrap_or_else(|err| engines.te().id_of_error_recovery(err)),
-                                    ctx.resolve_type(
-                                        handler,
-                                        t2.type_id,
-                                        &t2.span,
-                                        EnforceTypeArguments::No,
-                                        None,
-                                    )
-                                    .unwrap_or_else(|err| engines.te().id_of_error_recovery(err)),
-                                )
-                            })
-                },
+            let structure_trait_constraint_type_arguments = structure_trait_constraint
+                .type_arguments
+                .iter()
+                .map(|type_argument| {
+                    let mut type_argument = type_argument.clone();
+                    type_argument.type_id = ctx
+                        .resolve_type(
+                            handler,
+                            type_argument.type_id,
+                            &type_argument.span,
+                            EnforceTypeArguments::No,
+                            None,
+                        )
+                        .unwrap_or_else(|err| engines.te().id_of_error_recovery(err));
+                    type_argument
+                })
+                .collect::<Vec<_>>();
+
+            if !TraitMap::has_impl_for(
+                ctx.namespace().current_module(),
+                engines,
+                *structure_type_id,
+                structure_trait_constraint_trait_name,
+                &structure_trait_constraint_type_arguments,
             ) {
                 found_error = true;
                 f(structure_trait_constraint);
@@ -6,6 +6,8 @@ use crate::{
     },
     type_system::priv_prelude::*,
 };
+use parking_lot::Mutex;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 enum UnifyCheckMode {
@@ -178,6 +180,9 @@ pub(crate) struct UnifyCheck<'a> {
     mode: UnifyCheckMode,
     unify_ref_mut: bool,
     ignore_generic_names: bool,
+    /// Results of [UnifyCheck::check] already computed by this instance,
+    /// if memoization is enabled. See [UnifyCheck::with_memo].
+    memo: Option<Mutex<HashMap<(TypeId, TypeId), bool>>>,
 }
 
 impl<'a> UnifyCheck<'a> {
@@ -187,6 +192,7 @@ impl<'a> UnifyCheck<'a> {
             mode: UnifyCheckMode::Coercion,
             unify_ref_mut: true,
             ignore_generic_names: false,
+            memo: None,
         }
     }
     pub(crate) fn constraint_subset(engines: &'a Engines) -> Self {
@@ -195,6 +201,7 @@ impl<'a> UnifyCheck<'a> {
             mode: UnifyCheckMode::ConstraintSubset,
             unify_ref_mut: true,
             ignore_generic_names: false,
+            memo: None,
         }
     }
 
@@ -204,6 +211,7 @@ impl<'a> UnifyCheck<'a> {
             mode: UnifyCheckMode::NonGenericConstraintSubset,
             unify_ref_mut: true,
             ignore_generic_names: false,
+            memo: None,
         }
     }
 
@@ -213,6 +221,7 @@ impl<'a> UnifyCheck<'a> {
             mode: UnifyCheckMode::NonDynamicEquality,
             unify_ref_mut: true,
             ignore_generic_names: false,
+            memo: None,
         }
     }
 
@@ -222,6 +231,7 @@ impl<'a> UnifyCheck<'a> {
             ignore_generic_names: self.ignore_generic_names,
             engines: self.engines,
             mode: self.mode.clone(),
+            memo: self.fresh_memo(),
         }
     }
 
@@ -231,10 +241,42 @@ impl<'a> UnifyCheck<'a> {
             ignore_generic_names,
             engines: self.engines,
             mode: self.mode.clone(),
+            memo: self.fresh_memo(),
         }
     }
 
+    /// Remembers the results of [UnifyCheck::check] for each checked pair of [TypeId]s,
+    /// so that checking the same pair again does not walk the types again.
+    ///
+    /// The remembered results are valid only as long as none of the checked types
+    /// changes. Use memoization only for checkers that live within a single query
+    /// which does not unify types, e.g., within a single trait map lookup.
+    pub(crate) fn with_memo(mut self) -> Self {
+        self.memo = Some(Mutex::new(HashMap::new()));
+        self
+    }
+
+    /// Results depend on the checker settings, so a checker derived from
+    /// a memoizing one gets its own, empty, memo.
+    fn fresh_memo(&self) -> Option<Mutex<HashMap<(TypeId, TypeId), bool>>> {
+        self.memo.as_ref().map(|_| Mutex::new(HashMap::new()))
+    }
+
     pub(crate) fn check(&self, left: TypeId, right: TypeId) -> bool {
+        let Some(memo) = &self.memo else {
+            return self.check_uncached(left, right);
+        };
+
+        if let Some(result) = memo.lock().get(&(left, right)) {
+            return *result;
+        }
+        // The lock is not held while checking, because checks can be done in parallel.
+        let result = self.check_uncached(left, right);
+        memo.lock().insert((left, right), result);
+        result
+    }
+
+    fn check_uncached(&self, left: TypeId, right: TypeId) -> bool {
         use TypeInfo::*;
         use UnifyCheckMode::NonGenericConstraintSubset;
         if left == right {
@@ -15,6 +15,8 @@ pub enum ConvertParseTreeError {
     GenericsNotSupportedHere { span: Span },
     #[error("multiple generics are not supported")]
     MultipleGenericsNotSupported { span: Span },
+    #[error("associated type bindings are only supported in trait constraints")]
+    AssociatedTypeBindingsNotSupportedHere { span: Span },
     #[error("tuple index out of range")]
     TupleIndexOutOfRange { span: Span },
     #[error("shift-left expressions are not implemented")]
@@ -163,6 +165,19 @@ pub enum ConvertParseTreeError {
         arg: Ident,
         expected_values: Vec<&'static str>,
     },
+    #[error("\"{arg}\" argument of the attribute \"{attribute}\" cannot have nested arguments.")]
+    InvalidAttributeArgNestedArgs {
+        span: Span,
+        attribute: Ident,
+        arg: IdentUnique,
+    },
+    #[error("\"not\" predicate must have exactly one nested argument, but has {}.", num_to_str_or_none(*num_of_args))]
+    InvalidCfgNotPredicateArgsMultiplicity { span: Span, num_of_args: usize },
+    #[error("\"{predicate}\" predicate of the attribute \"cfg\" does not take a value.")]
+    InvalidCfgPredicateValue {
+        predicate: IdentUnique,
+        value_span: Span,
+    },
 }
 
 pub(crate) enum AttributeType {
@@ -212,6 +227,7 @@ impl Spanned for ConvertParseTreeError {
             ConvertParseTreeError::FunctionArbitraryExpression { span } => span.clone(),
             ConvertParseTreeError::GenericsNotSupportedHere { span } => span.clone(),
             ConvertParseTreeError::MultipleGenericsNotSupported { span } => span.clone(),
+            ConvertParseTreeError::AssociatedTypeBindingsNotSupportedHere { span } => span.clone(),
             ConvertParseTreeError::TupleIndexOutOfRange { span } => span.clone(),
             ConvertParseTreeError::ShlNotImplemented { span } => span.clone(),
             ConvertParseTreeError::ShrNotImplemented { span } => span.clone(),
@@ -268,6 +284,11 @@ impl Spanned for ConvertParseTreeError {
             ConvertParseTreeError::InvalidAttributeArgExpectsValue { arg, .. } => arg.span(),
             ConvertParseTreeError::InvalidAttributeArgValueType { span, .. } => span.clone(),
             ConvertParseTreeError::InvalidAttributeArgValue { span, .. } => span.clone(),
+            ConvertParseTreeError::InvalidAttributeArgNestedArgs { span, .. } => span.clone(),
+            ConvertParseTreeError::InvalidCfgNotPredicateArgsMultiplicity { span, .. } => {
+                span.clone()
+            }
+            ConvertParseTreeError::InvalidCfgPredicateValue { predicate, .. } => predicate.span(),
         }
     }
 }
@@ -384,18 +384,32 @@ pub enum CompileError {
     },
     #[error("Field \"{field_name}\" has multiple definitions.")]
     StructFieldDuplicated { field_name: Ident, duplicate: Ident },
-    #[error("No method \"{method}\" found for type \"{type_name}\".{}", 
+    #[error("No method \"{method}\" found for type \"{type_name}\".{}{}", 
         if matching_method_strings.is_empty() {
             "".to_string()
         } else {
             format!("  \nMatching method{}:\n{}", if matching_method_strings.len()> 1 {"s"} else {""},
             matching_method_strings.iter().map(|m| format!("    {m}")).collect::<Vec<_>>().join("\n"))
+        },
+        if similar_method_names.is_empty() {
+            "".to_string()
+        } else {
+            format!("  \nDid you mean {}?", sequence_to_str_or(similar_method_names, Enclosing::DoubleQuote, 3))
+        },
+        match impl_for_other_ref_mutability {
+            Some(other_impl) => format!("  \nHelp: {}", other_impl.help()),
+            None => "".to_string(),
         }
     )]
     MethodNotFound {
         method: String,
         type_name: String,
         matching_method_strings: Vec<String>,
+        /// Names of the methods available for the type that are similar to `method`.
+        similar_method_names: Vec<String>,
+        /// An impl providing the method for the same type, but with
+        /// references of different mutability.
+        impl_for_other_ref_mutability: Option<ImplForOtherRefMutability>,
         span: Span,
     },
     #[error("Module \"{name}\" could not be found.")]
@@ -451,6 +465,17 @@ pub enum CompileError {
     DeclIsNotATypeAlias { actually: String, span: Span },
     #[error("Could not find symbol \"{name}\" in this scope.")]
     SymbolNotFound { name: Ident, span: Span },
+    #[error("Could not find symbol \"{name}\" for type \"{type_name}\".")]
+    SymbolNotFoundForType {
+        name: Ident,
+        type_name: String,
+        /// Names of the items available for the type that are similar to `name`.
+        similar_item_names: Vec<String>,
+        /// An impl providing the item for the same type, but with
+        /// references of different mutability.
+        impl_for_other_ref_mutability: Option<ImplForOtherRefMutability>,
+        span: Span,
+    },
     #[error("Found multiple bindings for \"{name}\" in this scope.")]
     SymbolWithMultipleBindings {
         name: Ident,
@@ -520,6 +545,37 @@ pub enum CompileError {
         trait_name: String,
         span: Span,
     },
+    #[error("Trait \"{trait_name}\" is not implemented for type \"{ty}\". It is required by the type parameter \"{type_parameter}\" of the impl for \"{implementing_for}\".")]
+    ImplTraitConstraintNotSatisfied {
+        ty: String,
+        trait_name: String,
+        type_parameter: String,
+        /// The name of the implemented trait, or `None` for "impl self" blocks.
+        impl_trait_name: Option<String>,
+        implementing_for: String,
+        impl_span: Span,
+        span: Span,
+    },
+    #[error("Trait \"{trait_name}\" is not implemented for type \"{ty}\". The impl applies only where \"{type_parameter}: {required_trait_name}\", but \"{type_argument}\" does not implement \"{required_trait_name}\".")]
+    ImplWhereClauseNotSatisfied {
+        ty: String,
+        trait_name: String,
+        type_parameter: String,
+        type_argument: String,
+        required_trait_name: String,
+        impl_span: Span,
+        span: Span,
+    },
+    #[error("Associated type \"{associated_type_name}\" of trait \"{trait_name}\" for type \"{ty}\" is \"{actual}\", but \"{expected}\" is required.")]
+    TraitConstraintAssociatedTypeMismatch {
+        ty: String,
+        trait_name: String,
+        associated_type_name: String,
+        expected: String,
+        actual: String,
+        span: Span,
+        associated_type_span: Span,
+    },
     #[error(
         "Expects trait constraint \"{param}: {trait_name}\" which is missing from type parameter \"{param}\"."
     )]
@@ -634,14 +690,23 @@ pub enum CompileError {
         marker_trait_full_name: String,
         span: Span,
     },
+    #[error("Trait \"{trait_name}\" cannot be implemented for type \"{type_name}\", because neither of them is declared in the package \"{package_name}\".")]
+    OrphanTraitImpl {
+        trait_name: String,
+        type_name: String,
+        package_name: String,
+        span: Span,
+    },
     #[error("Duplicate definitions for the {decl_kind} \"{decl_name}\" for type \"{type_implementing_for}\".")]
     DuplicateDeclDefinedForType {
         decl_kind: String,
         decl_name: String,
         type_implementing_for: String,
         type_implementing_for_unaliased: String,
-        existing_impl_span: Span,
-        second_impl_span: Span,
+        /// The name span of the already existing declaration.
+        existing_decl_span: Span,
+        /// The name span of the duplicate declaration.
+        second_decl_span: Span,
     },
     #[error("The function \"{fn_name}\" in {interface_name} is defined with {num_parameters} parameters, but the provided implementation has {provided_parameters} parameters.")]
     IncorrectNumberOfInterfaceSurfaceFunctionParameters {
@@ -978,20 +1043,18 @@ pub enum CompileError {
     #[error("Configurable constants are not allowed in libraries.")]
     ConfigurableInLibrary { span: Span },
     #[error("Multiple applicable items in scope. {}", {
-        let mut candidates = "".to_string();
-        let mut as_traits = as_traits.clone();
-        // Make order deterministic
-        as_traits.sort_by_key(|a| a.0.to_lowercase());
-        for (index, as_trait) in as_traits.iter().enumerate() {
-            candidates = format!("{candidates}\n  Disambiguate the associated {item_kind} for candidate #{index}\n    <{} as {}>::{item_name}", as_trait.1, as_trait.0);
+        let mut candidates_list = "".to_string();
+        for (index, candidate) in candidates.iter().enumerate() {
+            candidates_list = format!("{candidates_list}\n  Disambiguate the associated {item_kind} for candidate #{index}\n    {}", candidate.qualified_path(item_name));
         }
-        candidates
+        candidates_list
     })]
     MultipleApplicableItemsInScope {
         span: Span,
         item_name: String,
         item_kind: String,
-        as_traits: Vec<(String, String)>,
+        /// The applicable candidates, in the order in which they are reported.
+        candidates: Vec<ApplicableItemCandidate>,
     },
     #[error("Provided generic type is not of type str.")]
     NonStrGenericType { span: Span },
@@ -1133,6 +1196,7 @@ impl Spanned for CompileError {
             NotIndexable { span, .. } => span.clone(),
             FieldAccessOnNonStruct { span, .. } => span.clone(),
             SymbolNotFound { span, .. } => span.clone(),
+            SymbolNotFoundForType { span, .. } => span.clone(),
             SymbolWithMultipleBindings { span, .. } => span.clone(),
             ImportPrivateSymbol { span, .. } => span.clone(),
             ImportPrivateModule { span, .. } => span.clone(),
@@ -1148,6 +1212,9 @@ impl Spanned for CompileError {
             UnableToInferGeneric { span, .. } => span.clone(),
             UnconstrainedGenericParameter { span, .. } => span.clone(),
             TraitConstraintNotSatisfied { span, .. } => span.clone(),
+            ImplTraitConstraintNotSatisfied { span, .. } => span.clone(),
+            ImplWhereClauseNotSatisfied { span, .. } => span.clone(),
+            TraitConstraintAssociatedTypeMismatch { span, .. } => span.clone(),
             TraitConstraintMissing { span, .. } => span.clone(),
             Immediate06TooLarge { span, .. } => span.clone(),
             Immediate12TooLarge { span, .. } => span.clone(),
@@ -1180,9 +1247,10 @@ impl Spanned for CompileError {
                 second_impl_span, ..
             } => second_impl_span.clone(),
             MarkerTraitExplicitlyImplemented { span, .. } => span.clone(),
+            OrphanTraitImpl { span, .. } => span.clone(),
             DuplicateDeclDefinedForType {
-                second_impl_span, ..
-            } => second_impl_span.clone(),
+                second_decl_span, ..
+            } => second_decl_span.clone(),
             IncorrectNumberOfInterfaceSurfaceFunctionParameters { span, .. } => span.clone(),
             ArgumentParameterTypeMismatch { span, .. } => span.clone(),
             RecursiveCall { span, .. } => span.clone(),
@@ -2350,13 +2418,105 @@ impl ToDiagnostic for CompileError {
                     "This property is called \"trait coherence\".".to_string(),
                 ],
             },
-            DuplicateDeclDefinedForType { decl_kind, decl_name, type_implementing_for, type_implementing_for_unaliased, existing_impl_span, second_impl_span } => {
+            TraitConstraintAssociatedTypeMismatch { ty, trait_name, associated_type_name, expected, actual, span, associated_type_span } => Diagnostic {
+                reason: Some(Reason::new(code(1), "Associated type does not match trait constraint".to_string())),
+                issue: Issue::error(
+                    source_engine,
+                    span.clone(),
+                    format!("Trait constraint requires \"{associated_type_name}\" of \"{trait_name}\" to be \"{expected}\", but it is \"{actual}\" for \"{ty}\".")
+                ),
+                hints: vec![
+                    Hint::info(
+                        source_engine,
+                        associated_type_span.clone(),
+                        format!("\"{associated_type_name}\" is defined as \"{actual}\" here.")
+                    ),
+                ],
+                help: vec![
+                    format!("Change the associated type \"{associated_type_name}\" to \"{expected}\", or use a type whose \"{associated_type_name}\" is \"{expected}\"."),
+                ],
+            },
+            SymbolNotFoundForType { name, type_name, similar_item_names, impl_for_other_ref_mutability, span } => Diagnostic {
+                reason: Some(Reason::new(code(1), "Symbol not found".to_string())),
+                issue: Issue::error(
+                    source_engine,
+                    span.clone(),
+                    format!("Could not find symbol \"{name}\" for type \"{type_name}\".")
+                ),
+                hints: vec![
+                    if similar_item_names.is_empty() {
+                        Hint::none()
+                    } else {
+                        Hint::help(
+                            source_engine,
+                            span.clone(),
+                            format!("Did you mean {}?", sequence_to_str_or(similar_item_names, Enclosing::DoubleQuote, 3))
+                        )
+                    },
+                    match impl_for_other_ref_mutability {
+                        Some(other_impl) => Hint::info(
+                            source_engine,
+                            other_impl.impl_span.clone(),
+                            format!("\"{name}\" is implemented here for \"{}\".", other_impl.type_name)
+                        ),
+                        None => Hint::none(),
+                    },
+                ],
+                help: match impl_for_other_ref_mutability {
+                    Some(other_impl) => vec![
+                        other_impl.help(),
+                        "References to mutable and immutable values are different types when it comes to impls.".to_string(),
+                    ],
+                    None => vec![],
+                },
+            },
+            ImplTraitConstraintNotSatisfied { ty, trait_name, type_parameter, impl_trait_name, implementing_for, impl_span, span } => Diagnostic {
+                reason: Some(Reason::new(code(1), "Trait constraint of an impl is not satisfied".to_string())),
+                issue: Issue::error(
+                    source_engine,
+                    span.clone(),
+                    format!("Trait \"{trait_name}\" is not implemented for type \"{ty}\".")
+                ),
+                hints: vec![
+                    Hint::info(
+                        source_engine,
+                        impl_span.clone(),
+                        match impl_trait_name {
+                            Some(impl_trait_name) => format!("\"{ty}\" does not implement \"{trait_name}\", required by the impl of \"{impl_trait_name}\" for \"{implementing_for}\"."),
+                            None => format!("\"{ty}\" does not implement \"{trait_name}\", required by the impl for \"{implementing_for}\"."),
+                        }
+                    ),
+                ],
+                help: vec![
+                    format!("The type parameter \"{type_parameter}\" of the impl must implement \"{trait_name}\"."),
+                    format!("Consider implementing \"{trait_name}\" for \"{ty}\"."),
+                ],
+            },
+            ImplWhereClauseNotSatisfied { ty, trait_name, type_parameter, type_argument, required_trait_name, impl_span, span } => Diagnostic {
+                reason: Some(Reason::new(code(1), "Where clause of an impl is not satisfied".to_string())),
+                issue: Issue::error(
+                    source_engine,
+                    span.clone(),
+                    format!("Trait \"{trait_name}\" is not implemented for type \"{ty}\".")
+                ),
+                hints: vec![
+                    Hint::info(
+                        source_engine,
+                        impl_span.clone(),
+                        format!("The impl applies only where \"{type_parameter}: {required_trait_name}\", but \"{type_argument}\" does not implement \"{required_trait_name}\"."),
+                    ),
+                ],
+                help: vec![
+                    format!("Consider implementing \"{required_trait_name}\" for \"{type_argument}\"."),
+                ],
+            },
+            DuplicateDeclDefinedForType { decl_kind, decl_name, type_implementing_for, type_implementing_for_unaliased, existing_decl_span, second_decl_span } => {
                 let decl_kind_snake_case = sway_types::style::to_upper_camel_case(decl_kind);
                 Diagnostic {
                     reason: Some(Reason::new(code(1), "Type contains duplicate declarations".to_string())),
                     issue: Issue::error(
                         source_engine,
-                        second_impl_span.clone(),
+                        second_decl_span.clone(),
                         if type_implementing_for == type_implementing_for_unaliased {
                             format!("{decl_kind_snake_case} \"{decl_name}\" already declared in type \"{type_implementing_for}\".")
                         } else {
@@ -2366,8 +2526,8 @@ impl ToDiagnostic for CompileError {
                     hints: vec![
                         Hint::info(
                             source_engine,
-                            existing_impl_span.clone(),
-                            format!("\"{decl_name}\" previously defined here.")
+                            existing_decl_span.clone(),
+                            format!("\"{decl_name}\" first defined here.")
                         )
                     ],
                     help: vec![
@@ -2394,6 +2554,21 @@ impl ToDiagnostic for CompileError {
                     _ => vec![],
                 }
             },
+            OrphanTraitImpl { trait_name, type_name, package_name, span } => Diagnostic {
+                reason: Some(Reason::new(code(1), "Trait implementations must involve a local trait or type".to_string())),
+                issue: Issue::error(
+                    source_engine,
+                    span.clone(),
+                    format!("Trait \"{trait_name}\" cannot be implemented for type \"{type_name}\", because neither of them is declared in the package \"{package_name}\".")
+                ),
+                hints: vec![],
+                help: vec![
+                    "Implementing a trait from another package for a type from another package".to_string(),
+                    "could conflict with the implementations provided by other dependencies.".to_string(),
+                    Diagnostic::help_empty_line(),
+                    format!("To implement the trait, declare a type in the package \"{package_name}\" that wraps \"{type_name}\"."),
+                ],
+            },
             AssignmentToNonMutableVariable { lhs_span, decl_name } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Immutable variables cannot be assigned to".to_string())),
                 issue: Issue::error(
@@ -2763,6 +2938,74 @@ impl ToDiagnostic for CompileError {
                         hints: vec![did_you_mean_help(source_engine, error.span.clone(), known_op_codes.iter(), 2, Enclosing::DoubleQuote)],
                         help: vec![]
                     },
+                    ParseErrorKind::OuterDocCommentBeforeModuleKind { module_kind, first_item, .. } => Diagnostic {
+                        reason: Some(Reason::new(code(1), "Outer doc comment (`///`) cannot document item".to_string())),
+                        issue: Issue::error(
+                            source_engine,
+                            error.span.clone(),
+                            format!("Outer doc comment (`///`) cannot document a {module_kind}.")
+                        ),
+                        hints: vec![],
+                        help: {
+                            let mut help = vec![
+                                "To document the module, replace `///` with `//!` to get an inner doc comment. E.g.:".to_string(),
+                                "//! This doc comment documents a module.".to_string(),
+                            ];
+                            if let Some((_, first_item_name)) = first_item {
+                                help.push(format!("Alternatively, to document the first item in the module, {}{first_item_name}, move the doc comment right above it.",
+                                    a_or_an(first_item_name),
+                                ));
+                            }
+                            help
+                        }
+                    },
+                    ParseErrorKind::ExpectedModuleKindBeforeItems { .. } => Diagnostic {
+                        reason: Some(Reason::new(code(1), "Module kind is missing".to_string())),
+                        issue: Issue::error(
+                            source_engine,
+                            error.span.clone(),
+                            "Expected a module kind before the items of the module.".to_string()
+                        ),
+                        hints: vec![],
+                        help: vec![
+                            "Add `library;` (or `contract;`, `script;`, `predicate;`) at the top of the file.".to_string(),
+                            "Only inner doc comments (`//!`) and inner attributes (`#![...]`) can precede the module kind.".to_string(),
+                        ]
+                    },
+                    ParseErrorKind::ExpectedSemicolonAfterModuleKind { .. } => Diagnostic {
+                        reason: Some(Reason::new(code(1), "Module kind is not terminated".to_string())),
+                        issue: Issue::error(
+                            source_engine,
+                            error.span.clone(),
+                            format!("Expected `;` after the module kind \"{}\".", error.span.as_str())
+                        ),
+                        hints: vec![
+                            Hint::help(
+                                source_engine,
+                                error.span.clone(),
+                                format!("Add `;` here: `{};`.", error.span.as_str())
+                            ),
+                        ],
+                        help: vec![]
+                    },
+                    ParseErrorKind::MisspelledModuleKind { suggestion } => Diagnostic {
+                        reason: Some(Reason::new(code(1), "Module kind is unknown".to_string())),
+                        issue: Issue::error(
+                            source_engine,
+                            error.span.clone(),
+                            format!("\"{}\" is not a module kind.", error.span.as_str())
+                        ),
+                        hints: vec![
+                            Hint::help(
+                                source_engine,
+                                error.span.clone(),
+                                format!("Did you mean \"{suggestion}\"?")
+                            ),
+                        ],
+                        help: vec![
+                            "Module kind must be one of `script`, `contract`, `predicate`, or `library`.".to_string(),
+                        ]
+                    },
                     _ => Diagnostic {
                                 // TODO: Temporary we use `self` here to achieve backward compatibility.
                                 //       In general, `self` must not be used. All the values for the formatting
@@ -2900,6 +3143,34 @@ impl ToDiagnostic for CompileError {
                         },
                         help: vec![],
                     },
+                    ConvertParseTreeError::InvalidAttributeArgNestedArgs { attribute, arg, .. } => Diagnostic {
+                        reason: Some(Reason::new(code(1), "Attribute argument cannot have nested arguments".to_string())),
+                        issue: Issue::error(
+                            source_engine,
+                            arg.span(),
+                            format!("\"{arg}\" argument of the attribute \"{attribute}\" cannot have nested arguments.")
+                        ),
+                        hints: vec![],
+                        help: if attribute.as_str() == "cfg" {
+                            vec!["Only the \"all\", \"any\", and \"not\" predicates of the \"cfg\" attribute can have nested arguments.".to_string()]
+                        } else {
+                            vec![]
+                        },
+                    },
+                    ConvertParseTreeError::InvalidCfgPredicateValue { predicate, value_span } => Diagnostic {
+                        reason: Some(Reason::new(code(1), "Cfg predicate cannot have a value".to_string())),
+                        issue: Issue::error(
+                            source_engine,
+                            predicate.span(),
+                            format!("\"{predicate}\" predicate of the attribute \"cfg\" does not take a value.")
+                        ),
+                        hints: vec![
+                            Hint::help(source_engine, value_span.clone(), format!("Remove the value: `= {}`.", value_span.as_str())),
+                        ],
+                        help: vec![
+                            format!("The \"{predicate}\" predicate is evaluated from its nested arguments, e.g., `{predicate}(target = \"fuel\")`."),
+                        ],
+                    },
                     _ => Diagnostic {
                                 // TODO: Temporarily we use `self` here to achieve backward compatibility.
                                 //       In general, `self` must not be used. All the values for the formatting
@@ -2988,6 +3259,26 @@ impl ToDiagnostic for CompileError {
                     format!("#{} {} for {}", e, name, type_id.clone())
                 ).collect::<Vec<_>>().join("\n"))],
             },
+            MultipleApplicableItemsInScope { span, item_name, item_kind, candidates } => Diagnostic {
+                reason: Some(Reason::new(code(1), "Multiple applicable items in scope".to_string())),
+                issue: Issue::error(
+                    source_engine,
+                    span.clone(),
+                    format!("Multiple applicable items in scope. Associated {item_kind} \"{item_name}\" is provided by {} candidates.", candidates.len()),
+                ),
+                hints: candidates.iter().enumerate().map(|(index, candidate)| Hint::info(
+                    source_engine,
+                    candidate.impl_span.clone(),
+                    format!("Candidate #{index} is implemented here: {}", candidate.qualified_path(item_name)),
+                )).collect(),
+                help: match candidates.first() {
+                    Some(candidate) => vec![
+                        format!("To disambiguate the associated {item_kind}, use the fully qualified path of one of the candidates."),
+                        format!("E.g.: {}{}", candidate.qualified_path(item_name), if item_kind == "function" { "(...)" } else { "" }),
+                    ],
+                    None => vec![],
+                },
+            },
             MultipleContractsMethodsWithTheSameName { spans } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Multiple contracts methods with the same name.".into())),
                 issue: Issue::error(
@@ -3064,6 +3355,72 @@ pub enum StructFieldUsageContext {
     //       (Destructing desugars to plain struct field access.)
 }
 
+/// An item that can be accessed for a type, reported when an access to an item
+/// is ambiguous because several such items are in scope.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApplicableItemCandidate {
+    /// The type the item is implemented for. E.g., `MyType`.
+    pub type_name: String,
+    /// The path of the trait providing the item, including the trait type
+    /// arguments. E.g., `foo::Convert<u64>`.
+    pub trait_path: String,
+    /// The span of the impl block that provides the item.
+    pub impl_span: Span,
+}
+
+/// An impl providing an item for a type that differs from the type the item
+/// was looked up for only in the mutability of its references.
+/// E.g., an impl for `&mut MyType` when the item is looked up for `&MyType`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImplForOtherRefMutability {
+    /// The name of the item provided by the impl.
+    pub item_name: String,
+    /// The name of the implemented trait, or `None` for "impl self" blocks.
+    pub trait_name: Option<String>,
+    /// The type the item is implemented for. E.g., `&mut MyType`.
+    pub type_name: String,
+    /// The mutability of the outermost reference of `type_name`, if it differs
+    /// from the mutability of the outermost reference of the looked up type.
+    pub outermost_ref_mutability: Option<bool>,
+    /// The span of the impl block that provides the item.
+    pub impl_span: Span,
+}
+
+impl ImplForOtherRefMutability {
+    /// Returns the help line explaining how to access the item provided by this impl.
+    pub fn help(&self) -> String {
+        format!(
+            "{} {}",
+            match &self.trait_name {
+                Some(trait_name) => format!(
+                    "An implementation of \"{trait_name}\" exists for \"{}\".",
+                    self.type_name
+                ),
+                None => format!(
+                    "\"{}\" is implemented for \"{}\".",
+                    self.item_name, self.type_name
+                ),
+            },
+            match self.outermost_ref_mutability {
+                Some(true) => "Consider taking a mutable reference, e.g., \"&mut x\" instead of \"&x\".".to_string(),
+                Some(false) => "Consider taking an immutable reference, e.g., \"&x\" instead of \"&mut x\".".to_string(),
+                None => format!(
+                    "Consider changing the mutability of the references so that the type becomes \"{}\".",
+                    self.type_name
+                ),
+            }
+        )
+    }
+}
+
+impl ApplicableItemCandidate {
+    /// Returns the fully qualified path to the `item_name` provided by this candidate.
+    /// E.g., `<MyType as foo::Convert<u64>>::convert`.
+    pub fn qualified_path(&self, item_name: &str) -> String {
+        format!("<{} as {}>::{item_name}", self.type_name, self.trait_path)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum InvalidImplementingForType {
     SelfType,
@@ -23,6 +23,20 @@ pub enum ParseErrorKind {
     },
     #[error("Expected an inner doc comment (`//!`) to be at the top of the module file.")]
     ExpectedInnerDocCommentAtTheTopOfFile,
+    #[error("Expected an inner attribute (`#![...]`) to be at the top of the module file, before the module kind.")]
+    ExpectedInnerAttributeAtTheTopOfFile,
+    #[error("Outer doc comment (`///`) cannot document a {module_kind}.")]
+    OuterDocCommentBeforeModuleKind {
+        /// Friendly name of the module kind, e.g., "module kind (library)".
+        module_kind: &'static str,
+        /// [Span]s of the `///` markers of the doc comment lines.
+        /// Replacing them with `//!` turns the doc comment into the module documentation.
+        outer_doc_markers: Vec<Span>,
+        /// [Span] of the first line of the first item in the module, if any,
+        /// and the friendly name of the item. Moved right above that item,
+        /// the doc comment would document it.
+        first_item: Option<(Span, &'static str)>,
+    },
     #[error("Expected a comma or closing parenthesis in function arguments.")]
     ExpectedCommaOrCloseParenInFnArgs,
     #[error("Unknown assembly instruction.")]
@@ -87,6 +101,21 @@ pub enum ParseErrorKind {
     ExpectedLiteral,
     #[error("Expected a module kind (script, contract, predicate, or library).")]
     ExpectedModuleKind,
+    #[error("Expected a module kind before the items of the module. Add `library;` (or `contract;`, `script;`, `predicate;`) at the top of the file.")]
+    ExpectedModuleKindBeforeItems {
+        /// Empty [Span] at which the missing module kind should be inserted.
+        insertion_span: Span,
+    },
+    #[error("Expected `;` after the module kind.")]
+    ExpectedSemicolonAfterModuleKind {
+        /// Empty [Span] right after the module kind, at which the missing `;` should be inserted.
+        insertion_span: Span,
+    },
+    #[error("Unknown module kind. Did you mean `{suggestion}`?")]
+    MisspelledModuleKind {
+        /// The valid module kind closest to the misspelled one.
+        suggestion: &'static str,
+    },
     #[error("Expected `{}`.", kinds.iter().map(PunctKind::as_char).collect::<String>())]
     ExpectedPunct { kinds: Vec<PunctKind> },
     #[error("Expected `{}`.", word)]
@@ -121,6 +150,10 @@ pub enum ParseErrorKind {
     MissingColonInEnumTypeField,
     #[error("Expected storage key of type U256.")]
     ExpectedStorageKeyU256,
+    #[error("Expected the end of the input.")]
+    ExpectedEndOfInput,
+    #[error("The input is nested too deeply. The parser supports at most {limit} levels of nested expressions, types, and patterns.")]
+    RecursionLimitReached { limit: usize },
 }
 
 #[derive(Debug, Error, Clone, PartialEq, Eq, Hash)]
@@ -135,6 +135,10 @@ pub enum Warning {
         second_field_key_is_compiler_generated: bool,
         key: String,
     },
+    TraitConstraintMatchedBySuffixOnly {
+        required_trait_name: String,
+        implemented_trait_name: String,
+    },
 }
 
 /// Elements that can be deprecated.
@@ -294,11 +298,12 @@ impl fmt::Display for Warning {
                 write!(f, "{deprecated_element} \"{deprecated_element_name}\" is deprecated. {}", help.as_ref().unwrap_or(&"".into())),
             DuplicatedStorageKey { first_field_full_name, second_field_full_name, key, .. } =>
                 write!(f, "Two storage fields have the same storage key.\nFirst field: {first_field_full_name}\nSecond field: {second_field_full_name}\nKey: {key}"),
+            TraitConstraintMatchedBySuffixOnly { required_trait_name, implemented_trait_name } =>
+                write!(f, "Trait constraint \"{required_trait_name}\" is satisfied by an implementation of \"{implemented_trait_name}\" only because the trait names match."),
         }
     }
 }
 
-#[allow(dead_code)]
 const FUTURE_HARD_ERROR_HELP: &str =
     "In future versions of Sway this warning will become a hard error.";
 
@@ -520,6 +525,21 @@ impl ToDiagnostic for CompileWarning {
                 ]),
                 help: vec![],
             },
+            TraitConstraintMatchedBySuffixOnly { required_trait_name, implemented_trait_name } => Diagnostic {
+                reason: Some(Reason::new(code(1), "Trait constraint is matched only by the trait name".to_string())),
+                issue: Issue::warning(
+                    source_engine,
+                    self.span(),
+                    format!("Trait constraint \"{required_trait_name}\" is satisfied by an implementation of \"{implemented_trait_name}\" only because the trait names match."),
+                ),
+                hints: vec![],
+                help: vec![
+                    "The full path of at least one of the traits could not be determined, so the traits are compared only by their names.".to_string(),
+                    "Different traits with the same name declared in different modules could be mistaken for each other.".to_string(),
+                    Diagnostic::help_empty_line(),
+                    FUTURE_HARD_ERROR_HELP.to_string(),
+                ],
+            },
             // "\"{arg}\" is an unknown argument for attribute \"{attribute}\". Known arguments are: {}.", sequence_to_str(&expected_args, Enclosing::DoubleQuote, usize::MAX)
            _ => Diagnostic {
                     // TODO: Temporarily we use self here to achieve backward compatibility.
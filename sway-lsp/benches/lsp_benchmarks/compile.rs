@@ -1,4 +1,5 @@
 use codspeed_criterion_compat::{black_box, criterion_group, Criterion};
+use lsp_types::Url;
 use std::sync::Arc;
 use sway_core::Engines;
 use sway_lsp::core::session;
@@ -48,6 +49,43 @@ fn benchmarks(c: &mut Criterion) {
         })
     });
 
+    c.bench_function("compile_trait_constraints", |b| {
+        let uri = Url::from_file_path(super::trait_constraints_benchmark_dir().join("src/main.sw"))
+            .unwrap();
+        let build_plan = session::build_plan(&uri).unwrap();
+        b.iter(|| {
+            let engines = Engines::default();
+            let _ = black_box(
+                session::compile(&build_plan, &engines, None, lsp_mode.as_ref()).unwrap(),
+            );
+        })
+    });
+
+    c.bench_function("compile_chained_method_calls", |b| {
+        let uri =
+            Url::from_file_path(super::chained_method_calls_benchmark_dir().join("src/main.sw"))
+                .unwrap();
+        let build_plan = session::build_plan(&uri).unwrap();
+        b.iter(|| {
+            let engines = Engines::default();
+            let _ = black_box(
+                session::compile(&build_plan, &engines, None, lsp_mode.as_ref()).unwrap(),
+            );
+        })
+    });
+
+    c.bench_function("compile_shared_types", |b| {
+        let uri =
+            Url::from_file_path(super::shared_types_benchmark_dir().join("src/main.sw")).unwrap();
+        let build_plan = session::build_plan(&uri).unwrap();
+        b.iter(|| {
+            let engines = Engines::default();
+            let _ = black_box(
+                session::compile(&build_plan, &engines, None, lsp_mode.as_ref()).unwrap(),
+            );
+        })
+    });
+
     lsp_mode.as_mut().unwrap().optimized_build = true;
     c.bench_function("did_change_with_caching", |b| {
         let engines = Engines::default();
@@ -42,3 +42,15 @@ pub fn sway_workspace_dir() -> PathBuf {
 pub fn benchmark_dir() -> PathBuf {
     sway_workspace_dir().join("sway-lsp/tests/fixtures/benchmark")
 }
+
+pub fn trait_constraints_benchmark_dir() -> PathBuf {
+    sway_workspace_dir().join("sway-lsp/tests/fixtures/benchmark_trait_constraints")
+}
+
+pub fn chained_method_calls_benchmark_dir() -> PathBuf {
+    sway_workspace_dir().join("sway-lsp/tests/fixtures/benchmark_chained_method_calls")
+}
+
+pub fn shared_types_benchmark_dir() -> PathBuf {
+    sway_workspace_dir().join("sway-lsp/tests/fixtures/benchmark_shared_types")
+}
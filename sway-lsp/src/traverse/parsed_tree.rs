@@ -1040,6 +1040,9 @@ impl Parse for TraitConstraint {
             ),
         );
         adaptive_iter(&self.type_arguments, |type_arg| type_arg.parse(ctx));
+        adaptive_iter(&self.associated_type_bindings, |binding| {
+            binding.type_argument.parse(ctx)
+        });
     }
 }
 
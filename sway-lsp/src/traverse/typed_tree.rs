@@ -1515,6 +1515,7 @@ fn collect_trait_constraint(
     trait_constraint @ TraitConstraint {
         trait_name,
         type_arguments,
+        associated_type_bindings,
     }: &TraitConstraint,
 ) {
     collect_call_path_prefixes(ctx, &trait_name.prefixes, trait_name.callpath_type);
@@ -1542,6 +1543,9 @@ fn collect_trait_constraint(
     adaptive_iter(type_arguments, |type_arg| {
         collect_type_argument(ctx, type_arg);
     });
+    adaptive_iter(associated_type_bindings, |binding| {
+        collect_type_argument(ctx, &binding.type_argument);
+    });
 }
 
 fn collect_supertrait(ctx: &ParseContext, supertrait: &Supertrait) {
@@ -0,0 +1,357 @@
+library;
+
+pub struct Wrapper<T> {
+    value: T,
+    count: u64,
+}
+
+pub trait Step {
+    fn step(self, x: u64) -> Self;
+}
+
+pub trait Scale {
+    fn scale(self, x: u64) -> Self;
+}
+
+pub trait Shift {
+    fn shift(self, x: u64) -> Self;
+}
+
+pub trait Mix {
+    fn mix(self, x: u64) -> Self;
+}
+
+impl Step for Wrapper<u8> {
+    fn step(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Scale for Wrapper<u8> {
+    fn scale(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Shift for Wrapper<u8> {
+    fn shift(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Mix for Wrapper<u8> {
+    fn mix(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Step for Wrapper<u16> {
+    fn step(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Scale for Wrapper<u16> {
+    fn scale(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Shift for Wrapper<u16> {
+    fn shift(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Mix for Wrapper<u16> {
+    fn mix(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Step for Wrapper<u32> {
+    fn step(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Scale for Wrapper<u32> {
+    fn scale(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Shift for Wrapper<u32> {
+    fn shift(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Mix for Wrapper<u32> {
+    fn mix(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Step for Wrapper<u64> {
+    fn step(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Scale for Wrapper<u64> {
+    fn scale(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Shift for Wrapper<u64> {
+    fn shift(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Mix for Wrapper<u64> {
+    fn mix(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Step for Wrapper<bool> {
+    fn step(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Scale for Wrapper<bool> {
+    fn scale(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Shift for Wrapper<bool> {
+    fn shift(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl Mix for Wrapper<bool> {
+    fn mix(self, x: u64) -> Self {
+        Wrapper {
+            value: self.value,
+            count: x,
+        }
+    }
+}
+
+impl<T> Wrapper<T> {
+    pub fn new(value: T) -> Self {
+        Wrapper { value, count: 0 }
+    }
+
+    pub fn count(self) -> u64 {
+        self.count
+    }
+}
+
+pub fn chain_u8() -> u64 {
+    Wrapper::new(1u8)
+        .step(0)
+        .scale(1)
+        .shift(2)
+        .mix(3)
+        .step(4)
+        .scale(5)
+        .shift(6)
+        .mix(7)
+        .step(8)
+        .scale(9)
+        .shift(10)
+        .mix(11)
+        .step(12)
+        .scale(13)
+        .shift(14)
+        .mix(15)
+        .step(16)
+        .scale(17)
+        .shift(18)
+        .mix(19)
+        .step(20)
+        .scale(21)
+        .shift(22)
+        .mix(23)
+        .count()
+}
+
+pub fn chain_u16() -> u64 {
+    Wrapper::new(1u16)
+        .step(0)
+        .scale(1)
+        .shift(2)
+        .mix(3)
+        .step(4)
+        .scale(5)
+        .shift(6)
+        .mix(7)
+        .step(8)
+        .scale(9)
+        .shift(10)
+        .mix(11)
+        .step(12)
+        .scale(13)
+        .shift(14)
+        .mix(15)
+        .step(16)
+        .scale(17)
+        .shift(18)
+        .mix(19)
+        .step(20)
+        .scale(21)
+        .shift(22)
+        .mix(23)
+        .count()
+}
+
+pub fn chain_u32() -> u64 {
+    Wrapper::new(1u32)
+        .step(0)
+        .scale(1)
+        .shift(2)
+        .mix(3)
+        .step(4)
+        .scale(5)
+        .shift(6)
+        .mix(7)
+        .step(8)
+        .scale(9)
+        .shift(10)
+        .mix(11)
+        .step(12)
+        .scale(13)
+        .shift(14)
+        .mix(15)
+        .step(16)
+        .scale(17)
+        .shift(18)
+        .mix(19)
+        .step(20)
+        .scale(21)
+        .shift(22)
+        .mix(23)
+        .count()
+}
+
+pub fn chain_u64() -> u64 {
+    Wrapper::new(1u64)
+        .step(0)
+        .scale(1)
+        .shift(2)
+        .mix(3)
+        .step(4)
+        .scale(5)
+        .shift(6)
+        .mix(7)
+        .step(8)
+        .scale(9)
+        .shift(10)
+        .mix(11)
+        .step(12)
+        .scale(13)
+        .shift(14)
+        .mix(15)
+        .step(16)
+        .scale(17)
+        .shift(18)
+        .mix(19)
+        .step(20)
+        .scale(21)
+        .shift(22)
+        .mix(23)
+        .count()
+}
+
+pub fn chain_bool() -> u64 {
+    Wrapper::new(true)
+        .step(0)
+        .scale(1)
+        .shift(2)
+        .mix(3)
+        .step(4)
+        .scale(5)
+        .shift(6)
+        .mix(7)
+        .step(8)
+        .scale(9)
+        .shift(10)
+        .mix(11)
+        .step(12)
+        .scale(13)
+        .shift(14)
+        .mix(15)
+        .step(16)
+        .scale(17)
+        .shift(18)
+        .mix(19)
+        .step(20)
+        .scale(21)
+        .shift(22)
+        .mix(23)
+        .count()
+}
@@ -0,0 +1,19 @@
+library;
+
+pub mod types;
+pub mod member_0;
+pub mod member_1;
+pub mod member_2;
+pub mod member_3;
+pub mod member_4;
+pub mod member_5;
+pub mod member_6;
+pub mod member_7;
+pub mod member_8;
+pub mod member_9;
+pub mod member_10;
+pub mod member_11;
+pub mod member_12;
+pub mod member_13;
+pub mod member_14;
+pub mod member_15;
@@ -0,0 +1,64 @@
+library;
+
+use ::types::{Account, Asset, Balance, Order, Position, Price};
+use ::types::{Id, Weight, Flags, Version};
+
+pub fn use_account(value: u64) -> u64 {
+    let x = Account::new(value);
+    let mut sum = 0;
+    sum = __add(sum, x.id());
+    sum = __add(sum, x.weight());
+    sum = __add(sum, x.flags());
+    sum = __add(sum, x.version());
+    sum
+}
+
+pub fn use_asset(value: u64) -> u64 {
+    let x = Asset::new(value);
+    let mut sum = 0;
+    sum = __add(sum, x.id());
+    sum = __add(sum, x.weight());
+    sum = __add(sum, x.flags());
+    sum = __add(sum, x.version());
+    sum
+}
+
+pub fn use_balance(value: u64) -> u64 {
+    let x = Balance::new(value);
+    let mut sum = 0;
+    sum = __add(sum, x.id());
+    sum = __add(sum, x.weight());
+    sum = __add(sum, x.flags());
+    sum = __add(sum, x.version());
+    sum
+}
+
+pub fn use_order(value: u64) -> u64 {
+    let x = Order::new(value);
+    let mut sum = 0;
+    sum = __add(sum, x.id());
+    sum = __add(sum, x.weight());
+    sum = __add(sum, x.flags());
+    sum = __add(sum, x.version());
+    sum
+}
+
+pub fn use_position(value: u64) -> u64 {
+    let x = Position::new(value);
+    let mut sum = 0;
+    sum = __add(sum, x.id());
+    sum = __add(sum, x.weight());
+    sum = __add(sum, x.flags());
+    sum = __add(sum, x.version());
+    sum
+}
+
+pub fn use_price(value: u64) -> u64 {
+    let x = Price::new(value);
+    let mut sum = 0;
+    sum = __add(sum, x.id());
+    sum = __add(sum, x.weight());
+    sum = __add(sum, x.flags());
+    sum = __add(sum, x.version());
+    sum
+}
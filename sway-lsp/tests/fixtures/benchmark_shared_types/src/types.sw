@@ -0,0 +1,221 @@
+library;
+
+pub trait Id {
+    fn id(self) -> u64;
+}
+
+pub trait Weight {
+    fn weight(self) -> u64;
+}
+
+pub trait Flags {
+    fn flags(self) -> u64;
+}
+
+pub trait Version {
+    fn version(self) -> u64;
+}
+
+pub struct Account {
+    pub value: u64,
+}
+
+impl Account {
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl Id for Account {
+    fn id(self) -> u64 {
+        self.value
+    }
+}
+
+impl Weight for Account {
+    fn weight(self) -> u64 {
+        self.value
+    }
+}
+
+impl Flags for Account {
+    fn flags(self) -> u64 {
+        self.value
+    }
+}
+
+impl Version for Account {
+    fn version(self) -> u64 {
+        self.value
+    }
+}
+
+pub struct Asset {
+    pub value: u64,
+}
+
+impl Asset {
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl Id for Asset {
+    fn id(self) -> u64 {
+        self.value
+    }
+}
+
+impl Weight for Asset {
+    fn weight(self) -> u64 {
+        self.value
+    }
+}
+
+impl Flags for Asset {
+    fn flags(self) -> u64 {
+        self.value
+    }
+}
+
+impl Version for Asset {
+    fn version(self) -> u64 {
+        self.value
+    }
+}
+
+pub struct Balance {
+    pub value: u64,
+}
+
+impl Balance {
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl Id for Balance {
+    fn id(self) -> u64 {
+        self.value
+    }
+}
+
+impl Weight for Balance {
+    fn weight(self) -> u64 {
+        self.value
+    }
+}
+
+impl Flags for Balance {
+    fn flags(self) -> u64 {
+        self.value
+    }
+}
+
+impl Version for Balance {
+    fn version(self) -> u64 {
+        self.value
+    }
+}
+
+pub struct Order {
+    pub value: u64,
+}
+
+impl Order {
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl Id for Order {
+    fn id(self) -> u64 {
+        self.value
+    }
+}
+
+impl Weight for Order {
+    fn weight(self) -> u64 {
+        self.value
+    }
+}
+
+impl Flags for Order {
+    fn flags(self) -> u64 {
+        self.value
+    }
+}
+
+impl Version for Order {
+    fn version(self) -> u64 {
+        self.value
+    }
+}
+
+pub struct Position {
+    pub value: u64,
+}
+
+impl Position {
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl Id for Position {
+    fn id(self) -> u64 {
+        self.value
+    }
+}
+
+impl Weight for Position {
+    fn weight(self) -> u64 {
+        self.value
+    }
+}
+
+impl Flags for Position {
+    fn flags(self) -> u64 {
+        self.value
+    }
+}
+
+impl Version for Position {
+    fn version(self) -> u64 {
+        self.value
+    }
+}
+
+pub struct Price {
+    pub value: u64,
+}
+
+impl Price {
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl Id for Price {
+    fn id(self) -> u64 {
+        self.value
+    }
+}
+
+impl Weight for Price {
+    fn weight(self) -> u64 {
+        self.value
+    }
+}
+
+impl Flags for Price {
+    fn flags(self) -> u64 {
+        self.value
+    }
+}
+
+impl Version for Price {
+    fn version(self) -> u64 {
+        self.value
+    }
+}
@@ -0,0 +1,399 @@
+library;
+
+pub trait T0 {
+    fn t0(self) -> u64;
+}
+
+pub trait T1 {
+    fn t1(self) -> u64;
+}
+
+pub trait T2 {
+    fn t2(self) -> u64;
+}
+
+pub trait T3 {
+    fn t3(self) -> u64;
+}
+
+pub trait T4 {
+    fn t4(self) -> u64;
+}
+
+pub trait T5 {
+    fn t5(self) -> u64;
+}
+
+pub trait T6 {
+    fn t6(self) -> u64;
+}
+
+pub trait T7 {
+    fn t7(self) -> u64;
+}
+
+pub trait T8 {
+    fn t8(self) -> u64;
+}
+
+pub trait T9 {
+    fn t9(self) -> u64;
+}
+
+pub trait T10 {
+    fn t10(self) -> u64;
+}
+
+pub trait T11 {
+    fn t11(self) -> u64;
+}
+
+pub struct S0 {
+    x: u64,
+}
+
+impl T0 for S0 {
+    fn t0(self) -> u64 {
+        self.x
+    }
+}
+
+impl T1 for S0 {
+    fn t1(self) -> u64 {
+        self.x
+    }
+}
+
+impl T2 for S0 {
+    fn t2(self) -> u64 {
+        self.x
+    }
+}
+
+impl T3 for S0 {
+    fn t3(self) -> u64 {
+        self.x
+    }
+}
+
+impl T4 for S0 {
+    fn t4(self) -> u64 {
+        self.x
+    }
+}
+
+impl T5 for S0 {
+    fn t5(self) -> u64 {
+        self.x
+    }
+}
+
+impl T6 for S0 {
+    fn t6(self) -> u64 {
+        self.x
+    }
+}
+
+impl T7 for S0 {
+    fn t7(self) -> u64 {
+        self.x
+    }
+}
+
+impl T8 for S0 {
+    fn t8(self) -> u64 {
+        self.x
+    }
+}
+
+impl T9 for S0 {
+    fn t9(self) -> u64 {
+        self.x
+    }
+}
+
+impl T10 for S0 {
+    fn t10(self) -> u64 {
+        self.x
+    }
+}
+
+impl T11 for S0 {
+    fn t11(self) -> u64 {
+        self.x
+    }
+}
+
+pub struct S1 {
+    x: u64,
+}
+
+impl T0 for S1 {
+    fn t0(self) -> u64 {
+        self.x
+    }
+}
+
+impl T1 for S1 {
+    fn t1(self) -> u64 {
+        self.x
+    }
+}
+
+impl T2 for S1 {
+    fn t2(self) -> u64 {
+        self.x
+    }
+}
+
+impl T3 for S1 {
+    fn t3(self) -> u64 {
+        self.x
+    }
+}
+
+impl T4 for S1 {
+    fn t4(self) -> u64 {
+        self.x
+    }
+}
+
+impl T5 for S1 {
+    fn t5(self) -> u64 {
+        self.x
+    }
+}
+
+impl T6 for S1 {
+    fn t6(self) -> u64 {
+        self.x
+    }
+}
+
+impl T7 for S1 {
+    fn t7(self) -> u64 {
+        self.x
+    }
+}
+
+impl T8 for S1 {
+    fn t8(self) -> u64 {
+        self.x
+    }
+}
+
+impl T9 for S1 {
+    fn t9(self) -> u64 {
+        self.x
+    }
+}
+
+impl T10 for S1 {
+    fn t10(self) -> u64 {
+        self.x
+    }
+}
+
+impl T11 for S1 {
+    fn t11(self) -> u64 {
+        self.x
+    }
+}
+
+pub struct S2 {
+    x: u64,
+}
+
+impl T0 for S2 {
+    fn t0(self) -> u64 {
+        self.x
+    }
+}
+
+impl T1 for S2 {
+    fn t1(self) -> u64 {
+        self.x
+    }
+}
+
+impl T2 for S2 {
+    fn t2(self) -> u64 {
+        self.x
+    }
+}
+
+impl T3 for S2 {
+    fn t3(self) -> u64 {
+        self.x
+    }
+}
+
+impl T4 for S2 {
+    fn t4(self) -> u64 {
+        self.x
+    }
+}
+
+impl T5 for S2 {
+    fn t5(self) -> u64 {
+        self.x
+    }
+}
+
+impl T6 for S2 {
+    fn t6(self) -> u64 {
+        self.x
+    }
+}
+
+impl T7 for S2 {
+    fn t7(self) -> u64 {
+        self.x
+    }
+}
+
+impl T8 for S2 {
+    fn t8(self) -> u64 {
+        self.x
+    }
+}
+
+impl T9 for S2 {
+    fn t9(self) -> u64 {
+        self.x
+    }
+}
+
+impl T10 for S2 {
+    fn t10(self) -> u64 {
+        self.x
+    }
+}
+
+impl T11 for S2 {
+    fn t11(self) -> u64 {
+        self.x
+    }
+}
+
+pub struct S3 {
+    x: u64,
+}
+
+impl T0 for S3 {
+    fn t0(self) -> u64 {
+        self.x
+    }
+}
+
+impl T1 for S3 {
+    fn t1(self) -> u64 {
+        self.x
+    }
+}
+
+impl T2 for S3 {
+    fn t2(self) -> u64 {
+        self.x
+    }
+}
+
+impl T3 for S3 {
+    fn t3(self) -> u64 {
+        self.x
+    }
+}
+
+impl T4 for S3 {
+    fn t4(self) -> u64 {
+        self.x
+    }
+}
+
+impl T5 for S3 {
+    fn t5(self) -> u64 {
+        self.x
+    }
+}
+
+impl T6 for S3 {
+    fn t6(self) -> u64 {
+        self.x
+    }
+}
+
+impl T7 for S3 {
+    fn t7(self) -> u64 {
+        self.x
+    }
+}
+
+impl T8 for S3 {
+    fn t8(self) -> u64 {
+        self.x
+    }
+}
+
+impl T9 for S3 {
+    fn t9(self) -> u64 {
+        self.x
+    }
+}
+
+impl T10 for S3 {
+    fn t10(self) -> u64 {
+        self.x
+    }
+}
+
+impl T11 for S3 {
+    fn t11(self) -> u64 {
+        self.x
+    }
+}
+
+pub fn all<A>(a: A) -> u64
+where
+    A: T0 + T1 + T2 + T3 + T4 + T5 + T6 + T7 + T8 + T9 + T10 + T11,
+{
+    let _ = a.t0();
+    let _ = a.t1();
+    let _ = a.t2();
+    let _ = a.t3();
+    let _ = a.t4();
+    let _ = a.t5();
+    let _ = a.t6();
+    let _ = a.t7();
+    let _ = a.t8();
+    let _ = a.t9();
+    let _ = a.t10();
+    a.t11()
+}
+
+pub fn pair<A, B>(a: A, b: B) -> u64
+where
+    A: T0 + T1 + T2 + T3 + T4 + T5 + T6 + T7 + T8 + T9 + T10 + T11,
+    B: T0 + T1 + T2 + T3 + T4 + T5 + T6 + T7 + T8 + T9 + T10 + T11,
+{
+    let _ = all(a);
+    all(b)
+}
+
+pub fn run() {
+    let _ = pair(S0 { x: 0 }, S0 { x: 0 });
+    let _ = pair(S0 { x: 0 }, S1 { x: 1 });
+    let _ = pair(S0 { x: 0 }, S2 { x: 2 });
+    let _ = pair(S0 { x: 0 }, S3 { x: 3 });
+    let _ = pair(S1 { x: 1 }, S0 { x: 0 });
+    let _ = pair(S1 { x: 1 }, S1 { x: 1 });
+    let _ = pair(S1 { x: 1 }, S2 { x: 2 });
+    let _ = pair(S1 { x: 1 }, S3 { x: 3 });
+    let _ = pair(S2 { x: 2 }, S0 { x: 0 });
+    let _ = pair(S2 { x: 2 }, S1 { x: 1 });
+    let _ = pair(S2 { x: 2 }, S2 { x: 2 });
+    let _ = pair(S2 { x: 2 }, S3 { x: 3 });
+    let _ = pair(S3 { x: 3 }, S0 { x: 0 });
+    let _ = pair(S3 { x: 3 }, S1 { x: 1 });
+    let _ = pair(S3 { x: 3 }, S2 { x: 2 });
+    let _ = pair(S3 { x: 3 }, S3 { x: 3 });
+}
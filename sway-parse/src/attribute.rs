@@ -1,12 +1,14 @@
+use crate::parser::ParseRecoveryStrategies;
 use crate::priv_prelude::{Peek, Peeker};
 use crate::{Parse, ParseBracket, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
 use sway_ast::attribute::{Annotated, Attribute, AttributeArg, AttributeDecl, AttributeHashKind};
 use sway_ast::brackets::Parens;
-use sway_ast::keywords::{EqToken, HashBangToken, HashToken, StorageToken};
+use sway_ast::keywords::{DoubleColonToken, EqToken, HashBangToken, HashToken, StorageToken};
 use sway_ast::literal::LitBool;
 use sway_ast::token::{DocComment, DocStyle};
 use sway_ast::Literal;
+use sway_error::handler::ErrorEmitted;
 use sway_error::parser_error::ParseErrorKind;
 use sway_types::{Ident, Span, Spanned};
 
@@ -61,50 +63,9 @@ impl Parse for Vec<AttributeDecl> {
 
 impl<T: Parse> Parse for Annotated<T> {
     fn parse(parser: &mut Parser) -> ParseResult<Self> {
-        let attributes = parser.parse::<Vec<AttributeDecl>>()?;
-
-        if parser.check_empty().is_some() {
-            // Provide a dedicated error message for the case when we have
-            // inner doc comments (`//!`) at the end of the module (because
-            // there are no items after the comments).
-            let error = if attributes
-                .iter()
-                .all(|attr| attr.is_inner() && attr.is_doc_comment())
-            {
-                // Show the error on the complete doc comment.
-                let first_doc_line = attributes.first().expect(
-                    "parsing `Annotated` guarantees that `attributes` have at least one element",
-                );
-                let last_doc_line = attributes.last().expect(
-                    "parsing `Annotated` guarantees that `attributes` have at least one element",
-                );
-                let span = Span::join(first_doc_line.span(), &last_doc_line.span().start_span());
-                parser.emit_error_with_span(
-                    ParseErrorKind::ExpectedInnerDocCommentAtTheTopOfFile,
-                    span,
-                )
-            } else {
-                let is_only_documented = attributes.iter().all(|attr| attr.is_doc_comment());
-                parser.emit_error(ParseErrorKind::ExpectedAnAnnotatedElement { is_only_documented })
-            };
-            Err(error)
-        } else {
-            // Parse the `T` value.
-            let value = match parser.parse_with_recovery() {
-                Ok(value) => value,
-                Err(r) => {
-                    let (spans, error) =
-                        r.recover_at_next_line_with_fallback_error(ParseErrorKind::InvalidItem);
-                    if let Some(error) = T::error(spans, error) {
-                        error
-                    } else {
-                        Err(error)?
-                    }
-                }
-            };
-
-            Ok(Annotated { attributes, value })
-        }
+        parse_annotated(parser, |r| {
+            r.recover_at_next_line_with_fallback_error(ParseErrorKind::InvalidItem)
+        })
     }
 
     fn error(
@@ -121,6 +82,53 @@ impl<T: Parse> Parse for Annotated<T> {
     }
 }
 
+/// Parses an [Annotated] `T`, using `recover` to recover from a malformed `T`.
+pub(crate) fn parse_annotated<T: Parse>(
+    parser: &mut Parser,
+    recover: impl FnOnce(&ParseRecoveryStrategies) -> (Box<[Span]>, ErrorEmitted),
+) -> ParseResult<Annotated<T>> {
+    let attributes = parser.parse::<Vec<AttributeDecl>>()?;
+
+    if parser.check_empty().is_some() {
+        // Provide a dedicated error message for the case when we have
+        // inner doc comments (`//!`) at the end of the module (because
+        // there are no items after the comments).
+        let error = if attributes
+            .iter()
+            .all(|attr| attr.is_inner() && attr.is_doc_comment())
+        {
+            // Show the error on the complete doc comment.
+            let first_doc_line = attributes.first().expect(
+                "parsing `Annotated` guarantees that `attributes` have at least one element",
+            );
+            let last_doc_line = attributes.last().expect(
+                "parsing `Annotated` guarantees that `attributes` have at least one element",
+            );
+            let span = Span::join(first_doc_line.span(), &last_doc_line.span().start_span());
+            parser.emit_error_with_span(ParseErrorKind::ExpectedInnerDocCommentAtTheTopOfFile, span)
+        } else {
+            let is_only_documented = attributes.iter().all(|attr| attr.is_doc_comment());
+            parser.emit_error(ParseErrorKind::ExpectedAnAnnotatedElement { is_only_documented })
+        };
+        Err(error)
+    } else {
+        // Parse the `T` value.
+        let value = match parser.parse_with_recovery() {
+            Ok(value) => value,
+            Err(r) => {
+                let (spans, error) = recover(&r);
+                if let Some(error) = T::error(spans, error) {
+                    error
+                } else {
+                    Err(error)?
+                }
+            }
+        };
+
+        Ok(Annotated { attributes, value })
+    }
+}
+
 impl Parse for AttributeDecl {
     fn parse(parser: &mut Parser) -> ParseResult<Self> {
         Ok(AttributeDecl {
@@ -162,22 +170,42 @@ impl Parse for AttributeArg {
                 Ok(AttributeArg {
                     name,
                     value: Some(value),
+                    args: None,
                 })
             }
-            None => Ok(AttributeArg { name, value: None }),
+            None => Ok(AttributeArg {
+                name,
+                value: None,
+                args: Parens::try_parse(parser)?,
+            }),
         }
     }
 }
 
 impl Parse for Attribute {
     fn parse(parser: &mut Parser) -> ParseResult<Self> {
-        let name = if let Some(storage) = parser.take::<StorageToken>() {
-            Ident::from(storage)
-        } else {
-            parser.parse()?
-        };
+        // The attribute name can be namespaced, e.g., `#[fuels::test]`.
+        let mut namespace = vec![];
+        let mut name = parse_attribute_name(parser)?;
+        while let Some(double_colon_token) = parser.take::<DoubleColonToken>() {
+            namespace.push((name, double_colon_token));
+            name = parse_attribute_name(parser)?;
+        }
         let args = Parens::try_parse(parser)?;
-        Ok(Attribute { name, args })
+        Ok(Attribute {
+            namespace,
+            name,
+            args,
+        })
+    }
+}
+
+/// Parses a single segment of an attribute name, accepting also the `storage` keyword.
+fn parse_attribute_name(parser: &mut Parser) -> ParseResult<Ident> {
+    if let Some(storage) = parser.take::<StorageToken>() {
+        Ok(Ident::from(storage))
+    } else {
+        parser.parse()
     }
 }
 
@@ -224,6 +252,7 @@ mod tests {
                 inner: Punctuated(
                   value_separator_pairs: [],
                   final_value_opt: Some(Attribute(
+                    namespace: [],
                     name: BaseIdent(
                       name_override_opt: Some("doc-comment"),
                       span: Span(
@@ -239,7 +268,7 @@ mod tests {
                         value_separator_pairs: [],
                         final_value_opt: Some(AttributeArg(
                           name: BaseIdent(
-                            name_override_opt: None,
+                            name_override_opt: Some("text"),
                             span: Span(
                               src: "\n            // I will be ignored.\n            //! This is a misplaced inner doc comment.\n            /// This is an outer doc comment.\n            #[storage(read)]\n            fn main() {\n                ()\n            }\n        ",
                               start: 50,
@@ -248,7 +277,16 @@ mod tests {
                             ),
                             is_raw_ident: false,
                           ),
-                          value: None,
+                          value: Some(String(LitString(
+                            span: Span(
+                              src: "\n            // I will be ignored.\n            //! This is a misplaced inner doc comment.\n            /// This is an outer doc comment.\n            #[storage(read)]\n            fn main() {\n                ()\n            }\n        ",
+                              start: 50,
+                              end: 89,
+                              source_id: None,
+                            ),
+                            parsed: " This is a misplaced inner doc comment.",
+                          ))),
+                          args: None,
                         )),
                       ),
                       span: Span(
@@ -281,6 +319,7 @@ mod tests {
                 inner: Punctuated(
                   value_separator_pairs: [],
                   final_value_opt: Some(Attribute(
+                    namespace: [],
                     name: BaseIdent(
                       name_override_opt: Some("doc-comment"),
                       span: Span(
@@ -296,7 +335,7 @@ mod tests {
                         value_separator_pairs: [],
                         final_value_opt: Some(AttributeArg(
                           name: BaseIdent(
-                            name_override_opt: None,
+                            name_override_opt: Some("text"),
                             span: Span(
                               src: "\n            // I will be ignored.\n            //! This is a misplaced inner doc comment.\n            /// This is an outer doc comment.\n            #[storage(read)]\n            fn main() {\n                ()\n            }\n        ",
                               start: 105,
@@ -305,7 +344,16 @@ mod tests {
                             ),
                             is_raw_ident: false,
                           ),
-                          value: None,
+                          value: Some(String(LitString(
+                            span: Span(
+                              src: "\n            // I will be ignored.\n            //! This is a misplaced inner doc comment.\n            /// This is an outer doc comment.\n            #[storage(read)]\n            fn main() {\n                ()\n            }\n        ",
+                              start: 105,
+                              end: 135,
+                              source_id: None,
+                            ),
+                            parsed: " This is an outer doc comment.",
+                          ))),
+                          args: None,
                         )),
                       ),
                       span: Span(
@@ -338,6 +386,7 @@ mod tests {
                 inner: Punctuated(
                   value_separator_pairs: [],
                   final_value_opt: Some(Attribute(
+                    namespace: [],
                     name: BaseIdent(
                       name_override_opt: None,
                       span: Span(
@@ -363,6 +412,7 @@ mod tests {
                             is_raw_ident: false,
                           ),
                           value: None,
+                          args: None,
                         )),
                       ),
                       span: Span(
@@ -457,6 +507,7 @@ mod tests {
             name(arg1, arg2 = "value", arg3)
         "#,), @r#"
         Attribute(
+          namespace: [],
           name: BaseIdent(
             name_override_opt: None,
             span: Span(
@@ -482,6 +533,7 @@ mod tests {
                     is_raw_ident: false,
                   ),
                   value: None,
+                  args: None,
                 ), CommaToken(
                   span: Span(
                     src: "\n            name(arg1, arg2 = \"value\", arg3)\n        ",
@@ -510,6 +562,7 @@ mod tests {
                     ),
                     parsed: "value",
                   ))),
+                  args: None,
                 ), CommaToken(
                   span: Span(
                     src: "\n            name(arg1, arg2 = \"value\", arg3)\n        ",
@@ -531,6 +584,7 @@ mod tests {
                   is_raw_ident: false,
                 ),
                 value: None,
+                args: None,
               )),
             ),
             span: Span(
@@ -543,4 +597,357 @@ mod tests {
         )
         "#);
     }
+
+    #[test]
+    fn parse_attribute_arg_values() {
+        assert_ron_snapshot!(parse::<Attribute>(r#"
+            name(text = "a", count = 42, flag = true,)
+        "#,), @r#"
+        Attribute(
+          namespace: [],
+          name: BaseIdent(
+            name_override_opt: None,
+            span: Span(
+              src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+              start: 13,
+              end: 17,
+              source_id: None,
+            ),
+            is_raw_ident: false,
+          ),
+          args: Some(Parens(
+            inner: Punctuated(
+              value_separator_pairs: [
+                (AttributeArg(
+                  name: BaseIdent(
+                    name_override_opt: None,
+                    span: Span(
+                      src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                      start: 18,
+                      end: 22,
+                      source_id: None,
+                    ),
+                    is_raw_ident: false,
+                  ),
+                  value: Some(String(LitString(
+                    span: Span(
+                      src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                      start: 25,
+                      end: 28,
+                      source_id: None,
+                    ),
+                    parsed: "a",
+                  ))),
+                  args: None,
+                ), CommaToken(
+                  span: Span(
+                    src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                    start: 28,
+                    end: 29,
+                    source_id: None,
+                  ),
+                )),
+                (AttributeArg(
+                  name: BaseIdent(
+                    name_override_opt: None,
+                    span: Span(
+                      src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                      start: 30,
+                      end: 35,
+                      source_id: None,
+                    ),
+                    is_raw_ident: false,
+                  ),
+                  value: Some(Int(LitInt(
+                    span: Span(
+                      src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                      start: 38,
+                      end: 40,
+                      source_id: None,
+                    ),
+                    parsed: [
+                      42,
+                    ],
+                    ty_opt: None,
+                    is_generated_b256: false,
+                  ))),
+                  args: None,
+                ), CommaToken(
+                  span: Span(
+                    src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                    start: 40,
+                    end: 41,
+                    source_id: None,
+                  ),
+                )),
+                (AttributeArg(
+                  name: BaseIdent(
+                    name_override_opt: None,
+                    span: Span(
+                      src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                      start: 42,
+                      end: 46,
+                      source_id: None,
+                    ),
+                    is_raw_ident: false,
+                  ),
+                  value: Some(Bool(LitBool(
+                    span: Span(
+                      src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                      start: 49,
+                      end: 53,
+                      source_id: None,
+                    ),
+                    kind: True,
+                  ))),
+                  args: None,
+                ), CommaToken(
+                  span: Span(
+                    src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+                    start: 53,
+                    end: 54,
+                    source_id: None,
+                  ),
+                )),
+              ],
+              final_value_opt: None,
+            ),
+            span: Span(
+              src: "\n            name(text = \"a\", count = 42, flag = true,)\n        ",
+              start: 17,
+              end: 55,
+              source_id: None,
+            ),
+          )),
+        )
+        "#);
+    }
+
+    #[test]
+    fn parse_attribute_nested_args() {
+        assert_ron_snapshot!(parse::<Attribute>(r#"
+            cfg(all(target = "fuel", not(any())))
+        "#,), @r#"
+        Attribute(
+          namespace: [],
+          name: BaseIdent(
+            name_override_opt: None,
+            span: Span(
+              src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+              start: 13,
+              end: 16,
+              source_id: None,
+            ),
+            is_raw_ident: false,
+          ),
+          args: Some(Parens(
+            inner: Punctuated(
+              value_separator_pairs: [],
+              final_value_opt: Some(AttributeArg(
+                name: BaseIdent(
+                  name_override_opt: None,
+                  span: Span(
+                    src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                    start: 17,
+                    end: 20,
+                    source_id: None,
+                  ),
+                  is_raw_ident: false,
+                ),
+                value: None,
+                args: Some(Parens(
+                  inner: Punctuated(
+                    value_separator_pairs: [
+                      (AttributeArg(
+                        name: BaseIdent(
+                          name_override_opt: None,
+                          span: Span(
+                            src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                            start: 21,
+                            end: 27,
+                            source_id: None,
+                          ),
+                          is_raw_ident: false,
+                        ),
+                        value: Some(String(LitString(
+                          span: Span(
+                            src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                            start: 30,
+                            end: 36,
+                            source_id: None,
+                          ),
+                          parsed: "fuel",
+                        ))),
+                        args: None,
+                      ), CommaToken(
+                        span: Span(
+                          src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                          start: 36,
+                          end: 37,
+                          source_id: None,
+                        ),
+                      )),
+                    ],
+                    final_value_opt: Some(AttributeArg(
+                      name: BaseIdent(
+                        name_override_opt: None,
+                        span: Span(
+                          src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                          start: 38,
+                          end: 41,
+                          source_id: None,
+                        ),
+                        is_raw_ident: false,
+                      ),
+                      value: None,
+                      args: Some(Parens(
+                        inner: Punctuated(
+                          value_separator_pairs: [],
+                          final_value_opt: Some(AttributeArg(
+                            name: BaseIdent(
+                              name_override_opt: None,
+                              span: Span(
+                                src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                                start: 42,
+                                end: 45,
+                                source_id: None,
+                              ),
+                              is_raw_ident: false,
+                            ),
+                            value: None,
+                            args: Some(Parens(
+                              inner: Punctuated(
+                                value_separator_pairs: [],
+                                final_value_opt: None,
+                              ),
+                              span: Span(
+                                src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                                start: 45,
+                                end: 47,
+                                source_id: None,
+                              ),
+                            )),
+                          )),
+                        ),
+                        span: Span(
+                          src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                          start: 41,
+                          end: 48,
+                          source_id: None,
+                        ),
+                      )),
+                    )),
+                  ),
+                  span: Span(
+                    src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+                    start: 20,
+                    end: 49,
+                    source_id: None,
+                  ),
+                )),
+              )),
+            ),
+            span: Span(
+              src: "\n            cfg(all(target = \"fuel\", not(any())))\n        ",
+              start: 16,
+              end: 50,
+              source_id: None,
+            ),
+          )),
+        )
+        "#);
+    }
+
+    #[test]
+    fn parse_namespaced_attribute() {
+        assert_ron_snapshot!(parse::<Attribute>(r#"
+            fuels::test(arg)
+        "#,), @r#"
+        Attribute(
+          namespace: [
+            (BaseIdent(
+              name_override_opt: None,
+              span: Span(
+                src: "\n            fuels::test(arg)\n        ",
+                start: 13,
+                end: 18,
+                source_id: None,
+              ),
+              is_raw_ident: false,
+            ), DoubleColonToken(
+              span: Span(
+                src: "\n            fuels::test(arg)\n        ",
+                start: 18,
+                end: 20,
+                source_id: None,
+              ),
+            )),
+          ],
+          name: BaseIdent(
+            name_override_opt: None,
+            span: Span(
+              src: "\n            fuels::test(arg)\n        ",
+              start: 20,
+              end: 24,
+              source_id: None,
+            ),
+            is_raw_ident: false,
+          ),
+          args: Some(Parens(
+            inner: Punctuated(
+              value_separator_pairs: [],
+              final_value_opt: Some(AttributeArg(
+                name: BaseIdent(
+                  name_override_opt: None,
+                  span: Span(
+                    src: "\n            fuels::test(arg)\n        ",
+                    start: 25,
+                    end: 28,
+                    source_id: None,
+                  ),
+                  is_raw_ident: false,
+                ),
+                value: None,
+                args: None,
+              )),
+            ),
+            span: Span(
+              src: "\n            fuels::test(arg)\n        ",
+              start: 24,
+              end: 29,
+              source_id: None,
+            ),
+          )),
+        )
+        "#);
+    }
+
+    #[test]
+    fn namespaced_attribute_path() {
+        let attribute = parse::<Attribute>("tool::lint::off");
+        assert!(attribute.is_namespaced());
+        assert_eq!(attribute.name.as_str(), "off");
+        assert_eq!(attribute.path_str(), "tool::lint::off");
+        assert_eq!(attribute.path_span().as_str(), "tool::lint::off");
+
+        let attribute = parse::<Attribute>("cfg(test)");
+        assert!(!attribute.is_namespaced());
+        assert!(attribute.is_cfg());
+        assert_eq!(attribute.path_str(), "cfg");
+
+        // Namespaced attributes are never built-in ones.
+        assert!(!parse::<Attribute>("tool::cfg").is_cfg());
+    }
+
+    #[test]
+    fn parse_attribute_arg_value_spans() {
+        let attribute = parse::<Attribute>(r#"name(text = "a", count = 42, flag = true,)"#);
+        let args = attribute.args.expect("attribute has args");
+        let spans = args
+            .get()
+            .into_iter()
+            .map(|arg| arg.span().as_str().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![r#"text = "a""#, "count = 42", "flag = true"]);
+    }
 }
@@ -87,7 +87,7 @@ impl Parse for IfCondition {
 
 impl Parse for Expr {
     fn parse(parser: &mut Parser) -> ParseResult<Expr> {
-        parse_reassignment(parser, ParseExprCtx::default())
+        parser.parse_nested(|parser| parse_reassignment(parser, ParseExprCtx::default()))
     }
 }
 
@@ -519,14 +519,20 @@ fn parse_unary_op(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
             expr,
         });
     }
-    if let Some((star_token, expr)) = parse_op_rhs(parser, ctx, parse_unary_op)? {
+    if let Some((star_token, expr)) = parse_op_rhs(parser, ctx, parse_nested_unary_op)? {
         return Ok(Expr::Deref { star_token, expr });
     }
-    if let Some((bang_token, expr)) = parse_op_rhs(parser, ctx, parse_unary_op)? {
+    if let Some((bang_token, expr)) = parse_op_rhs(parser, ctx, parse_nested_unary_op)? {
         return Ok(Expr::Not { bang_token, expr });
     }
     return parse_projection(parser, ctx);
 
+    // Chains of unary operators recurse without going through `Expr::parse`,
+    // so they are guarded against deep nesting here.
+    fn parse_nested_unary_op(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
+        parser.parse_nested(|parser| parse_unary_op(parser, ctx))
+    }
+
     #[allow(clippy::type_complexity)] // Used just here for getting the three parsed elements.
     fn parse_referencing(
         parser: &mut Parser,
@@ -534,7 +540,7 @@ fn parse_unary_op(parser: &mut Parser, ctx: ParseExprCtx) -> ParseResult<Expr> {
     ) -> ParseResult<Option<(AmpersandToken, Option<MutToken>, Box<Expr>)>> {
         if let Some(ampersand_token) = parser.take() {
             let mut_token = parser.take::<MutToken>();
-            let expr = Box::new(parse_unary_op(parser, ctx.not_statement())?);
+            let expr = Box::new(parse_nested_unary_op(parser, ctx.not_statement())?);
             return Ok(Some((ampersand_token, mut_token, expr)));
         }
         Ok(None)
@@ -1,9 +1,9 @@
 use crate::{Parse, ParseResult, Parser};
 
-use sway_ast::generics::GenericParam;
-use sway_ast::keywords::{ColonToken, CommaToken, ConstToken};
+use sway_ast::generics::{AssociatedTypeBinding, GenericParam};
+use sway_ast::keywords::{ColonToken, CommaToken, ConstToken, EqToken};
 use sway_ast::punctuated::Punctuated;
-use sway_ast::{AngleBrackets, GenericArgs, GenericParams};
+use sway_ast::{AngleBrackets, GenericArgs, GenericParams, Ty};
 use sway_types::Ident;
 
 impl Parse for GenericParam {
@@ -32,7 +32,58 @@ impl Parse for GenericParams {
 
 impl Parse for GenericArgs {
     fn parse(parser: &mut Parser) -> ParseResult<GenericArgs> {
-        parse_angle_comma(parser).map(|parameters| GenericArgs { parameters })
+        let open_angle_bracket_token = parser.parse()?;
+        let mut value_separator_pairs = Vec::<(Ty, CommaToken)>::new();
+        let mut final_value_opt = None;
+        let mut binding_separator_pairs = Vec::new();
+        let mut final_binding_opt = None;
+        let close_angle_bracket_token = loop {
+            if let Some(close_angle_bracket_token) = parser.take() {
+                break close_angle_bracket_token;
+            };
+            // Once we see the first associated type binding, e.g., `Item = u64`,
+            // all the remaining arguments must be bindings as well.
+            if !binding_separator_pairs.is_empty() || parser.peek::<(Ident, EqToken)>().is_some() {
+                let binding = parser.parse::<AssociatedTypeBinding>()?;
+                if let Some(close_angle_bracket_token) = parser.take() {
+                    final_binding_opt = Some(Box::new(binding));
+                    break close_angle_bracket_token;
+                };
+                let comma_token = parser.parse()?;
+                binding_separator_pairs.push((binding, comma_token));
+                continue;
+            }
+            let ty = parser.parse()?;
+            if let Some(close_angle_bracket_token) = parser.take() {
+                final_value_opt = Some(Box::new(ty));
+                break close_angle_bracket_token;
+            };
+            let comma_token = parser.parse()?;
+            value_separator_pairs.push((ty, comma_token));
+        };
+        Ok(GenericArgs {
+            parameters: AngleBrackets {
+                open_angle_bracket_token,
+                inner: Punctuated {
+                    value_separator_pairs,
+                    final_value_opt,
+                },
+                close_angle_bracket_token,
+            },
+            associated_type_bindings: Punctuated {
+                value_separator_pairs: binding_separator_pairs,
+                final_value_opt: final_binding_opt,
+            },
+        })
+    }
+}
+
+impl Parse for AssociatedTypeBinding {
+    fn parse(parser: &mut Parser) -> ParseResult<AssociatedTypeBinding> {
+        let name = parser.parse()?;
+        let eq_token = parser.parse()?;
+        let ty = parser.parse()?;
+        Ok(AssociatedTypeBinding { name, eq_token, ty })
     }
 }
 
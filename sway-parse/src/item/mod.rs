@@ -229,9 +229,13 @@ mod tests {
                     .map(|att| {
                         (
                             att.name.as_str(),
-                            att.args.as_ref().map(|arg| {
-                                arg.get().into_iter().map(|a| a.name.as_str()).collect()
-                            }),
+                            // For doc comments, we take the documentation line instead of the `text` arg name.
+                            match att.doc_comment_text() {
+                                Some(text) => Some(vec![text]),
+                                None => att.args.as_ref().map(|arg| {
+                                    arg.get().into_iter().map(|a| a.name.as_str()).collect()
+                                }),
+                            },
                         )
                     })
                     .collect()
@@ -30,9 +30,10 @@ pub use crate::{
 use sway_ast::{
     attribute::Annotated,
     token::{DocComment, DocStyle},
-    Module, ModuleKind,
+    Expr, Item, Module, ModuleKind, Ty,
 };
 use sway_error::handler::{ErrorEmitted, Handler};
+use sway_error::parser_error::ParseErrorKind;
 use sway_types::SourceId;
 
 use std::sync::Arc;
@@ -64,9 +65,53 @@ pub fn parse_module_kind(
     parser.parse()
 }
 
+/// Parses a standalone expression, e.g. `foo.bar + 1`, that must span the whole `src`.
+pub fn parse_expr(
+    handler: &Handler,
+    src: Arc<str>,
+    source_id: Option<SourceId>,
+) -> Result<Expr, ErrorEmitted> {
+    parse_standalone(handler, src, source_id)
+}
+
+/// Parses a standalone type, e.g. `Vec<(u64, bool)>`, that must span the whole `src`.
+pub fn parse_type(
+    handler: &Handler,
+    src: Arc<str>,
+    source_id: Option<SourceId>,
+) -> Result<Ty, ErrorEmitted> {
+    parse_standalone(handler, src, source_id)
+}
+
+/// Parses a standalone item, together with its attributes, that must span the whole `src`.
+pub fn parse_item(
+    handler: &Handler,
+    src: Arc<str>,
+    source_id: Option<SourceId>,
+) -> Result<Item, ErrorEmitted> {
+    parse_standalone(handler, src, source_id)
+}
+
+fn parse_standalone<T: Parse>(
+    handler: &Handler,
+    src: Arc<str>,
+    source_id: Option<SourceId>,
+) -> Result<T, ErrorEmitted> {
+    let ts = lex(handler, &src, 0, src.len(), source_id)?;
+    let mut parser = Parser::new(handler, &ts);
+    let value = parser.parse()?;
+    if !parser.is_empty() {
+        return Err(parser.emit_error(ParseErrorKind::ExpectedEndOfInput));
+    }
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use sway_ast::{ItemKind, Literal};
+    use sway_error::error::CompileError;
+    use sway_types::Spanned;
 
     #[test]
     fn parse_invalid() {
@@ -107,4 +152,170 @@ mod tests {
             None,
         );
     }
+
+    fn single_parse_error(handler: Handler) -> (String, String) {
+        let (errors, _) = handler.consume();
+        match errors.as_slice() {
+            [CompileError::Parse { error }] => (error.kind.to_string(), error.span.as_str().into()),
+            _ => panic!("expected a single parse error, got {errors:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_standalone_expr() {
+        let handler = Handler::default();
+        let source_id = SourceId::new(1, 2);
+        let src: Arc<str> = Arc::from("  foo.bar(1) + 2 * x  ");
+        let expr = parse_expr(&handler, src.clone(), Some(source_id)).unwrap();
+        assert!(!handler.has_errors());
+
+        let Expr::Add { lhs, rhs, .. } = &expr else {
+            panic!("expected an addition, got {expr:?}");
+        };
+        assert!(matches!(**lhs, Expr::MethodCall { .. }));
+        assert!(matches!(**rhs, Expr::Mul { .. }));
+
+        let span = expr.span();
+        assert_eq!(span.as_str(), "foo.bar(1) + 2 * x");
+        assert_eq!((span.start(), span.end()), (2, 20));
+        assert_eq!(span.source_id(), Some(&source_id));
+        assert_eq!(rhs.span().as_str(), "2 * x");
+        assert_eq!(rhs.span().start(), 15);
+
+        let expr = parse_expr(&handler, Arc::from("42"), None).unwrap();
+        assert!(matches!(expr, Expr::Literal(Literal::Int(_))));
+    }
+
+    #[test]
+    fn parse_standalone_expr_with_trailing_tokens() {
+        let handler = Handler::default();
+        assert!(parse_expr(&handler, Arc::from("a + b c"), None).is_err());
+        let (message, span) = single_parse_error(handler);
+        assert_eq!(message, "Expected the end of the input.");
+        assert_eq!(span, "c");
+
+        let handler = Handler::default();
+        assert!(parse_expr(&handler, Arc::from("x;"), None).is_err());
+        assert_eq!(single_parse_error(handler).1, ";");
+    }
+
+    #[test]
+    fn parse_standalone_type() {
+        let handler = Handler::default();
+        let ty = parse_type(&handler, Arc::from("Vec<(u64, bool)>"), None).unwrap();
+        assert!(!handler.has_errors());
+        assert!(matches!(ty, Ty::Path(_)));
+        assert_eq!(ty.span().as_str(), "Vec<(u64, bool)>");
+
+        let ty = parse_type(&handler, Arc::from(" [u8; 4] "), None).unwrap();
+        assert!(matches!(ty, Ty::Array(_)));
+        assert_eq!((ty.span().start(), ty.span().end()), (1, 8));
+
+        let handler = Handler::default();
+        assert!(parse_type(&handler, Arc::from("u64 u64"), None).is_err());
+        let (message, span) = single_parse_error(handler);
+        assert_eq!(message, "Expected the end of the input.");
+        assert_eq!(span, "u64");
+    }
+
+    #[test]
+    fn parse_standalone_item() {
+        let handler = Handler::default();
+        let item = parse_item(
+            &handler,
+            Arc::from("#[inline(never)]\nfn foo(x: u64) -> u64 { x }"),
+            None,
+        )
+        .unwrap();
+        assert!(!handler.has_errors());
+        assert_eq!(item.attributes.len(), 1);
+        let ItemKind::Fn(item_fn) = &item.value else {
+            panic!("expected a function, got {:?}", item.value);
+        };
+        assert_eq!(item_fn.fn_signature.name.as_str(), "foo");
+        assert_eq!(item.value.span().as_str(), "fn foo(x: u64) -> u64 { x }");
+
+        let handler = Handler::default();
+        assert!(parse_item(&handler, Arc::from("struct S {} struct T {}"), None).is_err());
+        let (message, span) = single_parse_error(handler);
+        assert_eq!(message, "Expected the end of the input.");
+        assert_eq!(span, "struct");
+    }
+
+    #[test]
+    fn parse_deeply_nested_expr() {
+        // Lexing and dropping the 10k levels of token trees is itself recursive, so this runs on
+        // a larger stack. Without the recursion limit, parsing would still overflow it.
+        std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(|| {
+                let depth = 10_000;
+                let src = format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+                let handler = Handler::default();
+                assert!(parse_expr(&handler, Arc::from(src), None).is_err());
+                let (errors, _) = handler.consume();
+                let [CompileError::Parse { error }] = errors.as_slice() else {
+                    panic!("expected a single parse error, got {errors:?}");
+                };
+                assert_eq!(
+                    error.kind,
+                    ParseErrorKind::RecursionLimitReached {
+                        limit: parser::DEFAULT_MAX_RECURSION_DEPTH
+                    }
+                );
+                assert_eq!(error.span.start(), parser::DEFAULT_MAX_RECURSION_DEPTH);
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn parse_deeply_nested_unary_ops() {
+        let depth = 10_000;
+        for op in ["!", "*", "& ", "&mut "] {
+            let src = format!("{}x", op.repeat(depth));
+            let handler = Handler::default();
+            assert!(parse_expr(&handler, Arc::from(src), None).is_err());
+            let (errors, _) = handler.consume();
+            let [CompileError::Parse { error }] = errors.as_slice() else {
+                panic!("expected a single parse error for `{op}`, got {errors:?}");
+            };
+            assert_eq!(
+                error.kind,
+                ParseErrorKind::RecursionLimitReached {
+                    limit: parser::DEFAULT_MAX_RECURSION_DEPTH
+                }
+            );
+            assert_eq!(
+                error.span.start(),
+                op.len() * parser::DEFAULT_MAX_RECURSION_DEPTH
+            );
+        }
+    }
+
+    #[test]
+    fn parse_with_max_recursion_depth() {
+        let parse = |src: &str, max_recursion_depth| {
+            let handler = Handler::default();
+            let token_stream = lex(&handler, &Arc::from(src), 0, src.len(), None).unwrap();
+            let mut parser = Parser::new(&handler, &token_stream);
+            parser.max_recursion_depth = max_recursion_depth;
+            let expr = parser.parse::<Expr>();
+            (expr, handler)
+        };
+
+        let (expr, handler) = parse("((x))", 3);
+        assert!(expr.is_ok());
+        assert!(!handler.has_errors());
+
+        let (expr, handler) = parse("((x))", 2);
+        assert!(expr.is_err());
+        let (message, span) = single_parse_error(handler);
+        assert_eq!(
+            message,
+            "The input is nested too deeply. The parser supports at most 2 levels of nested expressions, types, and patterns."
+        );
+        assert_eq!(span, "x");
+    }
 }
@@ -1,7 +1,16 @@
+use crate::attribute::parse_annotated;
 use crate::{Parse, ParseResult, ParseToEnd, Parser, ParserConsumed};
 
-use sway_ast::{attribute::Annotated, Module, ModuleKind};
-use sway_error::parser_error::ParseErrorKind;
+use sway_ast::{
+    attribute::Annotated,
+    keywords::{SemicolonToken, Token},
+    AttributeDecl, Item, ItemKind, Module, ModuleKind,
+};
+use sway_error::{formatting::did_you_mean, handler::ErrorEmitted, parser_error::ParseErrorKind};
+use sway_types::{Ident, Span, Spanned};
+
+/// The keywords of all the module kinds.
+const MODULE_KINDS: [&str; 4] = ["script", "contract", "predicate", "library"];
 
 impl Parse for ModuleKind {
     fn parse(parser: &mut Parser) -> ParseResult<Self> {
@@ -14,33 +23,201 @@ impl Parse for ModuleKind {
         } else if let Some(library_token) = parser.take() {
             Ok(Self::Library { library_token })
         } else {
-            Err(parser.emit_error(ParseErrorKind::ExpectedModuleKind))
+            Err(emit_expected_module_kind_error(parser))
+        }
+    }
+}
+
+/// Emits an error for every outer doc comment (`///`) that precedes the module kind.
+///
+/// Outer doc comments cannot document the module. The error points to the `///` markers
+/// that can be turned into `//!` to document the module, and to the first item of the
+/// module, which the doc comment could have been meant to document instead.
+fn check_outer_doc_comments_before_module_kind(
+    parser: &mut Parser,
+    attributes: &[AttributeDecl],
+    kind: &ModuleKind,
+    first_item: Option<&Item>,
+) {
+    let is_outer_doc_comment =
+        |attr_decl: &AttributeDecl| attr_decl.is_doc_comment() && !attr_decl.is_inner();
+
+    let first_item = first_item.map(|item| {
+        let span = item.value.span();
+        let first_line_len = span.as_str().lines().next().unwrap_or_default().len();
+        let first_line = Span::new(
+            span.src().clone(),
+            span.start(),
+            span.start() + first_line_len,
+            span.source_id().cloned(),
+        )
+        .unwrap_or(span);
+        (first_line, item.value.friendly_name())
+    });
+
+    // Report a complete doc comment at once, and not every documentation line.
+    for doc_lines in attributes
+        .chunk_by(|lhs, rhs| is_outer_doc_comment(lhs) == is_outer_doc_comment(rhs))
+        .filter(|attr_decls| is_outer_doc_comment(&attr_decls[0]))
+    {
+        let outer_doc_markers = doc_lines
+            .iter()
+            .map(|doc_line| {
+                let span = doc_line.span();
+                Span::new(
+                    span.src().clone(),
+                    span.start(),
+                    span.start() + "///".len(),
+                    span.source_id().cloned(),
+                )
+                .unwrap_or(span)
+            })
+            .collect();
+        // `chunk_by` guarantees at least one element in every chunk.
+        let first_doc_line = &doc_lines[0];
+        let last_doc_line = &doc_lines[doc_lines.len() - 1];
+        let span = Span::join(first_doc_line.span(), &last_doc_line.span().start_span());
+        parser.emit_error_with_span(
+            ParseErrorKind::OuterDocCommentBeforeModuleKind {
+                module_kind: kind.friendly_name(),
+                outer_doc_markers,
+                first_item: first_item.clone(),
+            },
+            span,
+        );
+    }
+}
+
+/// Parses the `;` that terminates the module kind.
+///
+/// If the `;` is missing, an error is emitted and a `;` with an empty [Span] right
+/// after the module kind is returned instead, so that the items of the module
+/// still get parsed.
+fn parse_module_kind_semicolon(parser: &mut Parser, kind: &ModuleKind) -> SemicolonToken {
+    if let Some(semicolon_token) = parser.take() {
+        return semicolon_token;
+    }
+
+    let insertion_span = kind.span().end_span();
+    parser.emit_error_with_span(
+        ParseErrorKind::ExpectedSemicolonAfterModuleKind {
+            insertion_span: insertion_span.clone(),
+        },
+        kind.span(),
+    );
+    SemicolonToken::new(insertion_span)
+}
+
+/// Emits an error for a missing module kind, guiding the user depending on
+/// what was found in place of the module kind.
+fn emit_expected_module_kind_error(parser: &mut Parser) -> ErrorEmitted {
+    // The module kind is missing and the module starts directly with its items.
+    if parser.is_at_item_start() {
+        if let Some(span) = parser.peek_span() {
+            return parser.emit_error(ParseErrorKind::ExpectedModuleKindBeforeItems {
+                insertion_span: span.start_span(),
+            });
+        }
+    }
+
+    // The module kind is misspelled, e.g. `libary;`.
+    if let Some(ident) = parser.peek::<Ident>() {
+        let suggestion = did_you_mean(ident.as_str(), MODULE_KINDS, 1)
+            .first()
+            .and_then(|suggestion| {
+                MODULE_KINDS
+                    .into_iter()
+                    .find(|kind| *kind == suggestion.as_str())
+            });
+        if let Some(suggestion) = suggestion {
+            return parser.emit_error_with_span(
+                ParseErrorKind::MisspelledModuleKind { suggestion },
+                ident.span(),
+            );
         }
     }
+
+    parser.emit_error(ParseErrorKind::ExpectedModuleKind)
 }
 
 impl ParseToEnd for Annotated<Module> {
     fn parse_to_end<'a, 'e>(mut parser: Parser<'a, '_>) -> ParseResult<(Self, ParserConsumed<'a>)> {
-        let attributes = parser.parse()?;
-        let (kind, semicolon_token) = parser.parse()?;
-        let (items, consumed) = parser.parse_to_end()?;
+        // The module span covers the whole file, including the leading
+        // inner doc comments and attributes that precede the module kind.
+        let span = parser.full_span().clone();
+        let attributes: Vec<AttributeDecl> = parser.parse()?;
+        let kind: ModuleKind = parser.parse()?;
+        let semicolon_token = parse_module_kind_semicolon(&mut parser, &kind);
+        let (items, consumed) = parse_items_to_end(&mut parser)?;
+        check_outer_doc_comments_before_module_kind(&mut parser, &attributes, &kind, items.first());
         let module = Annotated {
             attributes,
             value: Module {
                 kind,
                 semicolon_token,
                 items,
+                span,
             },
         };
         Ok((module, consumed))
     }
 }
 
+/// Parses the items of a module.
+///
+/// A malformed item is turned into an [ItemKind::Error] and the parsing continues at
+/// the start of the next item, so that a syntax error in one item does not prevent
+/// the following items from being parsed.
+///
+/// Inner attributes (`#![...]`) annotate the module and must precede the module kind.
+/// An error is emitted for every inner attribute that annotates an item instead.
+fn parse_items_to_end<'a>(
+    parser: &mut Parser<'a, '_>,
+) -> ParseResult<(Vec<Item>, ParserConsumed<'a>)> {
+    let mut items = Vec::new();
+    loop {
+        if let Some(consumed) = parser.check_empty() {
+            return Ok((items, consumed));
+        }
+
+        let item = parser.call_parsing_function_with_recovery(|p| {
+            parse_annotated(p, |r| {
+                r.recover_at_next_item_with_fallback_error(ParseErrorKind::InvalidItem)
+            })
+        });
+        match item {
+            Ok(item) => {
+                for attr_decl in item
+                    .attributes
+                    .iter()
+                    .filter(|attr_decl| attr_decl.is_inner() && !attr_decl.is_doc_comment())
+                {
+                    parser.emit_error_with_span(
+                        ParseErrorKind::ExpectedInnerAttributeAtTheTopOfFile,
+                        attr_decl.span(),
+                    );
+                }
+                items.push(item);
+            }
+            Err(r) => {
+                let (spans, error) =
+                    r.recover_at_next_item_with_fallback_error(ParseErrorKind::InvalidItem);
+                items.push(Annotated {
+                    attributes: vec![],
+                    value: ItemKind::Error(spans, error),
+                });
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_utils::parse_to_end;
     use insta::*;
+    use std::sync::Arc;
+    use sway_error::{error::CompileError, handler::Handler};
 
     #[test]
     fn parse_noop_script_module() {
@@ -141,8 +318,542 @@ mod tests {
                 )),
               ),
             ],
+            span: Span(
+              src: "\n            script;\n        \n            fn main() {\n                ()\n            }\n        ",
+              start: 0,
+              end: 95,
+              source_id: None,
+            ),
           ),
         )
         "#);
     }
+
+    #[test]
+    fn parse_module_with_malformed_items() {
+        let handler = Handler::default();
+        let module = crate::parse_file(
+            &handler,
+            Arc::from(
+                r#"
+            library;
+
+            fn first() {}
+
+            fn broken_one() -> u64 garbage
+            {
+                1
+            }
+
+            fn second() {}
+
+            pub 42 {
+                still_broken
+            }
+
+            struct Third {}
+        "#,
+            ),
+            None,
+        )
+        .expect("the module is parsed despite the malformed items");
+
+        let mut summary = "items:\n".to_string();
+        for item in &module.value.items {
+            summary.push_str(&format!("- {}\n", item.value.friendly_name()));
+        }
+        summary.push_str("errors:\n");
+        for error in handler.consume().0 {
+            match error {
+                CompileError::Parse { error } => {
+                    summary.push_str(&format!("- `{}`: {}\n", error.span.as_str(), error))
+                }
+                error => panic!("unexpected error: {error:?}"),
+            }
+        }
+
+        assert_snapshot!(summary, @r"
+        items:
+        - function declaration
+        - error
+        - function declaration
+        - error
+        - struct declaration
+        errors:
+        - `garbage`: Expected an opening brace.
+        - `42`: Expected an item.
+        ");
+    }
+
+    #[test]
+    fn module_span_covers_whole_file() {
+        let src = "//! Docs.\nlibrary;\n// Trailing comment.\n";
+        let module = parse_to_end::<Annotated<Module>>(src);
+        assert_eq!(module.value.span().as_str(), src);
+
+        let src = "//! Docs.\n#![allow(dead_code)]\n//! More docs.\nlibrary;\n";
+        let module = parse_to_end::<Annotated<Module>>(src);
+        assert_eq!(module.attributes.len(), 3);
+        assert_eq!(module.value.span().start(), 0);
+        assert_eq!(module.value.span().as_str(), src);
+
+        let module = parse_to_end::<Annotated<Module>>("script;\n\nfn main() {}\n\n");
+        assert_eq!(module.value.span().as_str(), "script;\n\nfn main() {}\n\n");
+    }
+
+    #[test]
+    fn parse_module_with_inner_attributes() {
+        assert_ron_snapshot!(parse_to_end::<Annotated<Module>>(r#"
+            //! Module documentation.
+            #![allow(dead_code)]
+            //! More documentation.
+            library;
+        "#,), @r#"
+        Annotated(
+          attributes: [
+            AttributeDecl(
+              hash_kind: Inner(HashBangToken(
+                span: Span(
+                  src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                  start: 13,
+                  end: 38,
+                  source_id: None,
+                ),
+              )),
+              attribute: SquareBrackets(
+                inner: Punctuated(
+                  value_separator_pairs: [],
+                  final_value_opt: Some(Attribute(
+                    namespace: [],
+                    name: BaseIdent(
+                      name_override_opt: Some("doc-comment"),
+                      span: Span(
+                        src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                        start: 13,
+                        end: 38,
+                        source_id: None,
+                      ),
+                      is_raw_ident: false,
+                    ),
+                    args: Some(Parens(
+                      inner: Punctuated(
+                        value_separator_pairs: [],
+                        final_value_opt: Some(AttributeArg(
+                          name: BaseIdent(
+                            name_override_opt: Some("text"),
+                            span: Span(
+                              src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                              start: 16,
+                              end: 38,
+                              source_id: None,
+                            ),
+                            is_raw_ident: false,
+                          ),
+                          value: Some(String(LitString(
+                            span: Span(
+                              src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                              start: 16,
+                              end: 38,
+                              source_id: None,
+                            ),
+                            parsed: " Module documentation.",
+                          ))),
+                          args: None,
+                        )),
+                      ),
+                      span: Span(
+                        src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                        start: 16,
+                        end: 38,
+                        source_id: None,
+                      ),
+                    )),
+                  )),
+                ),
+                span: Span(
+                  src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                  start: 13,
+                  end: 38,
+                  source_id: None,
+                ),
+              ),
+            ),
+            AttributeDecl(
+              hash_kind: Inner(HashBangToken(
+                span: Span(
+                  src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                  start: 51,
+                  end: 53,
+                  source_id: None,
+                ),
+              )),
+              attribute: SquareBrackets(
+                inner: Punctuated(
+                  value_separator_pairs: [],
+                  final_value_opt: Some(Attribute(
+                    namespace: [],
+                    name: BaseIdent(
+                      name_override_opt: None,
+                      span: Span(
+                        src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                        start: 54,
+                        end: 59,
+                        source_id: None,
+                      ),
+                      is_raw_ident: false,
+                    ),
+                    args: Some(Parens(
+                      inner: Punctuated(
+                        value_separator_pairs: [],
+                        final_value_opt: Some(AttributeArg(
+                          name: BaseIdent(
+                            name_override_opt: None,
+                            span: Span(
+                              src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                              start: 60,
+                              end: 69,
+                              source_id: None,
+                            ),
+                            is_raw_ident: false,
+                          ),
+                          value: None,
+                          args: None,
+                        )),
+                      ),
+                      span: Span(
+                        src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                        start: 59,
+                        end: 70,
+                        source_id: None,
+                      ),
+                    )),
+                  )),
+                ),
+                span: Span(
+                  src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                  start: 53,
+                  end: 71,
+                  source_id: None,
+                ),
+              ),
+            ),
+            AttributeDecl(
+              hash_kind: Inner(HashBangToken(
+                span: Span(
+                  src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                  start: 84,
+                  end: 107,
+                  source_id: None,
+                ),
+              )),
+              attribute: SquareBrackets(
+                inner: Punctuated(
+                  value_separator_pairs: [],
+                  final_value_opt: Some(Attribute(
+                    namespace: [],
+                    name: BaseIdent(
+                      name_override_opt: Some("doc-comment"),
+                      span: Span(
+                        src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                        start: 84,
+                        end: 107,
+                        source_id: None,
+                      ),
+                      is_raw_ident: false,
+                    ),
+                    args: Some(Parens(
+                      inner: Punctuated(
+                        value_separator_pairs: [],
+                        final_value_opt: Some(AttributeArg(
+                          name: BaseIdent(
+                            name_override_opt: Some("text"),
+                            span: Span(
+                              src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                              start: 87,
+                              end: 107,
+                              source_id: None,
+                            ),
+                            is_raw_ident: false,
+                          ),
+                          value: Some(String(LitString(
+                            span: Span(
+                              src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                              start: 87,
+                              end: 107,
+                              source_id: None,
+                            ),
+                            parsed: " More documentation.",
+                          ))),
+                          args: None,
+                        )),
+                      ),
+                      span: Span(
+                        src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                        start: 87,
+                        end: 107,
+                        source_id: None,
+                      ),
+                    )),
+                  )),
+                ),
+                span: Span(
+                  src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                  start: 84,
+                  end: 107,
+                  source_id: None,
+                ),
+              ),
+            ),
+          ],
+          value: Module(
+            kind: Library(
+              library_token: LibraryToken(
+                span: Span(
+                  src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                  start: 120,
+                  end: 127,
+                  source_id: None,
+                ),
+              ),
+            ),
+            semicolon_token: SemicolonToken(
+              span: Span(
+                src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+                start: 127,
+                end: 128,
+                source_id: None,
+              ),
+            ),
+            items: [],
+            span: Span(
+              src: "\n            //! Module documentation.\n            #![allow(dead_code)]\n            //! More documentation.\n            library;\n        ",
+              start: 0,
+              end: 137,
+              source_id: None,
+            ),
+          ),
+        )
+        "#);
+    }
+
+    #[test]
+    fn parse_inner_attribute_after_module_kind() {
+        let handler = Handler::default();
+        let module = crate::parse_file(
+            &handler,
+            Arc::from(
+                r#"
+            library;
+
+            fn first() {}
+
+            #![allow(dead_code)]
+            fn second() {}
+        "#,
+            ),
+            None,
+        )
+        .expect("the module is parsed despite the misplaced inner attribute");
+
+        assert_eq!(module.value.items.len(), 2);
+        let errors = handler.consume().0;
+        assert!(
+            matches!(
+                &errors[..],
+                [CompileError::Parse { error }]
+                    if error.kind == ParseErrorKind::ExpectedInnerAttributeAtTheTopOfFile
+                        && error.span.as_str() == "#![allow(dead_code)]"
+            ),
+            "unexpected errors: {errors:?}"
+        );
+    }
+
+    /// Parses a module with outer doc comments before the module kind and summarizes
+    /// the recovered items and the errors.
+    fn outer_doc_comment_summary(src: &str) -> String {
+        let handler = Handler::default();
+        let module = crate::parse_file(&handler, Arc::from(src), None)
+            .expect("the module is parsed despite the outer doc comment");
+
+        let mut summary = "items:\n".to_string();
+        for item in &module.value.items {
+            summary.push_str(&format!("- {}\n", item.value.friendly_name()));
+        }
+        summary.push_str("errors:\n");
+        for error in handler.consume().0 {
+            let CompileError::Parse { error } = error else {
+                panic!("unexpected error: {error:?}");
+            };
+            summary.push_str(&format!("- `{}`: {}\n", error.span.as_str(), error));
+            if let ParseErrorKind::OuterDocCommentBeforeModuleKind {
+                outer_doc_markers,
+                first_item,
+                ..
+            } = &error.kind
+            {
+                for marker in outer_doc_markers {
+                    summary.push_str(&format!(
+                        "  replace `{}` at: {}..{}\n",
+                        marker.as_str(),
+                        marker.start(),
+                        marker.end()
+                    ));
+                }
+                if let Some((span, name)) = first_item {
+                    summary.push_str(&format!("  or document {name}: `{}`\n", span.as_str()));
+                }
+            }
+        }
+        summary
+    }
+
+    #[test]
+    fn parse_lone_outer_doc_comment_before_module_kind() {
+        assert_snapshot!(outer_doc_comment_summary(r#"
+            /// My library.
+            library;
+        "#), @r"
+        items:
+        errors:
+        - `/// My library.`: Outer doc comment (`///`) cannot document a module kind (library).
+          replace `///` at: 13..16
+        ");
+    }
+
+    #[test]
+    fn parse_outer_doc_comment_before_module_kind_followed_by_items() {
+        assert_snapshot!(outer_doc_comment_summary(r#"
+            /// Adds two numbers.
+            /// Returns the sum.
+            library;
+
+            pub fn add(a: u64, b: u64) -> u64 {
+                a + b
+            }
+        "#), @r"
+        items:
+        - function declaration
+        errors:
+        - `/// Adds two numbers.
+                    `: Outer doc comment (`///`) cannot document a module kind (library).
+          replace `///` at: 13..16
+          replace `///` at: 47..50
+          or document function declaration: `pub fn add(a: u64, b: u64) -> u64 {`
+        ");
+    }
+
+    /// Parses a module whose module kind is not terminated with `;` and summarizes
+    /// the recovered module and the errors.
+    fn missing_semicolon_summary(src: &str) -> String {
+        let handler = Handler::default();
+        let module = crate::parse_file(&handler, Arc::from(src), None)
+            .expect("the module is parsed despite the missing semicolon");
+
+        let semicolon_span = module.value.semicolon_token.span();
+        let mut summary = format!(
+            "{}\nsemicolon: {}..{}\nitems:\n",
+            module.value.kind.friendly_name(),
+            semicolon_span.start(),
+            semicolon_span.end()
+        );
+        for item in &module.value.items {
+            summary.push_str(&format!("- {}\n", item.value.friendly_name()));
+        }
+        summary.push_str("errors:\n");
+        for error in handler.consume().0 {
+            let CompileError::Parse { error } = error else {
+                panic!("unexpected error: {error:?}");
+            };
+            summary.push_str(&format!("- `{}`: {}\n", error.span.as_str(), error));
+        }
+        summary
+    }
+
+    #[test]
+    fn parse_library_without_semicolon() {
+        assert_snapshot!(missing_semicolon_summary(r#"
+            library
+
+            pub fn foo() {}
+
+            struct Bar {}
+        "#), @r"
+        module kind (library)
+        semicolon: 20..20
+        items:
+        - function declaration
+        - struct declaration
+        errors:
+        - `library`: Expected `;` after the module kind.
+        ");
+    }
+
+    #[test]
+    fn parse_contract_without_semicolon() {
+        assert_snapshot!(missing_semicolon_summary(r#"
+            contract
+            abi MyContract {
+                fn foo();
+            }
+        "#), @r"
+        module kind (contract)
+        semicolon: 21..21
+        items:
+        - abi declaration
+        errors:
+        - `contract`: Expected `;` after the module kind.
+        ");
+    }
+
+    /// Parses a module with a missing or malformed module kind and summarizes the errors.
+    fn module_kind_errors(src: &str) -> String {
+        let handler = Handler::default();
+        assert!(crate::parse_file(&handler, Arc::from(src), None).is_err());
+
+        let mut summary = String::new();
+        for error in handler.consume().0 {
+            let CompileError::Parse { error } = error else {
+                panic!("unexpected error: {error:?}");
+            };
+            summary.push_str(&format!("- `{}`: {}\n", error.span.as_str(), error));
+            if let ParseErrorKind::ExpectedModuleKindBeforeItems { insertion_span } = &error.kind {
+                summary.push_str(&format!(
+                    "  insert at: {}..{}\n",
+                    insertion_span.start(),
+                    insertion_span.end()
+                ));
+            }
+        }
+        summary
+    }
+
+    #[test]
+    fn parse_module_without_module_kind() {
+        assert_snapshot!(module_kind_errors(r#"
+            fn main() {}
+        "#), @r"
+        - `fn`: Expected a module kind before the items of the module. Add `library;` (or `contract;`, `script;`, `predicate;`) at the top of the file.
+          insert at: 13..13
+        ");
+    }
+
+    #[test]
+    fn parse_module_with_misspelled_module_kind() {
+        assert_snapshot!(module_kind_errors(r#"
+            libary;
+
+            fn foo() {}
+        "#), @"- `libary`: Unknown module kind. Did you mean `library`?");
+    }
+
+    #[test]
+    fn parse_module_without_module_kind_after_outer_doc_comment() {
+        assert_snapshot!(module_kind_errors(r#"
+            /// Documentation.
+            fn main() {}
+        "#), @r"
+        - `fn`: Expected a module kind before the items of the module. Add `library;` (or `contract;`, `script;`, `predicate;`) at the top of the file.
+          insert at: 44..44
+        ");
+    }
 }
@@ -1,7 +1,10 @@
 use crate::{Parse, ParseToEnd, Peek};
 use core::marker::PhantomData;
 use std::cell::RefCell;
-use sway_ast::keywords::Keyword;
+use sway_ast::keywords::{
+    AbiToken, ClassToken, ConfigurableToken, ConstToken, EnumToken, FnToken, HashToken, ImplToken,
+    Keyword, ModToken, StorageToken, StructToken, TraitToken, TypeToken, UseToken,
+};
 use sway_ast::literal::Literal;
 use sway_ast::token::{
     DocComment, GenericTokenTree, Group, Punct, Spacing, TokenStream, TokenTree,
@@ -20,8 +23,15 @@ pub struct Parser<'a, 'e> {
     full_span: Span,
     handler: &'e Handler,
     pub check_double_underscore: bool,
+    recursion_depth: usize,
+    /// The maximum number of nested expressions, types and patterns the parser descends into
+    /// before reporting [ParseErrorKind::RecursionLimitReached].
+    pub max_recursion_depth: usize,
 }
 
+/// The default [Parser::max_recursion_depth].
+pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 256;
+
 impl<'a, 'e> Parser<'a, 'e> {
     pub fn new(handler: &'e Handler, token_stream: &'a TokenStream) -> Parser<'a, 'e> {
         Parser {
@@ -29,6 +39,8 @@ impl<'a, 'e> Parser<'a, 'e> {
             full_span: token_stream.span(),
             handler,
             check_double_underscore: true,
+            recursion_depth: 0,
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
         }
     }
 
@@ -99,6 +111,8 @@ impl<'a, 'e> Parser<'a, 'e> {
             full_span: self.full_span.clone(),
             handler: &handler,
             check_double_underscore: self.check_double_underscore,
+            recursion_depth: self.recursion_depth,
+            max_recursion_depth: self.max_recursion_depth,
         };
 
         match parsing_function(&mut fork) {
@@ -159,6 +173,8 @@ impl<'a, 'e> Parser<'a, 'e> {
             full_span: self.full_span.clone(),
             handler: &handler,
             check_double_underscore: self.check_double_underscore,
+            recursion_depth: self.recursion_depth,
+            max_recursion_depth: self.max_recursion_depth,
         };
 
         match fork.parse() {
@@ -193,6 +209,8 @@ impl<'a, 'e> Parser<'a, 'e> {
             full_span: self.full_span.clone(),
             handler: &handler,
             check_double_underscore: self.check_double_underscore,
+            recursion_depth: self.recursion_depth,
+            max_recursion_depth: self.max_recursion_depth,
         };
         let r = match T::parse(&mut fork) {
             Ok(result) => {
@@ -219,6 +237,25 @@ impl<'a, 'e> Parser<'a, 'e> {
         }
     }
 
+    /// Calls `parsing_function` one nesting level deeper.
+    ///
+    /// Errors with [ParseErrorKind::RecursionLimitReached] instead, if the parser is already
+    /// [Parser::max_recursion_depth] levels deep, so that deeply nested input cannot overflow the stack.
+    pub fn parse_nested<T>(
+        &mut self,
+        parsing_function: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        if self.recursion_depth >= self.max_recursion_depth {
+            return Err(self.emit_error(ParseErrorKind::RecursionLimitReached {
+                limit: self.max_recursion_depth,
+            }));
+        }
+        self.recursion_depth += 1;
+        let result = parsing_function(self);
+        self.recursion_depth -= 1;
+        result
+    }
+
     /// Parses a `T` in its canonical way.
     pub fn parse<T: Parse>(&mut self) -> ParseResult<T> {
         T::parse(self)
@@ -246,6 +283,8 @@ impl<'a, 'e> Parser<'a, 'e> {
             full_span: self.full_span.clone(),
             handler: &handler,
             check_double_underscore: self.check_double_underscore,
+            recursion_depth: self.recursion_depth,
+            max_recursion_depth: self.max_recursion_depth,
         };
         let r = T::parse_to_end(fork);
         if append_diagnostics {
@@ -272,6 +311,8 @@ impl<'a, 'e> Parser<'a, 'e> {
                     full_span: token_stream.span(),
                     handler: self.handler,
                     check_double_underscore: self.check_double_underscore,
+                    recursion_depth: self.recursion_depth,
+                    max_recursion_depth: self.max_recursion_depth,
                 };
                 Some((parser, span.clone()))
             }
@@ -309,6 +350,12 @@ impl<'a, 'e> Parser<'a, 'e> {
     pub fn full_span(&self) -> &Span {
         &self.full_span
     }
+
+    /// Returns the span of the current token, or `None` if the parser is empty.
+    pub fn peek_span(&self) -> Option<Span> {
+        self.token_trees.first().map(|token_tree| token_tree.span())
+    }
+
     /// Consume tokens while its line equals to `line`.
     ///
     /// # Warning
@@ -331,6 +378,38 @@ impl<'a, 'e> Parser<'a, 'e> {
         }
     }
 
+    /// Consume tokens until the current token can start an item, e.g. `fn`, `pub`,
+    /// an attribute, or a doc comment.
+    ///
+    /// Delimited groups are consumed as a whole, so only tokens outside of any
+    /// braces, brackets, or parentheses are considered.
+    pub fn consume_until_item_start(&mut self) {
+        while !self.is_empty() && !self.is_at_item_start() {
+            self.token_trees = &self.token_trees[1..];
+        }
+    }
+
+    /// Returns true if the current token can start an item, e.g. `fn`, `pub`,
+    /// an attribute, or a doc comment.
+    pub fn is_at_item_start(&self) -> bool {
+        self.peek::<PubToken>().is_some()
+            || self.peek::<ModToken>().is_some()
+            || self.peek::<UseToken>().is_some()
+            || self.peek::<ClassToken>().is_some()
+            || self.peek::<StructToken>().is_some()
+            || self.peek::<EnumToken>().is_some()
+            || self.peek::<FnToken>().is_some()
+            || self.peek::<TraitToken>().is_some()
+            || self.peek::<ImplToken>().is_some()
+            || self.peek::<AbiToken>().is_some()
+            || self.peek::<ConstToken>().is_some()
+            || self.peek::<StorageToken>().is_some()
+            || self.peek::<ConfigurableToken>().is_some()
+            || self.peek::<TypeToken>().is_some()
+            || self.peek::<HashToken>().is_some()
+            || self.peek::<DocComment>().is_some()
+    }
+
     pub fn has_errors(&self) -> bool {
         self.handler.has_errors()
     }
@@ -467,14 +546,7 @@ impl<'a> ParseRecoveryStrategies<'_, 'a, '_> {
         &self,
         kind: ParseErrorKind,
     ) -> (Box<[Span]>, ErrorEmitted) {
-        let line = if self.fork_token_trees.is_empty() {
-            None
-        } else {
-            self.last_consumed_token()
-                .map(|x| x.span())
-                .or_else(|| self.fork_token_trees.first().map(|x| x.span()))
-                .map(|x| x.start_pos().line_col().line)
-        };
+        let line = self.current_line();
 
         self.start(|p| {
             if let Some(line) = line {
@@ -486,6 +558,42 @@ impl<'a> ParseRecoveryStrategies<'_, 'a, '_> {
         })
     }
 
+    /// This strategy consumes everything at the current line and then everything until
+    /// the start of the next item, e.g. the next `fn`, `struct`, or `impl`, and emits the
+    /// fallback error if the forked parser does not contain any error.
+    ///
+    /// This allows the parsing of a module to continue after a malformed item that spans
+    /// multiple lines, without reporting an error for each of its remaining lines.
+    pub fn recover_at_next_item_with_fallback_error(
+        &self,
+        kind: ParseErrorKind,
+    ) -> (Box<[Span]>, ErrorEmitted) {
+        let line = self.current_line();
+
+        self.start(|p| {
+            if let Some(line) = line {
+                p.consume_while_line_equals(line);
+            }
+            p.consume_until_item_start();
+            if !p.has_errors() {
+                p.emit_error_with_span(kind, self.diff_span(p));
+            }
+        })
+    }
+
+    /// The line of the last token consumed by the forked parser, or of its head if
+    /// it did not consume any token.
+    fn current_line(&self) -> Option<usize> {
+        if self.fork_token_trees.is_empty() {
+            None
+        } else {
+            self.last_consumed_token()
+                .map(|x| x.span())
+                .or_else(|| self.fork_token_trees.first().map(|x| x.span()))
+                .map(|x| x.start_pos().line_col().line)
+        }
+    }
+
     /// Starts the parser recovery process calling the callback with the forked parser.
     /// All the changes to this forked parser will be imposed into the original parser,
     /// including diagnostics.
@@ -498,6 +606,8 @@ impl<'a> ParseRecoveryStrategies<'_, 'a, '_> {
             full_span: self.fork_full_span.clone(),
             handler: &self.handler,
             check_double_underscore: self.original.borrow().check_double_underscore,
+            recursion_depth: self.original.borrow().recursion_depth,
+            max_recursion_depth: self.original.borrow().max_recursion_depth,
         };
         f(&mut p);
         self.finish(p)
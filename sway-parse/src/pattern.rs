@@ -15,7 +15,7 @@ impl Parse for Pattern {
             pipe_token,
             rhs,
         };
-        parse_binary(parser, parse_atomic_pattern, combine)
+        parser.parse_nested(|parser| parse_binary(parser, parse_atomic_pattern, combine))
     }
 }
 
@@ -7,105 +7,110 @@ use sway_types::{ast::Delimiter, Ident};
 
 impl Parse for Ty {
     fn parse(parser: &mut Parser) -> ParseResult<Ty> {
-        // parse parens carefully, such that only patterns of (ty) are parsed as ty,
-        // and patterns of (ty,) are parsed as one-arity tuples with one element ty
-        if let Some((mut parser, span)) = parser.enter_delimited(Delimiter::Parenthesis) {
-            if let Some(_consumed) = parser.check_empty() {
-                return Ok(Ty::Tuple(Parens::new(TyTupleDescriptor::Nil, span)));
-            }
-            let head = parser.parse()?;
-            if let Some(comma_token) = parser.take() {
-                let (tail, _consumed) = parser.parse_to_end()?;
-                let tuple = TyTupleDescriptor::Cons {
-                    head,
-                    comma_token,
-                    tail,
-                };
-                return Ok(Ty::Tuple(Parens::new(tuple, span)));
-            }
-            if parser.check_empty().is_some() {
-                return Ok(*head);
-            }
-            return Err(parser
-                .emit_error(ParseErrorKind::ExpectedCommaOrCloseParenInTupleOrParenExpression));
-        }
-
-        if let Some((mut inner_parser, span)) = parser.enter_delimited(Delimiter::Bracket) {
-            // array like [type; len]
-            if let Ok((array, _)) = inner_parser.try_parse_to_end::<TyArrayDescriptor>(false) {
-                return Ok(Ty::Array(SquareBrackets { inner: array, span }));
-            }
+        parser.parse_nested(parse_ty)
+    }
+}
 
-            // slice like [type]
-            if let Ok(Some((ty, _))) = inner_parser.try_parse_and_check_empty::<Ty>(false) {
-                return Ok(Ty::Slice {
-                    slice_token: None,
-                    ty: SquareBrackets {
-                        inner: Box::new(ty),
-                        span,
-                    },
-                });
-            }
+fn parse_ty(parser: &mut Parser) -> ParseResult<Ty> {
+    // parse parens carefully, such that only patterns of (ty) are parsed as ty,
+    // and patterns of (ty,) are parsed as one-arity tuples with one element ty
+    if let Some((mut parser, span)) = parser.enter_delimited(Delimiter::Parenthesis) {
+        if let Some(_consumed) = parser.check_empty() {
+            return Ok(Ty::Tuple(Parens::new(TyTupleDescriptor::Nil, span)));
         }
-
-        if let Some(str_token) = parser.take() {
-            let length = SquareBrackets::try_parse_all_inner(parser, |mut parser| {
-                parser.emit_error(ParseErrorKind::UnexpectedTokenAfterStrLength)
-            })?;
-            let t = match length {
-                Some(length) => Ty::StringArray { str_token, length },
-                None => Ty::StringSlice(str_token),
+        let head = parser.parse()?;
+        if let Some(comma_token) = parser.take() {
+            let (tail, _consumed) = parser.parse_to_end()?;
+            let tuple = TyTupleDescriptor::Cons {
+                head,
+                comma_token,
+                tail,
             };
-            return Ok(t);
+            return Ok(Ty::Tuple(Parens::new(tuple, span)));
         }
-
-        if let Some(underscore_token) = parser.take() {
-            return Ok(Ty::Infer { underscore_token });
+        if parser.check_empty().is_some() {
+            return Ok(*head);
         }
+        return Err(
+            parser.emit_error(ParseErrorKind::ExpectedCommaOrCloseParenInTupleOrParenExpression)
+        );
+    }
 
-        if let Some(ptr_token) = parser.take::<PtrToken>() {
-            let ty = SquareBrackets::parse_all_inner(parser, |mut parser| {
-                parser.emit_error(ParseErrorKind::UnexpectedTokenAfterPtrType)
-            })?;
-            return Ok(Ty::Ptr { ptr_token, ty });
+    if let Some((mut inner_parser, span)) = parser.enter_delimited(Delimiter::Bracket) {
+        // array like [type; len]
+        if let Ok((array, _)) = inner_parser.try_parse_to_end::<TyArrayDescriptor>(false) {
+            return Ok(Ty::Array(SquareBrackets { inner: array, span }));
         }
 
-        // slice like __slice[type]
-        // TODO: deprecate this syntax (see https://github.com/FuelLabs/sway/issues/5110)
-        if let Some(slice_token) = parser.take::<SliceToken>() {
-            let ty = SquareBrackets::<Box<Ty>>::parse_all_inner(parser, |mut parser| {
-                parser.emit_error(ParseErrorKind::UnexpectedTokenAfterSliceType)
-            })?;
+        // slice like [type]
+        if let Ok(Some((ty, _))) = inner_parser.try_parse_and_check_empty::<Ty>(false) {
             return Ok(Ty::Slice {
-                slice_token: Some(slice_token),
-                ty,
+                slice_token: None,
+                ty: SquareBrackets {
+                    inner: Box::new(ty),
+                    span,
+                },
             });
         }
+    }
 
-        if let Some(ampersand_token) = parser.take() {
-            let mut_token = parser.take();
-            let ty = Box::new(parser.parse()?);
-            return Ok(Ty::Ref {
-                ampersand_token,
-                mut_token,
-                ty,
-            });
-        }
+    if let Some(str_token) = parser.take() {
+        let length = SquareBrackets::try_parse_all_inner(parser, |mut parser| {
+            parser.emit_error(ParseErrorKind::UnexpectedTokenAfterStrLength)
+        })?;
+        let t = match length {
+            Some(length) => Ty::StringArray { str_token, length },
+            None => Ty::StringSlice(str_token),
+        };
+        return Ok(t);
+    }
 
-        if let Some(bang_token) = parser.take() {
-            return Ok(Ty::Never { bang_token });
-        }
+    if let Some(underscore_token) = parser.take() {
+        return Ok(Ty::Infer { underscore_token });
+    }
 
-        if parser.peek::<OpenAngleBracketToken>().is_some()
-            || parser.peek::<DoubleColonToken>().is_some()
-            || parser.peek::<Ident>().is_some()
-        {
-            let path_type = parser.parse()?;
-            return Ok(Ty::Path(path_type));
-        }
+    if let Some(ptr_token) = parser.take::<PtrToken>() {
+        let ty = SquareBrackets::parse_all_inner(parser, |mut parser| {
+            parser.emit_error(ParseErrorKind::UnexpectedTokenAfterPtrType)
+        })?;
+        return Ok(Ty::Ptr { ptr_token, ty });
+    }
 
-        Err(parser.emit_error(ParseErrorKind::ExpectedType))
+    // slice like __slice[type]
+    // TODO: deprecate this syntax (see https://github.com/FuelLabs/sway/issues/5110)
+    if let Some(slice_token) = parser.take::<SliceToken>() {
+        let ty = SquareBrackets::<Box<Ty>>::parse_all_inner(parser, |mut parser| {
+            parser.emit_error(ParseErrorKind::UnexpectedTokenAfterSliceType)
+        })?;
+        return Ok(Ty::Slice {
+            slice_token: Some(slice_token),
+            ty,
+        });
     }
+
+    if let Some(ampersand_token) = parser.take() {
+        let mut_token = parser.take();
+        let ty = Box::new(parser.parse()?);
+        return Ok(Ty::Ref {
+            ampersand_token,
+            mut_token,
+            ty,
+        });
+    }
+
+    if let Some(bang_token) = parser.take() {
+        return Ok(Ty::Never { bang_token });
+    }
+
+    if parser.peek::<OpenAngleBracketToken>().is_some()
+        || parser.peek::<DoubleColonToken>().is_some()
+        || parser.peek::<Ident>().is_some()
+    {
+        let path_type = parser.parse()?;
+        return Ok(Ty::Path(path_type));
+    }
+
+    Err(parser.emit_error(ParseErrorKind::ExpectedType))
 }
 
 impl ParseToEnd for TyArrayDescriptor {
@@ -197,4 +202,20 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn parse_associated_type_bindings() {
+        let item = parse::<Ty>("Iterator<u8, Item = u64, Other = bool>");
+        let Ty::Path(path_type) = item else {
+            panic!("expected a path type");
+        };
+        let (_, generic_args) = path_type.prefix.generics_opt.unwrap();
+        assert_eq!(generic_args.parameters.inner.iter().count(), 1);
+        let bindings = generic_args
+            .associated_type_bindings
+            .iter()
+            .map(|binding| binding.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(bindings, vec!["Item", "Other"]);
+    }
 }
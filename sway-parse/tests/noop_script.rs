@@ -103,6 +103,12 @@ fn noop_script_file() {
             )),
           ),
         ],
+        span: Span(
+          src: "\n      script;\n      \n      fn main() {\n        ()\n      }\n    ",
+          start: 0,
+          end: 63,
+          source_id: None,
+        ),
       ),
     ))
     "#);
@@ -1,6 +1,7 @@
 use self::shape::Shape;
 use crate::comments::{write_comments, CommentsContext};
 use crate::parse::parse_file;
+use crate::utils::language::attribute::format_attributes;
 use crate::utils::map::comments::CommentMap;
 use crate::utils::map::{newline::handle_newlines, newline_style::apply_newline_style};
 pub use crate::{
@@ -110,16 +111,29 @@ impl Formatter {
 
         self.with_comments_context(src)?;
 
-        annotated_module.format(&mut raw_formatted_code, self)?;
+        // The module span covers the whole file, including the module attributes,
+        // so the comments following the attributes end at the module kind.
+        format_attributes(
+            &annotated_module.attributes,
+            module_kind_span.start(),
+            &mut raw_formatted_code,
+            self,
+        )?;
+        self.write_indent_into_buffer(&mut raw_formatted_code)?;
+        annotated_module
+            .value
+            .format(&mut raw_formatted_code, self)?;
 
         let mut formatted_code = String::from(&raw_formatted_code);
 
-        // Write post-module comments
-        write_comments(
-            &mut formatted_code,
-            annotated_module.value.span().end()..src.len() + 1,
-            self,
-        )?;
+        // Write post-module comments, which follow the last item, or the module kind if there are no items
+        let module = &annotated_module.value;
+        let module_end = module
+            .items
+            .last()
+            .map_or_else(|| module.semicolon_token.span(), |item| item.span())
+            .end();
+        write_comments(&mut formatted_code, module_end..src.len() + 1, self)?;
 
         // Add newline sequences
         handle_newlines(
@@ -132,3 +132,19 @@ intermediate_whitespace
     let val = 1;      // this is a normal comment
 }"
 );
+
+fmt_test_item!(  fn_with_nested_cfg_attribute
+"#[cfg(all(program_type = \"contract\", not(target = \"evm\")))]
+fn foo() {}",
+            intermediate_whitespace
+"#[cfg(  all( program_type=\"contract\" ,not( target = \"evm\" ) ) )]
+fn  foo( )  { }"
+);
+
+fmt_test_item!(  fn_with_namespaced_attribute
+"#[fuels::test, tool::lint::off(arg)]
+fn foo() {}",
+            intermediate_whitespace
+"#[fuels :: test ,tool:: lint ::off( arg ) ]
+fn  foo( )  { }"
+);
@@ -21,7 +21,7 @@ impl Format for Module {
         formatted_code: &mut FormattedCode,
         formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
-        write_comments(formatted_code, 0..self.span().start(), formatter)?;
+        write_comments(formatted_code, 0..self.kind.span().start(), formatter)?;
         self.kind.format(formatted_code, formatter)?;
         writeln!(formatted_code, "{}", SemicolonToken::AS_STR)?;
 
@@ -21,35 +21,53 @@ impl<T: Format + Spanned + std::fmt::Debug> Format for Annotated<T> {
         formatted_code: &mut FormattedCode,
         formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
-        // format each `Attribute`
-        let mut start = None;
-        for attr in &self.attributes {
-            if let Some(start) = start {
-                // Write any comments that may have been defined in between the
-                // attributes and the value
-                write_comments(formatted_code, start..attr.span().start(), formatter)?;
-                if !formatted_code.ends_with(NEW_LINE) {
-                    write!(formatted_code, "{}", NEW_LINE)?;
-                }
-            }
-            formatter.write_indent_into_buffer(formatted_code)?;
-            attr.format(formatted_code, formatter)?;
-            start = Some(attr.span().end());
-        }
+        format_attributes(
+            &self.attributes,
+            self.value.span().start(),
+            formatted_code,
+            formatter,
+        )?;
+        // format `ItemKind`
+        formatter.write_indent_into_buffer(formatted_code)?;
+        self.value.format(formatted_code, formatter)?;
+
+        Ok(())
+    }
+}
+
+/// Formats each [AttributeDecl] in `attributes`, together with the comments
+/// in between them and the comments up to `value_start`, the start of the annotated value.
+pub(crate) fn format_attributes(
+    attributes: &[AttributeDecl],
+    value_start: usize,
+    formatted_code: &mut FormattedCode,
+    formatter: &mut Formatter,
+) -> Result<(), FormatterError> {
+    // format each `Attribute`
+    let mut start = None;
+    for attr in attributes {
         if let Some(start) = start {
             // Write any comments that may have been defined in between the
             // attributes and the value
-            write_comments(formatted_code, start..self.value.span().start(), formatter)?;
+            write_comments(formatted_code, start..attr.span().start(), formatter)?;
             if !formatted_code.ends_with(NEW_LINE) {
                 write!(formatted_code, "{}", NEW_LINE)?;
             }
         }
-        // format `ItemKind`
         formatter.write_indent_into_buffer(formatted_code)?;
-        self.value.format(formatted_code, formatter)?;
-
-        Ok(())
+        attr.format(formatted_code, formatter)?;
+        start = Some(attr.span().end());
+    }
+    if let Some(start) = start {
+        // Write any comments that may have been defined in between the
+        // attributes and the value
+        write_comments(formatted_code, start..value_start, formatter)?;
+        if !formatted_code.ends_with(NEW_LINE) {
+            write!(formatted_code, "{}", NEW_LINE)?;
+        }
     }
+
+    Ok(())
 }
 
 impl Format for AttributeArg {
@@ -63,6 +81,14 @@ impl Format for AttributeArg {
             write!(formatted_code, " = ")?;
             value.format(formatted_code, formatter)?;
         }
+        if let Some(args) = &self.args {
+            // `(`
+            AttributeDecl::open_parenthesis(formatted_code, formatter)?;
+            // format and add nested args e.g. `target = "fuel", not(program_type = "script")`
+            args.get().format(formatted_code, formatter)?;
+            // ')'
+            AttributeDecl::close_parenthesis(formatted_code, formatter)?;
+        }
 
         Ok(())
     }
@@ -74,6 +100,9 @@ impl LeafSpans for AttributeArg {
         if let Some(value) = &self.value {
             collected_spans.push(ByteSpan::from(value.span()));
         }
+        if let Some(args) = &self.args {
+            collected_spans.append(&mut args.leaf_spans());
+        }
         collected_spans
     }
 }
@@ -92,22 +121,14 @@ impl Format for AttributeDecl {
 
         // invariant: doc comment attributes are singleton lists
         if let Some(attr) = doc_comment_attrs.into_iter().next() {
-            if let Some(Some(doc_comment)) = attr
-                .args
-                .as_ref()
-                .map(|args| args.inner.final_value_opt.as_ref())
-            {
+            if let Some(doc_comment) = attr.doc_comment_text() {
                 match self.hash_kind {
-                    AttributeHashKind::Inner(_) => writeln!(
-                        formatted_code,
-                        "//!{}",
-                        doc_comment.name.as_str().trim_end()
-                    )?,
-                    AttributeHashKind::Outer(_) => writeln!(
-                        formatted_code,
-                        "///{}",
-                        doc_comment.name.as_str().trim_end()
-                    )?,
+                    AttributeHashKind::Inner(_) => {
+                        writeln!(formatted_code, "//!{}", doc_comment.trim_end())?
+                    }
+                    AttributeHashKind::Outer(_) => {
+                        writeln!(formatted_code, "///{}", doc_comment.trim_end())?
+                    }
                 }
             }
             return Ok(());
@@ -130,8 +151,8 @@ impl Format for AttributeDecl {
             formatter.with_shape(
                 formatter.shape.with_default_code_line(),
                 |formatter| -> Result<(), FormatterError> {
-                    // name e.g. `storage`
-                    write!(formatted_code, "{}", attr.name.as_str())?;
+                    // name e.g. `storage`, or a namespaced name e.g. `fuels::test`
+                    write!(formatted_code, "{}", attr.path_str())?;
                     if let Some(args) = &attr.args {
                         // `(`
                         Self::open_parenthesis(formatted_code, formatter)?;
@@ -200,7 +221,12 @@ impl LeafSpans for AttributeDecl {
 }
 impl LeafSpans for Attribute {
     fn leaf_spans(&self) -> Vec<ByteSpan> {
-        let mut collected_spans = vec![ByteSpan::from(self.name.span())];
+        let mut collected_spans = Vec::new();
+        for (segment, double_colon_token) in &self.namespace {
+            collected_spans.push(ByteSpan::from(segment.span()));
+            collected_spans.push(ByteSpan::from(double_colon_token.span()));
+        }
+        collected_spans.push(ByteSpan::from(self.name.span()));
         if let Some(args) = &self.args {
             collected_spans.append(&mut args.leaf_spans());
         }
@@ -2,9 +2,10 @@ use crate::{
     formatter::*,
     utils::{close_angle_bracket, colon, open_angle_bracket},
 };
+use std::fmt::Write;
 use sway_ast::{
-    generics::GenericParam,
-    keywords::{ConstToken, Keyword},
+    generics::{AssociatedTypeBinding, GenericParam},
+    keywords::{CommaToken, ConstToken, EqToken, Keyword, Token},
     GenericArgs, GenericParams,
 };
 
@@ -17,7 +18,6 @@ impl Format for GenericParam {
         match self {
             GenericParam::Trait { ident } => ident.format(formatted_code, formatter),
             GenericParam::Const { ident, ty } => {
-                use std::fmt::Write;
                 write!(formatted_code, "{} ", ConstToken::AS_STR)?;
                 let _ = ident.format(formatted_code, formatter);
                 let _ = colon(formatted_code);
@@ -52,6 +52,18 @@ impl Format for GenericParams {
     }
 }
 
+impl Format for AssociatedTypeBinding {
+    fn format(
+        &self,
+        formatted_code: &mut FormattedCode,
+        formatter: &mut Formatter,
+    ) -> Result<(), FormatterError> {
+        self.name.format(formatted_code, formatter)?;
+        write!(formatted_code, " {} ", EqToken::AS_STR)?;
+        self.ty.format(formatted_code, formatter)
+    }
+}
+
 impl Format for GenericArgs {
     fn format(
         &self,
@@ -66,6 +78,14 @@ impl Format for GenericArgs {
                 open_angle_bracket(formatted_code)?;
                 // format and add parameters
                 params.format(formatted_code, formatter)?;
+                // format and add associated type bindings, e.g. `Item = u64`
+                if !self.associated_type_bindings.is_empty() {
+                    if !params.is_empty() {
+                        write!(formatted_code, "{} ", CommaToken::AS_STR)?;
+                    }
+                    self.associated_type_bindings
+                        .format(formatted_code, formatter)?;
+                }
                 // `>`
                 close_angle_bracket(formatted_code)?;
 
@@ -268,6 +268,7 @@ pub(crate) async fn compile_to_bytes(file_name: &str, run_config: &RunConfig) ->
             ast: false,
             dca_graph: None,
             dca_graph_url_format: None,
+            trait_map: None,
             asm: run_config.print_asm,
             bytecode: run_config.print_bytecode,
             bytecode_spans: run_config.print_bytecode,
@@ -0,0 +1,6 @@
+library;
+
+pub trait Convert {
+    type Output;
+    fn convert() -> u64;
+}
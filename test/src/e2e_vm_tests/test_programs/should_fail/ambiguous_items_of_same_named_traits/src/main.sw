@@ -0,0 +1,25 @@
+script;
+
+mod a;
+mod b;
+
+struct MyType {}
+
+impl a::Convert for MyType {
+    type Output = u64;
+    fn convert() -> u64 {
+        1
+    }
+}
+
+impl b::Convert for MyType {
+    type Output = u32;
+    fn convert() -> u64 {
+        2
+    }
+}
+
+fn main() {
+    let _ = MyType::convert();
+    let _o: MyType::Output = 0;
+}
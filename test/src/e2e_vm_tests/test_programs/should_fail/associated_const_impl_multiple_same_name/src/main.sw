@@ -0,0 +1,22 @@
+library;
+
+trait ConstantId {
+    const ID: u32 = 7;
+}
+
+struct Struct {}
+
+impl ConstantId for Struct {
+    const ID: u32 = 1;
+    const ID: u32 = 2;
+}
+
+struct Other {}
+
+impl Other {
+    const VALUE: u32 = 1;
+}
+
+impl Other {
+    const VALUE: u32 = 2;
+}
@@ -0,0 +1,31 @@
+library;
+
+struct S {}
+
+trait Trait {
+    const TRAIT_CONST: u64;
+}
+
+impl Trait for S {
+    const TRAIT_CONST: u64 = 1;
+}
+
+trait OtherTrait {
+    const TRAIT_CONSTANT: u64;
+}
+
+impl OtherTrait for S {
+    const TRAIT_CONSTANT: u64 = 4;
+}
+
+impl S {
+    const SELF_CONST: u64 = 2;
+    const SELF_CONST_2: u64 = 3;
+}
+
+pub fn test() {
+    let _ = S::TRAIT_CONS;
+    let _ = S::SELF_CONTS;
+    let _ = S::NOT_SIMILAR_AT_ALL;
+    let _ = <S as OtherTrait>::TRAIT_CONS;
+}
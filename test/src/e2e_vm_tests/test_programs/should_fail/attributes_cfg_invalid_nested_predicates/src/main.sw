@@ -0,0 +1,8 @@
+script;
+
+#[cfg(all(not(testing), not(), any(target(fuel))))]
+fn foo() {}
+
+fn main() {
+  foo()
+}
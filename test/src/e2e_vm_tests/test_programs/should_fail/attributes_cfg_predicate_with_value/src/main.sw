@@ -0,0 +1,12 @@
+script;
+
+#[cfg(any = "fuel")]
+fn foo() {}
+
+#[cfg(all(target = "fuel", not = "evm"))]
+fn bar() {}
+
+fn main() {
+  foo();
+  bar();
+}
@@ -0,0 +1,46 @@
+script;
+
+struct S {}
+
+trait Trait {
+    fn trait_method(self);
+}
+
+impl Trait for &mut S {
+    fn trait_method(self) {}
+}
+
+impl &mut S {
+    fn ref_mut_method(self) {}
+}
+
+impl &mut &S {
+    fn ref_mut_ref_method(self) {}
+}
+
+impl &mut &mut S {
+    fn ref_mut_ref_mut_method(self) {}
+}
+
+impl &S {
+    fn ref_method(self) {}
+}
+
+fn main() {
+    let mut s = S {};
+
+    let r = &s;
+    r.trait_method();
+    r.ref_mut_method();
+
+    let r = & &s;
+    r.ref_mut_ref_method();
+
+    let r = &mut &s;
+    r.ref_mut_ref_mut_method();
+
+    let r = &mut s;
+    r.trait_method();
+    r.ref_mut_method();
+    r.ref_method();
+}
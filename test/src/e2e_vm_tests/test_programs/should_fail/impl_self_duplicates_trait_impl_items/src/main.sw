@@ -0,0 +1,24 @@
+library;
+
+struct S {}
+
+trait Trait {
+    const TRAIT_CONST: u64;
+    fn trait_method(self) -> u64;
+}
+
+impl Trait for S {
+    const TRAIT_CONST: u64 = 1;
+
+    fn trait_method(self) -> u64 {
+        1
+    }
+}
+
+impl S {
+    const TRAIT_CONST: u64 = 2;
+
+    fn trait_method(self) -> u64 {
+        2
+    }
+}
@@ -0,0 +1,54 @@
+script;
+
+trait Hash {
+    fn hash(self) -> u64;
+}
+
+trait Store {
+    fn store(self) -> u64;
+}
+
+struct Wrapper<T> {
+    value: T,
+}
+
+impl<T> Store for Wrapper<T>
+where
+    T: Hash,
+{
+    fn store(self) -> u64 {
+        self.value.hash()
+    }
+}
+
+impl<T> Wrapper<T>
+where
+    T: Hash,
+{
+    fn hash_value(self) -> u64 {
+        self.value.hash()
+    }
+}
+
+struct Hashable {}
+
+impl Hash for Hashable {
+    fn hash(self) -> u64 {
+        42
+    }
+}
+
+struct MyType {}
+
+fn main() {
+    let hashable = Wrapper {
+        value: Hashable {},
+    };
+    let _ = hashable.store();
+
+    let my_type = Wrapper { value: MyType {} };
+    let _ = my_type.store();
+
+    let my_type = Wrapper { value: MyType {} };
+    let _ = my_type.hash_value();
+}
@@ -0,0 +1,49 @@
+script;
+
+trait Hash {
+    fn hash(self) -> u64;
+}
+
+trait Store {
+    fn store(self) -> u64;
+}
+
+struct Wrapper<T> {
+    value: T,
+}
+
+impl<T> Store for Wrapper<T>
+where
+    T: Hash,
+{
+    fn store(self) -> u64 {
+        self.value.hash()
+    }
+}
+
+struct Hashable {}
+
+impl Hash for Hashable {
+    fn hash(self) -> u64 {
+        42
+    }
+}
+
+struct MyType {}
+
+fn store<T>(value: T) -> u64
+where
+    T: Store,
+{
+    value.store()
+}
+
+fn main() {
+    let _ = store(Wrapper {
+        value: Hashable {},
+    });
+
+    let _ = store(Wrapper { value: MyType {} });
+
+    let _ = Wrapper { value: MyType {} }.store();
+}
@@ -0,0 +1,17 @@
+library;
+
+struct S {}
+
+impl S {
+    fn method(self) {}
+}
+
+pub fn test() {
+    let o = Some(42u64);
+    let _ = o.is_nnoe();
+    let _ = o.unwarp();
+
+    let s = S {};
+    s.mehtod();
+    s.not_similar_at_all();
+}
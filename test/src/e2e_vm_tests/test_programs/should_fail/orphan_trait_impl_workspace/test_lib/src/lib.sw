@@ -0,0 +1,13 @@
+library;
+
+pub trait Describe {
+    fn describe(self) -> u64;
+}
+
+pub struct Point {
+    pub x: u64,
+}
+
+pub enum Shape {
+    Dot: (),
+}
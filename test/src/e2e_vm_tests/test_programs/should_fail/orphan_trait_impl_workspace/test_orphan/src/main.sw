@@ -0,0 +1,54 @@
+library;
+
+use test_lib::{Describe, Point, Shape};
+
+trait LocalDescribe {
+    fn local_describe(self) -> u64;
+}
+
+struct LocalPoint {
+    x: u64,
+}
+
+// Foreign trait for a foreign type.
+impl Describe for Point {
+    fn describe(self) -> u64 {
+        self.x
+    }
+}
+
+// Foreign trait for a reference to a foreign type.
+impl Describe for &Point {
+    fn describe(self) -> u64 {
+        self.x
+    }
+}
+
+// OK: Foreign trait for a local type.
+impl Describe for LocalPoint {
+    fn describe(self) -> u64 {
+        self.x
+    }
+}
+
+// OK: Local trait for a foreign type.
+impl LocalDescribe for Point {
+    fn local_describe(self) -> u64 {
+        self.x
+    }
+}
+
+// OK: Foreign trait for a built-in type.
+impl Describe for u64 {
+    fn describe(self) -> u64 {
+        self
+    }
+}
+
+// OK: Explicitly allowed.
+#[allow(orphan_impl)]
+impl Describe for Shape {
+    fn describe(self) -> u64 {
+        0
+    }
+}
@@ -0,0 +1,43 @@
+script;
+
+trait Container {
+    type Item;
+    fn first(self) -> Self::Item;
+}
+
+struct U64Box {
+    value: u64,
+}
+
+impl Container for U64Box {
+    type Item = u64;
+    fn first(self) -> Self::Item {
+        self.value
+    }
+}
+
+struct BoolBox {
+    value: bool,
+}
+
+impl Container for BoolBox {
+    type Item = bool;
+    fn first(self) -> Self::Item {
+        self.value
+    }
+}
+
+fn first_u64<T>(c: T) -> u64
+where
+    T: Container<Item = u64>,
+{
+    let _ = c;
+    42
+}
+
+fn main() {
+    let _ = first_u64(U64Box { value: 1 });
+    let _ = first_u64(BoolBox { value: true });
+    let _ = U64Box { value: 1 }.first();
+    let _ = BoolBox { value: true }.first();
+}
@@ -0,0 +1,5 @@
+library;
+
+pub trait Convert {
+    fn convert(self) -> u64;
+}
@@ -0,0 +1,54 @@
+script;
+
+mod a;
+mod b;
+
+use a::Convert;
+use b::Convert as OtherConvert;
+
+struct S {}
+
+impl Convert for S {
+    fn convert(self) -> u64 {
+        42
+    }
+}
+
+fn requires_a<T>(_t: T) where T: Convert {}
+
+fn requires_b<T>(_t: T) where T: OtherConvert {}
+
+fn requires_a_by_path<T>(_t: T) where T: a::Convert {}
+
+fn requires_b_by_path<T>(_t: T) where T: b::Convert {}
+
+struct Wrapper<T> {
+    value: T,
+}
+
+trait Store {
+    fn store(self) -> u64;
+}
+
+// The imported name is bound in this module, but the constraint
+// must be matched against the path of the trait declaration.
+impl<T> Store for Wrapper<T>
+where
+    T: Convert,
+{
+    fn store(self) -> u64 {
+        self.value.convert()
+    }
+}
+
+fn requires_store<T>(_t: T) where T: Store {}
+
+fn main() {
+    requires_a(S {});
+    requires_b(S {});
+
+    requires_a_by_path(S {});
+    requires_b_by_path(S {});
+
+    requires_store(Wrapper { value: S {} });
+}
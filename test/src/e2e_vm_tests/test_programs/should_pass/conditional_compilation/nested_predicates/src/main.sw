@@ -0,0 +1,18 @@
+script;
+
+// Deeply nested predicates, including empty groups.
+// `all()` always evaluates to true and `any()` to false.
+#[cfg(all(all(), not(any()), target = "fuel", any(program_type = "contract", not(not(program_type = "script")))))]
+const VALUE: u64 = 40;
+#[cfg(not(all(all(), not(any()), target = "fuel", any(program_type = "contract", not(not(program_type = "script"))))))]
+const VALUE: u64 = 0;
+#[cfg(any())]
+const VALUE: u64 = 1;
+#[cfg(not(all()))]
+const VALUE: u64 = 2;
+#[cfg(any(all(target = "fuel", program_type = "predicate"), all(target = "evm")))]
+const VALUE: u64 = 3;
+
+fn main() -> u64 {
+  VALUE
+}
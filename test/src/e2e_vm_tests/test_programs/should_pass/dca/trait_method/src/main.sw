@@ -10,6 +10,7 @@ pub trait MyTrait2 {
     }
 }
 
+#[allow(orphan_impl)]
 impl MyTrait for MyStruct {
     fn trait_method(self) -> bool {
         true
@@ -0,0 +1,35 @@
+script;
+
+trait ConstantId {
+    const ID: u32 = 7;
+}
+
+struct Overriding {}
+
+impl ConstantId for Overriding {
+    const ID: u32 = 5;
+}
+
+struct Inheriting {}
+
+impl ConstantId for Inheriting {
+    const ID: u32;
+}
+
+struct AlsoOverriding {}
+
+impl ConstantId for AlsoOverriding {
+    const ID: u32 = 3;
+}
+
+fn main() {}
+
+#[test]
+fn test() {
+    assert_eq(5, Overriding::ID);
+    assert_eq(5, <Overriding as ConstantId>::ID);
+    assert_eq(7, Inheriting::ID);
+    assert_eq(7, <Inheriting as ConstantId>::ID);
+    assert_eq(3, AlsoOverriding::ID);
+    assert_eq(3, <AlsoOverriding as ConstantId>::ID);
+}
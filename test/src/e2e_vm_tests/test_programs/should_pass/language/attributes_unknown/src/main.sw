@@ -6,4 +6,6 @@ library;
 #[alow(arg = 0)]
 #[depricated(note = "note")]
 #[unknown_0, unknown_1(arg), unknown_2(arg_1 = "value", arg_2)]
+#[fuels::test]
+#[lsp::ignore, tool::lint::off(arg)]
 struct S { }
\ No newline at end of file
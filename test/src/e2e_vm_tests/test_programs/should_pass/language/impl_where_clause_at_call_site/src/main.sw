@@ -0,0 +1,67 @@
+library;
+
+trait Hash {
+    fn hash(self) -> u64;
+}
+
+trait Store {
+    fn store(self) -> u64;
+}
+
+trait Archive: Store {
+    fn archive(self) -> u64;
+}
+
+struct Wrapper<T> {
+    value: T,
+}
+
+impl<T> Store for Wrapper<T>
+where
+    T: Hash,
+{
+    fn store(self) -> u64 {
+        self.value.hash()
+    }
+}
+
+impl Archive for Wrapper<Hashable> {
+    fn archive(self) -> u64 {
+        self.store()
+    }
+}
+
+struct Hashable {}
+
+impl Hash for Hashable {
+    fn hash(self) -> u64 {
+        42
+    }
+}
+
+fn store<T>(value: T) -> u64
+where
+    T: Store,
+{
+    value.store()
+}
+
+fn archive<T>(value: T) -> u64
+where
+    T: Archive,
+{
+    value.archive()
+}
+
+pub fn call_sites() -> u64 {
+    let stored = store(Wrapper {
+        value: Hashable {},
+    });
+    let archived = archive(Wrapper {
+        value: Hashable {},
+    });
+    let stored_directly = Wrapper {
+        value: Hashable {},
+    }.store();
+    __add(__add(stored, archived), stored_directly)
+}
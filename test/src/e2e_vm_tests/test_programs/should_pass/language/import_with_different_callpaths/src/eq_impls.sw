@@ -20,6 +20,7 @@ impl PartialEq for SomeStruct<u32> {
 }
 impl Eq for SomeStruct<u32> {}
 
+#[allow(orphan_impl)]
 impl PartialEq for Vec<SomeStruct<u32>> {
     fn eq(self, other: Self) -> bool {
         if self.len() != other.len() {
@@ -35,8 +36,10 @@ impl PartialEq for Vec<SomeStruct<u32>> {
         true
     }
 }
+#[allow(orphan_impl)]
 impl Eq for Vec<SomeStruct<u32>> {}
 
+#[allow(orphan_impl)]
 impl PartialEq for Vec<SomeEnum<u32>> {
     fn eq(self, other: Self) -> bool {
         if self.len() != other.len() {
@@ -53,4 +56,5 @@ impl PartialEq for Vec<SomeEnum<u32>> {
         true
     }
 }
+#[allow(orphan_impl)]
 impl Eq for Vec<SomeEnum<u32>> {}
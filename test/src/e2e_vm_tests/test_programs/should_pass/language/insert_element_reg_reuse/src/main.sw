@@ -14,6 +14,7 @@ impl PartialEq for [u64; 2] {
 }
 impl Eq for [u64; 2] {}
 
+#[allow(orphan_impl)]
 impl PartialEq for Vec<[u64; 2]> {
     fn eq(self, other: Self) -> bool {
         if self.len() != other.len() {
@@ -29,6 +30,7 @@ impl PartialEq for Vec<[u64; 2]> {
         true
     }
 }
+#[allow(orphan_impl)]
 impl Eq for Vec<[u64; 2]> {}
 
 fn tester1(arg: Vec<[u64; 2]>) {
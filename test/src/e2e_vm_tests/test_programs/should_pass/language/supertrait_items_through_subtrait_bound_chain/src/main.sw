@@ -0,0 +1,29 @@
+library;
+
+mod traits;
+mod value;
+
+// Only the trait at the bottom of the chain is imported.
+// The items of `Middle` and `Base` are available through its supertraits.
+use traits::{base_of, Top};
+use value::Value;
+
+fn sum_of_chain<T>(value: T) -> u64
+where
+    T: Top,
+{
+    let base = __add(value.base(), value.base_twice());
+    __add(__add(base, value.middle()), value.top())
+}
+
+fn base_through_subtrait<T>(value: T) -> u64
+where
+    T: Top,
+{
+    base_of(value)
+}
+
+pub fn call_chain() -> u64 {
+    let value = Value { inner: 40 };
+    __add(sum_of_chain(value), base_through_subtrait(value))
+}
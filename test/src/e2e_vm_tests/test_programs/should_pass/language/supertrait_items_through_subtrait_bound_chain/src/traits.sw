@@ -0,0 +1,24 @@
+library;
+
+pub trait Base {
+    fn base(self) -> u64;
+} {
+    fn base_twice(self) -> u64 {
+        __add(self.base(), self.base())
+    }
+}
+
+pub trait Middle: Base {
+    fn middle(self) -> u64;
+}
+
+pub trait Top: Middle {
+    fn top(self) -> u64;
+}
+
+pub fn base_of<T>(value: T) -> u64
+where
+    T: Base,
+{
+    value.base()
+}
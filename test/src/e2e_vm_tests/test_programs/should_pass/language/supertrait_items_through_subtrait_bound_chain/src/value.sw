@@ -0,0 +1,25 @@
+library;
+
+use ::traits::{Base, Middle, Top};
+
+pub struct Value {
+    pub inner: u64,
+}
+
+impl Base for Value {
+    fn base(self) -> u64 {
+        self.inner
+    }
+}
+
+impl Middle for Value {
+    fn middle(self) -> u64 {
+        __add(self.inner, 1)
+    }
+}
+
+impl Top for Value {
+    fn top(self) -> u64 {
+        __add(self.inner, 2)
+    }
+}
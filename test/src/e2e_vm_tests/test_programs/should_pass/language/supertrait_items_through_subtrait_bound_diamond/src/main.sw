@@ -0,0 +1,83 @@
+library;
+
+/// Inheritance graph:
+///      Base
+///      /  \
+///   Left  Right
+///      \  /
+///     Bottom
+trait Base {
+    fn base(self) -> u64;
+} {
+    fn base_twice(self) -> u64 {
+        __add(self.base(), self.base())
+    }
+}
+
+trait Left: Base {
+    fn left(self) -> u64;
+}
+
+trait Right: Base {
+    fn right(self) -> u64;
+}
+
+trait Bottom: Left + Right {
+    fn bottom(self) -> u64;
+}
+
+struct Value {
+    inner: u64,
+}
+
+impl Base for Value {
+    fn base(self) -> u64 {
+        self.inner
+    }
+}
+
+impl Left for Value {
+    fn left(self) -> u64 {
+        __add(self.inner, 1)
+    }
+}
+
+impl Right for Value {
+    fn right(self) -> u64 {
+        __add(self.inner, 2)
+    }
+}
+
+impl Bottom for Value {
+    fn bottom(self) -> u64 {
+        __add(self.inner, 3)
+    }
+}
+
+fn base_of<T>(value: T) -> u64
+where
+    T: Base,
+{
+    value.base()
+}
+
+fn sum_of_diamond<T>(value: T) -> u64
+where
+    T: Bottom,
+{
+    let base = __add(value.base(), value.base_twice());
+    let sides = __add(value.left(), value.right());
+    __add(__add(base, sides), __add(value.bottom(), base_of(value)))
+}
+
+fn sum_of_sides<T>(value: T) -> u64
+where
+    T: Left + Right,
+{
+    __add(value.base(), __add(value.left(), value.right()))
+}
+
+pub fn call_diamond() -> u64 {
+    let value = Value { inner: 40 };
+    __add(sum_of_diamond(value), sum_of_sides(value))
+}
@@ -0,0 +1,52 @@
+script;
+
+trait Container {
+    type Item;
+    fn first(self) -> Self::Item;
+}
+
+struct U64Box {
+    value: u64,
+}
+
+impl Container for U64Box {
+    type Item = u64;
+    fn first(self) -> Self::Item {
+        self.value
+    }
+}
+
+struct BoolBox {
+    value: bool,
+}
+
+impl Container for BoolBox {
+    type Item = bool;
+    fn first(self) -> Self::Item {
+        self.value
+    }
+}
+
+fn is_container_of_u64<T>(_c: T) -> bool
+where
+    T: Container<Item = u64>,
+{
+    true
+}
+
+fn is_container_of_bool<T>(_c: T) -> bool
+where
+    T: Container<Item = bool>,
+{
+    true
+}
+
+fn main() -> u64 {
+    let a = is_container_of_u64(U64Box { value: 1 });
+    let b = is_container_of_bool(BoolBox { value: true });
+    if a && b {
+        U64Box { value: 1 }.first()
+    } else {
+        0
+    }
+}
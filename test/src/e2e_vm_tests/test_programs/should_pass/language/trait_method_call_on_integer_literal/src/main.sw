@@ -0,0 +1,131 @@
+library;
+
+trait Double {
+    fn double(self) -> Self;
+}
+
+impl Double for u8 {
+    fn double(self) -> Self {
+        __add(self, self)
+    }
+}
+
+impl Double for u16 {
+    fn double(self) -> Self {
+        __add(self, self)
+    }
+}
+
+impl Double for u32 {
+    fn double(self) -> Self {
+        __add(self, self)
+    }
+}
+
+impl Double for u64 {
+    fn double(self) -> Self {
+        __add(self, self)
+    }
+}
+
+trait Plus {
+    fn plus(self, other: Self) -> Self;
+}
+
+impl Plus for u8 {
+    fn plus(self, other: Self) -> Self {
+        __add(self, other)
+    }
+}
+
+impl Plus for u16 {
+    fn plus(self, other: Self) -> Self {
+        __add(self, other)
+    }
+}
+
+impl Plus for u32 {
+    fn plus(self, other: Self) -> Self {
+        __add(self, other)
+    }
+}
+
+impl Plus for u64 {
+    fn plus(self, other: Self) -> Self {
+        __add(self, other)
+    }
+}
+
+trait First {
+    type Item;
+    fn first(self) -> Self::Item;
+}
+
+impl First for (u64, u64) {
+    type Item = u64;
+    fn first(self) -> Self::Item {
+        self.0
+    }
+}
+
+fn double_generic<T>(value: T) -> T
+where
+    T: Double,
+{
+    value.double()
+}
+
+pub fn double_literal() -> u64 {
+    21.double()
+}
+
+pub fn double_unannotated_variable() -> u64 {
+    let value = 21;
+    value.double()
+}
+
+pub fn double_annotated_literal() -> u8 {
+    21u8.double()
+}
+
+pub fn double_literal_returning_u8() -> u8 {
+    21.double()
+}
+
+pub fn double_literal_returning_u16() -> u16 {
+    21.double()
+}
+
+pub fn double_literal_returning_u32() -> u32 {
+    21.double()
+}
+
+pub fn double_literal_annotated_by_let() -> u16 {
+    let value: u16 = 21.double();
+    value
+}
+
+pub fn literal_plus_u8(x: u8) -> u8 {
+    1.plus(x)
+}
+
+pub fn literal_plus_u16(x: u16) -> u16 {
+    1.plus(x)
+}
+
+pub fn literal_plus_u32(x: u32) -> u32 {
+    1.plus(x)
+}
+
+pub fn double_literal_via_constraint() -> u64 {
+    double_generic(21)
+}
+
+pub fn first_of_literal_tuple() -> u64 {
+    (42, 0).first()
+}
+
+pub fn first_of_literal_tuple_via_associated_type() -> <(u64, u64) as First>::Item {
+    let tuple = (42, 0);
+    tuple.first()
+}